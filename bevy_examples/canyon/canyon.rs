@@ -12,6 +12,7 @@ use bevy_examples::{
 };
 use bevy_ghx_proc_gen::{
     bevy_ghx_grid::debug_plugin::{view::DebugGridView, DebugGridView3dBundle},
+    camera::{update_pan_orbit_camera, FrontierFollow, PanOrbitCameraBundle, PanOrbitState},
     gen::{
         assets::AssetSpawner,
         debug_plugin::{GenerationControl, GenerationViewMode},
@@ -25,7 +26,6 @@ use bevy_ghx_proc_gen::{
     },
     GeneratorBundle,
 };
-use bevy_ghx_utils::camera::{update_pan_orbit_camera, PanOrbitCameraBundle, PanOrbitState};
 
 use rand::Rng;
 use rules::{CustomComponents, RotationRandomizer, ScaleRandomizer, WindRotation};
@@ -61,19 +61,25 @@ fn setup_scene(mut commands: Commands) {
     let camera_position = Vec3::new(0., 1.5 * GRID_HEIGHT as f32, 1.5 * GRID_Z as f32 / 2.);
     let look_target = Vec3::new(0., -10., 0.);
     let radius = (look_target - camera_position).length();
-    commands.spawn((PanOrbitCameraBundle {
-        camera: Camera3dBundle {
-            transform: Transform::from_translation(camera_position)
-                .looking_at(look_target, Vec3::Y),
-            ..default()
-        },
-        state: PanOrbitState {
-            radius,
-            pitch: -FRAC_PI_2 / 2.,
+    commands.spawn((
+        PanOrbitCameraBundle {
+            camera: Camera3dBundle {
+                transform: Transform::from_translation(camera_position)
+                    .looking_at(look_target, Vec3::Y),
+                ..default()
+            },
+            state: PanOrbitState {
+                radius,
+                pitch: -FRAC_PI_2 / 2.,
+                ..Default::default()
+            },
             ..Default::default()
         },
-        ..Default::default()
-    },));
+        FrontierFollow {
+            enabled: false,
+            ..default()
+        },
+    ));
 
     // Scene lights
     commands.insert_resource(AmbientLight {
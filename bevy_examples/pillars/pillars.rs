@@ -14,6 +14,7 @@ use bevy_examples::{plugin::ProcGenExamplesPlugin, utils::load_assets};
 
 use bevy_ghx_proc_gen::{
     bevy_ghx_grid::debug_plugin::{view::DebugGridView, DebugGridView3dBundle},
+    camera::{update_pan_orbit_camera, FrontierFollow, PanOrbitCameraBundle, PanOrbitState},
     gen::{
         assets::{AssetSpawner, RulesModelsAssets},
         debug_plugin::GenerationViewMode,
@@ -24,7 +25,6 @@ use bevy_ghx_proc_gen::{
     },
     GeneratorBundle,
 };
-use bevy_ghx_utils::camera::{update_pan_orbit_camera, PanOrbitCameraBundle, PanOrbitState};
 
 use crate::rules::rules_and_assets;
 
@@ -69,6 +69,10 @@ fn setup_scene(
             },
             ..Default::default()
         },
+        FrontierFollow {
+            enabled: false,
+            ..default()
+        },
         FogSettings {
             color: Color::srgba(0.2, 0.15, 0.1, 1.0),
             falloff: FogFalloff::Linear {
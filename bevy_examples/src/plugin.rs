@@ -39,6 +39,7 @@ use bevy_ghx_proc_gen::{
         },
         ghx_grid::coordinate_system::CoordinateSystem,
     },
+    camera::{follow_generation_frontier, toggle_auto_orbit, toggle_frontier_follow},
     gen::{
         assets::{AssetsBundleSpawner, ComponentSpawner, NoComponents},
         debug_plugin::{
@@ -51,7 +52,7 @@ use bevy_ghx_proc_gen::{
     },
     proc_gen::ghx_grid::cartesian::coordinates::CartesianCoordinates,
 };
-use bevy_ghx_utils::{camera::toggle_auto_orbit, systems::toggle_visibility};
+use bevy_ghx_utils::systems::toggle_visibility;
 use bevy_mod_picking::{picking_core::Pickable, DefaultPickingPlugins};
 
 use crate::{
@@ -94,7 +95,11 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
             GridDebugPlugin::<C>::new(),
             DefaultPickingPlugins,
             EguiPlugin,
-            ProcGenDebugPlugin::<C, A, T>::new(self.generation_view_mode, CursorUiMode::Overlay),
+            ProcGenDebugPlugin::<C, A, T>::new(self.generation_view_mode, CursorUiMode::Overlay)
+                .with_contradiction_panel(true)
+                .with_heatmaps(true)
+                .with_model_gallery(true)
+                .with_sockets_gizmos(true),
         ));
         app.insert_resource(SpawningScaleAnimation::new(
             DEFAULT_SPAWN_ANIMATION_DURATION,
@@ -118,6 +123,8 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
                 toggle_debug_grids_visibilities.run_if(input_just_pressed(KeyCode::F3)),
                 toggle_grid_markers_visibilities.run_if(input_just_pressed(KeyCode::F4)),
                 toggle_auto_orbit.run_if(input_just_pressed(KeyCode::F5)),
+                toggle_frontier_follow.run_if(input_just_pressed(KeyCode::F6)),
+                follow_generation_frontier::<C>,
                 update_generation_control_ui,
                 // Quick adjust of the slowish spawn animation to be more snappy when painting
                 adjust_spawn_animation_when_painting
@@ -182,12 +189,25 @@ pub fn setup_ui(mut commands: Commands, view_mode: Res<GenerationViewMode>) {
         'F3' Show/hide grid\n\
         'F4' Show/hide markers\n\
         'F5' Enable/disable camera rotation\n\
+        'F6' Enable/disable camera following the generation\n\
         \n\
         Selection:\n\
        'Click' Select\n\
        'x/y/z'+'Left/Right' Move selection\n\
        'Esc' Deselect\n\
-       'Tab' Switch active grid\n"
+       'Tab' Switch active grid\n\
+       'Shift'+'x/y/z'+'Left/Right' Box selection\n\
+        \n\
+        Editor:\n\
+        'C' Copy seed\n\
+        'H' Show/hide entropy heatmap\n\
+        'F' Show/hide failure heatmap\n\
+        'L' Cycle layer slice (off/Y/Z)\n\
+        'PageUp/PageDown' Move layer slice\n\
+        'G' Show/hide model gallery\n\
+        'K' Show/hide sockets gizmos\n\
+        'R' Regenerate box selection\n\
+        'M' Highlight selected model\n"
         .to_string();
 
     if *view_mode == GenerationViewMode::StepByStepManual {
@@ -68,15 +68,13 @@ where
         for asset_def in assets {
             models_assets.add(
                 model_index,
-                ModelAsset {
-                    assets_bundle: asset_server.load(format!(
-                        "{assets_directory}/{}.{extension}",
-                        asset_def.path()
-                    )),
-                    grid_offset: asset_def.grid_offset.clone(),
-                    offset: asset_def.offset,
-                    components: asset_def.components.clone(),
-                },
+                ModelAsset::new(asset_server.load(format!(
+                    "{assets_directory}/{}.{extension}",
+                    asset_def.path()
+                )))
+                .with_grid_offset(asset_def.grid_offset.clone())
+                .with_offset(asset_def.offset)
+                .with_components(asset_def.components.clone()),
             )
         }
     }
@@ -4,7 +4,7 @@ use bevy_ghx_proc_gen::{
     bevy_ghx_grid::ghx_grid::direction::Direction,
     proc_gen::{
         generator::{
-            model::{ModelCollection, ModelRotation},
+            model::ModelCollection,
             socket::{Socket, SocketCollection, SocketsCartesian3D},
         },
         ghx_grid::cartesian::coordinates::{Cartesian3D, GridDelta},
@@ -112,41 +112,29 @@ pub(crate) fn rules_and_assets() -> (
     }
     .to_template();
 
-    models.create(green_grass_corner_out.clone());
-    assets.push(asset("green_grass_corner_out_tl"));
-
-    models.create(green_grass_corner_out.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("green_grass_corner_out_bl"));
-
-    models.create(green_grass_corner_out.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("green_grass_corner_out_br"));
-
-    models.create(green_grass_corner_out.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("green_grass_corner_out_tr"));
-
-    models.create(green_grass_corner_in.clone());
-    assets.push(asset("green_grass_corner_in_tl"));
-
-    models.create(green_grass_corner_in.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("green_grass_corner_in_bl"));
-
-    models.create(green_grass_corner_in.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("green_grass_corner_in_br"));
-
-    models.create(green_grass_corner_in.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("green_grass_corner_in_tr"));
-
-    models.create(green_grass_side.clone());
-    assets.push(asset("green_grass_side_t"));
-
-    models.create(green_grass_side.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("green_grass_side_l"));
+    models.create_all_rotations("green_grass_corner_out", green_grass_corner_out, UP_AXIS);
+    assets.extend([
+        asset("green_grass_corner_out_tl"),
+        asset("green_grass_corner_out_bl"),
+        asset("green_grass_corner_out_br"),
+        asset("green_grass_corner_out_tr"),
+    ]);
 
-    models.create(green_grass_side.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("green_grass_side_b"));
+    models.create_all_rotations("green_grass_corner_in", green_grass_corner_in, UP_AXIS);
+    assets.extend([
+        asset("green_grass_corner_in_tl"),
+        asset("green_grass_corner_in_bl"),
+        asset("green_grass_corner_in_br"),
+        asset("green_grass_corner_in_tr"),
+    ]);
 
-    models.create(green_grass_side.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("green_grass_side_r"));
+    models.create_all_rotations("green_grass_side", green_grass_side, UP_AXIS);
+    assets.extend([
+        asset("green_grass_side_t"),
+        asset("green_grass_side_l"),
+        asset("green_grass_side_b"),
+        asset("green_grass_side_r"),
+    ]);
 
     // ---------------------------- Layer 2 ----------------------------
 
@@ -198,41 +186,29 @@ pub(crate) fn rules_and_assets() -> (
     }
     .to_template();
 
-    models.create(yellow_grass_corner_out.clone());
-    assets.push(asset("yellow_grass_corner_out_tl"));
-
-    models.create(yellow_grass_corner_out.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("yellow_grass_corner_out_bl"));
-
-    models.create(yellow_grass_corner_out.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("yellow_grass_corner_out_br"));
-
-    models.create(yellow_grass_corner_out.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("yellow_grass_corner_out_tr"));
-
-    models.create(yellow_grass_corner_in.clone());
-    assets.push(asset("yellow_grass_corner_in_tl"));
-
-    models.create(yellow_grass_corner_in.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("yellow_grass_corner_in_bl"));
-
-    models.create(yellow_grass_corner_in.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("yellow_grass_corner_in_br"));
-
-    models.create(yellow_grass_corner_in.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("yellow_grass_corner_in_tr"));
-
-    models.create(yellow_grass_side.clone());
-    assets.push(asset("yellow_grass_side_t"));
-
-    models.create(yellow_grass_side.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("yellow_grass_side_l"));
+    models.create_all_rotations("yellow_grass_corner_out", yellow_grass_corner_out, UP_AXIS);
+    assets.extend([
+        asset("yellow_grass_corner_out_tl"),
+        asset("yellow_grass_corner_out_bl"),
+        asset("yellow_grass_corner_out_br"),
+        asset("yellow_grass_corner_out_tr"),
+    ]);
 
-    models.create(yellow_grass_side.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("yellow_grass_side_b"));
+    models.create_all_rotations("yellow_grass_corner_in", yellow_grass_corner_in, UP_AXIS);
+    assets.extend([
+        asset("yellow_grass_corner_in_tl"),
+        asset("yellow_grass_corner_in_bl"),
+        asset("yellow_grass_corner_in_br"),
+        asset("yellow_grass_corner_in_tr"),
+    ]);
 
-    models.create(yellow_grass_side.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("yellow_grass_side_r"));
+    models.create_all_rotations("yellow_grass_side", yellow_grass_side, UP_AXIS);
+    assets.extend([
+        asset("yellow_grass_side_t"),
+        asset("yellow_grass_side_l"),
+        asset("yellow_grass_side_b"),
+        asset("yellow_grass_side_r"),
+    ]);
 
     // ---------------------------- Layer 3 ----------------------------
 
@@ -290,41 +266,29 @@ pub(crate) fn rules_and_assets() -> (
     .to_template()
     .with_weight(WATER_WEIGHT);
 
-    models.create(water_corner_out.clone());
-    assets.push(asset("water_corner_out_tl"));
-
-    models.create(water_corner_out.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("water_corner_out_bl"));
-
-    models.create(water_corner_out.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("water_corner_out_br"));
-
-    models.create(water_corner_out.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("water_corner_out_tr"));
-
-    models.create(water_corner_in.clone());
-    assets.push(asset("water_corner_in_tl"));
-
-    models.create(water_corner_in.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("water_corner_in_bl"));
-
-    models.create(water_corner_in.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("water_corner_in_br"));
-
-    models.create(water_corner_in.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("water_corner_in_tr"));
-
-    models.create(water_side.clone());
-    assets.push(asset("water_side_t"));
-
-    models.create(water_side.rotated(ModelRotation::Rot90, UP_AXIS));
-    assets.push(asset("water_side_l"));
+    models.create_all_rotations("water_corner_out", water_corner_out, UP_AXIS);
+    assets.extend([
+        asset("water_corner_out_tl"),
+        asset("water_corner_out_bl"),
+        asset("water_corner_out_br"),
+        asset("water_corner_out_tr"),
+    ]);
 
-    models.create(water_side.rotated(ModelRotation::Rot180, UP_AXIS));
-    assets.push(asset("water_side_b"));
+    models.create_all_rotations("water_corner_in", water_corner_in, UP_AXIS);
+    assets.extend([
+        asset("water_corner_in_tl"),
+        asset("water_corner_in_bl"),
+        asset("water_corner_in_br"),
+        asset("water_corner_in_tr"),
+    ]);
 
-    models.create(water_side.rotated(ModelRotation::Rot270, UP_AXIS));
-    assets.push(asset("water_side_r"));
+    models.create_all_rotations("water_side", water_side, UP_AXIS);
+    assets.extend([
+        asset("water_side_t"),
+        asset("water_side_l"),
+        asset("water_side_b"),
+        asset("water_side_r"),
+    ]);
 
     // ---------------------------- Layer 4 ----------------------------
 
@@ -0,0 +1,321 @@
+use std::{
+    collections::VecDeque,
+    f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, TAU},
+};
+
+use bevy::{
+    core_pipeline::core_3d::Camera3dBundle,
+    ecs::{
+        bundle::Bundle,
+        component::Component,
+        event::EventReader,
+        query::With,
+        system::{Local, Query, Res},
+    },
+    input::{
+        mouse::{MouseButton, MouseMotion, MouseScrollUnit, MouseWheel},
+        ButtonInput,
+    },
+    math::{EulerRot, Quat, Vec2, Vec3},
+    render::camera::Projection,
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+    window::{PrimaryWindow, Window},
+};
+use ghx_proc_gen::ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid};
+
+#[cfg(feature = "debug-plugin")]
+use {
+    crate::gen::debug_plugin::generation::{ActiveGeneration, GenerationEvent},
+    bevy_ghx_grid::debug_plugin::view::DebugGridView,
+};
+
+/// Amount of pixels scrolled for one line of a [`MouseWheel`] event
+const PIXELS_PER_SCROLL_LINE: f32 = 16.;
+/// Minimum distance between a pan-orbit camera and its center
+const MIN_ORBIT_RADIUS: f32 = 0.05;
+
+/// Bundle of a 3d camera controlled by [update_pan_orbit_camera]
+#[derive(Bundle, Default)]
+pub struct PanOrbitCameraBundle {
+    /// Camera
+    pub camera: Camera3dBundle,
+    /// Position of the camera around its center
+    pub state: PanOrbitState,
+    /// Controls of the camera
+    pub settings: PanOrbitSettings,
+}
+
+/// Component describing the position of a pan-orbit camera around the point it looks at
+#[derive(Component, Clone, Debug)]
+pub struct PanOrbitState {
+    /// Point looked at by the camera, in world space
+    pub center: Vec3,
+    /// Distance between the camera and its center
+    pub radius: f32,
+    /// Rotation of the camera around the horizontal axis, in radians. Negative values look down at the center
+    pub pitch: f32,
+    /// Rotation of the camera around the vertical axis, in radians
+    pub yaw: f32,
+}
+
+impl Default for PanOrbitState {
+    fn default() -> Self {
+        Self {
+            center: Vec3::ZERO,
+            radius: 1.,
+            pitch: 0.,
+            yaw: 0.,
+        }
+    }
+}
+
+impl PanOrbitState {
+    /// Returns the rotation of a camera in this state
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.)
+    }
+
+    /// Returns the [Transform] of a camera in this state
+    pub fn transform(&self) -> Transform {
+        let rotation = self.rotation();
+        Transform {
+            translation: self.center + rotation * Vec3::Z * self.radius,
+            rotation,
+            ..Default::default()
+        }
+    }
+}
+
+/// Component used to customize the controls of a pan-orbit camera
+#[derive(Component, Clone, Debug)]
+pub struct PanOrbitSettings {
+    /// Mouse button used to orbit around the center
+    pub orbit_button: MouseButton,
+    /// Mouse button used to move the center
+    pub pan_button: MouseButton,
+    /// Orbit speed. `1.0` orbits a full turn when moving the mouse across the whole window width
+    pub orbit_sensitivity: f32,
+    /// Pan speed. `1.0` moves the center by the camera radius when moving the mouse across the whole window height
+    pub pan_sensitivity: f32,
+    /// Zoom speed, ratio of the radius removed per scrolled line
+    pub zoom_sensitivity: f32,
+    /// Whether or not the camera automatically orbits around its center
+    pub auto_orbit: bool,
+    /// Auto-orbit speed, in radians per second
+    pub auto_orbit_speed: f32,
+}
+
+impl Default for PanOrbitSettings {
+    fn default() -> Self {
+        Self {
+            orbit_button: MouseButton::Right,
+            pan_button: MouseButton::Middle,
+            orbit_sensitivity: 1.0,
+            pan_sensitivity: 1.0,
+            zoom_sensitivity: 0.1,
+            auto_orbit: false,
+            auto_orbit_speed: 0.1,
+        }
+    }
+}
+
+/// System updating the [PanOrbitState] and [Transform] of the pan-orbit cameras from the mouse inputs and their [PanOrbitSettings]
+pub fn update_pan_orbit_camera(
+    time: Res<Time>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&PanOrbitSettings, &mut PanOrbitState, &mut Transform)>,
+) {
+    let mouse_delta: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    let scrolled_lines: f32 = mouse_wheel
+        .read()
+        .map(|wheel| match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / PIXELS_PER_SCROLL_LINE,
+        })
+        .sum();
+    let window_size = match primary_window.get_single() {
+        Ok(window) => Vec2::new(window.width(), window.height()).max(Vec2::ONE),
+        Err(_) => Vec2::ONE,
+    };
+
+    for (settings, mut state, mut transform) in cameras.iter_mut() {
+        if mouse_delta != Vec2::ZERO && mouse_buttons.pressed(settings.orbit_button) {
+            state.yaw -= mouse_delta.x / window_size.x * TAU * settings.orbit_sensitivity;
+            state.pitch = (state.pitch
+                - mouse_delta.y / window_size.y * PI * settings.orbit_sensitivity)
+                .clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+        } else if mouse_delta != Vec2::ZERO && mouse_buttons.pressed(settings.pan_button) {
+            let rotation = state.rotation();
+            let pan = (rotation * Vec3::NEG_X * mouse_delta.x + rotation * Vec3::Y * mouse_delta.y)
+                / window_size.y
+                * state.radius
+                * settings.pan_sensitivity;
+            state.center += pan;
+        }
+        if scrolled_lines != 0. {
+            state.radius = (state.radius * (1. - scrolled_lines * settings.zoom_sensitivity))
+                .max(MIN_ORBIT_RADIUS);
+        }
+        if settings.auto_orbit {
+            state.yaw += settings.auto_orbit_speed * time.delta_seconds();
+        }
+
+        if state.is_changed() {
+            *transform = state.transform().with_scale(transform.scale);
+        }
+    }
+}
+
+/// System that enables/disables the auto-orbit of all the pan-orbit cameras
+pub fn toggle_auto_orbit(mut cameras: Query<&mut PanOrbitSettings>) {
+    for mut settings in cameras.iter_mut() {
+        settings.auto_orbit = !settings.auto_orbit;
+    }
+}
+
+/// Component enabling the "follow the frontier" mode of a pan-orbit camera: the camera center smoothly tracks the most recently generated nodes of the [`crate::gen::debug_plugin::generation::ActiveGeneration`].
+///
+/// Mostly useful when watching a large generation step by step. See `follow_generation_frontier`.
+#[derive(Component, Clone, Debug)]
+pub struct FrontierFollow {
+    /// Whether or not the camera currently follows the frontier
+    pub enabled: bool,
+    /// How many of the most recently generated nodes are averaged to compute the tracked position
+    pub tracked_nodes: usize,
+    /// How fast the camera center moves towards the tracked position. Higher is snappier
+    pub smoothing: f32,
+}
+
+impl Default for FrontierFollow {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tracked_nodes: 8,
+            smoothing: 3.,
+        }
+    }
+}
+
+/// System that enables/disables the [FrontierFollow] mode of all the pan-orbit cameras
+pub fn toggle_frontier_follow(mut cameras: Query<&mut FrontierFollow>) {
+    for mut frontier_follow in cameras.iter_mut() {
+        frontier_follow.enabled = !frontier_follow.enabled;
+    }
+}
+
+/// Moves the center of `state` to the center of `grid`, and sets its radius so that the whole grid fits in the view of a camera with a vertical field of view of `fov` (in radians).
+///
+/// `node_size` is the size of a node of the grid in world units, and `grid_gtransform` the [GlobalTransform] of the grid entity. The pitch and yaw of `state` are kept as is.
+pub fn fit_camera_to_grid<C: CartesianCoordinates>(
+    state: &mut PanOrbitState,
+    grid: &CartesianGrid<C>,
+    node_size: Vec3,
+    grid_gtransform: &GlobalTransform,
+    fov: f32,
+) {
+    let grid_extent = node_size
+        * Vec3::new(
+            grid.size_x() as f32,
+            grid.size_y() as f32,
+            grid.size_z() as f32,
+        );
+    let (scale, _, _) = grid_gtransform.to_scale_rotation_translation();
+    let bounding_radius = (grid_extent * scale).length() / 2.;
+    state.center = grid_gtransform.transform_point(grid_extent / 2.);
+    state.radius = (bounding_radius / (fov / 2.).sin()).max(MIN_ORBIT_RADIUS);
+}
+
+/// System that frames the grid of the [ActiveGeneration] with all the pan-orbit cameras, each time the active generation changes. See [fit_camera_to_grid].
+///
+/// Should run after the transforms propagation (e.g. in `PostUpdate`, after `TransformSystem::TransformPropagate`), so that a newly spawned generation is framed at its final position.
+#[cfg(feature = "debug-plugin")]
+pub fn frame_active_generation<C: CartesianCoordinates>(
+    active_generation: Res<ActiveGeneration>,
+    generations: Query<(&CartesianGrid<C>, &DebugGridView, &GlobalTransform)>,
+    mut cameras: Query<(&mut PanOrbitState, Option<&Projection>)>,
+) {
+    if !active_generation.is_changed() {
+        return;
+    }
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    let Ok((grid, grid_view, grid_gtransform)) = generations.get(active_generation) else {
+        return;
+    };
+    for (mut state, projection) in cameras.iter_mut() {
+        let fov = match projection {
+            Some(Projection::Perspective(perspective)) => perspective.fov,
+            _ => FRAC_PI_4,
+        };
+        fit_camera_to_grid(&mut state, grid, grid_view.node_size, grid_gtransform, fov);
+    }
+}
+
+/// System moving the center of the pan-orbit cameras with an enabled [FrontierFollow] towards the most recently generated nodes of the [ActiveGeneration].
+///
+/// The generated nodes are read from the [GenerationEvent]s, the tracked nodes are reset when the active generation changes or is reinitialized.
+#[cfg(feature = "debug-plugin")]
+pub fn follow_generation_frontier<C: CartesianCoordinates>(
+    time: Res<Time>,
+    active_generation: Res<ActiveGeneration>,
+    mut recent_nodes: Local<VecDeque<Vec3>>,
+    mut generation_events: EventReader<GenerationEvent>,
+    generations: Query<(&CartesianGrid<C>, &DebugGridView, &GlobalTransform)>,
+    mut cameras: Query<(&FrontierFollow, &mut PanOrbitState)>,
+) {
+    if active_generation.is_changed() {
+        recent_nodes.clear();
+    }
+    let tracked_nodes = cameras
+        .iter()
+        .map(|(frontier_follow, _)| frontier_follow.tracked_nodes)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for event in generation_events.read() {
+        match event {
+            GenerationEvent::Reinitialized(gen_entity) => {
+                if active_generation.0 == Some(*gen_entity) {
+                    recent_nodes.clear();
+                }
+            }
+            GenerationEvent::Updated(gen_entity, node_index) => {
+                if active_generation.0 != Some(*gen_entity) {
+                    continue;
+                }
+                let Ok((grid, grid_view, grid_gtransform)) = generations.get(*gen_entity) else {
+                    continue;
+                };
+                let pos = grid.pos_from_index(*node_index);
+                let node_center = grid_view.node_size
+                    * (Vec3::new(pos.x as f32, pos.y as f32, pos.z as f32) + 0.5);
+                recent_nodes.push_back(grid_gtransform.transform_point(node_center));
+            }
+        }
+    }
+    while recent_nodes.len() > tracked_nodes {
+        recent_nodes.pop_front();
+    }
+    if recent_nodes.is_empty() {
+        return;
+    }
+
+    for (frontier_follow, mut state) in cameras.iter_mut() {
+        if !frontier_follow.enabled {
+            continue;
+        }
+        let followed_nodes = recent_nodes.len().min(frontier_follow.tracked_nodes.max(1));
+        let target =
+            recent_nodes.iter().rev().take(followed_nodes).sum::<Vec3>() / followed_nodes as f32;
+        if state.center.distance_squared(target) < 1e-6 {
+            continue;
+        }
+        let lerp_factor = 1. - (-frontier_follow.smoothing * time.delta_seconds()).exp();
+        state.center = state.center.lerp(target, lerp_factor);
+    }
+}
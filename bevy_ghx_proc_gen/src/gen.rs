@@ -1,14 +1,18 @@
+use std::collections::HashMap;
+
 use bevy::{
+    core::Name,
     ecs::{
         bundle::Bundle,
         component::Component,
         entity::Entity,
-        query::Added,
+        query::{Added, With},
         system::{Commands, Query, Res, Resource},
     },
-    hierarchy::BuildChildren,
+    hierarchy::{BuildChildren, Children, Parent},
     math::Vec3,
     prelude::Without,
+    render::spatial_bundle::SpatialBundle,
 };
 use debug_plugin::picking::CursorTarget;
 use ghx_proc_gen::{
@@ -17,28 +21,116 @@ use ghx_proc_gen::{
     NodeIndex,
 };
 
-use self::assets::{AssetSpawner, AssetsBundleSpawner, ComponentSpawner};
+#[cfg(feature = "reflect")]
+use bevy::{ecs::reflect::ReflectComponent, reflect::Reflect};
+
+use self::assets::{AssetSpawner, AssetsBundleSpawner, ComponentSpawner, TintColor};
 
 /// Types to define and spawn assets
 pub mod assets;
 
+/// Plugin building generators asynchronously from settings components
+pub mod deferred_build;
+
+/// Component and plugin linking adjacent generations so that they agree at their seams
+pub mod linked_generations;
+
+/// Component limiting the attempts of a failing generation, with a backoff between the attempts
+pub mod retry_watchdog;
+
+/// Observer component and plugin translating the updates of a generator into typed Bevy events
+pub mod events_observer;
+
 /// Debug plugin to run the generation & spawn assets automatically with different visualization options
 #[cfg(feature = "debug-plugin")]
 pub mod debug_plugin;
+/// Plugin reloading the rules of the generators when their rules asset is modified
+#[cfg(feature = "rules-hot-reload")]
+pub mod rules_reload;
+/// Plugin running seeds analyses of some rules in the background
+pub mod seeds_analysis;
 /// Simple plugin to run the generation & spawn assets automatically
 #[cfg(feature = "simple-plugin")]
 pub mod simple_plugin;
 
+/// [`AssetsBundleSpawner`] for glTF scenes whose animation starts playing when they are spawned
+#[cfg(feature = "animated-scenes")]
+pub mod animated_scenes;
+
 /// Adds default [`AssetsBundleSpawner`] implementations for common types.
 ///
-/// **WARNING**: those default implementations each assume a specific `Rotation Axis` for the `Models` (Z+ for 2d, Y+ for 3d)
+/// **WARNING**: those default implementations each assume a specific `Rotation Axis` for the `Models` (Z+ for 2d, Y+ for 3d), which can be overridden with [`AssetSpawner::with_assets_orientation`]
 #[cfg(feature = "default-assets-bundle-spawners")]
 pub mod default_bundles;
 
 /// Used to mark a node spawned by a [`ghx_proc_gen::generator::Generator`]. Stores the [NodeIndex] of this node
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct GridNode(pub NodeIndex);
 
+/// Used to mark an entity grouping the nodes of one layer of a generation, see [`AssetSpawner::with_layers`]. Stores the coordinate of the layer along the [`assets::LayerAxis`]
+#[derive(Component, Clone, Copy)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
+pub struct NodeLayer(pub u32);
+
+/// Returns the generation entity of a spawned node from its [`Parent`], whether or not the node was grouped into a [`NodeLayer`]
+pub fn node_generation(parent: &Parent, layers: &Query<&Parent, With<NodeLayer>>) -> Entity {
+    match layers.get(parent.get()) {
+        Ok(layer_parent) => layer_parent.get(),
+        Err(_) => parent.get(),
+    }
+}
+
+/// Utility system grouping the newly spawned nodes of the generations whose [`AssetSpawner`] has some [`AssetSpawner::layers_axis`] under one [`NodeLayer`] entity per layer.
+///
+/// The layer entities are children of the generation entity and are spawned when their first node is. Used by [`simple_plugin::ProcGenSimplePlugin`] and [`debug_plugin::ProcGenDebugPlugin`].
+pub fn group_spawned_nodes_into_layers<
+    C: CartesianCoordinates,
+    A: AssetsBundleSpawner,
+    T: ComponentSpawner,
+>(
+    mut commands: Commands,
+    spawned_nodes: Query<(Entity, &GridNode, &Parent), (Added<GridNode>, Without<CursorTarget>)>,
+    generations: Query<(&CartesianGrid<C>, &AssetSpawner<A, T>, Option<&Children>)>,
+    existing_layers: Query<&NodeLayer>,
+) {
+    let mut spawned_layers = HashMap::new();
+    for (node_entity, node, parent) in spawned_nodes.iter() {
+        let gen_entity = parent.get();
+        let Ok((grid, asset_spawner, children)) = generations.get(gen_entity) else {
+            continue;
+        };
+        let Some(layers_axis) = asset_spawner.layers_axis else {
+            continue;
+        };
+        let layer = layers_axis.layer(&grid.pos_from_index(node.0));
+
+        let layer_entity = *spawned_layers
+            .entry((gen_entity, layer))
+            .or_insert_with(|| {
+                let existing_layer = children.and_then(|children| {
+                    children.iter().copied().find(|child| {
+                        existing_layers
+                            .get(*child)
+                            .is_ok_and(|node_layer| node_layer.0 == layer)
+                    })
+                });
+                existing_layer.unwrap_or_else(|| {
+                    let layer_entity = commands
+                        .spawn((
+                            NodeLayer(layer),
+                            Name::new(format!("Layer {}", layer)),
+                            SpatialBundle::default(),
+                        ))
+                        .id();
+                    commands.entity(gen_entity).add_child(layer_entity);
+                    layer_entity
+                })
+            });
+        commands.entity(layer_entity).add_child(node_entity);
+    }
+}
+
 /// Utility system. Adds a [`Bundle`] (or a [`Component`]) to every [`Entity`] that has [`GridNode`] Component (this is the case of nodes spawned by the `spawn_node` system). The `Bundle` will have its default value.
 ///
 /// ### Example
@@ -105,6 +197,8 @@ pub fn insert_bundle_from_resource_to_spawned_nodes<B: Bundle + Resource + Clone
 
 /// Utility system to spawn grid nodes. Can work for multiple asset types.
 ///
+/// Each spawned asset entity holds a [`GridNode`] and the spawned [`ModelInstance`].
+///
 /// Used by [`simple_plugin::ProcGenSimplePlugin`] and [`debug_plugin::ProcGenDebugPlugin`] to spawn assets automatically.
 ///
 /// ### Examples
@@ -124,39 +218,92 @@ pub fn spawn_node<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentS
     asset_spawner: &AssetSpawner<A, T>,
     instance: &ModelInstance,
     node_index: NodeIndex,
+) {
+    let node_pos = Vec3::from_array(
+        asset_spawner
+            .grid_transform()
+            .node_units(grid, &grid.pos_from_index(node_index)),
+    );
+    spawn_model_assets(
+        commands,
+        gen_entity,
+        asset_spawner,
+        instance,
+        node_pos,
+        (GridNode(node_index), *instance),
+    );
+}
+
+/// Utility function to spawn the assets of a model `instance` as children of `parent`, without any grid.
+///
+/// `node_pos` is expressed in nodes units (it can be fractional or outside of any grid), and `bundle` is inserted on every spawned asset entity. Used by [`spawn_node`].
+pub fn spawn_model_assets<A: AssetsBundleSpawner, T: ComponentSpawner, B: Bundle + Clone>(
+    commands: &mut Commands,
+    parent: Entity,
+    asset_spawner: &AssetSpawner<A, T>,
+    instance: &ModelInstance,
+    node_pos: Vec3,
+    bundle: B,
 ) {
     let node_assets = match asset_spawner.assets.get(&instance.model_index) {
         Some(node_assets) => node_assets,
         None => return,
     };
 
-    let pos = grid.pos_from_index(node_index);
+    let grid_transform = asset_spawner.grid_transform();
+    // + 0.5 to center `translation` in the node
+    let node_center = Vec3::from_array(grid_transform.units_to_world([
+        node_pos.x + 0.5,
+        node_pos.y + 0.5,
+        node_pos.z + 0.5,
+    ]));
+    let grid_origin = Vec3::from_array(grid_transform.units_to_world([0., 0., 0.]));
     for node_asset in node_assets {
-        let offset = &node_asset.offset;
         let grid_offset = &node_asset.grid_offset;
-        // + (0.5 * size) to center `translation` in the node
-        let mut translation = Vec3::new(
-            offset.x + asset_spawner.node_size.x * (pos.x as f32 + grid_offset.dx as f32 + 0.5),
-            offset.y + asset_spawner.node_size.y * (pos.y as f32 + grid_offset.dy as f32 + 0.5),
-            offset.z + asset_spawner.node_size.z * (pos.z as f32 + grid_offset.dz as f32 + 0.5),
-        );
-
-        if asset_spawner.z_offset_from_y {
-            translation.z += asset_spawner.node_size.z * (1. - pos.y as f32 / grid.size_y() as f32);
+        let mut offset = Vec3::from_array(grid_transform.units_to_world([
+            grid_offset.dx as f32,
+            grid_offset.dy as f32,
+            grid_offset.dz as f32,
+        ])) - grid_origin
+            + node_asset.offset;
+        let orientation = asset_spawner
+            .assets_orientation
+            .unwrap_or_else(|| node_asset.assets_bundle.assumed_orientation());
+        // Flipped and rotated around the node center, like the asset itself
+        if asset_spawner.rotate_offsets {
+            offset = orientation.rotation(instance.rotation, instance.facing())
+                * (orientation.flip_scale(instance.flipped) * offset);
         }
+        let translation = node_center + offset;
+        // Flipped in the local space of the asset, before its rotation
+        let scale = asset_spawner.spawn_scale * orientation.flip_scale(instance.flipped);
 
-        let node_entity = commands.spawn(GridNode(node_index)).id();
+        let node_entity = commands.spawn(bundle.clone()).id();
 
         let node_entity_commands = &mut commands.entity(node_entity);
-        node_asset.assets_bundle.insert_bundle(
-            node_entity_commands,
-            translation,
-            asset_spawner.spawn_scale,
-            instance.rotation,
-        );
+        match &asset_spawner.assets_orientation {
+            Some(orientation) => node_asset.assets_bundle.insert_bundle_with_orientation(
+                node_entity_commands,
+                translation,
+                scale,
+                instance.rotation,
+                instance.facing(),
+                orientation,
+            ),
+            None => node_asset.assets_bundle.insert_oriented_bundle(
+                node_entity_commands,
+                translation,
+                scale,
+                instance.rotation,
+                instance.facing(),
+            ),
+        }
         for component in node_asset.components.iter() {
             component.insert(node_entity_commands);
         }
-        commands.entity(gen_entity).add_child(node_entity);
+        if let Some(tint) = &node_asset.tint {
+            node_entity_commands.insert(TintColor(tint.color(instance, node_pos)));
+        }
+        commands.entity(parent).add_child(node_entity);
     }
 }
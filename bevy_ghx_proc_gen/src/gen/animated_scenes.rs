@@ -0,0 +1,165 @@
+#[cfg(feature = "reflect")]
+use bevy::reflect::TypePath;
+use bevy::{
+    animation::{
+        graph::{AnimationGraph, AnimationNodeIndex},
+        AnimationClip, AnimationPlayer,
+    },
+    app::{App, Plugin, Update},
+    asset::{Assets, Handle},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::Added,
+        system::{Commands, EntityCommands, Query},
+    },
+    hierarchy::{HierarchyQueryExt, Parent},
+    math::Vec3,
+    scene::{Scene, SceneBundle},
+    transform::components::Transform,
+    utils::default,
+};
+use ghx_proc_gen::generator::model::{ModelFacing, ModelRotation};
+
+use super::assets::{AssetsBundleSpawner, AssetsOrientation};
+
+/// Custom type to store a [`Handle`] to a glTF [`Scene`] and the animation to play when it is spawned (windmills, flags, water tiles, ...).
+///
+/// Once the scene is loaded, the [`ProcGenAnimatedScenesPlugin`] starts the animation on the [`AnimationPlayer`] of the scene.
+#[derive(Clone)]
+#[cfg_attr(feature = "reflect", derive(TypePath))]
+pub struct AnimatedScene {
+    /// Scene handle
+    pub scene: Handle<Scene>,
+    /// Animation played when the scene is spawned
+    pub auto_play: SceneAutoPlay,
+}
+
+impl AnimatedScene {
+    /// Creates an [`AnimatedScene`] playing `clip` in a loop. `graphs` is used to store the [`AnimationGraph`] of the clip
+    pub fn new(
+        scene: Handle<Scene>,
+        clip: Handle<AnimationClip>,
+        graphs: &mut Assets<AnimationGraph>,
+    ) -> Self {
+        let (graph, animation) = AnimationGraph::from_clip(clip);
+        Self {
+            scene,
+            auto_play: SceneAutoPlay {
+                graph: graphs.add(graph),
+                animation,
+                repeat: true,
+            },
+        }
+    }
+
+    /// Sets whether or not the animation is played in a loop. Defaults to `true`
+    pub fn with_repeat(mut self, repeat: bool) -> Self {
+        self.auto_play.repeat = repeat;
+        self
+    }
+}
+
+/// Component inserted on the root entity of a spawned [`AnimatedScene`], holding the animation to play on the [`AnimationPlayer`] of the scene
+#[derive(Component, Clone, Debug)]
+pub struct SceneAutoPlay {
+    /// Graph containing the animation
+    pub graph: Handle<AnimationGraph>,
+    /// Node of the animation in `graph`
+    pub animation: AnimationNodeIndex,
+    /// Whether or not the animation is played in a loop
+    pub repeat: bool,
+}
+
+/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Y+, unless the [`super::assets::AssetSpawner`] has some [`super::assets::AssetSpawner::assets_orientation`]
+impl AssetsBundleSpawner for AnimatedScene {
+    fn insert_bundle(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            ModelFacing::Up,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_oriented_bundle(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            facing,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_bundle_with_orientation(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+        orientation: &AssetsOrientation,
+    ) {
+        commands.insert((
+            SceneBundle {
+                scene: self.scene.clone(),
+                transform: Transform::from_translation(translation)
+                    .with_scale(scale)
+                    .with_rotation(orientation.rotation(rotation, facing)),
+                ..default()
+            },
+            self.auto_play.clone(),
+        ));
+    }
+}
+
+/// [`Plugin`] starting the animations of the spawned [`AnimatedScene`]s
+pub struct ProcGenAnimatedScenesPlugin;
+
+impl Plugin for ProcGenAnimatedScenesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, start_scenes_auto_play);
+    }
+}
+
+/// System used by [`ProcGenAnimatedScenesPlugin`] to start the [`SceneAutoPlay`] animation of a scene as soon as its [`AnimationPlayer`] is spawned
+pub fn start_scenes_auto_play(
+    mut commands: Commands,
+    mut new_players: Query<(Entity, &mut AnimationPlayer), Added<AnimationPlayer>>,
+    parents: Query<&Parent>,
+    auto_plays: Query<&SceneAutoPlay>,
+) {
+    for (player_entity, mut player) in new_players.iter_mut() {
+        // The player is spawned somewhere in the hierarchy of the scene, below its root entity
+        let Some(auto_play) = parents
+            .iter_ancestors(player_entity)
+            .find_map(|ancestor| auto_plays.get(ancestor).ok())
+        else {
+            continue;
+        };
+        commands
+            .entity(player_entity)
+            .insert(auto_play.graph.clone());
+        let animation = player.play(auto_play.animation);
+        if auto_play.repeat {
+            animation.repeat();
+        }
+    }
+}
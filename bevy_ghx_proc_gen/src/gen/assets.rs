@@ -1,16 +1,26 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::{Deref, DerefMut},
     sync::Arc,
 };
 
 use bevy::{
+    color::Color,
     ecs::{component::Component, system::EntityCommands},
-    math::Vec3,
+    math::{Quat, Vec3},
+};
+#[cfg(feature = "reflect")]
+use bevy::{
+    ecs::reflect::ReflectComponent,
+    reflect::{Reflect, TypePath},
 };
 use ghx_proc_gen::{
-    generator::model::{ModelIndex, ModelRotation},
-    ghx_grid::cartesian::coordinates::GridDelta,
+    generator::model::{ModelFacing, ModelIndex, ModelInstance, ModelRotation},
+    ghx_grid::{
+        cartesian::coordinates::{CartesianPosition, GridDelta},
+        direction::Direction,
+    },
+    grid_transform::{AxisMapping, GridTransform, ZOrdering},
 };
 
 /// Defines a struct which can spawn an assets [`bevy::prelude::Bundle`] (for example, a [`bevy::prelude::SpriteBundle`], a [`bevy::prelude::PbrBundle`], a [`bevy::prelude::SceneBundle`], ...).
@@ -23,6 +33,119 @@ pub trait AssetsBundleSpawner: Sync + Send + 'static {
         scale: Vec3,
         rotation: ModelRotation,
     );
+
+    /// Same as [`AssetsBundleSpawner::insert_bundle`], for a model which is also reoriented to `facing` (see [`ModelFacing`]).
+    ///
+    /// The default implementation ignores `facing`.
+    fn insert_oriented_bundle(
+        &self,
+        command: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        _facing: ModelFacing,
+    ) {
+        self.insert_bundle(command, translation, scale, rotation);
+    }
+
+    /// Same as [`AssetsBundleSpawner::insert_oriented_bundle`], with the rotation axis and handedness to use instead of the ones assumed by the implementation. Used when an [`AssetSpawner`] has some [`AssetSpawner::assets_orientation`].
+    ///
+    /// The default implementation ignores `orientation`.
+    fn insert_bundle_with_orientation(
+        &self,
+        command: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+        _orientation: &AssetsOrientation,
+    ) {
+        self.insert_oriented_bundle(command, translation, scale, rotation, facing);
+    }
+
+    /// Rotation axis and handedness assumed by this `AssetsBundleSpawner` when the [`AssetSpawner`] has no [`AssetSpawner::assets_orientation`]. Used to rotate the offsets of the [`ModelAsset`] along with the model.
+    ///
+    /// The default implementation returns [`AssetsOrientation::Y_UP`].
+    fn assumed_orientation(&self) -> AssetsOrientation {
+        AssetsOrientation::Y_UP
+    }
+}
+
+/// Handedness of the coordinate system the assets are spawned in
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Handedness {
+    /// Positive rotations are counter-clockwise when looking from the rotation axis towards the origin, as in Bevy
+    #[default]
+    RightHanded,
+    /// Positive rotations are clockwise when looking from the rotation axis towards the origin
+    LeftHanded,
+}
+
+/// Rotation axis and handedness used to orient the spawned assets from their [`ModelRotation`] and [`ModelFacing`].
+///
+/// It should match the rotation axis of the [`ghx_proc_gen::generator::rules::Rules`] of the spawned models, as seen in the world of the assets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssetsOrientation {
+    /// Axis the models are rotated around
+    pub rotation_axis: Direction,
+    /// Handedness of the rotations
+    pub handedness: Handedness,
+}
+
+impl AssetsOrientation {
+    /// Right-handed rotations around Y+, assumed by the default 3d [`AssetsBundleSpawner`] implementations
+    pub const Y_UP: Self = Self::new(Direction::YForward, Handedness::RightHanded);
+    /// Right-handed rotations around Z+, assumed by the default 2d [`AssetsBundleSpawner`] implementations
+    pub const Z_UP: Self = Self::new(Direction::ZForward, Handedness::RightHanded);
+
+    /// Constructor
+    pub const fn new(rotation_axis: Direction, handedness: Handedness) -> Self {
+        Self {
+            rotation_axis,
+            handedness,
+        }
+    }
+
+    /// Returns the rotation of a model rotated by `rotation` around the rotation axis, then reoriented to `facing`
+    pub fn rotation(&self, rotation: ModelRotation, facing: ModelFacing) -> Quat {
+        let rotation = Quat::from_axis_angle(
+            direction_to_vec(self.rotation_axis),
+            self.signed_angle(rotation),
+        );
+        match facing.reorientation(self.rotation_axis) {
+            None => rotation,
+            Some((reorientation, axis)) => {
+                Quat::from_axis_angle(direction_to_vec(axis), self.signed_angle(reorientation))
+                    * rotation
+            }
+        }
+    }
+
+    /// Returns the scale that flips an asset along the rotation axis if `flipped` is `true` (see [`ModelInstance::flipped`]), to be multiplied with the scale of the asset
+    pub fn flip_scale(&self, flipped: bool) -> Vec3 {
+        match flipped {
+            true => Vec3::ONE - 2. * direction_to_vec(self.rotation_axis).abs(),
+            false => Vec3::ONE,
+        }
+    }
+
+    fn signed_angle(&self, rotation: ModelRotation) -> f32 {
+        match self.handedness {
+            Handedness::RightHanded => rotation.rad(),
+            Handedness::LeftHanded => -rotation.rad(),
+        }
+    }
+}
+
+fn direction_to_vec(direction: Direction) -> Vec3 {
+    match direction {
+        Direction::XForward => Vec3::X,
+        Direction::XBackward => Vec3::NEG_X,
+        Direction::YForward => Vec3::Y,
+        Direction::YBackward => Vec3::NEG_Y,
+        Direction::ZForward => Vec3::Z,
+        Direction::ZBackward => Vec3::NEG_Z,
+    }
 }
 
 /// Trait used to represent a generic [`Component`]/[`bevy::prelude::Bundle`] container.
@@ -37,6 +160,7 @@ pub trait ComponentSpawner: Sync + Send + 'static {
 ///
 /// `Insert` will not even be called if your [`ModelAsset`] don't have components.
 #[derive(Clone)]
+#[cfg_attr(feature = "reflect", derive(TypePath))]
 pub struct NoComponents;
 impl ComponentSpawner for NoComponents {
     fn insert(&self, _commands: &mut EntityCommands) {}
@@ -51,12 +175,77 @@ pub struct ModelAsset<A: AssetsBundleSpawner, T: ComponentSpawner = NoComponents
     pub assets_bundle: A,
     /// Optionnal vector of [`ComponentSpawner`] that will be spawned for this model
     pub components: Vec<T>,
-    /// Grid offset from the generated grid node position. Added to `offset`.
+    /// Grid offset from the generated grid node position. Added to `offset`. Rotated with the model, unless [`AssetSpawner::rotate_offsets`] is `false`.
     pub grid_offset: GridDelta,
-    /// World offset from the generated grid node position. Added to `grid_offset`.
+    /// World offset from the generated grid node position. Added to `grid_offset`. Rotated with the model, unless [`AssetSpawner::rotate_offsets`] is `false`.
     pub offset: Vec3,
+    /// Optional tint applied to each spawned instance of this asset, see [`InstanceTint`]
+    pub tint: Option<InstanceTint>,
+}
+
+impl<A: AssetsBundleSpawner, T: ComponentSpawner> ModelAsset<A, T> {
+    /// Constructor for a `ModelAsset` spawning `assets_bundle`, with no components, no offsets and no tint
+    pub fn new(assets_bundle: A) -> Self {
+        Self {
+            assets_bundle,
+            components: Vec::new(),
+            grid_offset: Default::default(),
+            offset: Vec3::ZERO,
+            tint: None,
+        }
+    }
+
+    /// Sets the [`ComponentSpawner`] spawned with this asset
+    pub fn with_components(mut self, components: Vec<T>) -> Self {
+        self.components = components;
+        self
+    }
+
+    /// Sets the grid offset from the generated grid node position
+    pub fn with_grid_offset(mut self, grid_offset: GridDelta) -> Self {
+        self.grid_offset = grid_offset;
+        self
+    }
+
+    /// Sets the world offset from the generated grid node position
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the [`InstanceTint`] applied to each spawned instance of this asset
+    pub fn with_tint(mut self, tint: InstanceTint) -> Self {
+        self.tint = Some(tint);
+        self
+    }
+}
+
+/// Tint of the spawned instances of a [`ModelAsset`], which allows a single asset to serve multiple models with distinct looks.
+///
+/// The resulting [`TintColor`] is inserted on each spawned asset entity. With the `default-assets-bundle-spawners` feature, the [`super::default_bundles::ProcGenTintsPlugin`] applies it to the sprites and standard materials of the spawned entities.
+#[derive(Clone, Copy, Debug)]
+pub enum InstanceTint {
+    /// Same color for every instance
+    Fixed(Color),
+    /// Color computed for each instance, from the [`ModelInstance`] and its position in nodes units (for example a color derived from the rotation, or from the layer of the node)
+    PerInstance(fn(&ModelInstance, Vec3) -> Color),
 }
 
+impl InstanceTint {
+    /// Returns the tint color of an `instance` spawned at `node_pos`, expressed in nodes units
+    pub fn color(&self, instance: &ModelInstance, node_pos: Vec3) -> Color {
+        match self {
+            InstanceTint::Fixed(color) => *color,
+            InstanceTint::PerInstance(tint_fn) => tint_fn(instance, node_pos),
+        }
+    }
+}
+
+/// Component inserted on the spawned asset entities of a [`ModelAsset`] with an [`InstanceTint`]. Stores the color multiplied with the color of the asset
+#[derive(Component, Clone, Copy, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
+pub struct TintColor(pub Color);
+
 /// Defines a map which links a `Model` via its [`ModelIndex`] to his spawnable(s) [`ModelAsset`]
 #[derive(Debug)]
 pub struct RulesModelsAssets<A: AssetsBundleSpawner, T: ComponentSpawner = NoComponents> {
@@ -76,6 +265,12 @@ impl<A: AssetsBundleSpawner, T: ComponentSpawner> DerefMut for RulesModelsAssets
     }
 }
 
+impl<A: AssetsBundleSpawner, T: ComponentSpawner> Default for RulesModelsAssets<A, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<A: AssetsBundleSpawner, T: ComponentSpawner> RulesModelsAssets<A, T> {
     /// Create a new RulesModelsAssets with an empty map
     pub fn new() -> Self {
@@ -91,13 +286,7 @@ impl<A: AssetsBundleSpawner, T: ComponentSpawner> RulesModelsAssets<A, T> {
 
     /// Adds a [`ModelAsset`] with no grid offset, to the model `index`
     pub fn add_asset(&mut self, index: ModelIndex, asset: A) {
-        let model_asset = ModelAsset {
-            assets_bundle: asset,
-            grid_offset: Default::default(),
-            offset: Vec3::ZERO,
-            components: Vec::new(),
-        };
-        self.add(index, model_asset);
+        self.add(index, ModelAsset::new(asset));
     }
 
     /// Adds a [`ModelAsset`] to the model `index`
@@ -113,21 +302,58 @@ impl<A: AssetsBundleSpawner, T: ComponentSpawner> RulesModelsAssets<A, T> {
     }
 }
 
+/// Grid axis used to group the spawned nodes into layers, see [`AssetSpawner::with_layers`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayerAxis {
+    /// One layer per Y coordinate
+    Y,
+    /// One layer per Z coordinate
+    Z,
+}
+
+impl LayerAxis {
+    /// Returns the layer of a grid position
+    pub fn layer(&self, pos: &CartesianPosition) -> u32 {
+        match self {
+            LayerAxis::Y => pos.y,
+            LayerAxis::Z => pos.z,
+        }
+    }
+}
+
 /// Stores information needed to spawn assets from a [`ghx_proc_gen::generator::Generator`]
+///
+/// With the `reflect` feature, it implements `Reflect` when `A` and `T` implement `TypePath` (the default bundles and [`NoComponents`] do), but since it is generic, it has to be registered by the app: `app.register_type::<AssetSpawner<PbrMesh>>()`. Its [`AssetSpawner::assets`] are not reflected.
 #[derive(Component, Clone, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct AssetSpawner<A: AssetsBundleSpawner, T: ComponentSpawner = NoComponents> {
     /// Link a `Model` via its [`ModelIndex`] to his spawnable assets (can be shared by multiple [`AssetSpawner`])
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
     pub assets: Arc<RulesModelsAssets<A, T>>,
     /// Size of a node in world units
     pub node_size: Vec3,
     /// Scale of the assets when spawned
     pub spawn_scale: Vec3,
-    /// Whether to offset the z coordinate of spawned nodes from the y coordinate (used for 2d ordering of sprites)
-    pub z_offset_from_y: bool,
+    /// How to offset the z coordinate of spawned nodes (used for 2d ordering of sprites)
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    pub z_ordering: ZOrdering,
+    /// Position of the minimum corner of the grid, relatively to the generation entity
+    pub grid_origin: Vec3,
+    /// Mapping from the grid axis to the axis of the generation entity
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    pub axis_mapping: AxisMapping,
+    /// Rotation axis and handedness used to orient the spawned assets. When `None`, each [`AssetsBundleSpawner`] uses the one it assumes (Z+ for 2d, Y+ for 3d with the default implementations)
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    pub assets_orientation: Option<AssetsOrientation>,
+    /// When `Some`, the spawned nodes are grouped under one [`super::NodeLayer`] entity per layer along this axis, see [`AssetSpawner::with_layers`]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    pub layers_axis: Option<LayerAxis>,
+    /// If `true`, the `grid_offset` and `offset` of the [`ModelAsset`] are rotated with the model instance, so that multi-nodes assets stay together when their model is rotated
+    pub rotate_offsets: bool,
 }
 
 impl<A: AssetsBundleSpawner, T: ComponentSpawner> AssetSpawner<A, T> {
-    /// Constructor for a `AssetSpawner`, `z_offset_from_y` defaults to `false`
+    /// Constructor for a `AssetSpawner`, `z_ordering` defaults to [`ZOrdering::None`], `grid_origin` to [`Vec3::ZERO`], `axis_mapping` to [`AxisMapping::Identity`], `assets_orientation` and `layers_axis` to `None` and `rotate_offsets` to `true`
     pub fn new(
         models_assets: RulesModelsAssets<A, T>,
         node_size: Vec3,
@@ -137,13 +363,77 @@ impl<A: AssetsBundleSpawner, T: ComponentSpawner> AssetSpawner<A, T> {
             node_size,
             assets: Arc::new(models_assets),
             spawn_scale,
-            z_offset_from_y: false,
+            z_ordering: ZOrdering::None,
+            grid_origin: Vec3::ZERO,
+            axis_mapping: AxisMapping::Identity,
+            assets_orientation: None,
+            layers_axis: None,
+            rotate_offsets: true,
         }
     }
 
-    /// Sets the `z_offset_from_y` value
-    pub fn with_z_offset_from_y(mut self, z_offset_from_y: bool) -> Self {
-        self.z_offset_from_y = z_offset_from_y;
+    /// Sets the [`ZOrdering`] used to offset the z coordinate of spawned nodes, for example [`ZOrdering::FromY`] for top-down 2d views or [`ZOrdering::FromXY`] for isometric ones
+    pub fn with_z_ordering(mut self, z_ordering: ZOrdering) -> Self {
+        self.z_ordering = z_ordering;
+        self
+    }
+
+    /// Shortcut to use [`ZOrdering::FromY`] when `z_offset_from_y` is `true`, or [`ZOrdering::None`] otherwise
+    pub fn with_z_offset_from_y(self, z_offset_from_y: bool) -> Self {
+        self.with_z_ordering(match z_offset_from_y {
+            true => ZOrdering::FromY,
+            false => ZOrdering::None,
+        })
+    }
+
+    /// Sets the position of the minimum corner of the grid, relatively to the generation entity. Allows to place a generation anywhere without adding a parent entity.
+    ///
+    /// The grid debug views and markers of `bevy_ghx_grid` are not offset, and stay relative to the generation entity
+    pub fn with_grid_origin(mut self, grid_origin: Vec3) -> Self {
+        self.grid_origin = grid_origin;
+        self
+    }
+
+    /// Sets the mapping from the grid axis to the axis of the generation entity, for example [`AxisMapping::SwapYZ`] to spawn a grid generated with Y up in a Z-up world.
+    ///
+    /// Only the positions of the nodes are mapped, the assets themselves should be oriented with [`AssetSpawner::with_assets_orientation`]
+    pub fn with_axis_mapping(mut self, axis_mapping: AxisMapping) -> Self {
+        self.axis_mapping = axis_mapping;
         self
     }
+
+    /// Sets the rotation axis and handedness used to orient the spawned assets, for example `AssetsOrientation::new(Direction::ZForward, Handedness::RightHanded)` for 3d assets in a Z-up world
+    pub fn with_assets_orientation(mut self, assets_orientation: AssetsOrientation) -> Self {
+        self.assets_orientation = Some(assets_orientation);
+        self
+    }
+
+    /// Groups the spawned nodes under one [`super::NodeLayer`] child entity of the generation per layer along `layers_axis`, so that a whole layer can be hidden or despawned at once.
+    ///
+    /// The nodes are reparented by the [`super::group_spawned_nodes_into_layers`] system, which is added by the simple and debug plugins
+    pub fn with_layers(mut self, layers_axis: LayerAxis) -> Self {
+        self.layers_axis = Some(layers_axis);
+        self
+    }
+
+    /// Sets whether or not the offsets of the [`ModelAsset`] are rotated with the model instance. Disable it for assets whose offsets should stay fixed in the world whatever the rotation of their model
+    pub fn with_rotated_offsets(mut self, rotate_offsets: bool) -> Self {
+        self.rotate_offsets = rotate_offsets;
+        self
+    }
+
+    /// Returns the [`GridTransform`] used to place the nodes spawned by this `AssetSpawner`, relatively to their grid entity
+    pub fn grid_transform(&self) -> GridTransform {
+        GridTransform::new(self.node_size.to_array())
+            .with_origin(self.grid_origin.to_array())
+            .with_axis_mapping(self.axis_mapping)
+            .with_z_ordering(self.z_ordering)
+    }
+
+    /// Returns the indexes of the models (among the `original_models_count` models of some rules) which have no assets registered in this `AssetSpawner`
+    pub fn models_without_assets(&self, original_models_count: usize) -> HashSet<ModelIndex> {
+        (0..original_models_count)
+            .filter(|model_index| !self.assets.contains_key(model_index))
+            .collect()
+    }
 }
@@ -1,33 +1,92 @@
 use std::{marker::PhantomData, time::Duration};
 
+#[cfg(feature = "bindings-config")]
+use bevy::log::{info, warn};
+#[cfg(feature = "bindings-config")]
+use std::path::PathBuf;
+
 use bevy::{
     app::{App, Plugin, PostStartup, PostUpdate, PreUpdate, Startup, Update},
     color::{Alpha, Color},
     ecs::{schedule::IntoSystemConfigs, system::Resource},
-    input::keyboard::KeyCode,
+    input::{keyboard::KeyCode, ButtonInput},
+    math::{Vec2, Vec3},
     time::{Timer, TimerMode},
+    ui::{PositionType, Style, UiRect, Val},
 };
+#[cfg(feature = "reflect")]
+use bevy::{ecs::reflect::ReflectResource, reflect::Reflect};
 use bevy_ghx_grid::ghx_grid::coordinate_system::CoordinateSystem;
 use ghx_proc_gen::ghx_grid::cartesian::coordinates::CartesianCoordinates;
 
 use self::{
+    adjacency_check::{check_adjacency_violations, AdjacencyCheckSettings},
+    box_selection::{
+        update_selection_box, update_selection_box_info, SelectionBox, SelectionBoxSettings,
+    },
+    breakpoints::GenerationBreakpoints,
+    census::{
+        setup_model_census_panel, update_model_census_panel_text, ModelCensusSettings,
+        ModelCensusTracker,
+    },
+    comparison::sync_compared_generations,
+    contradiction::{
+        clear_contradiction_on_reinit, flash_contradiction_marker, handle_contradiction_events,
+        setup_contradiction_panel, update_contradiction_panel, ContradictionEvent,
+        ContradictionFlash, ContradictionReport, ContradictionSettings,
+    },
     cursor::{
-        deselect_from_keybinds, move_selection_from_keybinds, setup_cursor, setup_cursors_overlays,
-        setup_cursors_panel, switch_generation_selection_from_keybinds,
+        deselect_from_keybinds, move_selection_from_keybinds, setup_cursor,
+        setup_cursors_billboards, setup_cursors_overlays, setup_cursors_panel,
+        switch_generation_selection_from_keybinds, update_cursors_billboards,
         update_cursors_info_from_generation_events, update_cursors_info_on_cursors_changes,
-        update_cursors_overlays, update_selection_cursor_panel_text, CursorKeyboardMovement,
-        CursorKeyboardMovementSettings, SelectCursor, SelectionCursorMarkerSettings,
+        update_cursors_overlays, update_cursors_overlays_viewports,
+        update_selection_cursor_panel_text, CursorKeyboardMovement, CursorKeyboardMovementSettings,
+        SelectCursor, SelectionCursorMarkerSettings,
+    },
+    entropy_heatmap::{
+        toggle_entropy_heatmap_from_keybinds, update_entropy_heatmap, EntropyHeatmapSettings,
+    },
+    failure_heatmap::{
+        toggle_failure_heatmap_from_keybinds, update_failure_heatmap, FailureHeatmapSettings,
     },
     generation::{
-        generate_all, insert_error_markers_to_new_generations,
+        cleanup_removed_generations, generate_all, insert_error_markers_to_new_generations,
         insert_void_nodes_to_new_generations, step_by_step_input_update, step_by_step_timed_update,
-        update_active_generation, update_generation_control, update_generation_view,
+        sync_debug_grid_views_node_size, time_sliced_update, update_active_generation,
+        update_generation_control, update_generation_view, update_void_nodes_on_assets_change,
         ActiveGeneration, GenerationEvent,
     },
+    input::ProcGenGamepadBindings,
+    layer_slice::{update_layer_slice_from_keybinds, update_layer_slice_visibility, LayerSlice},
+    model_gallery::{
+        toggle_model_gallery_from_keybinds, update_model_gallery_labels, ModelGallery,
+        ModelGallerySettings,
+    },
+    model_highlight::{
+        toggle_model_highlight_from_keybinds, update_model_highlight_markers, HighlightedModel,
+        ModelHighlightSettings,
+    },
+    recording::record_generations,
+    region_reroll::reroll_selection_box_from_keybinds,
+    sockets_gizmos::{
+        draw_sockets_gizmos, toggle_sockets_gizmos_from_keybinds, update_generated_variants,
+        SocketsGizmosSettings,
+    },
+    stats::{
+        setup_generation_stats_panel, update_generation_stats_panel_text, GenerationStatsTracker,
+    },
+    timeline::update_generation_timelines,
+    ui_theme::apply_ui_theme_to_markers,
 };
 use super::{
-    assets::NoComponents, insert_default_bundle_to_spawned_nodes, spawn_node, AssetSpawner,
-    AssetsBundleSpawner, ComponentSpawner,
+    assets::NoComponents, group_spawned_nodes_into_layers, insert_default_bundle_to_spawned_nodes,
+    spawn_node, AssetSpawner, AssetsBundleSpawner, ComponentSpawner,
+};
+
+#[cfg(feature = "bindings-config")]
+use self::bindings_config::{
+    load_bindings_config, save_bindings_config, BindingsConfig, BindingsConfigPath,
 };
 
 #[cfg(feature = "picking")]
@@ -37,8 +96,10 @@ use bevy_mod_picking::PickableBundle;
 use self::picking::{
     insert_cursor_picking_handlers_to_grid_nodes, picking_remove_previous_over_cursor,
     picking_update_cursors_position, setup_picking_assets, update_cursor_targets_nodes,
-    update_over_cursor_from_generation_events, update_over_cursor_panel_text, CursorTargetAssets,
-    NodeOutEvent, NodeOverEvent, NodeSelectedEvent, OverCursor, OverCursorMarkerSettings,
+    update_over_cursor_from_generation_events, update_over_cursor_neighbours,
+    update_over_cursor_panel_text, CursorTargetAssets, NodeContextMenuEvent, NodeOutEvent,
+    NodeOverEvent, NodeSelectedEvent, OverCursor, OverCursorMarkerSettings,
+    OverCursorNeighboursSettings,
 };
 
 /// Module with picking features, enabled with the `picking` feature
@@ -47,18 +108,85 @@ pub mod picking;
 
 #[cfg(feature = "egui-edit")]
 use self::egui_editor::{
-    draw_edition_panel, editor_enabled, paint, update_brush, update_painting_state, BrushEvent,
-    EditorConfig, EditorContext,
+    copy_seed_from_keybinds, draw_edition_panel, draw_node_context_menu, editor_enabled,
+    open_node_context_menu, paint, update_brush, update_painting_state, BrushEvent, EditorConfig,
+    EditorContext,
 };
 
 /// Module providing a small egui editor, enabled with the `egui-edit` feature
 #[cfg(feature = "egui-edit")]
 pub mod egui_editor;
 
+#[cfg(feature = "egui-edit")]
+use self::rules_editor::{draw_rules_editor, draw_weights_panel, RulesEditor, WeightsPanel};
+
+/// Module providing an egui rules editor and models weights panel, enabled with the `egui-edit` feature
+#[cfg(feature = "egui-edit")]
+pub mod rules_editor;
+
+#[cfg(feature = "egui-edit")]
+use self::hover_inspector::{draw_hover_inspector, hover_inspector_enabled, HoverInspector};
+
+/// Module providing an egui panel detailing the internal state of the hovered node, enabled with the `egui-edit` feature
+#[cfg(feature = "egui-edit")]
+pub mod hover_inspector;
+
+#[cfg(feature = "egui-edit")]
+use self::painted_nodes::{draw_painted_nodes_panel, PaintedNodesFile};
+
+#[cfg(feature = "egui-edit")]
+use self::recording::{draw_recording_panel, RecordingFile};
+
+#[cfg(feature = "egui-edit")]
+use self::ui_theme::apply_ui_theme_to_egui;
+
+/// Module providing an egui panel to save and load the painted nodes, enabled with the `egui-edit` feature
+#[cfg(feature = "egui-edit")]
+pub mod painted_nodes;
+
+/// Module providing the adjacency check, used to highlight neighbour nodes not allowed by the rules
+pub mod adjacency_check;
+/// Module providing the loading and saving of the bindings from/to a config file, enabled with the `bindings-config` feature
+#[cfg(feature = "bindings-config")]
+pub mod bindings_config;
+/// Module providing the box selection of the selection cursor
+pub mod box_selection;
+/// Module providing generation breakpoints, used to pause the generation on specific events
+pub mod breakpoints;
+/// Module providing the model census panel, comparing the instances count of each model to its expected count from the weights
+pub mod census;
+/// Module providing the side by side comparison of generations sharing the same stepping state
+pub mod comparison;
+/// Module handling the report of the generation contradictions
+pub mod contradiction;
 /// Module providing all the grid cursors features
 pub mod cursor;
+/// Module providing the entropy heatmap overlay
+pub mod entropy_heatmap;
+/// Module providing the failure heatmap overlay, used to locate where the contradictions occur
+pub mod failure_heatmap;
 /// Module handling the generation fetaures of the debug_plugin
 pub mod generation;
+/// Module handling the keyboard and gamepad inputs of the debug plugin
+pub mod input;
+/// Module providing the layer slice view, used to display a single layer of the generated nodes
+pub mod layer_slice;
+/// Module providing a gallery displaying every model variation of a generation, used to check the models assets
+pub mod model_gallery;
+/// Module providing the highlight of all the instances of a model, used to judge the distribution of a model
+pub mod model_highlight;
+/// Module providing the recording of the generations events, which can be saved to a file and loaded back for replay
+pub mod recording;
+/// Module providing the regeneration of the nodes of the box selection
+pub mod region_reroll;
+/// Module providing gizmos displaying the sockets of the generated nodes
+pub mod sockets_gizmos;
+/// Module providing the generation stats panel
+pub mod stats;
+/// Module providing the generation timeline, used to move backward and forward in a generation history
+pub mod timeline;
+/// Module applying the debug UI style from [GridCursorsUiSettings]
+pub mod ui_theme;
 
 /// Used to configure how the cursors UI should be displayed
 #[derive(Default, Debug, PartialEq, Eq)]
@@ -70,10 +198,31 @@ pub enum CursorUiMode {
     /// Display as a small overlay panel over the [cursor::Cursor]
     #[default]
     Overlay,
+    /// Display as a world-space text billboard next to the [cursor::Cursor] marker, facing the camera.
+    ///
+    /// Unlike the screen-space modes, the billboard lives in the world and is seen by every camera rendering it. Uses a [`bevy::text::Text2dBundle`], so it is only rendered by 2d cameras.
+    Billboard,
 }
 
-/// Resource used to customize cursors UI
+/// Corner of the screen a debug UI panel is anchored to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(Reflect))]
+pub enum UiPanelAnchor {
+    /// Top left corner of the screen
+    TopLeft,
+    /// Top right corner of the screen
+    TopRight,
+    /// Bottom left corner of the screen
+    BottomLeft,
+    /// Bottom right corner of the screen
+    BottomRight,
+}
+
+/// Resource used to customize the style of the debug UI: cursors panel, overlays & billboards, stats, contradiction and census panels, model gallery labels, cursors markers and egui editor.
+///
+/// Panels style is read when they are spawned, other elements are updated when the resource changes.
 #[derive(Resource, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub struct GridCursorsUiSettings {
     /// Font size in the UI panels/overlays
     pub font_size: f32,
@@ -81,6 +230,32 @@ pub struct GridCursorsUiSettings {
     pub background_color: Color,
     /// Text colors in the UI panels/overlays
     pub text_color: Color,
+    /// Scale of the world-space billboards, see [CursorUiMode::Billboard]
+    pub billboard_scale: f32,
+    /// Offset of the world-space billboards from the cursors markers, see [CursorUiMode::Billboard]
+    pub billboard_offset: Vec3,
+    /// Offset (in pixels) of the screen-space overlays from the point they are attached to
+    pub overlay_offset: Vec2,
+    /// Margin between the UI panels and the screen borders
+    pub panel_margin: Val,
+    /// Padding inside the UI panels/overlays
+    pub panel_padding: UiRect,
+    /// Corner of the screen the cursors panel is anchored to, see [CursorUiMode::Panel]
+    pub cursors_panel_anchor: UiPanelAnchor,
+    /// Corner of the screen the generation stats panel is anchored to
+    pub stats_panel_anchor: UiPanelAnchor,
+    /// Corner of the screen the contradiction panel is anchored to
+    pub contradiction_panel_anchor: UiPanelAnchor,
+    /// Corner of the screen the model census panel is anchored to
+    pub census_panel_anchor: UiPanelAnchor,
+    /// Line width of the grid markers gizmos (cursors, errors, ...). [None] keeps the current gizmos config
+    pub marker_line_width: Option<f32>,
+    /// Color of the selection cursor marker. [None] keeps the color from [cursor::SelectionCursorMarkerSettings]
+    pub selection_cursor_color: Option<Color>,
+    /// Color of the over cursor marker. [None] keeps the color from the `OverCursorMarkerSettings`
+    pub over_cursor_color: Option<Color>,
+    /// Whether or not the egui windows (editor, rules editor, ...) also use the background & text colors
+    pub style_egui: bool,
 }
 
 impl Default for GridCursorsUiSettings {
@@ -89,6 +264,44 @@ impl Default for GridCursorsUiSettings {
             font_size: 16.0,
             background_color: Color::BLACK.with_alpha(0.45),
             text_color: Color::WHITE,
+            billboard_scale: 1.0,
+            billboard_offset: Vec3::new(0., 0., 10.),
+            overlay_offset: Vec2::splat(5.0),
+            panel_margin: Val::Percent(1.),
+            panel_padding: UiRect::all(Val::Px(4.0)),
+            cursors_panel_anchor: UiPanelAnchor::BottomRight,
+            stats_panel_anchor: UiPanelAnchor::TopRight,
+            contradiction_panel_anchor: UiPanelAnchor::BottomLeft,
+            census_panel_anchor: UiPanelAnchor::TopLeft,
+            marker_line_width: None,
+            selection_cursor_color: None,
+            over_cursor_color: None,
+            style_egui: false,
+        }
+    }
+}
+
+impl GridCursorsUiSettings {
+    /// Returns the [Style] of a debug UI panel anchored to `anchor`
+    pub fn panel_style(&self, anchor: UiPanelAnchor) -> Style {
+        let (left, right, top, bottom) = match anchor {
+            UiPanelAnchor::TopLeft => (self.panel_margin, Val::Auto, self.panel_margin, Val::Auto),
+            UiPanelAnchor::TopRight => (Val::Auto, self.panel_margin, self.panel_margin, Val::Auto),
+            UiPanelAnchor::BottomLeft => {
+                (self.panel_margin, Val::Auto, Val::Auto, self.panel_margin)
+            }
+            UiPanelAnchor::BottomRight => {
+                (Val::Auto, self.panel_margin, Val::Auto, self.panel_margin)
+            }
+        };
+        Style {
+            position_type: PositionType::Absolute,
+            left,
+            right,
+            top,
+            bottom,
+            padding: self.panel_padding,
+            ..Default::default()
         }
     }
 }
@@ -97,7 +310,7 @@ impl Default for GridCursorsUiSettings {
 ///
 /// It takes in a [`GenerationViewMode`] to control how the generators components will be run.
 ///
-/// It also uses the following `Resources`: [`ProcGenKeyBindings`] and [`GenerationControl`] (and will init them to their defaults if not inserted by the user).
+/// It also uses the following `Resources`: [`ProcGenKeyBindings`], [`input::ProcGenGamepadBindings`], [`GenerationControl`] and [`breakpoints::GenerationBreakpoints`] (and will init them to their defaults if not inserted by the user).
 pub struct ProcGenDebugPlugin<
     C: CoordinateSystem,
     A: AssetsBundleSpawner,
@@ -105,6 +318,14 @@ pub struct ProcGenDebugPlugin<
 > {
     generation_view_mode: GenerationViewMode,
     cursor_ui_mode: CursorUiMode,
+    stats_panel: bool,
+    census_panel: bool,
+    contradiction_panel: bool,
+    heatmaps: bool,
+    model_gallery: bool,
+    sockets_gizmos: bool,
+    #[cfg(feature = "bindings-config")]
+    bindings_config: Option<PathBuf>,
     typestate: PhantomData<(C, A, T)>,
 }
 
@@ -114,9 +335,64 @@ impl<C: CoordinateSystem, A: AssetsBundleSpawner, T: ComponentSpawner> ProcGenDe
         Self {
             generation_view_mode,
             cursor_ui_mode,
+            stats_panel: false,
+            census_panel: false,
+            contradiction_panel: false,
+            heatmaps: false,
+            model_gallery: false,
+            sockets_gizmos: false,
+            #[cfg(feature = "bindings-config")]
+            bindings_config: None,
             typestate: PhantomData,
         }
     }
+
+    /// Enables or disables the generation stats panel (steps per second, bans, contradictions, ...). Disabled by default.
+    pub fn with_stats_panel(mut self, enabled: bool) -> Self {
+        self.stats_panel = enabled;
+        self
+    }
+
+    /// Enables or disables the model census panel, listing the instances count of each model against its expected count from the weights. Disabled by default.
+    pub fn with_census_panel(mut self, enabled: bool) -> Self {
+        self.census_panel = enabled;
+        self
+    }
+
+    /// Enables or disables the contradiction panel, describing the last contradiction of the active generation. Disabled by default.
+    ///
+    /// The selection cursor is focused on the failing node and its marker flashes whether the panel is enabled or not.
+    pub fn with_contradiction_panel(mut self, enabled: bool) -> Self {
+        self.contradiction_panel = enabled;
+        self
+    }
+
+    /// Enables or disables the entropy and failure heatmaps (and their keybinds). Disabled by default.
+    pub fn with_heatmaps(mut self, enabled: bool) -> Self {
+        self.heatmaps = enabled;
+        self
+    }
+
+    /// Enables or disables the model gallery (and its keybind). Disabled by default.
+    pub fn with_model_gallery(mut self, enabled: bool) -> Self {
+        self.model_gallery = enabled;
+        self
+    }
+
+    /// Enables or disables the sockets gizmos of the generated nodes (and their keybind). Disabled by default.
+    pub fn with_sockets_gizmos(mut self, enabled: bool) -> Self {
+        self.sockets_gizmos = enabled;
+        self
+    }
+
+    /// Loads the keyboard and gamepad bindings from the RON config file at `path` when the plugin is built, see [`bindings_config::BindingsConfig`]. The bindings of the config file replace any bindings `Resource` already inserted.
+    ///
+    /// If the file does not exist, it is created with the default bindings. The bindings can be saved back to this file with [`bindings_config::save_bindings_to_config`].
+    #[cfg(feature = "bindings-config")]
+    pub fn with_bindings_config<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.bindings_config = Some(path.into());
+        self
+    }
 }
 
 impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugin
@@ -125,34 +401,94 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
     // TODO Clean: Split into multiple plugins
     fn build(&self, app: &mut App) {
         app.insert_resource(self.generation_view_mode);
+
+        #[cfg(feature = "reflect")]
+        app.register_type::<crate::gen::GridNode>()
+            .register_type::<crate::gen::NodeLayer>()
+            .register_type::<generation::VoidNodes>()
+            .register_type::<generation::ErrorMarkers>()
+            .register_type::<generation::ActiveGeneration>()
+            .register_type::<comparison::ComparedGeneration>()
+            .register_type::<GenerationViewMode>()
+            .register_type::<GenerationControl>()
+            .register_type::<StepByStepTimed>()
+            .register_type::<GridCursorsUiSettings>()
+            .register_type::<cursor::Cursor>()
+            .register_type::<cursor::SelectCursor>()
+            .register_type::<cursor::CursorOverlay>()
+            .register_type::<cursor::CursorBillboard>()
+            .register_type::<cursor::SelectionCursorMarkerSettings>()
+            .register_type::<cursor::CursorKeyboardMovementSettings>();
+        #[cfg(all(feature = "reflect", feature = "picking"))]
+        app.register_type::<picking::OverCursor>()
+            .register_type::<picking::OverCursorMarkerSettings>();
         app.insert_resource(ActiveGeneration::default());
 
+        #[cfg(feature = "bindings-config")]
+        if let Some(path) = &self.bindings_config {
+            load_bindings_from_config(app, path);
+        }
+
         // If the resources already exists, nothing happens, else, add them with default values.
         app.init_resource::<ProcGenKeyBindings>()
             .init_resource::<GenerationControl>()
+            .init_resource::<GenerationBreakpoints>()
+            .init_resource::<ProcGenGamepadBindings>()
             .init_resource::<SelectionCursorMarkerSettings>()
+            .init_resource::<SelectionBoxSettings>()
+            .init_resource::<SelectionBox>()
             .init_resource::<CursorKeyboardMovement>()
-            .init_resource::<CursorKeyboardMovementSettings>();
-        match self.cursor_ui_mode {
-            CursorUiMode::None => (),
-            _ => {
-                app.init_resource::<GridCursorsUiSettings>();
-            }
-        }
+            .init_resource::<CursorKeyboardMovementSettings>()
+            .init_resource::<EntropyHeatmapSettings>()
+            .init_resource::<FailureHeatmapSettings>()
+            .init_resource::<LayerSlice>()
+            .init_resource::<ModelGallerySettings>()
+            .init_resource::<ModelGallery>()
+            .init_resource::<SocketsGizmosSettings>()
+            .init_resource::<AdjacencyCheckSettings>()
+            .init_resource::<ModelHighlightSettings>()
+            .init_resource::<HighlightedModel>()
+            .init_resource::<GridCursorsUiSettings>();
 
         app.add_event::<GenerationEvent>();
+        #[cfg(feature = "rules-hot-reload")]
+        app.add_event::<crate::gen::rules_reload::RulesReloadedEvent>()
+            .add_systems(Update, generation::resume_reloaded_generations);
+
+        app.add_systems(Update, apply_ui_theme_to_markers)
+            .init_resource::<ContradictionSettings>()
+            .init_resource::<ContradictionReport>()
+            .init_resource::<ContradictionFlash>()
+            .add_event::<ContradictionEvent>()
+            .add_systems(Update, flash_contradiction_marker)
+            .add_systems(
+                PostUpdate,
+                (
+                    clear_contradiction_on_reinit,
+                    handle_contradiction_events::<C>,
+                )
+                    .chain(),
+            );
 
         #[cfg(feature = "egui-edit")]
         app.init_resource::<EditorConfig>()
             .init_resource::<EditorContext>()
-            .add_event::<BrushEvent>();
+            .init_resource::<RulesEditor>()
+            .init_resource::<HoverInspector>()
+            .init_resource::<WeightsPanel>()
+            .init_resource::<PaintedNodesFile>()
+            .init_resource::<RecordingFile>()
+            .add_event::<BrushEvent>()
+            .add_systems(Update, apply_ui_theme_to_egui);
 
         #[cfg(feature = "picking")]
         app.init_resource::<CursorTargetAssets>()
             .init_resource::<OverCursorMarkerSettings>()
+            .init_resource::<OverCursorNeighboursSettings>()
             .add_event::<NodeOverEvent>()
             .add_event::<NodeOutEvent>()
-            .add_event::<NodeSelectedEvent>();
+            .add_event::<NodeSelectedEvent>()
+            .add_event::<NodeContextMenuEvent>();
 
         app
             // PostStartup to wait for setup_cursors_overlays to be applied.
@@ -161,20 +497,41 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
             .add_systems(
                 PreUpdate,
                 (
+                    cleanup_removed_generations::<C>,
                     deselect_from_keybinds,
                     switch_generation_selection_from_keybinds::<C>,
                     move_selection_from_keybinds::<C>,
+                    update_layer_slice_from_keybinds::<C>,
+                    reroll_selection_box_from_keybinds::<C>,
+                    toggle_model_highlight_from_keybinds::<C>,
                 ),
             )
             .add_systems(
                 Update,
                 (
+                    sync_debug_grid_views_node_size::<A, T>,
                     update_generation_control,
                     update_active_generation::<C>,
                     update_cursors_info_on_cursors_changes::<C>,
                 ),
             )
-            .add_systems(PostUpdate, update_cursors_info_from_generation_events::<C>);
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        update_cursors_info_from_generation_events::<C>,
+                        update_selection_box,
+                        update_selection_box_info::<C>,
+                    )
+                        .chain(),
+                    update_generation_timelines::<C>,
+                    record_generations::<C>,
+                    update_layer_slice_visibility::<C>,
+                    check_adjacency_violations::<C>,
+                    update_model_highlight_markers::<C>,
+                    group_spawned_nodes_into_layers::<C, A, T>,
+                ),
+            );
 
         #[cfg(feature = "picking")]
         app.add_systems(Startup, setup_picking_assets)
@@ -209,8 +566,12 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
             )
             .add_systems(
                 PostUpdate,
-                update_over_cursor_from_generation_events::<C>
-                    .before(update_cursors_info_from_generation_events::<C>),
+                (
+                    update_over_cursor_from_generation_events::<C>
+                        .before(update_cursors_info_from_generation_events::<C>),
+                    update_over_cursor_neighbours::<C>
+                        .after(update_cursors_info_from_generation_events::<C>),
+                ),
             );
 
         #[cfg(feature = "egui-edit")]
@@ -218,13 +579,30 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
             Update,
             (
                 draw_edition_panel::<C>,
+                open_node_context_menu,
+                draw_node_context_menu::<C>,
                 update_brush,
                 update_painting_state,
                 paint::<C>,
             )
                 .chain()
                 .run_if(editor_enabled),
-        );
+        )
+        .add_systems(
+            Update,
+            (
+                draw_rules_editor::<C>,
+                draw_weights_panel::<C>,
+                draw_painted_nodes_panel::<C>,
+                draw_recording_panel::<C>,
+            )
+                .run_if(editor_enabled),
+        )
+        .add_systems(
+            Update,
+            draw_hover_inspector::<C>.run_if(hover_inspector_enabled),
+        )
+        .add_systems(Update, copy_seed_from_keybinds::<C>);
 
         match self.cursor_ui_mode {
             CursorUiMode::None => (),
@@ -236,8 +614,61 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
             }
             CursorUiMode::Overlay => {
                 app.add_systems(Startup, setup_cursors_overlays);
-                app.add_systems(Update, update_cursors_overlays);
+                app.add_systems(
+                    Update,
+                    (update_cursors_overlays_viewports, update_cursors_overlays).chain(),
+                );
             }
+            CursorUiMode::Billboard => {
+                app.add_systems(Startup, setup_cursors_billboards);
+                app.add_systems(Update, update_cursors_billboards);
+            }
+        }
+
+        if self.stats_panel {
+            app.init_resource::<GenerationStatsTracker>()
+                .add_systems(Startup, setup_generation_stats_panel)
+                .add_systems(PostUpdate, update_generation_stats_panel_text::<C>);
+        }
+
+        if self.census_panel {
+            app.init_resource::<ModelCensusSettings>()
+                .init_resource::<ModelCensusTracker>()
+                .add_systems(Startup, setup_model_census_panel)
+                .add_systems(PostUpdate, update_model_census_panel_text::<C>);
+        }
+
+        if self.contradiction_panel {
+            app.add_systems(Startup, setup_contradiction_panel)
+                .add_systems(
+                    PostUpdate,
+                    update_contradiction_panel.after(handle_contradiction_events::<C>),
+                );
+        }
+
+        if self.heatmaps {
+            app.add_systems(
+                PreUpdate,
+                (
+                    toggle_entropy_heatmap_from_keybinds,
+                    toggle_failure_heatmap_from_keybinds,
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (update_entropy_heatmap::<C>, update_failure_heatmap::<C>),
+            );
+        }
+
+        if self.model_gallery {
+            app.add_systems(PreUpdate, toggle_model_gallery_from_keybinds::<C, A, T>)
+                .add_systems(Update, update_model_gallery_labels);
+        }
+
+        if self.sockets_gizmos {
+            app.add_systems(PreUpdate, toggle_sockets_gizmos_from_keybinds)
+                .add_systems(Update, draw_sockets_gizmos::<C>)
+                .add_systems(PostUpdate, update_generated_variants::<C>);
         }
 
         match self.generation_view_mode {
@@ -251,8 +682,10 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
                         (
                             insert_error_markers_to_new_generations::<C>,
                             insert_void_nodes_to_new_generations::<C, A, T>,
+                            update_void_nodes_on_assets_change::<C, A, T>,
                         ),
                         step_by_step_timed_update::<C>,
+                        sync_compared_generations::<C>,
                         update_generation_view::<C, A, T>,
                     )
                         .chain(),
@@ -262,6 +695,25 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
                     timer: Timer::new(Duration::from_millis(interval_ms), TimerMode::Repeating),
                 });
             }
+            GenerationViewMode::TimeSliced { budget_ms } => {
+                app.add_systems(
+                    Update,
+                    (
+                        (
+                            insert_error_markers_to_new_generations::<C>,
+                            insert_void_nodes_to_new_generations::<C, A, T>,
+                            update_void_nodes_on_assets_change::<C, A, T>,
+                        ),
+                        time_sliced_update::<C>,
+                        sync_compared_generations::<C>,
+                        update_generation_view::<C, A, T>,
+                    )
+                        .chain(),
+                );
+                app.insert_resource(TimeSliced {
+                    budget: Duration::from_millis(budget_ms),
+                });
+            }
             GenerationViewMode::StepByStepManual => {
                 app.add_systems(
                     Update,
@@ -269,8 +721,10 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
                         (
                             insert_error_markers_to_new_generations::<C>,
                             insert_void_nodes_to_new_generations::<C, A, T>,
+                            update_void_nodes_on_assets_change::<C, A, T>,
                         ),
                         step_by_step_input_update::<C>,
+                        sync_compared_generations::<C>,
                         update_generation_view::<C, A, T>,
                     )
                         .chain(),
@@ -279,15 +733,45 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
             GenerationViewMode::Final => {
                 app.add_systems(
                     Update,
-                    (generate_all::<C>, update_generation_view::<C, A, T>).chain(),
+                    (
+                        generate_all::<C>,
+                        sync_compared_generations::<C>,
+                        update_generation_view::<C, A, T>,
+                    )
+                        .chain(),
                 );
             }
         }
     }
 }
 
+#[cfg(feature = "bindings-config")]
+fn load_bindings_from_config(app: &mut App, path: &PathBuf) {
+    app.insert_resource(BindingsConfigPath(path.clone()));
+    if !path.exists() {
+        let config = BindingsConfig::default();
+        match save_bindings_config(path, &config.key_bindings, &config.gamepad_bindings) {
+            Ok(()) => info!("Created a default bindings config at {:?}", path),
+            Err(err) => warn!("Failed to create a bindings config at {:?}: {}", path, err),
+        }
+        return;
+    }
+    match load_bindings_config(path) {
+        Ok(config) => {
+            info!("Loaded the bindings config from {:?}", path);
+            app.insert_resource(config.key_bindings)
+                .insert_resource(config.gamepad_bindings);
+        }
+        Err(err) => warn!(
+            "Failed to load the bindings config from {:?}, using the default bindings: {}",
+            path, err
+        ),
+    }
+}
+
 /// Controls how the generation occurs.
 #[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub enum GenerationViewMode {
     /// Generates steps by steps and waits at least the specified amount (in milliseconds) between each step.
     StepByStepTimed {
@@ -296,6 +780,13 @@ pub enum GenerationViewMode {
         /// Time to wait in ms before the next steps
         interval_ms: u64,
     },
+    /// Generates step by step and runs as many steps as fit in a time budget on each frame (at least one step per frame).
+    ///
+    /// Unlike [`GenerationViewMode::StepByStepTimed`], the generation speed adapts to the cost of the steps: fast on simple rules, without hitches on rules with heavy propagations.
+    TimeSliced {
+        /// Time budget in ms given to the generation on each frame
+        budget_ms: u64,
+    },
     /// Generates step by step and waits for a user input between each step.
     StepByStepManual,
     /// Generates it all at once at the start
@@ -305,6 +796,7 @@ pub enum GenerationViewMode {
 
 /// Used to track the status of the generation control
 #[derive(Resource, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub enum GenerationControlStatus {
     /// Generation control is paused, systems won't automatically step the generation
     Paused,
@@ -314,6 +806,7 @@ pub enum GenerationControlStatus {
 
 /// Read by the systems while generating
 #[derive(Resource)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub struct GenerationControl {
     /// Current status of the generation
     pub status: GenerationControlStatus,
@@ -352,6 +845,7 @@ impl Default for GenerationControl {
 
 /// Resource to track the generation steps when using [`GenerationViewMode::StepByStepTimed`]
 #[derive(Resource)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub struct StepByStepTimed {
     /// How many steps should be done once the timer has expired
     pub steps_count: u32,
@@ -359,9 +853,72 @@ pub struct StepByStepTimed {
     pub timer: Timer,
 }
 
-/// Resource available to override the default keybindings used by the [`ProcGenDebugPlugin`], usign a QWERTY layout ()
+/// Resource holding the per-frame time budget of the generation when using [`GenerationViewMode::TimeSliced`]
 #[derive(Resource)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
+pub struct TimeSliced {
+    /// Time budget given to the generation on each frame
+    pub budget: Duration,
+}
+
+/// Resource available to override the default keybindings used by the [`ProcGenDebugPlugin`], usign a QWERTY layout ()
+///
+/// The keybindings are split into input contexts which can each be disabled or remapped individually, at any time, to avoid collisions with the keybindings of an application embedding the plugin.
+#[derive(Resource, Default)]
+#[cfg_attr(
+    feature = "bindings-config",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ProcGenKeyBindings {
+    /// Keybindings of the selection cursor
+    pub cursor: CursorKeyBindings,
+    /// Keybindings of the generation control
+    pub generation: GenerationKeyBindings,
+    /// Keybindings of the editor and of the debug views
+    pub editor: EditorKeyBindings,
+}
+
+impl ProcGenKeyBindings {
+    /// Returns whether or not the input `context` is enabled
+    pub fn is_enabled(&self, context: ProcGenInputContext) -> bool {
+        match context {
+            ProcGenInputContext::Cursor => self.cursor.enabled,
+            ProcGenInputContext::Generation => self.generation.enabled,
+            ProcGenInputContext::Editor => self.editor.enabled,
+        }
+    }
+
+    /// Enables or disables the input `context`. Inputs of a disabled context are ignored, on the keyboard as well as on the gamepads.
+    pub fn set_enabled(&mut self, context: ProcGenInputContext, enabled: bool) {
+        match context {
+            ProcGenInputContext::Cursor => self.cursor.enabled = enabled,
+            ProcGenInputContext::Generation => self.generation.enabled = enabled,
+            ProcGenInputContext::Editor => self.editor.enabled = enabled,
+        }
+    }
+}
+
+/// Input contexts of the [`ProcGenKeyBindings`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProcGenInputContext {
+    /// Selection cursor movements and selection, see [`CursorKeyBindings`]
+    Cursor,
+    /// Generation control, see [`GenerationKeyBindings`]
+    Generation,
+    /// Editor and debug views, see [`EditorKeyBindings`]
+    Editor,
+}
+
+/// Keybindings of the selection cursor
+#[cfg_attr(
+    feature = "bindings-config",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct CursorKeyBindings {
+    /// Whether or not those keybindings are enabled
+    pub enabled: bool,
     /// Key to move the selection cursor to the previous node on the current axis
     pub prev_node: KeyCode,
     /// Key to move the selection cursor to the next node on the current axis
@@ -374,30 +931,108 @@ pub struct ProcGenKeyBindings {
     pub cursor_z_axis: KeyCode,
     /// Key to deselect the current selection
     pub deselect: KeyCode,
+    /// Key held to extend the selection as a box when moving the selection cursor
+    pub box_selection: KeyCode,
     /// Key to move the selection cursor to another grid
     pub switch_grid: KeyCode,
-
-    /// Key to pause/unpause the current [`GenerationControlStatus`]
-    pub pause_toggle: KeyCode,
-    /// Key used only with [`GenerationViewMode::StepByStepManual`] to step once per press
-    pub step: KeyCode,
-    /// Key used only with [`GenerationViewMode::StepByStepManual`] to step continuously as long as pressed
-    pub continuous_step: KeyCode,
 }
 
-impl Default for ProcGenKeyBindings {
+impl Default for CursorKeyBindings {
     fn default() -> Self {
         Self {
+            enabled: true,
             prev_node: KeyCode::ArrowLeft,
             next_node: KeyCode::ArrowRight,
             cursor_x_axis: KeyCode::KeyX,
             cursor_y_axis: KeyCode::KeyY,
             cursor_z_axis: KeyCode::KeyZ,
             deselect: KeyCode::Escape,
+            box_selection: KeyCode::ShiftLeft,
             switch_grid: KeyCode::Tab,
+        }
+    }
+}
+
+/// Keybindings of the generation control
+#[cfg_attr(
+    feature = "bindings-config",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct GenerationKeyBindings {
+    /// Whether or not those keybindings are enabled
+    pub enabled: bool,
+    /// Key to pause/unpause the current [`GenerationControlStatus`]
+    pub pause_toggle: KeyCode,
+    /// Key used only with [`GenerationViewMode::StepByStepManual`] to step once per press
+    pub step: KeyCode,
+    /// Key used only with [`GenerationViewMode::StepByStepManual`] to step continuously as long as pressed
+    pub continuous_step: KeyCode,
+}
+
+impl Default for GenerationKeyBindings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
             pause_toggle: KeyCode::Space,
             step: KeyCode::ArrowDown,
             continuous_step: KeyCode::ArrowUp,
         }
     }
 }
+
+/// Keybindings of the editor and of the debug views
+#[cfg_attr(
+    feature = "bindings-config",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct EditorKeyBindings {
+    /// Whether or not those keybindings are enabled
+    pub enabled: bool,
+    /// Key to copy the seed of the active generation to the clipboard. Only used with the `egui-edit` feature
+    pub copy_seed: KeyCode,
+    /// Key to toggle the entropy heatmap of the generations
+    pub toggle_entropy_heatmap: KeyCode,
+    /// Key to toggle the failure heatmap of the generations
+    pub toggle_failure_heatmap: KeyCode,
+    /// Key to cycle the layer slice view between disabled, Y axis and Z axis slicing
+    pub toggle_layer_slice: KeyCode,
+    /// Key to move the layer slice to the next layer
+    pub slice_up: KeyCode,
+    /// Key to move the layer slice to the previous layer
+    pub slice_down: KeyCode,
+    /// Key to spawn/despawn the model gallery of the active generation
+    pub toggle_model_gallery: KeyCode,
+    /// Key to toggle the sockets gizmos of the generated nodes
+    pub toggle_sockets_gizmos: KeyCode,
+    /// Key to regenerate the nodes of the current box selection
+    pub reroll_selection: KeyCode,
+    /// Key to highlight all the instances of the model generated on the selected node
+    pub toggle_model_highlight: KeyCode,
+}
+
+impl Default for EditorKeyBindings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            copy_seed: KeyCode::KeyC,
+            toggle_entropy_heatmap: KeyCode::KeyH,
+            toggle_failure_heatmap: KeyCode::KeyF,
+            toggle_layer_slice: KeyCode::KeyL,
+            slice_up: KeyCode::PageUp,
+            slice_down: KeyCode::PageDown,
+            toggle_model_gallery: KeyCode::KeyG,
+            toggle_sockets_gizmos: KeyCode::KeyK,
+            reroll_selection: KeyCode::KeyR,
+            toggle_model_highlight: KeyCode::KeyM,
+        }
+    }
+}
+
+impl EditorKeyBindings {
+    /// Returns whether or not `key` was just pressed, and those keybindings are enabled
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>, key: KeyCode) -> bool {
+        self.enabled && keys.just_pressed(key)
+    }
+}
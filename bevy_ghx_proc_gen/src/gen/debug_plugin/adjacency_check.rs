@@ -0,0 +1,145 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{
+    color::{palettes::css::FUCHSIA, Color},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        query::{With, Without},
+        system::{Commands, Query, Res, Resource},
+    },
+    hierarchy::Parent,
+    log::warn,
+    prelude::{Deref, DerefMut},
+};
+use bevy_ghx_grid::debug_plugin::markers::{spawn_marker, MarkerDespawnEvent};
+use ghx_proc_gen::{
+    generator::{
+        model::{ModelInstance, ModelVariantIndex},
+        Generator,
+    },
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    NodeIndex,
+};
+
+use crate::gen::{node_generation, GridNode, NodeLayer};
+
+use super::{generation::GenerationEvent, picking::CursorTarget};
+
+/// Resource used to customize the adjacency check of the generations
+#[derive(Resource, Debug)]
+pub struct AdjacencyCheckSettings {
+    /// Whether or not the nodes of the generations are validated against their rules when they are updated. Defaults to `false`
+    pub enabled: bool,
+    /// Color of the markers spawned on the nodes of an illegal neighbour pair
+    pub color: Color,
+}
+
+impl Default for AdjacencyCheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Color::Srgba(FUCHSIA),
+        }
+    }
+}
+
+/// Component used to store the [`bevy_ghx_grid::debug_plugin::markers::GridMarker`] entities of the adjacency violations of a generation
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct AdjacencyViolationMarkers(pub Vec<Entity>);
+
+/// System validating the nodes of the generations updated by a [`GenerationEvent`] against their rules, see [`Generator::adjacency_violations`].
+///
+/// The validated nodes are the ones the user sees or edits: the spawned [`GridNode`] entities (with the [`ModelInstance`] they were spawned with) and the initial nodes of the generator (forced or painted nodes). When a node is both, its spawned model is checked.
+///
+/// Both nodes of each adjacent pair that is not allowed by the rules are highlighted with a marker.
+pub fn check_adjacency_violations<C: CartesianCoordinates>(
+    mut commands: Commands,
+    check_settings: Res<AdjacencyCheckSettings>,
+    mut generation_events: EventReader<GenerationEvent>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    mut generations: Query<(
+        Entity,
+        &Generator<C, CartesianGrid<C>>,
+        Option<&mut AdjacencyViolationMarkers>,
+    )>,
+    spawned_nodes: Query<(&GridNode, &ModelInstance, &Parent), Without<CursorTarget>>,
+    node_layers: Query<&Parent, With<NodeLayer>>,
+) {
+    let mut updated_generations = HashSet::new();
+    for event in generation_events.read() {
+        match event {
+            GenerationEvent::Reinitialized(gen_entity) => updated_generations.insert(*gen_entity),
+            GenerationEvent::Updated(gen_entity, _) => updated_generations.insert(*gen_entity),
+        };
+    }
+    if !check_settings.is_changed() && updated_generations.is_empty() {
+        return;
+    }
+
+    let mut spawned_models: HashMap<Entity, Vec<(NodeIndex, ModelInstance)>> = HashMap::new();
+    if check_settings.enabled {
+        for (node, instance, parent) in spawned_nodes.iter() {
+            spawned_models
+                .entry(node_generation(parent, &node_layers))
+                .or_default()
+                .push((node.0, *instance));
+        }
+    }
+
+    for (gen_entity, generator, violation_markers) in generations.iter_mut() {
+        if !check_settings.is_changed() && !updated_generations.contains(&gen_entity) {
+            continue;
+        }
+
+        if let Some(mut violation_markers) = violation_markers {
+            for marker in violation_markers.drain(..) {
+                marker_events.send(MarkerDespawnEvent::Marker(marker));
+            }
+        }
+        if !check_settings.enabled {
+            continue;
+        }
+
+        let rules = generator.rules();
+        let mut nodes: Vec<(NodeIndex, ModelVariantIndex)> = generator.initial_nodes().clone();
+        if let Some(spawned) = spawned_models.get(&gen_entity) {
+            nodes.extend(spawned.iter().filter_map(|(node_index, instance)| {
+                rules
+                    .instance_variant_index(instance)
+                    .map(|variant_index| (*node_index, variant_index))
+            }));
+        }
+        let violations = generator.adjacency_violations(&nodes);
+        if violations.is_empty() {
+            continue;
+        }
+        warn!(
+            "Generation {:?} has {} illegal neighbour pair(s), first one: node {} ({}) next to node {} ({})",
+            gen_entity,
+            violations.len(),
+            violations[0].from.node_index,
+            violations[0].from.model_instance,
+            violations[0].to.node_index,
+            violations[0].to.model_instance,
+        );
+        let mut highlighted_nodes = HashSet::new();
+        let mut markers = Vec::new();
+        for violation in violations.iter() {
+            for node in [violation.from, violation.to] {
+                if highlighted_nodes.insert(node.node_index) {
+                    markers.push(spawn_marker(
+                        &mut commands,
+                        gen_entity,
+                        check_settings.color,
+                        generator.grid().pos_from_index(node.node_index),
+                    ));
+                }
+            }
+        }
+        commands
+            .entity(gen_entity)
+            .insert(AdjacencyViolationMarkers(markers));
+    }
+}
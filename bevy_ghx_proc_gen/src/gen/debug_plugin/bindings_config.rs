@@ -0,0 +1,104 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    ecs::system::{Res, Resource},
+    log::{info, warn},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{input::ProcGenGamepadBindings, ProcGenKeyBindings};
+
+/// Content of a bindings config file: the keyboard and gamepad bindings of the [`super::ProcGenDebugPlugin`].
+///
+/// Serialized as RON. Missing fields are read with their default value, so a config file only needs to contain the remapped bindings.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BindingsConfig {
+    /// Keyboard bindings
+    pub key_bindings: ProcGenKeyBindings,
+    /// Gamepad bindings
+    pub gamepad_bindings: ProcGenGamepadBindings,
+}
+
+/// Errors that can occur when loading or saving a [BindingsConfig]
+#[derive(Debug)]
+pub enum BindingsConfigError {
+    /// The config file could not be read or written
+    Io(io::Error),
+    /// The config file content is not a valid [BindingsConfig]
+    Parse(ron::error::SpannedError),
+    /// The bindings could not be serialized
+    Serialize(ron::Error),
+}
+
+impl fmt::Display for BindingsConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingsConfigError::Io(err) => write!(f, "io error: {}", err),
+            BindingsConfigError::Parse(err) => write!(f, "invalid bindings config: {}", err),
+            BindingsConfigError::Serialize(err) => {
+                write!(f, "failed to serialize the bindings: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BindingsConfigError {}
+
+/// Resource holding the path of the bindings config file used by the [`super::ProcGenDebugPlugin`], see [`super::ProcGenDebugPlugin::with_bindings_config`]
+#[derive(Resource, Clone, Debug)]
+pub struct BindingsConfigPath(pub PathBuf);
+
+/// Loads a [BindingsConfig] from the RON file at `path`
+pub fn load_bindings_config<P: AsRef<Path>>(
+    path: P,
+) -> Result<BindingsConfig, BindingsConfigError> {
+    let content = fs::read_to_string(path).map_err(BindingsConfigError::Io)?;
+    ron::from_str(&content).map_err(BindingsConfigError::Parse)
+}
+
+/// Saves the keyboard and gamepad bindings to a RON file at `path`. The file can then be loaded back with [load_bindings_config]
+pub fn save_bindings_config<P: AsRef<Path>>(
+    path: P,
+    key_bindings: &ProcGenKeyBindings,
+    gamepad_bindings: &ProcGenGamepadBindings,
+) -> Result<(), BindingsConfigError> {
+    #[derive(Serialize)]
+    struct BindingsConfigRef<'a> {
+        key_bindings: &'a ProcGenKeyBindings,
+        gamepad_bindings: &'a ProcGenGamepadBindings,
+    }
+    let content = ron::ser::to_string_pretty(
+        &BindingsConfigRef {
+            key_bindings,
+            gamepad_bindings,
+        },
+        ron::ser::PrettyConfig::default(),
+    )
+    .map_err(BindingsConfigError::Serialize)?;
+    fs::write(path, content).map_err(BindingsConfigError::Io)
+}
+
+/// System that saves the current [ProcGenKeyBindings] and [ProcGenGamepadBindings] to the [BindingsConfigPath], if any.
+///
+/// Not scheduled by the plugin: can be run by the user (e.g. with a run condition, or as a one-shot system) after modifying the bindings at runtime.
+pub fn save_bindings_to_config(
+    config_path: Option<Res<BindingsConfigPath>>,
+    key_bindings: Res<ProcGenKeyBindings>,
+    gamepad_bindings: Res<ProcGenGamepadBindings>,
+) {
+    let Some(config_path) = config_path else {
+        warn!("No bindings config path, add one to the ProcGenDebugPlugin to save the bindings");
+        return;
+    };
+    match save_bindings_config(&config_path.0, &key_bindings, &gamepad_bindings) {
+        Ok(()) => info!("Saved the bindings to {:?}", config_path.0),
+        Err(err) => warn!(
+            "Failed to save the bindings to {:?}: {}",
+            config_path.0, err
+        ),
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+
+use bevy::{
+    color::{palettes::css::DARK_GREEN, Color},
+    ecs::{
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        query::{Changed, With},
+        system::{Commands, Local, Query, Res, ResMut, Resource},
+    },
+};
+use bevy_ghx_grid::debug_plugin::markers::{spawn_marker, MarkerDespawnEvent};
+use ghx_proc_gen::{
+    generator::Generator,
+    ghx_grid::cartesian::{
+        coordinates::{CartesianCoordinates, CartesianPosition},
+        grid::CartesianGrid,
+    },
+    NodeIndex,
+};
+
+use super::{
+    cursor::{Cursor, CursorInfo, SelectCursor},
+    generation::GenerationEvent,
+    input::{ProcGenInput, ProcGenInputs},
+};
+
+/// Settings for the box selection of the selection cursor
+#[derive(Resource)]
+pub struct SelectionBoxSettings {
+    /// Color of the markers spawned on the nodes of the box
+    pub color: Color,
+}
+
+impl Default for SelectionBoxSettings {
+    fn default() -> Self {
+        Self {
+            color: Color::Srgba(DARK_GREEN),
+        }
+    }
+}
+
+/// A box of nodes in a grid, selected with the selection cursor
+#[derive(Debug)]
+pub struct BoxSelection {
+    /// Grid entity the box belongs to
+    pub grid: Entity,
+    /// Position where the box selection started
+    pub anchor: CartesianPosition,
+    /// Minimum coordinates of the box (included)
+    pub min: CartesianPosition,
+    /// Maximum coordinates of the box (included)
+    pub max: CartesianPosition,
+    /// Marker entities of the nodes in the box
+    pub markers: Vec<Entity>,
+}
+
+impl BoxSelection {
+    /// Returns how many nodes are in the box
+    pub fn nodes_count(&self) -> usize {
+        ((self.max.x - self.min.x + 1)
+            * (self.max.y - self.min.y + 1)
+            * (self.max.z - self.min.z + 1)) as usize
+    }
+
+    /// Returns the positions of all the nodes in the box
+    pub fn positions(&self) -> Vec<CartesianPosition> {
+        let mut positions = Vec::with_capacity(self.nodes_count());
+        for z in self.min.z..=self.max.z {
+            for y in self.min.y..=self.max.y {
+                for x in self.min.x..=self.max.x {
+                    positions.push(CartesianPosition::new(x, y, z));
+                }
+            }
+        }
+        positions
+    }
+
+    /// Returns the indexes in `grid` of all the nodes in the box
+    pub fn node_indexes<C: CartesianCoordinates>(&self, grid: &CartesianGrid<C>) -> Vec<NodeIndex> {
+        self.positions()
+            .iter()
+            .map(|pos| grid.index_from_coords(pos.x, pos.y, pos.z))
+            .collect()
+    }
+}
+
+/// Resource holding the current box selection of the selection cursor, if any.
+///
+/// A box selection is started by moving the selection cursor while the box selection [ProcGenInput] is pressed, and is extended along the axes by the following movements. It is cleared as soon as the selection cursor moves without the box selection input.
+#[derive(Resource, Default, Debug)]
+pub struct SelectionBox(pub Option<BoxSelection>);
+
+/// Summary of the nodes in a [BoxSelection]
+#[derive(Clone, Copy, Debug)]
+pub struct SelectionBoxInfo {
+    /// Minimum coordinates of the box (included)
+    pub min: CartesianPosition,
+    /// Maximum coordinates of the box (included)
+    pub max: CartesianPosition,
+    /// How many nodes are in the box
+    pub nodes_count: usize,
+    /// How many nodes of the box are generated
+    pub generated_nodes_count: usize,
+    /// How many nodes of the box have no possible model left
+    pub contradicting_nodes_count: usize,
+    /// Minimum count of possible model variations among the non-generated nodes of the box
+    pub min_possible_models_count: Option<usize>,
+}
+impl fmt::Display for SelectionBoxInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Box: {{{}}} to {{{}}}, {} nodes\n\
+            {} generated, {} contradictions",
+            self.min,
+            self.max,
+            self.nodes_count,
+            self.generated_nodes_count,
+            self.contradicting_nodes_count
+        )?;
+        if let Some(min_possible_models_count) = self.min_possible_models_count {
+            write!(f, ", min {} possible models", min_possible_models_count)?;
+        }
+        Ok(())
+    }
+}
+
+/// System updating the [SelectionBox] when the selection cursor moves, depending on the box selection [ProcGenInput]
+pub fn update_selection_box(
+    mut commands: Commands,
+    mut previous_position: Local<Option<(Entity, CartesianPosition)>>,
+    mut inputs: ProcGenInputs,
+    box_settings: Res<SelectionBoxSettings>,
+    mut selection_box: ResMut<SelectionBox>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    moved_cursor: Query<&Cursor, (Changed<Cursor>, With<SelectCursor>)>,
+) {
+    let Ok(cursor) = moved_cursor.get_single() else {
+        return;
+    };
+    let current_position = cursor
+        .0
+        .as_ref()
+        .map(|targeted_node| (targeted_node.grid, targeted_node.position));
+    let previous = std::mem::replace(&mut *previous_position, current_position);
+
+    if let Some(box_selection) = selection_box.0.take() {
+        for marker in box_selection.markers.iter() {
+            marker_events.send(MarkerDespawnEvent::Marker(*marker));
+        }
+        if let Some((grid, position)) = current_position {
+            if inputs.pressed(ProcGenInput::BoxSelection) && box_selection.grid == grid {
+                selection_box.0 = Some(spawn_box_selection(
+                    &mut commands,
+                    grid,
+                    box_selection.anchor,
+                    position,
+                    box_settings.color,
+                ));
+            }
+        }
+        return;
+    }
+
+    let (Some((grid, position)), Some((previous_grid, anchor))) = (current_position, previous)
+    else {
+        return;
+    };
+    if inputs.pressed(ProcGenInput::BoxSelection) && previous_grid == grid {
+        selection_box.0 = Some(spawn_box_selection(
+            &mut commands,
+            grid,
+            anchor,
+            position,
+            box_settings.color,
+        ));
+    }
+}
+
+fn spawn_box_selection(
+    commands: &mut Commands,
+    grid: Entity,
+    anchor: CartesianPosition,
+    position: CartesianPosition,
+    color: Color,
+) -> BoxSelection {
+    let mut box_selection = BoxSelection {
+        grid,
+        anchor,
+        min: CartesianPosition::new(
+            anchor.x.min(position.x),
+            anchor.y.min(position.y),
+            anchor.z.min(position.z),
+        ),
+        max: CartesianPosition::new(
+            anchor.x.max(position.x),
+            anchor.y.max(position.y),
+            anchor.z.max(position.z),
+        ),
+        markers: Vec::new(),
+    };
+    // The selection cursor already has its own marker
+    box_selection.markers = box_selection
+        .positions()
+        .into_iter()
+        .filter(|pos| *pos != position)
+        .map(|pos| spawn_marker(commands, grid, color, pos))
+        .collect();
+    box_selection
+}
+
+/// System updating the box summary in the [CursorInfo] of the selection cursor, when the [SelectionBox] changes or when its generation is updated
+pub fn update_selection_box_info<C: CartesianCoordinates>(
+    selection_box: Res<SelectionBox>,
+    mut generation_events: EventReader<GenerationEvent>,
+    generators: Query<&Generator<C, CartesianGrid<C>>>,
+    mut selection_cursor: Query<&mut CursorInfo, With<SelectCursor>>,
+) {
+    let box_generation_updated = match &selection_box.0 {
+        Some(box_selection) => generation_events.read().any(|event| match event {
+            GenerationEvent::Reinitialized(grid) | GenerationEvent::Updated(grid, _) => {
+                *grid == box_selection.grid
+            }
+        }),
+        None => {
+            generation_events.clear();
+            false
+        }
+    };
+    if !selection_box.is_changed() && !box_generation_updated {
+        return;
+    }
+    let Ok(mut cursor_info) = selection_cursor.get_single_mut() else {
+        return;
+    };
+    cursor_info.selection_box = match &selection_box.0 {
+        Some(box_selection) => generators
+            .get(box_selection.grid)
+            .ok()
+            .map(|generator| selection_box_info(generator, box_selection)),
+        None => None,
+    };
+}
+
+/// Returns the [SelectionBoxInfo] of `box_selection`, in the current state of the `generator`
+pub fn selection_box_info<C: CartesianCoordinates>(
+    generator: &Generator<C, CartesianGrid<C>>,
+    box_selection: &BoxSelection,
+) -> SelectionBoxInfo {
+    let mut info = SelectionBoxInfo {
+        min: box_selection.min,
+        max: box_selection.max,
+        nodes_count: box_selection.nodes_count(),
+        generated_nodes_count: 0,
+        contradicting_nodes_count: 0,
+        min_possible_models_count: None,
+    };
+    for node_index in box_selection.node_indexes(generator.grid()) {
+        let Some(node_entropy) = generator.node_entropy(node_index) else {
+            continue;
+        };
+        match node_entropy.possible_models_count {
+            0 => info.contradicting_nodes_count += 1,
+            1 => info.generated_nodes_count += 1,
+            count => {
+                info.min_possible_models_count = Some(
+                    info.min_possible_models_count
+                        .map_or(count, |min_count| min_count.min(count)),
+                )
+            }
+        }
+    }
+    info
+}
@@ -0,0 +1,99 @@
+use bevy::ecs::system::Resource;
+use ghx_proc_gen::{
+    generator::{model::ModelIndex, GeneratedNode, Generator},
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    NodeIndex,
+};
+
+/// A condition on which the generation should be paused
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationBreakpoint {
+    /// Pauses when the node with this index is generated
+    NodeGenerated(NodeIndex),
+    /// Pauses when a model with this index (with any rotation) is generated on any node
+    ModelGenerated(ModelIndex),
+    /// Pauses when the count of possible model variations on the node drops below the threshold
+    PossibilitiesBelow {
+        /// Index of the watched node
+        node_index: NodeIndex,
+        /// The generation pauses when the count of possible model variations on the node becomes strictly lower than this threshold
+        threshold: usize,
+    },
+}
+
+/// Resource used to register [GenerationBreakpoint]
+///
+/// Breakpoints are only checked when stepping the generations: they are not used with [`super::GenerationViewMode::Final`].
+#[derive(Resource, Default, Debug)]
+pub struct GenerationBreakpoints {
+    /// Registered breakpoints, and whether or not they are currently armed
+    breakpoints: Vec<(GenerationBreakpoint, bool)>,
+}
+
+impl GenerationBreakpoints {
+    /// Registers a new breakpoint. Does nothing if the breakpoint is already registered.
+    pub fn add(&mut self, breakpoint: GenerationBreakpoint) {
+        if !self.contains(&breakpoint) {
+            self.breakpoints.push((breakpoint, true));
+        }
+    }
+
+    /// Unregisters a breakpoint
+    pub fn remove(&mut self, breakpoint: &GenerationBreakpoint) {
+        self.breakpoints.retain(|(bp, _)| bp != breakpoint);
+    }
+
+    /// Unregisters all the breakpoints
+    pub fn clear(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Returns whether or not the breakpoint is registered
+    pub fn contains(&self, breakpoint: &GenerationBreakpoint) -> bool {
+        self.breakpoints.iter().any(|(bp, _)| bp == breakpoint)
+    }
+
+    /// Returns an iterator over all the registered breakpoints
+    pub fn iter(&self) -> impl Iterator<Item = &GenerationBreakpoint> {
+        self.breakpoints.iter().map(|(bp, _)| bp)
+    }
+
+    /// Checks the registered breakpoints against the `generated_nodes` of a generation step and the current state of the `generator`.
+    ///
+    /// Returns the first breakpoint hit, if any.
+    ///
+    /// A [GenerationBreakpoint::PossibilitiesBelow] breakpoint is only hit once when the possibilities drop below its threshold, and is re-armed when they go back above it (after a reinitialization for example).
+    pub fn check<C: CartesianCoordinates>(
+        &mut self,
+        generator: &Generator<C, CartesianGrid<C>>,
+        generated_nodes: &[GeneratedNode],
+    ) -> Option<GenerationBreakpoint> {
+        let mut hit = None;
+        for (breakpoint, armed) in self.breakpoints.iter_mut() {
+            let breakpoint_hit = match breakpoint {
+                GenerationBreakpoint::NodeGenerated(node_index) => generated_nodes
+                    .iter()
+                    .any(|node| node.node_index == *node_index),
+                GenerationBreakpoint::ModelGenerated(model_index) => generated_nodes
+                    .iter()
+                    .any(|node| node.model_instance.model_index == *model_index),
+                GenerationBreakpoint::PossibilitiesBelow {
+                    node_index,
+                    threshold,
+                } => {
+                    let Some(node_entropy) = generator.node_entropy(*node_index) else {
+                        continue;
+                    };
+                    let below = node_entropy.possible_models_count < *threshold;
+                    let newly_below = below && *armed;
+                    *armed = !below;
+                    newly_below
+                }
+            };
+            if breakpoint_hit && hit.is_none() {
+                hit = Some(*breakpoint);
+            }
+        }
+        hit
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use bevy::{
+    color::Color,
+    core::Name,
+    ecs::{
+        component::Component,
+        query::With,
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    hierarchy::BuildChildren,
+    text::{Text, TextSection, TextStyle},
+    time::{Time, Timer, TimerMode},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        BackgroundColor,
+    },
+    utils::default,
+};
+use ghx_proc_gen::{
+    generator::{model::ALL_MODEL_ROTATIONS, Generator},
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+};
+
+#[cfg(feature = "picking")]
+use bevy_mod_picking::picking_core::Pickable;
+
+use super::{generation::ActiveGeneration, GridCursorsUiSettings};
+
+/// Root marker for the model census panel UI
+#[derive(Component)]
+pub struct ModelCensusPanelRoot;
+
+/// Text component marker for the model census panel UI
+#[derive(Component)]
+pub struct ModelCensusPanelText;
+
+const CENSUS_REFRESH_INTERVAL_MS: u64 = 500;
+
+/// Resource used to customize the model census panel
+#[derive(Resource, Debug)]
+pub struct ModelCensusSettings {
+    /// Relative deviation (see [`ghx_proc_gen::generator::ModelCensusEntry::deviation`]) above which a model is highlighted
+    pub deviation_threshold: f32,
+    /// Text color of the highlighted models
+    pub highlight_color: Color,
+}
+
+impl Default for ModelCensusSettings {
+    fn default() -> Self {
+        Self {
+            deviation_threshold: 0.5,
+            highlight_color: Color::srgb(1.0, 0.4, 0.2),
+        }
+    }
+}
+
+/// Resource used to throttle the refreshes of the model census panel
+#[derive(Resource)]
+pub struct ModelCensusTracker {
+    /// Timer used to refresh the census
+    pub refresh_timer: Timer,
+}
+
+impl Default for ModelCensusTracker {
+    fn default() -> Self {
+        Self {
+            refresh_timer: Timer::new(
+                Duration::from_millis(CENSUS_REFRESH_INTERVAL_MS),
+                TimerMode::Repeating,
+            ),
+        }
+    }
+}
+
+/// Setup system used to spawn the model census UI panel
+pub fn setup_model_census_panel(mut commands: Commands, ui_config: Res<GridCursorsUiSettings>) {
+    let root = commands
+        .spawn((
+            ModelCensusPanelRoot,
+            Name::new("ModelCensusPanelRoot"),
+            NodeBundle {
+                background_color: BackgroundColor(ui_config.background_color),
+                style: ui_config.panel_style(ui_config.census_panel_anchor),
+                ..default()
+            },
+        ))
+        .id();
+    let text = commands
+        .spawn((
+            ModelCensusPanelText,
+            TextBundle {
+                text: Text::from_section(
+                    "No active generation",
+                    TextStyle {
+                        font_size: ui_config.font_size,
+                        color: ui_config.text_color,
+                        ..default()
+                    },
+                ),
+                ..default()
+            },
+        ))
+        .id();
+    commands.entity(root).add_child(text);
+
+    #[cfg(feature = "picking")]
+    commands.entity(root).insert(Pickable::IGNORE);
+    #[cfg(feature = "picking")]
+    commands.entity(text).insert(Pickable::IGNORE);
+}
+
+/// System updating the model census UI panel with the [`Generator::model_census`] of the [ActiveGeneration].
+///
+/// Models whose count deviates from their expected count by more than [`ModelCensusSettings::deviation_threshold`] are highlighted.
+pub fn update_model_census_panel_text<C: CartesianCoordinates>(
+    time: Res<Time>,
+    active_generation: Res<ActiveGeneration>,
+    census_settings: Res<ModelCensusSettings>,
+    ui_config: Res<GridCursorsUiSettings>,
+    mut census_tracker: ResMut<ModelCensusTracker>,
+    generators: Query<&Generator<C, CartesianGrid<C>>>,
+    mut census_panel_text: Query<&mut Text, With<ModelCensusPanelText>>,
+) {
+    census_tracker.refresh_timer.tick(time.delta());
+    if !census_tracker.refresh_timer.just_finished() {
+        return;
+    }
+
+    let Some(gen_entity) = active_generation.0 else {
+        return;
+    };
+    let Ok(generator) = generators.get(gen_entity) else {
+        return;
+    };
+    let rules = generator.rules();
+
+    let style = TextStyle {
+        font_size: ui_config.font_size,
+        color: ui_config.text_color,
+        ..default()
+    };
+    let mut sections = vec![TextSection::new(
+        format!("Models census {:?} (count/expected)", gen_entity),
+        style.clone(),
+    )];
+    for entry in generator.model_census() {
+        let name = ALL_MODEL_ROTATIONS
+            .iter()
+            .find_map(|rot| rules.variant_index(entry.model_index, *rot))
+            .and_then(|variant_index| rules.name_str(variant_index))
+            .unwrap_or("None");
+        let deviation = entry.deviation();
+        let color = match deviation.abs() > census_settings.deviation_threshold {
+            true => census_settings.highlight_color,
+            false => ui_config.text_color,
+        };
+        sections.push(TextSection::new(
+            format!(
+                "\n{}: {}/{:.1} ({:+.0}%)",
+                name,
+                entry.count,
+                entry.expected_count,
+                deviation * 100.
+            ),
+            TextStyle {
+                color,
+                ..style.clone()
+            },
+        ));
+    }
+
+    for mut text in &mut census_panel_text {
+        text.sections = sections.clone();
+    }
+}
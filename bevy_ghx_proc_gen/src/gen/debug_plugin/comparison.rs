@@ -0,0 +1,165 @@
+#[cfg(feature = "reflect")]
+use bevy::{ecs::reflect::ReflectComponent, reflect::Reflect};
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        system::{Commands, Local, Query, Res},
+    },
+    log::{info, warn},
+    math::Vec3,
+    prelude::SpatialBundle,
+    transform::components::Transform,
+};
+use ghx_proc_gen::{
+    generator::{
+        builder::{GeneratorBuilder, Set},
+        GenerationStatus, Generator, RngMode,
+    },
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    GeneratorBuilderError, GeneratorError, NodeIndex,
+};
+
+use crate::GeneratorBundle;
+
+use super::{generation::ActiveGeneration, AssetSpawner, AssetsBundleSpawner, ComponentSpawner};
+
+/// Marker component for generations compared side by side, see [`spawn_compared_generations`].
+///
+/// When the [`ActiveGeneration`] is a compared generation, it leads the other compared generations: they are reinitialized with it and stepped to the same trace length, see [`sync_compared_generations`].
+#[derive(Component, Default, Clone, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
+pub struct ComparedGeneration {
+    /// Node where this generation encountered a contradiction while following the leading generation, if any
+    pub failed_at: Option<NodeIndex>,
+}
+
+/// Returns one clone of `builder` per seed in `seeds`, each one set to use its seed.
+///
+/// Can be used with [`spawn_compared_generations`] to compare the effect of different seeds on the same rules and grid. To compare heuristics or weights, the builders can be modified before spawning them.
+pub fn seeded_builders<C: CartesianCoordinates>(
+    builder: &GeneratorBuilder<Set, Set, C, CartesianGrid<C>>,
+    seeds: &[u64],
+) -> Vec<GeneratorBuilder<Set, Set, C, CartesianGrid<C>>> {
+    seeds
+        .iter()
+        .map(|seed| builder.clone().with_rng(RngMode::Seeded(*seed)))
+        .collect()
+}
+
+/// Builds a [`Generator`] from each of the `builders` and spawns them side by side, the generation `i` being translated by `i * offset`.
+///
+/// Each generation entity gets a [`ghx_proc_gen::generator::observer::QueuedObserver`] (needed by the debug plugin) and a [`ComparedGeneration`] marker. Returns the spawned entities, in the order of `builders`.
+pub fn spawn_compared_generations<
+    C: CartesianCoordinates,
+    A: AssetsBundleSpawner,
+    T: ComponentSpawner,
+>(
+    commands: &mut Commands,
+    builders: Vec<GeneratorBuilder<Set, Set, C, CartesianGrid<C>>>,
+    asset_spawner: &AssetSpawner<A, T>,
+    offset: Vec3,
+) -> Result<Vec<Entity>, GeneratorBuilderError> {
+    let mut gen_entities = Vec::with_capacity(builders.len());
+    for (i, mut builder) in builders.into_iter().enumerate() {
+        let observer = builder.add_queued_observer();
+        let generator = builder.build()?;
+        info!(
+            "Compared generation {} created, seed: {}; grid: {}",
+            i,
+            generator.seed(),
+            generator.grid()
+        );
+        let gen_entity = commands
+            .spawn((
+                GeneratorBundle {
+                    spatial: SpatialBundle::from_transform(Transform::from_translation(
+                        offset * i as f32,
+                    )),
+                    grid: generator.grid().clone(),
+                    generator,
+                    asset_spawner: asset_spawner.clone(),
+                },
+                observer,
+                ComparedGeneration::default(),
+            ))
+            .id();
+        gen_entities.push(gen_entity);
+    }
+    Ok(gen_entities)
+}
+
+/// System keeping the [`ComparedGeneration`]s in sync with the [`ActiveGeneration`], when the latter is a compared generation.
+///
+/// All the compared generations share the stepping state of the leading one:
+/// - when the leading generation is reinitialized, the other ones are reinitialized too (with their next seed)
+/// - the other ones are stepped (or rewound) to the same trace length as the leading generation, see [`Generator::trace`]
+/// - when the leading generation is done, the other ones are generated until they are done or fail
+pub fn sync_compared_generations<C: CartesianCoordinates>(
+    active_generation: Res<ActiveGeneration>,
+    mut last_leader_seed: Local<Option<(Entity, u64)>>,
+    mut compared_generations: Query<(
+        Entity,
+        &mut Generator<C, CartesianGrid<C>>,
+        &mut ComparedGeneration,
+    )>,
+) {
+    let Some(leader_entity) = active_generation.0 else {
+        return;
+    };
+    let Ok((_, leader, _)) = compared_generations.get(leader_entity) else {
+        return;
+    };
+    let leader_seed = leader.seed();
+    let target_steps = leader.trace().len();
+    let leader_done = leader.nodes_left() == 0;
+
+    let reinitialize = match *last_leader_seed {
+        Some((entity, seed)) => entity == leader_entity && seed != leader_seed,
+        None => false,
+    };
+    *last_leader_seed = Some((leader_entity, leader_seed));
+
+    for (gen_entity, mut generator, mut compared) in compared_generations.iter_mut() {
+        if gen_entity == leader_entity {
+            continue;
+        }
+        if reinitialize {
+            generator.reinitialize();
+            compared.failed_at = None;
+        }
+
+        if generator.trace().len() > target_steps {
+            match generator.rewind(target_steps) {
+                Ok(_) => compared.failed_at = None,
+                Err(err) => {
+                    warn!(
+                        "Compared generation {:?} failed to rewind: {}",
+                        gen_entity, err
+                    );
+                    continue;
+                }
+            }
+        }
+        if compared.failed_at.is_some() {
+            continue;
+        }
+
+        while leader_done || generator.trace().len() < target_steps {
+            match generator.select_and_propagate() {
+                Ok(GenerationStatus::Ongoing) => (),
+                Ok(GenerationStatus::Done) => break,
+                Err(GeneratorError { node_index }) => {
+                    warn!(
+                        "Compared generation {:?} failed at node {}, seed: {}",
+                        gen_entity,
+                        node_index,
+                        generator.seed()
+                    );
+                    compared.failed_at = Some(node_index);
+                    break;
+                }
+            }
+        }
+    }
+}
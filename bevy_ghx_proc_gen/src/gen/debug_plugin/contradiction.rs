@@ -0,0 +1,338 @@
+use std::time::Duration;
+
+use bevy::{
+    color::{palettes::css::YELLOW, Color},
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        query::With,
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    hierarchy::BuildChildren,
+    log::warn,
+    text::{Text, TextStyle},
+    time::{Time, Timer, TimerMode},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        BackgroundColor, Display, Style,
+    },
+    utils::default,
+};
+use bevy_ghx_grid::debug_plugin::markers::{spawn_marker, MarkerDespawnEvent};
+use ghx_proc_gen::{
+    generator::{GenerationStep, Generator},
+    ghx_grid::cartesian::{
+        coordinates::{CartesianCoordinates, CartesianPosition},
+        grid::CartesianGrid,
+    },
+    NodeIndex,
+};
+
+#[cfg(feature = "picking")]
+use bevy_mod_picking::picking_core::Pickable;
+
+use super::{
+    cursor::{
+        neighbours_info, neighbours_info_to_string, spawn_marker_and_create_cursor, Cursor,
+        CursorMarkerSettings, NeighbourInfo, SelectCursor, SelectionCursorMarkerSettings,
+    },
+    generation::{ActiveGeneration, GenerationEvent},
+    GridCursorsUiSettings,
+};
+
+/// How many of the last generation steps are kept in a [ContradictionReport]
+const REPORT_LAST_STEPS_COUNT: usize = 5;
+
+/// Resource used to customize how contradictions are reported.
+///
+/// Whether or not the generation pauses on a contradiction is controlled by [`super::GenerationControl`]
+#[derive(Resource, Debug)]
+pub struct ContradictionSettings {
+    /// Whether or not the selection cursor is moved to the node where the contradiction happened
+    pub focus_selection_cursor: bool,
+    /// How long the failing node should flash, in milliseconds. No flash if 0.
+    pub flash_duration_ms: u64,
+    /// Interval between two flashes of the failing node, in milliseconds
+    pub flash_interval_ms: u64,
+    /// Color of the flashing marker
+    pub flash_color: Color,
+    /// Whether or not the contradiction panel is displayed
+    pub show_panel: bool,
+}
+
+impl Default for ContradictionSettings {
+    fn default() -> Self {
+        Self {
+            focus_selection_cursor: true,
+            flash_duration_ms: 3000,
+            flash_interval_ms: 250,
+            flash_color: Color::Srgba(YELLOW),
+            show_panel: true,
+        }
+    }
+}
+
+/// Event raised when a generation fails due to a contradiction, and is not immediately reinitialized
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ContradictionEvent {
+    /// Generation entity
+    pub generation: Entity,
+    /// Index of the node where the contradiction happened
+    pub node_index: NodeIndex,
+}
+
+/// Diagnostic information about the last contradiction
+#[derive(Debug)]
+pub struct ContradictionInfo {
+    /// Generation entity
+    pub generation: Entity,
+    /// Index of the node where the contradiction happened
+    pub node_index: NodeIndex,
+    /// Position of the node where the contradiction happened
+    pub position: CartesianPosition,
+    /// Seed of the failed generation
+    pub seed: u64,
+    /// Last steps executed before the contradiction, most recent last
+    pub last_steps: Vec<GenerationStep>,
+    /// Neighbours of the node where the contradiction happened
+    pub neighbours: Vec<NeighbourInfo>,
+}
+
+/// Resource holding the report of the last contradiction, if any.
+///
+/// The report is cleared when its generation is reinitialized
+#[derive(Resource, Default, Debug)]
+pub struct ContradictionReport(pub Option<ContradictionInfo>);
+
+/// Resource used to track the flashing marker of the last contradiction
+#[derive(Resource, Default)]
+pub struct ContradictionFlash {
+    /// Grid and position of the flashing node
+    pub target: Option<(Entity, CartesianPosition)>,
+    /// Current flashing marker, if currently displayed
+    pub marker: Option<Entity>,
+    /// Remaining flash duration
+    pub duration: Timer,
+    /// Flash interval
+    pub interval: Timer,
+}
+
+/// Root marker for the contradiction panel UI
+#[derive(Component)]
+pub struct ContradictionPanelRoot;
+
+/// Text component marker for the contradiction panel UI
+#[derive(Component)]
+pub struct ContradictionPanelText;
+
+/// Setup system used to spawn the contradiction UI panel, hidden until a contradiction is reported
+pub fn setup_contradiction_panel(mut commands: Commands, ui_config: Res<GridCursorsUiSettings>) {
+    let root = commands
+        .spawn((
+            ContradictionPanelRoot,
+            Name::new("ContradictionPanelRoot"),
+            NodeBundle {
+                background_color: BackgroundColor(ui_config.background_color),
+                style: Style {
+                    display: Display::None,
+                    ..ui_config.panel_style(ui_config.contradiction_panel_anchor)
+                },
+                ..default()
+            },
+        ))
+        .id();
+    let text = commands
+        .spawn((
+            ContradictionPanelText,
+            TextBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font_size: ui_config.font_size,
+                        color: ui_config.text_color,
+                        ..default()
+                    },
+                ),
+                ..default()
+            },
+        ))
+        .id();
+    commands.entity(root).add_child(text);
+
+    #[cfg(feature = "picking")]
+    commands.entity(root).insert(Pickable::IGNORE);
+    #[cfg(feature = "picking")]
+    commands.entity(text).insert(Pickable::IGNORE);
+}
+
+/// System handling the [ContradictionEvent]: fills the [ContradictionReport], starts the [ContradictionFlash] and focuses the selection cursor on the failing node, according to the [ContradictionSettings].
+pub fn handle_contradiction_events<C: CartesianCoordinates>(
+    mut commands: Commands,
+    settings: Res<ContradictionSettings>,
+    selection_marker_settings: Res<SelectionCursorMarkerSettings>,
+    mut contradiction_events: EventReader<ContradictionEvent>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    mut report: ResMut<ContradictionReport>,
+    mut flash: ResMut<ContradictionFlash>,
+    mut active_generation: ResMut<ActiveGeneration>,
+    generators: Query<&Generator<C, CartesianGrid<C>>>,
+    mut selection_cursor: Query<&mut Cursor, With<SelectCursor>>,
+) {
+    let Some(event) = contradiction_events.read().last() else {
+        return;
+    };
+    let Ok(generator) = generators.get(event.generation) else {
+        return;
+    };
+
+    let position = generator.grid().pos_from_index(event.node_index);
+    let trace = generator.trace();
+    let info = ContradictionInfo {
+        generation: event.generation,
+        node_index: event.node_index,
+        position,
+        seed: generator.seed(),
+        last_steps: trace[trace.len().saturating_sub(REPORT_LAST_STEPS_COUNT)..].to_vec(),
+        neighbours: neighbours_info(generator, event.node_index),
+    };
+    warn!("Contradiction:\n{}", contradiction_info_to_string(&info));
+    report.0 = Some(info);
+
+    if settings.flash_duration_ms > 0 {
+        if let Some(marker) = flash.marker.take() {
+            marker_events.send(MarkerDespawnEvent::Marker(marker));
+        }
+        flash.target = Some((event.generation, position));
+        flash.duration = Timer::new(
+            Duration::from_millis(settings.flash_duration_ms),
+            TimerMode::Once,
+        );
+        flash.interval = Timer::new(
+            Duration::from_millis(settings.flash_interval_ms),
+            TimerMode::Repeating,
+        );
+    }
+
+    if settings.focus_selection_cursor {
+        let Ok(mut cursor) = selection_cursor.get_single_mut() else {
+            return;
+        };
+        if let Some(selected_node) = &cursor.0 {
+            marker_events.send(MarkerDespawnEvent::Marker(selected_node.marker));
+        }
+        active_generation.0 = Some(event.generation);
+        cursor.0 = Some(spawn_marker_and_create_cursor(
+            &mut commands,
+            event.generation,
+            position,
+            event.node_index,
+            selection_marker_settings.color(),
+        ));
+    }
+}
+
+/// System making the marker of the last contradiction flash, as configured by the [ContradictionSettings]
+pub fn flash_contradiction_marker(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<ContradictionSettings>,
+    mut flash: ResMut<ContradictionFlash>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+) {
+    let Some((grid_entity, position)) = flash.target else {
+        return;
+    };
+    flash.duration.tick(time.delta());
+    if flash.duration.finished() {
+        if let Some(marker) = flash.marker.take() {
+            marker_events.send(MarkerDespawnEvent::Marker(marker));
+        }
+        flash.target = None;
+        return;
+    }
+    flash.interval.tick(time.delta());
+    if flash.interval.just_finished() {
+        match flash.marker.take() {
+            Some(marker) => {
+                marker_events.send(MarkerDespawnEvent::Marker(marker));
+            }
+            None => {
+                flash.marker = Some(spawn_marker(
+                    &mut commands,
+                    grid_entity,
+                    settings.flash_color,
+                    position,
+                ));
+            }
+        }
+    }
+}
+
+/// System clearing the [ContradictionReport] and the [ContradictionFlash] when their generation is reinitialized
+pub fn clear_contradiction_on_reinit(
+    mut generation_events: EventReader<GenerationEvent>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    mut report: ResMut<ContradictionReport>,
+    mut flash: ResMut<ContradictionFlash>,
+) {
+    for event in generation_events.read() {
+        let GenerationEvent::Reinitialized(gen_entity) = event else {
+            continue;
+        };
+        if let Some(info) = &report.0 {
+            if info.generation == *gen_entity {
+                report.0 = None;
+            }
+        }
+        if let Some((flash_grid, _)) = flash.target {
+            if flash_grid == *gen_entity {
+                if let Some(marker) = flash.marker.take() {
+                    marker_events.send(MarkerDespawnEvent::Marker(marker));
+                }
+                flash.target = None;
+            }
+        }
+    }
+}
+
+/// System updating the contradiction UI panel from the [ContradictionReport]
+pub fn update_contradiction_panel(
+    settings: Res<ContradictionSettings>,
+    report: Res<ContradictionReport>,
+    mut panel_root: Query<&mut Style, With<ContradictionPanelRoot>>,
+    mut panel_text: Query<&mut Text, With<ContradictionPanelText>>,
+) {
+    if !report.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let display = match (&report.0, settings.show_panel) {
+        (Some(info), true) => {
+            for mut text in &mut panel_text {
+                text.sections[0].value =
+                    format!("Contradiction!\n{}", contradiction_info_to_string(info));
+            }
+            Display::Flex
+        }
+        _ => Display::None,
+    };
+    for mut style in &mut panel_root {
+        style.display = display;
+    }
+}
+
+/// Utility function to transform data from a [ContradictionInfo] into a [String]
+pub fn contradiction_info_to_string(info: &ContradictionInfo) -> String {
+    let mut text = format!(
+        "Generation {:?}, seed: {}\n\
+        Failed on node {{{}}}, index: {}\n\
+        Last steps:\n",
+        info.generation, info.seed, info.position, info.node_index
+    );
+    for step in info.last_steps.iter() {
+        text.push_str(&format!("  {:?}\n", step));
+    }
+    text.push_str(&neighbours_info_to_string(&info.neighbours));
+    text
+}
@@ -7,31 +7,40 @@ use bevy::{
         component::Component,
         entity::Entity,
         event::{EventReader, EventWriter},
-        query::{Changed, With, Without},
+        query::{Added, Changed, With, Without},
         system::{Commands, Local, Query, Res, ResMut, Resource},
     },
-    hierarchy::BuildChildren,
-    input::{keyboard::KeyCode, ButtonInput},
+    hierarchy::{BuildChildren, DespawnRecursiveExt},
     log::warn,
-    render::camera::Camera,
-    text::{BreakLineOn, Text, TextSection, TextStyle},
+    math::Vec3,
+    render::{camera::Camera, spatial_bundle::SpatialBundle, view::Visibility},
+    sprite::Anchor,
+    text::{BreakLineOn, Text, Text2dBundle, TextSection, TextStyle},
     time::{Time, Timer, TimerMode},
-    transform::components::GlobalTransform,
+    transform::components::{GlobalTransform, Transform},
     ui::{
         node_bundles::{NodeBundle, TextBundle},
-        BackgroundColor, PositionType, Style, UiRect, Val,
+        BackgroundColor, PositionType, Style, TargetCamera, Val,
     },
     utils::default,
 };
+#[cfg(feature = "reflect")]
+use bevy::{
+    ecs::reflect::{ReflectComponent, ReflectResource},
+    reflect::Reflect,
+};
 use bevy_ghx_grid::{
     debug_plugin::markers::{spawn_marker, GridMarker, MarkerDespawnEvent},
     ghx_grid::{coordinate_system::CoordinateSystem, direction::Direction},
 };
 use ghx_proc_gen::{
     generator::{Generator, ModelVariations},
-    ghx_grid::cartesian::{
-        coordinates::{CartesianCoordinates, CartesianPosition},
-        grid::CartesianGrid,
+    ghx_grid::{
+        cartesian::{
+            coordinates::{CartesianCoordinates, CartesianPosition},
+            grid::CartesianGrid,
+        },
+        grid::Grid,
     },
     NodeIndex,
 };
@@ -40,14 +49,16 @@ use ghx_proc_gen::{
 use bevy_mod_picking::picking_core::Pickable;
 
 use super::{
+    box_selection::SelectionBoxInfo,
     generation::{ActiveGeneration, GenerationEvent},
-    GridCursorsUiSettings, ProcGenKeyBindings,
+    input::{ProcGenInput, ProcGenInputs},
+    GridCursorsUiSettings,
 };
 
 /// Marker component to be put on a [Camera] to signal that it should be used to display curosr overlays
 ///
 /// - **Not needed** if only a single camera is used.
-/// - If used, should not be present on more than 1 camera
+/// - Can be put on multiple cameras (split-screen, editor with multiple viewports, ...): each of them gets its own [CursorsOverlaysRoot], with overlays positioned in its viewport.
 #[derive(Component)]
 pub struct GridCursorsOverlayCamera;
 
@@ -59,12 +70,24 @@ pub struct CursorsPanelRoot;
 #[derive(Component)]
 pub struct CursorsOverlaysRoot;
 
+/// Component put on the [CursorsOverlaysRoot] spawned for a [GridCursorsOverlayCamera], along with a [TargetCamera]
+#[derive(Component, Debug)]
+pub struct CursorsOverlaysViewport {
+    /// Camera entity whose viewport displays the overlays of this root
+    pub camera: Entity,
+}
+
+/// Root marker for the cursors world-space billboards
+#[derive(Component)]
+pub struct CursorsBillboardsRoot;
+
 /// Text component marker for the cursors panel UI
 #[derive(Component)]
 pub struct CursorsPanelText;
 
 /// Represents a node in a grid and its [GridMarker]
 #[derive(Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect))]
 pub struct TargetedNode {
     /// Grid entity the node bleongs to
     pub grid: Entity,
@@ -83,6 +106,7 @@ impl fmt::Display for TargetedNode {
 
 /// Represents a generic cursor and its optional target
 #[derive(Component, Default, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct Cursor(pub Option<TargetedNode>);
 
 /// Information about what is being pointed by a cursor
@@ -92,12 +116,43 @@ pub struct CursorInfo {
     pub total_models_count: u32,
     /// Groups of models for the node pointed by the cursor
     pub models_variations: Vec<ModelVariations>,
+    /// Seed of the generation pointed by the cursor
+    pub seed: u64,
+    /// Information about the neighbours of the node pointed by the cursor. Only filled for some cursors, can be empty.
+    pub neighbours: Vec<NeighbourInfo>,
+    /// Summary of the box selection of the cursor, if any. Only filled for the selection cursor.
+    pub selection_box: Option<SelectionBoxInfo>,
+}
+
+/// Information about a neighbour of a node pointed by a cursor
+#[derive(Clone, Copy, Debug)]
+pub struct NeighbourInfo {
+    /// Direction of the neighbour, from the pointed node
+    pub direction: Direction,
+    /// Index of the neighbour in its grid
+    pub node_index: NodeIndex,
+    /// How many model variations are still possible on the neighbour
+    pub possible_models_count: usize,
 }
 impl CursorInfo {
     /// Clear all information in the [CursorInfo]
     pub fn clear(&mut self) {
         self.total_models_count = 0;
         self.models_variations.clear();
+        self.seed = 0;
+        self.neighbours.clear();
+        self.selection_box = None;
+    }
+
+    /// Updates the [CursorInfo] with the current state of `generator` at `node_index`
+    pub fn update<C: CartesianCoordinates>(
+        &mut self,
+        generator: &Generator<C, CartesianGrid<C>>,
+        node_index: NodeIndex,
+    ) {
+        (self.models_variations, self.total_models_count) =
+            generator.get_models_variations_on(node_index);
+        self.seed = generator.seed();
     }
 }
 
@@ -111,9 +166,20 @@ pub trait CursorBehavior: Component {
 
 /// Marker component for a cursor's UI overlay
 #[derive(Component, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct CursorOverlay {
     /// The cursor Entity
     pub cursor_entity: Entity,
+    /// Camera the overlay is displayed for. [None] for the overlays of the default root, which use the only camera of the app
+    pub camera: Option<Entity>,
+}
+
+/// Marker component for a cursor's world-space billboard
+#[derive(Component, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
+pub struct CursorBillboard {
+    /// The cursor Entity
+    pub cursor_entity: Entity,
 }
 
 /// Trait implemented by cursors settings resources
@@ -124,6 +190,7 @@ pub trait CursorMarkerSettings: Resource {
 
 /// Settings for the selection cursor
 #[derive(Resource)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub struct SelectionCursorMarkerSettings(pub Color);
 impl Default for SelectionCursorMarkerSettings {
     fn default() -> Self {
@@ -138,6 +205,7 @@ impl CursorMarkerSettings for SelectionCursorMarkerSettings {
 
 /// Selection cursor marker component
 #[derive(Component, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct SelectCursor;
 impl CursorBehavior for SelectCursor {
     fn new() -> Self {
@@ -161,15 +229,7 @@ pub fn setup_cursors_panel(mut commands: Commands, ui_config: Res<GridCursorsUiS
             Name::new("CursorsPanelRoot"),
             NodeBundle {
                 background_color: BackgroundColor(ui_config.background_color),
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    right: Val::Percent(1.),
-                    bottom: Val::Percent(1.),
-                    top: Val::Auto,
-                    left: Val::Auto,
-                    padding: UiRect::all(Val::Px(4.0)),
-                    ..default()
-                },
+                style: ui_config.panel_style(ui_config.cursors_panel_anchor),
                 ..default()
             },
         ))
@@ -218,23 +278,59 @@ pub fn setup_cursors_overlays(mut commands: Commands) {
     commands.entity(root).insert(Pickable::IGNORE);
 }
 
-/// Setup system to spawn a cursor and its overlay
-pub fn setup_cursor<C: CoordinateSystem, CI: CursorBehavior>(
+/// Setup system used to spawn the cursors world-space billboards root
+pub fn setup_cursors_billboards(mut commands: Commands) {
+    commands.spawn((
+        CursorsBillboardsRoot,
+        Name::new("CursorsBillboardsRoot"),
+        SpatialBundle::default(),
+    ));
+}
+
+/// System spawning a [CursorsOverlaysRoot] (with one overlay per cursor) for each new [GridCursorsOverlayCamera], and despawning the roots of the removed ones
+pub fn update_cursors_overlays_viewports(
     mut commands: Commands,
-    overlays_root: Query<Entity, With<CursorsOverlaysRoot>>,
+    new_overlay_cameras: Query<Entity, (With<Camera>, Added<GridCursorsOverlayCamera>)>,
+    overlay_cameras: Query<(), With<GridCursorsOverlayCamera>>,
+    viewports_roots: Query<(Entity, &CursorsOverlaysViewport)>,
+    cursors: Query<Entity, With<Cursor>>,
 ) {
-    let cursor_entity = commands
-        .spawn((Cursor::default(), CursorInfo::default(), CI::new()))
-        .id();
-
-    let Ok(root) = overlays_root.get_single() else {
-        // No overlays
-        return;
-    };
+    for (root, viewport) in viewports_roots.iter() {
+        if !overlay_cameras.contains(viewport.camera) {
+            commands.entity(root).despawn_recursive();
+        }
+    }
+    for camera in new_overlay_cameras.iter() {
+        let root = commands
+            .spawn((
+                CursorsOverlaysRoot,
+                CursorsOverlaysViewport { camera },
+                Name::new("CursorsOverlaysRoot"),
+                NodeBundle { ..default() },
+                TargetCamera(camera),
+            ))
+            .id();
+        #[cfg(feature = "picking")]
+        commands.entity(root).insert(Pickable::IGNORE);
+        for cursor_entity in cursors.iter() {
+            spawn_cursor_overlay(&mut commands, root, cursor_entity, Some(camera));
+        }
+    }
+}
 
+/// Spawns the UI overlay of `cursor_entity` as a child of the overlays `root`
+pub fn spawn_cursor_overlay(
+    commands: &mut Commands,
+    root: Entity,
+    cursor_entity: Entity,
+    camera: Option<Entity>,
+) {
     let cursor_overlay_entity = commands
         .spawn((
-            CursorOverlay { cursor_entity },
+            CursorOverlay {
+                cursor_entity,
+                camera,
+            },
             // https://github.com/bevyengine/bevy/issues/11572
             // If we only add the node later, Bevy panics in 0.12.1
             TextBundle { ..default() },
@@ -248,6 +344,37 @@ pub fn setup_cursor<C: CoordinateSystem, CI: CursorBehavior>(
         .insert(Pickable::IGNORE);
 }
 
+/// Setup system to spawn a cursor and its overlay (or billboard)
+pub fn setup_cursor<C: CoordinateSystem, CI: CursorBehavior>(
+    mut commands: Commands,
+    overlays_root: Query<Entity, (With<CursorsOverlaysRoot>, Without<CursorsOverlaysViewport>)>,
+    billboards_root: Query<Entity, With<CursorsBillboardsRoot>>,
+) {
+    let cursor_entity = commands
+        .spawn((Cursor::default(), CursorInfo::default(), CI::new()))
+        .id();
+
+    if let Ok(root) = billboards_root.get_single() {
+        let cursor_billboard_entity = commands
+            .spawn((
+                CursorBillboard { cursor_entity },
+                Text2dBundle {
+                    text_anchor: Anchor::BottomLeft,
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+            ))
+            .id();
+        commands.entity(root).add_child(cursor_billboard_entity);
+    }
+
+    let Ok(root) = overlays_root.get_single() else {
+        // No overlays
+        return;
+    };
+    spawn_cursor_overlay(&mut commands, root, cursor_entity, None);
+}
+
 /// System updating all the [CursorInfo] components when [Cursor] components are changed
 pub fn update_cursors_info_on_cursors_changes<C: CartesianCoordinates>(
     mut moved_cursors: Query<(&mut CursorInfo, &Cursor), Changed<Cursor>>,
@@ -257,10 +384,7 @@ pub fn update_cursors_info_on_cursors_changes<C: CartesianCoordinates>(
         match &cursor.0 {
             Some(grid_cursor) => {
                 if let Ok(generator) = generators.get(grid_cursor.grid) {
-                    (
-                        cursor_info.models_variations,
-                        cursor_info.total_models_count,
-                    ) = generator.get_models_variations_on(grid_cursor.node_index);
+                    cursor_info.update(generator, grid_cursor.node_index);
                 }
             }
             None => cursor_info.clear(),
@@ -285,20 +409,14 @@ pub fn update_cursors_info_from_generation_events<C: CartesianCoordinates>(
                     let Ok(generator) = generators.get(*grid_entity) else {
                         continue;
                     };
-                    (
-                        cursor_info.models_variations,
-                        cursor_info.total_models_count,
-                    ) = generator.get_models_variations_on(grid_cursor.node_index);
+                    cursor_info.update(generator, grid_cursor.node_index);
                 }
                 GenerationEvent::Updated(grid_entity, node_index) => {
                     let Ok(generator) = generators.get(*grid_entity) else {
                         continue;
                     };
                     if grid_cursor.node_index == *node_index {
-                        (
-                            cursor_info.models_variations,
-                            cursor_info.total_models_count,
-                        ) = generator.get_models_variations_on(grid_cursor.node_index);
+                        cursor_info.update(generator, grid_cursor.node_index);
                     }
                 }
             }
@@ -327,14 +445,13 @@ pub fn update_selection_cursor_panel_text(
     }
 }
 
-/// Listen to [KeyCode] to deselect the current selection cursor
+/// Listen to the keyboard and gamepads [ProcGenInputs] to deselect the current selection cursor
 pub fn deselect_from_keybinds(
-    keys: Res<ButtonInput<KeyCode>>,
-    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut inputs: ProcGenInputs,
     mut marker_events: EventWriter<MarkerDespawnEvent>,
     mut selection_cursor: Query<&mut Cursor, With<SelectCursor>>,
 ) {
-    if keys.just_pressed(proc_gen_key_bindings.deselect) {
+    if inputs.just_pressed(ProcGenInput::Deselect) {
         let Ok(mut cursor) = selection_cursor.get_single_mut() else {
             return;
         };
@@ -375,19 +492,18 @@ impl EntityProvider {
     }
 }
 
-/// System that listens to the generation switch [KeyCode] to switch the current active generation grid
+/// System that listens to the generation switch [ProcGenInput] to switch the current active generation grid
 pub fn switch_generation_selection_from_keybinds<C: CartesianCoordinates>(
     mut local_grid_cycler: Local<EntityProvider>,
     mut commands: Commands,
     mut active_generation: ResMut<ActiveGeneration>,
-    keys: Res<ButtonInput<KeyCode>>,
+    mut inputs: ProcGenInputs,
     selection_marker_settings: Res<SelectionCursorMarkerSettings>,
-    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
     mut marker_events: EventWriter<MarkerDespawnEvent>,
     mut selection_cursor: Query<&mut Cursor, With<SelectCursor>>,
     generators: Query<Entity, (With<Generator<C, CartesianGrid<C>>>, With<CartesianGrid<C>>)>,
 ) {
-    if keys.just_pressed(proc_gen_key_bindings.switch_grid) {
+    if inputs.just_pressed(ProcGenInput::SwitchGrid) {
         let Ok(mut cursor) = selection_cursor.get_single_mut() else {
             return;
         };
@@ -416,6 +532,7 @@ const CURSOR_KEYS_MOVEMENT_SPEED_UP_DELAY_MS: u64 = 350;
 
 /// Resource used to customize keyboard movement of the selection cursor
 #[derive(Resource)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub struct CursorKeyboardMovementSettings {
     /// Cooldown between two movements when not sped up
     pub default_cooldown_ms: u64,
@@ -462,13 +579,12 @@ impl Default for CursorKeyboardMovement {
     }
 }
 
-/// System handling movements of the selection cursor from the keyboard
+/// System handling movements of the selection cursor from the keyboard and gamepads
 pub fn move_selection_from_keybinds<C: CartesianCoordinates>(
     mut commands: Commands,
-    keys: Res<ButtonInput<KeyCode>>,
+    mut inputs: ProcGenInputs,
     time: Res<Time>,
     selection_marker_settings: Res<SelectionCursorMarkerSettings>,
-    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
     mut marker_events: EventWriter<MarkerDespawnEvent>,
     key_mvmt_values: Res<CursorKeyboardMovementSettings>,
     mut key_mvmt: ResMut<CursorKeyboardMovement>,
@@ -479,11 +595,11 @@ pub fn move_selection_from_keybinds<C: CartesianCoordinates>(
         return;
     };
 
-    let axis_selection = if keys.pressed(proc_gen_key_bindings.cursor_x_axis) {
+    let axis_selection = if inputs.pressed(ProcGenInput::CursorXAxis) {
         Some(Direction::XForward)
-    } else if keys.pressed(proc_gen_key_bindings.cursor_y_axis) {
+    } else if inputs.pressed(ProcGenInput::CursorYAxis) {
         Some(Direction::YForward)
-    } else if keys.pressed(proc_gen_key_bindings.cursor_z_axis) {
+    } else if inputs.pressed(ProcGenInput::CursorZAxis) {
         Some(Direction::ZForward)
     } else {
         None
@@ -494,24 +610,24 @@ pub fn move_selection_from_keybinds<C: CartesianCoordinates>(
         // Pressed => moves with default cooldown
         // Pressed for a while => speeds up, shorter cooldown
         // Sped up & no press => resets to default cooldown
-        let cursor_movement = if keys.just_pressed(proc_gen_key_bindings.prev_node) {
+        let cursor_movement = if inputs.just_pressed(ProcGenInput::PrevNode) {
             Some(-1)
-        } else if keys.just_pressed(proc_gen_key_bindings.next_node) {
+        } else if inputs.just_pressed(ProcGenInput::NextNode) {
             Some(1)
         } else {
             let (movement, pressed) = match key_mvmt.cooldown.finished() {
                 true => {
-                    if keys.pressed(proc_gen_key_bindings.prev_node) {
+                    if inputs.pressed(ProcGenInput::PrevNode) {
                         (Some(-1), true)
-                    } else if keys.pressed(proc_gen_key_bindings.next_node) {
+                    } else if inputs.pressed(ProcGenInput::NextNode) {
                         (Some(1), true)
                     } else {
                         (None, false)
                     }
                 }
                 false => {
-                    if keys.pressed(proc_gen_key_bindings.prev_node)
-                        || keys.pressed(proc_gen_key_bindings.next_node)
+                    if inputs.pressed(ProcGenInput::PrevNode)
+                        || inputs.pressed(ProcGenInput::NextNode)
                     {
                         (None, true)
                     } else {
@@ -604,16 +720,88 @@ pub fn spawn_marker_and_create_cursor(
     }
 }
 
+/// Parses a node reference typed by a user, and returns the index of the referenced node if it exists in `grid`.
+///
+/// The reference can either be:
+/// - grid coordinates `x,y,z`, separated by commas and/or spaces. `z` can be omitted for 2d grids.
+/// - a node index, as a single number, optionally prefixed by `#`.
+pub fn parse_node_reference<C: CartesianCoordinates>(
+    input: &str,
+    grid: &CartesianGrid<C>,
+) -> Option<NodeIndex> {
+    let input = input.trim();
+    if let Some(index) = input.strip_prefix('#') {
+        return parse_index(grid, index.trim().parse::<NodeIndex>().ok()?);
+    }
+    let coords: Vec<u32> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<u32>())
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match coords[..] {
+        [index] => parse_index(grid, index as NodeIndex),
+        [x, y] => parse_coords(grid, x, y, 0),
+        [x, y, z] => parse_coords(grid, x, y, z),
+        _ => None,
+    }
+}
+
+fn parse_index<C: CartesianCoordinates>(
+    grid: &CartesianGrid<C>,
+    index: NodeIndex,
+) -> Option<NodeIndex> {
+    match index < grid.total_size() {
+        true => Some(index),
+        false => None,
+    }
+}
+
+fn parse_coords<C: CartesianCoordinates>(
+    grid: &CartesianGrid<C>,
+    x: u32,
+    y: u32,
+    z: u32,
+) -> Option<NodeIndex> {
+    match x < grid.size_x() && y < grid.size_y() && z < grid.size_z() {
+        true => Some(grid.index_from_coords(x, y, z)),
+        false => None,
+    }
+}
+
+/// Moves the selection [Cursor] to the node at `node_index` in the grid `grid_entity`, replacing its previous marker
+pub fn move_selection_cursor<C: CartesianCoordinates>(
+    commands: &mut Commands,
+    marker_events: &mut EventWriter<MarkerDespawnEvent>,
+    cursor: &mut Cursor,
+    grid_entity: Entity,
+    grid: &CartesianGrid<C>,
+    node_index: NodeIndex,
+    color: Color,
+) {
+    if let Some(grid_cursor) = &cursor.0 {
+        marker_events.send(MarkerDespawnEvent::Marker(grid_cursor.marker));
+    }
+    cursor.0 = Some(spawn_marker_and_create_cursor(
+        commands,
+        grid_entity,
+        grid.pos_from_index(node_index),
+        node_index,
+        color,
+    ));
+}
+
 /// Utility function to transform data from a [CursorInfo] into a [String]
 pub fn cursor_info_to_string(cursor: &TargetedNode, cursor_info: &CursorInfo) -> String {
     let text = if cursor_info.models_variations.len() > 1 {
         format!(
-            "Grid: {{{}}}\n\
+            "Grid: {{{}}}, seed: {}\n\
             {} possible models, {} variations:\n\
             {{{}}}\n\
             {{{}}}\n\
             {}",
             cursor,
+            cursor_info.seed,
             cursor_info.models_variations.len(),
             cursor_info.total_models_count,
             cursor_info.models_variations[0],
@@ -626,17 +814,81 @@ pub fn cursor_info_to_string(cursor: &TargetedNode, cursor_info: &CursorInfo) ->
         )
     } else if cursor_info.models_variations.len() == 1 {
         format!(
-            "Grid: {{{}}}\n\
+            "Grid: {{{}}}, seed: {}\n\
             Model: {{{}}}\n",
-            cursor, cursor_info.models_variations[0],
+            cursor, cursor_info.seed, cursor_info.models_variations[0],
         )
     } else {
         format!(
-            "Grid: {{{}}}\n\
+            "Grid: {{{}}}, seed: {}\n\
             No models possible\n",
-            cursor,
+            cursor, cursor_info.seed,
         )
     };
+    let text = match cursor_info.neighbours.is_empty() {
+        true => text,
+        false => format!(
+            "{}{}",
+            text,
+            neighbours_info_to_string(&cursor_info.neighbours)
+        ),
+    };
+    match &cursor_info.selection_box {
+        Some(selection_box) => format!("{}{}\n", text, selection_box),
+        None => text,
+    }
+}
+
+/// Returns the [NeighbourInfo] of all the neighbours of the node at `node_index`, in the current state of the `generator`
+pub fn neighbours_info<C: CartesianCoordinates>(
+    generator: &Generator<C, CartesianGrid<C>>,
+    node_index: NodeIndex,
+) -> Vec<NeighbourInfo> {
+    let grid = generator.grid();
+    let mut neighbours_indexes = vec![None; grid.directions_count()];
+    grid.get_neighbours_in_all_directions(node_index, &mut neighbours_indexes);
+
+    let mut neighbours = Vec::new();
+    for direction in grid.coord_system().directions() {
+        let Some(neighbour_index) = neighbours_indexes[(*direction).into()] else {
+            continue;
+        };
+        let Some(node_entropy) = generator.node_entropy(neighbour_index) else {
+            continue;
+        };
+        neighbours.push(NeighbourInfo {
+            direction: *direction,
+            node_index: neighbour_index,
+            possible_models_count: node_entropy.possible_models_count,
+        });
+    }
+    neighbours
+}
+
+/// Returns the most constrained [NeighbourInfo], the ungenerated neighbour with the least possible models, if any
+pub fn most_constrained_neighbour(neighbours: &[NeighbourInfo]) -> Option<&NeighbourInfo> {
+    neighbours
+        .iter()
+        .filter(|neighbour| neighbour.possible_models_count > 1)
+        .min_by_key(|neighbour| neighbour.possible_models_count)
+}
+
+/// Utility function to transform a list of [NeighbourInfo] into a [String]
+pub fn neighbours_info_to_string(neighbours: &[NeighbourInfo]) -> String {
+    let most_constrained = most_constrained_neighbour(neighbours).map(|n| n.node_index);
+    let mut text = "Neighbours possibilities:\n".to_owned();
+    for neighbour in neighbours.iter() {
+        text.push_str(&format!(
+            "  {:?}: {}{}\n",
+            neighbour.direction,
+            neighbour.possible_models_count,
+            if Some(neighbour.node_index) == most_constrained {
+                " (most constrained)"
+            } else {
+                ""
+            }
+        ));
+    }
     text
 }
 
@@ -645,31 +897,39 @@ pub fn cursor_info_to_string(cursor: &TargetedNode, cursor_info: &CursorInfo) ->
 pub struct Flag(pub bool);
 
 /// System updating the cursors overlay UI
+///
+/// Overlays of a [CursorsOverlaysViewport] are positioned in the viewport of their camera. Overlays of the default root use the only camera of the app, and are hidden when [GridCursorsOverlayCamera]s are used.
 pub fn update_cursors_overlays(
     mut camera_warning_flag: Local<Flag>,
     mut commands: Commands,
     ui_config: Res<GridCursorsUiSettings>,
     just_one_camera: Query<(&Camera, &GlobalTransform), Without<GridCursorsOverlayCamera>>,
-    overlay_camera: Query<(&Camera, &GlobalTransform), With<GridCursorsOverlayCamera>>,
+    overlay_cameras: Query<(&Camera, &GlobalTransform), With<GridCursorsOverlayCamera>>,
     cursor_overlays: Query<(Entity, &CursorOverlay)>,
     cursors: Query<(&CursorInfo, &Cursor)>,
     markers: Query<&GlobalTransform, With<GridMarker>>,
 ) {
-    let (camera, cam_gtransform) = match just_one_camera.get_single() {
-        Ok(found) => found,
-        Err(_) => match overlay_camera.get_single() {
-            Ok(found) => found,
-            Err(_) => {
-                if !camera_warning_flag.0 {
-                    warn!("None (or too many) Camera(s) found with 'GridCursorsOverlayCamera' component to display cursors overlays. Add `GridCursorsOverlayCamera` component to a Camera or change the cursor UI mode.");
-                    camera_warning_flag.0 = true;
-                }
-                return;
-            }
-        },
+    let default_camera = match overlay_cameras.is_empty() {
+        true => just_one_camera.get_single().ok(),
+        false => None,
     };
+    if default_camera.is_none() && overlay_cameras.is_empty() && !camera_warning_flag.0 {
+        warn!("None (or too many) Camera(s) found with 'GridCursorsOverlayCamera' component to display cursors overlays. Add `GridCursorsOverlayCamera` component to a Camera or change the cursor UI mode.");
+        camera_warning_flag.0 = true;
+    }
 
     for (overlay_entity, overlay) in cursor_overlays.iter() {
+        let camera = match overlay.camera {
+            Some(camera) => overlay_cameras.get(camera).ok(),
+            None => default_camera,
+        };
+        let Some((camera, cam_gtransform)) = camera else {
+            // No camera => no text overlay
+            commands.entity(overlay_entity).insert(TextBundle {
+                ..Default::default()
+            });
+            continue;
+        };
         let Ok((cursor_info, cursor)) = cursors.get(overlay.cursor_entity) else {
             continue;
         };
@@ -710,11 +970,67 @@ pub fn update_cursors_overlays(
             },
             style: Style {
                 position_type: PositionType::Absolute,
-                left: Val::Px(viewport_pos.x + 5.0),
-                top: Val::Px(viewport_pos.y + 5.0),
+                left: Val::Px(viewport_pos.x + ui_config.overlay_offset.x),
+                top: Val::Px(viewport_pos.y + ui_config.overlay_offset.y),
+                padding: ui_config.panel_padding,
                 ..Default::default()
             },
             ..Default::default()
         });
     }
 }
+
+/// System updating the cursors world-space billboards
+///
+/// The billboards are placed next to the cursors markers and rotated to face the cursors overlay camera (see [GridCursorsOverlayCamera]), if any.
+pub fn update_cursors_billboards(
+    ui_config: Res<GridCursorsUiSettings>,
+    just_one_camera: Query<&GlobalTransform, (With<Camera>, Without<GridCursorsOverlayCamera>)>,
+    overlay_camera: Query<&GlobalTransform, (With<Camera>, With<GridCursorsOverlayCamera>)>,
+    mut cursor_billboards: Query<(&CursorBillboard, &mut Text, &mut Transform, &mut Visibility)>,
+    cursors: Query<(&CursorInfo, &Cursor)>,
+    markers: Query<&GlobalTransform, With<GridMarker>>,
+) {
+    let camera_rotation = match just_one_camera.get_single() {
+        Ok(cam_gtransform) => Some(cam_gtransform.compute_transform().rotation),
+        Err(_) => overlay_camera
+            .get_single()
+            .ok()
+            .map(|cam_gtransform| cam_gtransform.compute_transform().rotation),
+    };
+
+    for (billboard, mut text, mut transform, mut visibility) in cursor_billboards.iter_mut() {
+        let Ok((cursor_info, cursor)) = cursors.get(billboard.cursor_entity) else {
+            continue;
+        };
+        let Some(grid_cursor) = &cursor.0 else {
+            // No cursor => no billboard
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let Ok(marker_gtransform) = markers.get(grid_cursor.marker) else {
+            // No marker => no billboard
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        *visibility = Visibility::Inherited;
+        *transform = Transform {
+            translation: marker_gtransform.translation() + ui_config.billboard_offset,
+            rotation: camera_rotation.unwrap_or_default(),
+            scale: Vec3::splat(ui_config.billboard_scale),
+        };
+        *text = Text {
+            linebreak_behavior: BreakLineOn::NoWrap,
+            sections: vec![TextSection {
+                value: cursor_info_to_string(&grid_cursor, cursor_info),
+                style: TextStyle {
+                    font_size: ui_config.font_size,
+                    color: ui_config.text_color,
+                    ..Default::default()
+                },
+            }],
+            ..Default::default()
+        };
+    }
+}
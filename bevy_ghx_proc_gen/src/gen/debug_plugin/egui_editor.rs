@@ -1,11 +1,13 @@
 use bevy::{
     ecs::{
+        entity::Entity,
         event::{Event, EventReader, EventWriter},
         query::With,
-        system::{Query, Res, ResMut, Resource},
+        system::{Commands, Local, Query, Res, ResMut, Resource},
     },
-    input::{mouse::MouseButton, ButtonInput},
-    log::warn,
+    hierarchy::Parent,
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    log::{info, warn},
 };
 use bevy_egui::{
     egui::{self, Color32, Pos2},
@@ -15,17 +17,25 @@ use ghx_proc_gen::{
     generator::{
         model::{ModelInstance, ModelRotation},
         rules::ModelInfo,
-        Generator,
+        GenerationStatus, Generator,
     },
     ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    NodeIndex,
 };
 
-use crate::gen::GridNode;
+use bevy_ghx_grid::debug_plugin::markers::MarkerDespawnEvent;
+
+use crate::gen::{node_generation, GridNode, NodeLayer};
 
 use super::{
-    cursor::{Cursor, CursorInfo, SelectCursor},
+    cursor::{
+        move_selection_cursor, parse_node_reference, Cursor, CursorInfo, CursorMarkerSettings,
+        SelectCursor, SelectionCursorMarkerSettings,
+    },
     generation::ActiveGeneration,
-    picking::{CursorTarget, NodeOverEvent, NodeSelectedEvent},
+    picking::{CursorTarget, NodeContextMenuEvent, NodeOverEvent, NodeSelectedEvent},
+    timeline::{scrub_generation, GenerationTimeline},
+    GenerationControl, GenerationControlStatus, ProcGenKeyBindings,
 };
 
 /// Resource sued to track the status of the edgui editor
@@ -48,6 +58,28 @@ pub struct EditorContext {
     pub model_brush: Option<ModelBrush>,
     /// Is the editor currently painting
     pub painting: bool,
+    /// Content of the seed text field
+    pub seed_input: String,
+    /// Content of the "go to" text field, see [`super::cursor::parse_node_reference`] for the accepted formats
+    pub jump_input: String,
+    /// Currently opened node context menu, if any
+    pub context_menu: Option<NodeContextMenu>,
+}
+
+/// A context menu opened on a node, used to force or ban its possible models
+#[derive(Clone, Copy, Debug)]
+pub struct NodeContextMenu {
+    /// Grid entity the node belongs to
+    pub grid: Entity,
+    /// Index of the node in its grid
+    pub node_index: NodeIndex,
+    /// Screen position of the menu
+    pub position: Pos2,
+}
+
+enum NodeContextMenuAction {
+    Force(ModelInstance),
+    Ban(ModelInstance),
 }
 
 /// A model "brush" holding information about what model it paints
@@ -80,25 +112,64 @@ pub fn toggle_editor(mut editor_config: ResMut<EditorConfig>) {
     editor_config.enabled = !editor_config.enabled;
 }
 
+/// System that copies the seed of the [ActiveGeneration] to the clipboard on a keypress.
+///
+/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+pub fn copy_seed_from_keybinds<C: CartesianCoordinates>(
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut contexts: EguiContexts,
+    active_generation: Res<ActiveGeneration>,
+    generations: Query<&Generator<C, CartesianGrid<C>>>,
+) {
+    if !proc_gen_key_bindings
+        .editor
+        .just_pressed(&keys, proc_gen_key_bindings.editor.copy_seed)
+    {
+        return;
+    }
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    let Ok(generator) = generations.get(active_generation) else {
+        return;
+    };
+    let seed = generator.seed();
+    contexts
+        .ctx_mut()
+        .output_mut(|output| output.copied_text = seed.to_string());
+    info!("Copied seed {} to the clipboard", seed);
+}
+
 /// System used to draw the editor egui window
 pub fn draw_edition_panel<C: CartesianCoordinates>(
-    editor_context: ResMut<EditorContext>,
+    mut commands: Commands,
+    mut editor_context: ResMut<EditorContext>,
     mut contexts: EguiContexts,
     active_generation: Res<ActiveGeneration>,
+    mut generation_control: ResMut<GenerationControl>,
     mut brush_events: EventWriter<BrushEvent>,
-    generations: Query<&mut Generator<C, CartesianGrid<C>>>,
-    selection_cursor: Query<(&Cursor, &CursorInfo), With<SelectCursor>>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    selection_marker_settings: Res<SelectionCursorMarkerSettings>,
+    mut generations: Query<&mut Generator<C, CartesianGrid<C>>>,
+    timelines: Query<&GenerationTimeline>,
+    mut selection_cursor: Query<(&mut Cursor, &CursorInfo), With<SelectCursor>>,
 ) {
     let Some(active_generation) = active_generation.0 else {
         return;
     };
-    let Ok(generator) = generations.get(active_generation) else {
+    let Ok(mut generator) = generations.get_mut(active_generation) else {
         return;
     };
-    let Ok((cursor, cursor_info)) = selection_cursor.get_single() else {
+    let Ok((mut cursor, cursor_info)) = selection_cursor.get_single_mut() else {
         return;
     };
 
+    let mut requested_seed = None;
+    let current_step = generator.trace().len();
+    let mut requested_step = current_step;
+    let mut requested_jump = false;
+    let timeline = timelines.get(active_generation).ok();
     // TODO Cache ? rules models groups
     egui::Window::new("Edition panel")
         .title_bar(true)
@@ -118,6 +189,56 @@ pub fn draw_edition_panel<C: CartesianCoordinates>(
                 );
             });
 
+            ui.horizontal_wrapped(|ui| {
+                ui.label("🌱 Seed:");
+                ui.colored_label(Color32::WHITE, format!("{}", generator.seed()));
+                if ui.button("Copy").clicked() {
+                    ui.output_mut(|output| output.copied_text = generator.seed().to_string());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut editor_context.seed_input);
+                if ui.button("Run with seed").clicked() {
+                    match editor_context.seed_input.trim().parse::<u64>() {
+                        Ok(seed) => requested_seed = Some(seed),
+                        Err(_) => warn!("Invalid seed: '{}'", editor_context.seed_input),
+                    }
+                }
+            });
+
+            if let Some(timeline) = timeline {
+                ui.horizontal(|ui| {
+                    ui.label("⏱ Timeline:");
+                    if ui.button("⏮").clicked() {
+                        requested_step = 0;
+                    }
+                    if ui.button("◀").clicked() {
+                        requested_step = current_step.saturating_sub(1);
+                    }
+                    ui.add(
+                        egui::Slider::new(&mut requested_step, 0..=timeline.len()).text("steps"),
+                    );
+                    if ui.button("▶").clicked() {
+                        requested_step = current_step + 1;
+                    }
+                    if ui.button("⏭").clicked() {
+                        requested_step = timeline.len();
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("🎯 Go to:");
+                let response = ui
+                    .text_edit_singleline(&mut editor_context.jump_input)
+                    .on_hover_text("x,y,z coordinates or #node_index");
+                if ui.button("Go").clicked()
+                    || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+                {
+                    requested_jump = true;
+                }
+            });
+
             match &cursor.0 {
                 Some(targeted_node) => {
                     ui.horizontal_wrapped(|ui| {
@@ -185,10 +306,10 @@ pub fn draw_edition_panel<C: CartesianCoordinates>(
                         {
                             brush_events.send(BrushEvent::UpdateBrush(ModelBrush {
                                 info: model_group.info.clone(),
-                                instance: ModelInstance {
-                                    model_index: model_group.index,
-                                    rotation: model_group.rotations[0],
-                                },
+                                instance: ModelInstance::new(
+                                    model_group.index,
+                                    model_group.rotations[0],
+                                ),
                             }));
                         }
                         if selected {
@@ -209,6 +330,153 @@ pub fn draw_edition_panel<C: CartesianCoordinates>(
                 }
             });
         });
+
+    if requested_jump {
+        match parse_node_reference(&editor_context.jump_input, generator.grid()) {
+            Some(node_index) => move_selection_cursor(
+                &mut commands,
+                &mut marker_events,
+                &mut cursor,
+                active_generation,
+                generator.grid(),
+                node_index,
+                selection_marker_settings.color(),
+            ),
+            None => warn!("Invalid node reference: '{}'", editor_context.jump_input),
+        }
+    }
+
+    if let Some(seed) = requested_seed {
+        info!(
+            "Reinitializing generation {:?} with seed {}",
+            active_generation, seed
+        );
+        generation_control.need_reinit = false;
+        match generator.reinitialize_with_seed(seed) {
+            GenerationStatus::Ongoing => {
+                generation_control.status = GenerationControlStatus::Ongoing;
+            }
+            GenerationStatus::Done => generation_control.need_reinit = true,
+        }
+    } else if requested_step != current_step {
+        if let Some(timeline) = timeline {
+            scrub_generation(
+                &mut generator,
+                timeline,
+                &mut generation_control,
+                requested_step,
+            );
+        }
+    }
+}
+
+/// System opening a [NodeContextMenu] on the node targeted by the last [NodeContextMenuEvent]
+pub fn open_node_context_menu(
+    mut editor_context: ResMut<EditorContext>,
+    mut contexts: EguiContexts,
+    mut context_menu_events: EventReader<NodeContextMenuEvent>,
+    grid_nodes: Query<(&GridNode, &Parent)>,
+    node_layers: Query<&Parent, With<NodeLayer>>,
+) {
+    let Some(event) = context_menu_events.read().last() else {
+        return;
+    };
+    let Ok((node, node_parent)) = grid_nodes.get(event.0) else {
+        return;
+    };
+    let position = contexts
+        .ctx_mut()
+        .input(|input| input.pointer.hover_pos())
+        .unwrap_or_default();
+    editor_context.context_menu = Some(NodeContextMenu {
+        grid: node_generation(node_parent, &node_layers),
+        node_index: node.0,
+        position,
+    });
+}
+
+/// System used to draw the [NodeContextMenu] egui popup, listing the possible models of a node with actions to force or ban them
+pub fn draw_node_context_menu<C: CartesianCoordinates>(
+    mut editor_context: ResMut<EditorContext>,
+    mut contexts: EguiContexts,
+    mut generations: Query<&mut Generator<C, CartesianGrid<C>>>,
+) {
+    let Some(menu) = editor_context.context_menu else {
+        return;
+    };
+    let Ok(mut generator) = generations.get_mut(menu.grid) else {
+        editor_context.context_menu = None;
+        return;
+    };
+
+    let (models_variations, _) = generator.get_models_variations_on(menu.node_index);
+    let mut action = None;
+    let mut close = false;
+    egui::Area::new(egui::Id::new("node_context_menu"))
+        .fixed_pos(menu.position)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        Color32::WHITE,
+                        format!(
+                            "Node {{{}}}, index: {}",
+                            generator.grid().pos_from_index(menu.node_index),
+                            menu.node_index
+                        ),
+                    );
+                    if ui.button("✖").clicked() {
+                        close = true;
+                    }
+                });
+                ui.separator();
+                if models_variations.is_empty() {
+                    ui.label("No models possible");
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(300.)
+                    .show(ui, |ui| {
+                        for model_group in models_variations.iter() {
+                            for rotation in model_group.rotations.iter() {
+                                let instance = ModelInstance::new(model_group.index, *rotation);
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("{}, {}", model_group.info.name, instance));
+                                    if ui.button("Force").clicked() {
+                                        action = Some(NodeContextMenuAction::Force(instance));
+                                    }
+                                    if ui.button("Ban").clicked() {
+                                        action = Some(NodeContextMenuAction::Ban(instance));
+                                    }
+                                });
+                            }
+                        }
+                    });
+            });
+        });
+
+    match action {
+        Some(NodeContextMenuAction::Force(instance)) => {
+            if let Err(err) = generator.set_and_propagate(menu.node_index, instance, true) {
+                warn!(
+                    "Failed to generate model {} on node {}: {}",
+                    instance, menu.node_index, err
+                );
+            }
+            close = true;
+        }
+        Some(NodeContextMenuAction::Ban(instance)) => {
+            if let Err(err) = generator.ban_and_propagate(menu.node_index, instance) {
+                warn!(
+                    "Failed to ban model {} from node {}: {}",
+                    instance, menu.node_index, err
+                );
+            }
+        }
+        None => (),
+    }
+    if close {
+        editor_context.context_menu = None;
+    }
 }
 
 /// System reading [BrushEvent] to update the current model brush in the [EditorContext]
@@ -252,41 +520,91 @@ pub fn update_painting_state(
     }
 }
 
-/// System issuing the generation requests to the geenrator based on the painting state
+/// System issuing the generation requests to the geenrator based on the painting state.
+///
+/// While painting, the brush is applied to the node where the painting started, then to every node the pointer passes over. Nodes skipped between two pointer events (fast pointer movements) are also painted.
 pub fn paint<C: CartesianCoordinates>(
     editor_context: ResMut<EditorContext>,
     active_generation: Res<ActiveGeneration>,
+    mut last_painted_node: Local<Option<(Entity, NodeIndex)>>,
+    mut node_select_events: EventReader<NodeSelectedEvent>,
     mut node_over_events: EventReader<NodeOverEvent>,
     mut generations: Query<&mut Generator<C, CartesianGrid<C>>>,
     cursor_targets: Query<&GridNode, With<CursorTarget>>,
 ) {
     if !editor_context.painting {
+        *last_painted_node = None;
+        node_select_events.clear();
         node_over_events.clear();
         return;
     }
     let Some(model_brush) = &editor_context.model_brush else {
+        node_select_events.clear();
         node_over_events.clear();
         return;
     };
     let Some(active_generation) = active_generation.0 else {
+        node_select_events.clear();
         node_over_events.clear();
         return;
     };
     let Ok(mut generator) = generations.get_mut(active_generation) else {
+        node_select_events.clear();
         node_over_events.clear();
         return;
     };
 
-    for ev in node_over_events.read() {
-        let Ok(node) = cursor_targets.get(ev.0) else {
+    let painted_entities = node_select_events
+        .read()
+        .map(|ev| ev.0)
+        .chain(node_over_events.read().map(|ev| ev.0))
+        .collect::<Vec<_>>();
+    for entity in painted_entities {
+        let Ok(node) = cursor_targets.get(entity) else {
             continue;
         };
 
-        if let Err(err) = generator.set_and_propagate(node.0, model_brush.instance, true) {
-            warn!(
-                "Failed to generate model {} on node {}: {}",
-                model_brush.instance, node.0, err
-            );
+        let nodes_to_paint = match *last_painted_node {
+            Some((grid_entity, last_node_index)) if grid_entity == active_generation => {
+                nodes_between(generator.grid(), last_node_index, node.0)
+            }
+            _ => vec![node.0],
+        };
+        for node_index in nodes_to_paint {
+            if let Err(err) = generator.set_and_propagate(node_index, model_brush.instance, true) {
+                warn!(
+                    "Failed to generate model {} on node {}: {}",
+                    model_brush.instance, node_index, err
+                );
+            }
         }
+        *last_painted_node = Some((active_generation, node.0));
     }
 }
+
+/// Returns the indexes of the nodes on the line from `from` (excluded) to `to` (included)
+fn nodes_between<C: CartesianCoordinates>(
+    grid: &CartesianGrid<C>,
+    from: NodeIndex,
+    to: NodeIndex,
+) -> Vec<NodeIndex> {
+    let (from, to) = (grid.pos_from_index(from), grid.pos_from_index(to));
+    let delta = [
+        to.x as i64 - from.x as i64,
+        to.y as i64 - from.y as i64,
+        to.z as i64 - from.z as i64,
+    ];
+    let steps = delta.iter().map(|d| d.abs()).max().unwrap_or(0);
+    (1..=steps)
+        .map(|step| {
+            let lerp = |start: u32, d: i64| {
+                (start as i64 + (d * step + d.signum() * steps / 2) / steps) as u32
+            };
+            grid.index_from_coords(
+                lerp(from.x, delta[0]),
+                lerp(from.y, delta[1]),
+                lerp(from.z, delta[2]),
+            )
+        })
+        .collect()
+}
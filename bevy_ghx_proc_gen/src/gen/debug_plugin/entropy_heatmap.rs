@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use bevy::{
+    color::{
+        palettes::css::{BLUE, ORANGE_RED},
+        Color, LinearRgba, Mix,
+    },
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    input::{keyboard::KeyCode, ButtonInput},
+    prelude::{Deref, DerefMut},
+};
+use bevy_ghx_grid::debug_plugin::markers::{spawn_marker, MarkerDespawnEvent};
+use ghx_proc_gen::{
+    generator::Generator,
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        grid::Grid,
+    },
+};
+
+use super::{generation::GenerationEvent, ProcGenKeyBindings};
+
+/// Resource used to customize the entropy heatmap
+#[derive(Resource, Debug)]
+pub struct EntropyHeatmapSettings {
+    /// Whether or not the heatmap is currently displayed
+    pub enabled: bool,
+    /// Color of the ungenerated nodes with the lowest entropy (the most constrained ones)
+    pub low_entropy_color: Color,
+    /// Color of the ungenerated nodes with the highest entropy (the least constrained ones)
+    pub high_entropy_color: Color,
+}
+
+impl Default for EntropyHeatmapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            low_entropy_color: Color::Srgba(ORANGE_RED),
+            high_entropy_color: Color::Srgba(BLUE),
+        }
+    }
+}
+
+/// Component used to store the heatmap [`bevy_ghx_grid::debug_plugin::markers::GridMarker`] entities of a generation
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct EntropyMarkers(pub Vec<Entity>);
+
+/// System that toggles the entropy heatmap on a keypress.
+///
+/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+pub fn toggle_entropy_heatmap_from_keybinds(
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut heatmap_settings: ResMut<EntropyHeatmapSettings>,
+) {
+    if proc_gen_key_bindings
+        .editor
+        .just_pressed(&keys, proc_gen_key_bindings.editor.toggle_entropy_heatmap)
+    {
+        heatmap_settings.enabled = !heatmap_settings.enabled;
+    }
+}
+
+/// System that rebuilds the entropy heatmap of the generations updated by a [`GenerationEvent`].
+///
+/// Each ungenerated node gets a marker colored by its entropy, relatively to the other ungenerated nodes of its generation.
+pub fn update_entropy_heatmap<C: CartesianCoordinates>(
+    mut commands: Commands,
+    heatmap_settings: Res<EntropyHeatmapSettings>,
+    mut generation_events: EventReader<GenerationEvent>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    mut generations: Query<(
+        Entity,
+        &Generator<C, CartesianGrid<C>>,
+        Option<&mut EntropyMarkers>,
+    )>,
+) {
+    let mut updated_generations = HashSet::new();
+    for event in generation_events.read() {
+        match event {
+            GenerationEvent::Reinitialized(gen_entity) => updated_generations.insert(*gen_entity),
+            GenerationEvent::Updated(gen_entity, _) => updated_generations.insert(*gen_entity),
+        };
+    }
+    if !heatmap_settings.is_changed() && updated_generations.is_empty() {
+        return;
+    }
+
+    for (gen_entity, generator, entropy_markers) in generations.iter_mut() {
+        if !heatmap_settings.is_changed() && !updated_generations.contains(&gen_entity) {
+            continue;
+        }
+
+        if let Some(mut entropy_markers) = entropy_markers {
+            for marker in entropy_markers.drain(..) {
+                marker_events.send(MarkerDespawnEvent::Marker(marker));
+            }
+        }
+        if !heatmap_settings.enabled {
+            continue;
+        }
+
+        let mut markers = Vec::new();
+        let snapshot = generator.entropy_snapshot();
+        let max_entropy = snapshot
+            .iter()
+            .filter(|node| node.possible_models_count > 1)
+            .fold(0., |max: f32, node| max.max(node.entropy));
+        let low_color = heatmap_settings.low_entropy_color.to_linear();
+        let high_color = heatmap_settings.high_entropy_color.to_linear();
+
+        for (node_index, node) in snapshot.iter().enumerate() {
+            if node.possible_models_count <= 1 {
+                continue;
+            }
+            let factor = match max_entropy > 0. {
+                true => node.entropy / max_entropy,
+                false => 1.,
+            };
+            markers.push(spawn_marker(
+                &mut commands,
+                gen_entity,
+                Color::LinearRgba(LinearRgba::mix(&low_color, &high_color, factor)),
+                generator.grid().pos_from_index(node_index),
+            ));
+        }
+        commands.entity(gen_entity).insert(EntropyMarkers(markers));
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use bevy::{
+    color::{
+        palettes::css::{RED, YELLOW},
+        Color, LinearRgba, Mix,
+    },
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    input::{keyboard::KeyCode, ButtonInput},
+    prelude::{Deref, DerefMut},
+};
+use bevy_ghx_grid::debug_plugin::markers::{spawn_marker, MarkerDespawnEvent};
+use ghx_proc_gen::{
+    generator::Generator,
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        grid::Grid,
+    },
+};
+
+use super::{generation::GenerationEvent, ProcGenKeyBindings};
+
+/// Resource used to customize the failure heatmap
+#[derive(Resource, Debug)]
+pub struct FailureHeatmapSettings {
+    /// Whether or not the heatmap is currently displayed
+    pub enabled: bool,
+    /// Color of the nodes where the fewest contradictions occured (at least one)
+    pub few_contradictions_color: Color,
+    /// Color of the nodes where the most contradictions occured
+    pub many_contradictions_color: Color,
+}
+
+impl Default for FailureHeatmapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            few_contradictions_color: Color::Srgba(YELLOW),
+            many_contradictions_color: Color::Srgba(RED),
+        }
+    }
+}
+
+/// Component used to store the failure heatmap [`bevy_ghx_grid::debug_plugin::markers::GridMarker`] entities of a generation
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct FailureMarkers(pub Vec<Entity>);
+
+/// System that toggles the failure heatmap on a keypress.
+///
+/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+pub fn toggle_failure_heatmap_from_keybinds(
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut heatmap_settings: ResMut<FailureHeatmapSettings>,
+) {
+    if proc_gen_key_bindings
+        .editor
+        .just_pressed(&keys, proc_gen_key_bindings.editor.toggle_failure_heatmap)
+    {
+        heatmap_settings.enabled = !heatmap_settings.enabled;
+    }
+}
+
+/// System that rebuilds the failure heatmap of the generations reinitialized since the last update.
+///
+/// Each node where a contradiction occured, in any try, gets a marker colored by its contradictions count (see [`ghx_proc_gen::generator::GenStats::contradiction_sites`]), relatively to the other nodes of its generation.
+pub fn update_failure_heatmap<C: CartesianCoordinates>(
+    mut commands: Commands,
+    heatmap_settings: Res<FailureHeatmapSettings>,
+    mut generation_events: EventReader<GenerationEvent>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    mut generations: Query<(
+        Entity,
+        &Generator<C, CartesianGrid<C>>,
+        Option<&mut FailureMarkers>,
+    )>,
+) {
+    // Contradiction sites only change when a generation fails, which is always followed by a reinitialization
+    let mut reinitialized_generations = HashSet::new();
+    for event in generation_events.read() {
+        if let GenerationEvent::Reinitialized(gen_entity) = event {
+            reinitialized_generations.insert(*gen_entity);
+        }
+    }
+    if !heatmap_settings.is_changed() && reinitialized_generations.is_empty() {
+        return;
+    }
+
+    for (gen_entity, generator, failure_markers) in generations.iter_mut() {
+        if !heatmap_settings.is_changed() && !reinitialized_generations.contains(&gen_entity) {
+            continue;
+        }
+
+        if let Some(mut failure_markers) = failure_markers {
+            for marker in failure_markers.drain(..) {
+                marker_events.send(MarkerDespawnEvent::Marker(marker));
+            }
+        }
+        if !heatmap_settings.enabled {
+            continue;
+        }
+
+        let mut markers = Vec::new();
+        let contradiction_sites = &generator.stats().contradiction_sites;
+        let max_count = contradiction_sites.values().copied().max().unwrap_or(0);
+        let few_color = heatmap_settings.few_contradictions_color.to_linear();
+        let many_color = heatmap_settings.many_contradictions_color.to_linear();
+
+        for (node_index, count) in contradiction_sites.iter() {
+            let factor = match max_count > 1 {
+                true => (count - 1) as f32 / (max_count - 1) as f32,
+                false => 1.,
+            };
+            markers.push(spawn_marker(
+                &mut commands,
+                gen_entity,
+                Color::LinearRgba(LinearRgba::mix(&few_color, &many_color, factor)),
+                generator.grid().pos_from_index(*node_index),
+            ));
+        }
+        commands.entity(gen_entity).insert(FailureMarkers(markers));
+    }
+}
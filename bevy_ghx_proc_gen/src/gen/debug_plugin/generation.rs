@@ -6,16 +6,25 @@ use bevy::{
         component::Component,
         entity::Entity,
         event::{Event, EventWriter},
-        query::{With, Without},
+        query::{Added, Changed, Or, With, Without},
+        removal_detection::RemovedComponents,
         system::{Commands, Query, Res, ResMut, Resource},
     },
-    hierarchy::{Children, DespawnRecursiveExt},
-    input::{keyboard::KeyCode, ButtonInput},
+    hierarchy::{Children, DespawnRecursiveExt, Parent},
     log::{info, warn},
     prelude::{Deref, DerefMut},
     time::Time,
+    utils::Instant,
+};
+#[cfg(feature = "reflect")]
+use bevy::{
+    ecs::reflect::{ReflectComponent, ReflectResource},
+    reflect::Reflect,
+};
+use bevy_ghx_grid::debug_plugin::{
+    markers::{spawn_marker, GridMarker, MarkerDespawnEvent},
+    view::DebugGridView,
 };
-use bevy_ghx_grid::debug_plugin::markers::{spawn_marker, MarkerDespawnEvent};
 use ghx_proc_gen::{
     generator::{
         model::ModelIndex,
@@ -26,19 +35,28 @@ use ghx_proc_gen::{
     GeneratorError, NodeIndex,
 };
 
-use crate::gen::GridNode;
+use crate::gen::{GridNode, NodeLayer};
 
 use super::{
+    box_selection::SelectionBox,
+    breakpoints::GenerationBreakpoints,
+    contradiction::ContradictionEvent,
+    cursor::Cursor,
+    input::{ProcGenInput, ProcGenInputs},
+    model_gallery::ModelGallery,
+    model_highlight::HighlightedModel,
     spawn_node, AssetSpawner, AssetsBundleSpawner, ComponentSpawner, GenerationControl,
-    GenerationControlStatus, ProcGenKeyBindings, StepByStepTimed,
+    GenerationControlStatus, StepByStepTimed, TimeSliced,
 };
 
 /// Component used to store model indexes of models with no assets, just to be able to skip their generation when stepping
 #[derive(Component, Default, Deref, DerefMut)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct VoidNodes(pub HashSet<ModelIndex>);
 
 /// Component used to store a collection of [`bevy_ghx_grid::debug_plugin::markers::GridMarker`] entities
 #[derive(Component, Default, Deref, DerefMut)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct ErrorMarkers(pub Vec<Entity>);
 
 /// Event relating to a generation
@@ -54,9 +72,12 @@ pub enum GenerationEvent {
 ///
 /// The contained option can be [None] if no generation is active
 #[derive(Resource, Default)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub struct ActiveGeneration(pub Option<Entity>);
 
 /// Simple system that calculates and add a [`VoidNodes`] component for generator entites which don't have one yet.
+///
+/// The void nodes are the models with no assets registered in the [`AssetSpawner`] of the generation, see [`AssetSpawner::models_without_assets`]
 pub fn insert_void_nodes_to_new_generations<
     C: CartesianCoordinates,
     A: AssetsBundleSpawner,
@@ -73,16 +94,52 @@ pub fn insert_void_nodes_to_new_generations<
     >,
 ) {
     for (gen_entity, generation, asset_spawner) in new_generations.iter_mut() {
-        let mut void_nodes = HashSet::new();
-        for model_index in 0..generation.rules().original_models_count() {
-            if !asset_spawner.assets.contains_key(&model_index) {
-                void_nodes.insert(model_index);
-            }
-        }
+        let void_nodes =
+            asset_spawner.models_without_assets(generation.rules().original_models_count());
         commands.entity(gen_entity).insert(VoidNodes(void_nodes));
     }
 }
 
+/// System that re-calculates the [`VoidNodes`] of the generations whose [`AssetSpawner`] changed, so that they stay in sync with the registered assets
+pub fn update_void_nodes_on_assets_change<
+    C: CartesianCoordinates,
+    A: AssetsBundleSpawner,
+    T: ComponentSpawner,
+>(
+    mut generations: Query<
+        (
+            &Generator<C, CartesianGrid<C>>,
+            &AssetSpawner<A, T>,
+            &mut VoidNodes,
+        ),
+        Changed<AssetSpawner<A, T>>,
+    >,
+) {
+    for (generation, asset_spawner, mut void_nodes) in generations.iter_mut() {
+        let new_void_nodes =
+            asset_spawner.models_without_assets(generation.rules().original_models_count());
+        if **void_nodes != new_void_nodes {
+            **void_nodes = new_void_nodes;
+        }
+    }
+}
+
+/// System that copies the node size of the [`AssetSpawner`] of a generation into its [`DebugGridView`], when the view is added or when the spawner changes. The grid lines and markers of the view then stay aligned with the spawned assets, without duplicating the node size.
+///
+/// The extent of the view is read by `bevy_ghx_grid` from the [`CartesianGrid`] of the generation, and does not need to be synced.
+pub fn sync_debug_grid_views_node_size<A: AssetsBundleSpawner, T: ComponentSpawner>(
+    mut grid_views: Query<
+        (&AssetSpawner<A, T>, &mut DebugGridView),
+        Or<(Added<DebugGridView>, Changed<AssetSpawner<A, T>>)>,
+    >,
+) {
+    for (asset_spawner, mut grid_view) in grid_views.iter_mut() {
+        if grid_view.node_size != asset_spawner.node_size {
+            grid_view.node_size = asset_spawner.node_size;
+        }
+    }
+}
+
 /// System used to insert an empty [ErrorMarkers] component into new generation entities
 pub fn insert_error_markers_to_new_generations<C: CartesianCoordinates>(
     mut commands: Commands,
@@ -110,15 +167,86 @@ pub fn update_active_generation<C: CartesianCoordinates>(
     }
 }
 
+/// System cleaning up after the generations whose [`Generator`] component was removed, or whose entity was despawned:
+/// - despawns their spawned nodes and their markers, which would otherwise be left orphaned
+/// - clears the cursors, box selection, model highlight and model gallery referencing them
+/// - clears the [`ActiveGeneration`] if it was one of them
+pub fn cleanup_removed_generations<C: CartesianCoordinates>(
+    mut commands: Commands,
+    mut removed_generators: RemovedComponents<Generator<C, CartesianGrid<C>>>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    mut active_generation: ResMut<ActiveGeneration>,
+    mut selection_box: ResMut<SelectionBox>,
+    mut highlighted_model: ResMut<HighlightedModel>,
+    mut model_gallery: ResMut<ModelGallery>,
+    mut cursors: Query<&mut Cursor>,
+    spawned_entities: Query<
+        (Entity, &Parent, Option<&GridMarker>),
+        Or<(With<GridNode>, With<GridMarker>, With<NodeLayer>)>,
+    >,
+) {
+    let removed: HashSet<Entity> = removed_generators.read().collect();
+    if removed.is_empty() {
+        return;
+    }
+
+    // Children of a generation despawned without `despawn_recursive` keep a dangling `Parent`
+    for (entity, parent, marker) in spawned_entities.iter() {
+        if !removed.contains(&parent.get()) {
+            continue;
+        }
+        match marker {
+            Some(_) => {
+                marker_events.send(MarkerDespawnEvent::Marker(entity));
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+
+    for mut cursor in cursors.iter_mut() {
+        if let Some(targeted_node) = &cursor.0 {
+            if removed.contains(&targeted_node.grid) {
+                cursor.0 = None;
+            }
+        }
+    }
+    if let Some(box_selection) = &selection_box.0 {
+        if removed.contains(&box_selection.grid) {
+            selection_box.0 = None;
+        }
+    }
+    if let Some(info) = &highlighted_model.0 {
+        if removed.contains(&info.generation) {
+            highlighted_model.0 = None;
+        }
+    }
+    if let Some(gallery) = model_gallery.0 {
+        if removed.contains(&gallery.generation) {
+            if let Some(labels_root) = commands.get_entity(gallery.labels_root) {
+                labels_root.despawn_recursive();
+            }
+            if let Some(root) = commands.get_entity(gallery.root) {
+                root.despawn_recursive();
+            }
+            model_gallery.0 = None;
+        }
+    }
+    if let Some(gen_entity) = active_generation.0 {
+        if removed.contains(&gen_entity) {
+            info!("Active generation {:?} was removed", gen_entity);
+            active_generation.0 = None;
+        }
+    }
+}
+
 /// This system pauses/unpauses the [`GenerationControlStatus`] in the [`GenerationControl`] `Resource` on a keypress.
 ///
-/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+/// The bindings are read from the [`super::ProcGenKeyBindings`] and [`super::input::ProcGenGamepadBindings`] `Resources`
 pub fn update_generation_control(
-    keys: Res<ButtonInput<KeyCode>>,
-    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut inputs: ProcGenInputs,
     mut generation_control: ResMut<GenerationControl>,
 ) {
-    if keys.just_pressed(proc_gen_key_bindings.pause_toggle) {
+    if inputs.just_pressed(ProcGenInput::PauseToggle) {
         generation_control.status = match generation_control.status {
             GenerationControlStatus::Ongoing => GenerationControlStatus::Paused,
             GenerationControlStatus::Paused => GenerationControlStatus::Ongoing,
@@ -157,6 +285,25 @@ pub fn handle_reinitialization_and_continue<C: CartesianCoordinates>(
     return true;
 }
 
+/// System that resumes the generation control when the rules of the [`ActiveGeneration`] were reloaded.
+///
+/// The reloaded generator was already reinitialized, its spawned nodes are despawned when its [`QueuedObserver`] receives the reinitialization update.
+#[cfg(feature = "rules-hot-reload")]
+pub fn resume_reloaded_generations(
+    mut reloaded_events: bevy::ecs::event::EventReader<
+        crate::gen::rules_reload::RulesReloadedEvent,
+    >,
+    mut generation_control: ResMut<GenerationControl>,
+    active_generation: Res<ActiveGeneration>,
+) {
+    for event in reloaded_events.read() {
+        if active_generation.0 == Some(event.0) {
+            generation_control.need_reinit = false;
+            generation_control.status = GenerationControlStatus::Ongoing;
+        }
+    }
+}
+
 /// Function used to display some info about a generation that finished,
 /// as well as to properly handle reinitialization status and pause.
 pub fn handle_generation_done<C: CartesianCoordinates>(
@@ -240,11 +387,11 @@ pub fn generate_all<C: CartesianCoordinates>(
 
 /// This system steps a [`Generator`] component if it is  observed through a [`QueuedObserver`] component, if the current control status is [`GenerationControlStatus::Ongoing`], if it is currently the [`ActiveGeneration`] and if the appropriate keys are pressed.
 ///
-/// The keybinds are read from the [`ProcGenKeyBindings`] `Resource`
+/// The bindings are read from the [`super::ProcGenKeyBindings`] and [`super::input::ProcGenGamepadBindings`] `Resources`
 pub fn step_by_step_input_update<C: CartesianCoordinates>(
-    keys: Res<ButtonInput<KeyCode>>,
-    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut inputs: ProcGenInputs,
     mut generation_control: ResMut<GenerationControl>,
+    mut breakpoints: ResMut<GenerationBreakpoints>,
     active_generation: Res<ActiveGeneration>,
     mut observed_generations: Query<
         (&mut Generator<C, CartesianGrid<C>>, &VoidNodes),
@@ -256,8 +403,7 @@ pub fn step_by_step_input_update<C: CartesianCoordinates>(
     };
 
     if generation_control.status == GenerationControlStatus::Ongoing
-        && (keys.just_pressed(proc_gen_key_bindings.step)
-            || keys.pressed(proc_gen_key_bindings.continuous_step))
+        && (inputs.just_pressed(ProcGenInput::Step) || inputs.pressed(ProcGenInput::ContinuousStep))
     {
         if let Ok((mut generation, void_nodes)) = observed_generations.get_mut(active_generation) {
             step_generation(
@@ -265,6 +411,7 @@ pub fn step_by_step_input_update<C: CartesianCoordinates>(
                 active_generation,
                 void_nodes,
                 &mut generation_control,
+                &mut breakpoints,
             );
         }
     }
@@ -273,6 +420,7 @@ pub fn step_by_step_input_update<C: CartesianCoordinates>(
 /// This system steps a [`Generator`] component if it is observed through a [`QueuedObserver`] component, if the current control status is [`GenerationControlStatus::Ongoing`] if it is currently the [`ActiveGeneration`] and if the timer in the [`StepByStepTimed`] `Resource` has finished.
 pub fn step_by_step_timed_update<C: CartesianCoordinates>(
     mut generation_control: ResMut<GenerationControl>,
+    mut breakpoints: ResMut<GenerationBreakpoints>,
     mut steps_and_timer: ResMut<StepByStepTimed>,
     time: Res<Time>,
     active_generation: Res<ActiveGeneration>,
@@ -296,6 +444,7 @@ pub fn step_by_step_timed_update<C: CartesianCoordinates>(
                     active_generation,
                     void_nodes,
                     &mut generation_control,
+                    &mut breakpoints,
                 );
                 if generation_control.status != GenerationControlStatus::Ongoing {
                     return;
@@ -305,6 +454,44 @@ pub fn step_by_step_timed_update<C: CartesianCoordinates>(
     }
 }
 
+/// This system steps a [`Generator`] component if it is observed through a [`QueuedObserver`] component, if the current control status is [`GenerationControlStatus::Ongoing`] and if it is currently the [`ActiveGeneration`]. Steps are run until the time budget in the [`TimeSliced`] `Resource` is spent, with at least one step per frame.
+pub fn time_sliced_update<C: CartesianCoordinates>(
+    mut generation_control: ResMut<GenerationControl>,
+    mut breakpoints: ResMut<GenerationBreakpoints>,
+    time_sliced: Res<TimeSliced>,
+    active_generation: Res<ActiveGeneration>,
+    mut observed_generations: Query<
+        (&mut Generator<C, CartesianGrid<C>>, &VoidNodes),
+        With<QueuedObserver>,
+    >,
+) {
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    if generation_control.status != GenerationControlStatus::Ongoing {
+        return;
+    }
+    let Ok((mut generation, void_nodes)) = observed_generations.get_mut(active_generation) else {
+        return;
+    };
+
+    let start = Instant::now();
+    loop {
+        step_generation(
+            &mut generation,
+            active_generation,
+            void_nodes,
+            &mut generation_control,
+            &mut breakpoints,
+        );
+        if generation_control.status != GenerationControlStatus::Ongoing
+            || start.elapsed() >= time_sliced.budget
+        {
+            return;
+        }
+    }
+}
+
 /// System used to spawn nodes, emit [GenerationEvent] and despawn markers, based on data read from a [QueuedObserver] on a generation entity
 pub fn update_generation_view<
     C: CartesianCoordinates,
@@ -314,6 +501,7 @@ pub fn update_generation_view<
     mut commands: Commands,
     mut marker_events: EventWriter<MarkerDespawnEvent>,
     mut generation_events: EventWriter<GenerationEvent>,
+    mut contradiction_events: EventWriter<ContradictionEvent>,
     mut generators: Query<(
         Entity,
         &CartesianGrid<C>,
@@ -322,12 +510,13 @@ pub fn update_generation_view<
         Option<&Children>,
         Option<&mut ErrorMarkers>,
     )>,
-    existing_nodes: Query<Entity, With<GridNode>>,
+    existing_nodes: Query<Entity, Or<(With<GridNode>, With<NodeLayer>)>>,
 ) {
     for (grid_entity, grid, asset_spawner, mut observer, children, mut error_markers) in
         generators.iter_mut()
     {
         let mut reinitialized = false;
+        let mut failed_on = None;
         let mut nodes_to_spawn = Vec::new();
         for update in observer.dequeue_all() {
             match update {
@@ -336,9 +525,11 @@ pub fn update_generation_view<
                 }
                 GenerationUpdate::Reinitializing(_) => {
                     reinitialized = true;
+                    failed_on = None;
                     nodes_to_spawn.clear();
                 }
                 GenerationUpdate::Failed(node_index) => {
+                    failed_on = Some(node_index);
                     if let Some(error_markers) = error_markers.as_mut() {
                         error_markers.push(spawn_marker(
                             &mut commands,
@@ -369,6 +560,13 @@ pub fn update_generation_view<
             }
         }
 
+        if let Some(node_index) = failed_on {
+            contradiction_events.send(ContradictionEvent {
+                generation: grid_entity,
+                node_index,
+            });
+        }
+
         for grid_node in nodes_to_spawn {
             generation_events.send(GenerationEvent::Updated(grid_entity, grid_node.node_index));
 
@@ -389,6 +587,7 @@ fn step_generation<C: CartesianCoordinates>(
     gen_entity: Entity,
     void_nodes: &VoidNodes,
     generation_control: &mut ResMut<GenerationControl>,
+    breakpoints: &mut GenerationBreakpoints,
 ) {
     loop {
         if !handle_reinitialization_and_continue(generation_control, generator) {
@@ -398,6 +597,7 @@ fn step_generation<C: CartesianCoordinates>(
         let mut non_void_spawned = false;
         match generator.select_and_propagate_collected() {
             Ok((status, nodes_to_spawn)) => {
+                let breakpoint_hit = breakpoints.check(generator, &nodes_to_spawn);
                 for grid_node in nodes_to_spawn {
                     // We still collect the generated nodes here even though we don't really use them to spawn entities. We just check them for void nodes (for visualization purposes)
                     if !void_nodes.contains(&grid_node.model_instance.model_index) {
@@ -411,6 +611,14 @@ fn step_generation<C: CartesianCoordinates>(
                         break;
                     }
                 }
+                if let Some(breakpoint) = breakpoint_hit {
+                    info!(
+                        "Breakpoint hit {:?} on generation {:?}",
+                        breakpoint, gen_entity
+                    );
+                    generation_control.status = GenerationControlStatus::Paused;
+                    break;
+                }
             }
             Err(GeneratorError { node_index }) => {
                 handle_generation_error(generation_control, generator, gen_entity, node_index);
@@ -0,0 +1,108 @@
+use bevy::ecs::{
+    query::With,
+    system::{Query, Res, ResMut, Resource},
+};
+use bevy_egui::{
+    egui::{self, Color32},
+    EguiContexts,
+};
+use ghx_proc_gen::{
+    generator::Generator,
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+};
+
+use super::{cursor::Cursor, picking::OverCursor};
+
+/// Maximum number of variants listed by the hover inspector, the remaining ones are only counted
+pub const HOVER_INSPECTOR_MAX_VARIANTS: usize = 64;
+
+/// Resource used to enable the hover inspector: an egui panel detailing the internal state of the node hovered by the [OverCursor]
+#[derive(Resource, Default)]
+pub struct HoverInspector {
+    /// Whether or not the hover inspector is displayed. Disabled by default
+    pub enabled: bool,
+}
+
+/// System condition to check if the hover inspector is enabled
+pub fn hover_inspector_enabled(hover_inspector: Res<HoverInspector>) -> bool {
+    hover_inspector.enabled
+}
+
+/// System that can be used to toggle on/off the hover inspector
+pub fn toggle_hover_inspector(mut hover_inspector: ResMut<HoverInspector>) {
+    hover_inspector.enabled = !hover_inspector.enabled;
+}
+
+/// System used to draw the hover inspector egui window.
+///
+/// Lists all the model variants still possible on the node hovered by the [OverCursor], with their current weight and the neighbour direction leaving them the fewest supports, see [`Generator::possible_variants_on`].
+pub fn draw_hover_inspector<C: CartesianCoordinates>(
+    mut contexts: EguiContexts,
+    generations: Query<&Generator<C, CartesianGrid<C>>>,
+    over_cursor: Query<&Cursor, With<OverCursor>>,
+) {
+    let Ok(cursor) = over_cursor.get_single() else {
+        return;
+    };
+    let Some(targeted_node) = &cursor.0 else {
+        return;
+    };
+    let Ok(generator) = generations.get(targeted_node.grid) else {
+        return;
+    };
+
+    let variants = generator.possible_variants_on(targeted_node.node_index);
+    let weights_sum: f32 = variants.iter().map(|variant| variant.weight).sum();
+
+    egui::Window::new("Hovered node")
+        .title_bar(true)
+        .default_pos([10., 400.])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!(
+                "Node {}, {} possible variants",
+                targeted_node,
+                variants.len()
+            ));
+            ui.separator();
+            egui::Grid::new("hover_inspector_variants")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Variant");
+                    ui.label("Weight");
+                    ui.label("Most constrained by");
+                    ui.end_row();
+                    for variant in variants.iter().take(HOVER_INSPECTOR_MAX_VARIANTS) {
+                        let name = generator
+                            .rules()
+                            .name_str(variant.variant_index)
+                            .unwrap_or("");
+                        ui.label(format!("{} {}", name, variant.instance));
+                        let probability = match weights_sum > 0. {
+                            true => 100. * variant.weight / weights_sum,
+                            false => 0.,
+                        };
+                        ui.label(format!("{:.3} ({:.1}%)", variant.weight, probability));
+                        match variant.most_constraining {
+                            Some((direction, 0)) => ui.colored_label(
+                                Color32::RED,
+                                format!("{:?}: no support", direction),
+                            ),
+                            Some((direction, supports)) => {
+                                ui.label(format!("{:?}: {} supports", direction, supports))
+                            }
+                            None => ui.label("-"),
+                        };
+                        ui.end_row();
+                    }
+                });
+            if variants.len() > HOVER_INSPECTOR_MAX_VARIANTS {
+                ui.colored_label(
+                    Color32::GRAY,
+                    format!(
+                        "... and {} more",
+                        variants.len() - HOVER_INSPECTOR_MAX_VARIANTS
+                    ),
+                );
+            }
+        });
+}
@@ -0,0 +1,261 @@
+#[cfg(not(feature = "egui-edit"))]
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::system::{Res, Resource, SystemParam},
+    input::{
+        gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+        keyboard::KeyCode,
+        Axis, ButtonInput,
+    },
+};
+
+#[cfg(feature = "egui-edit")]
+use bevy_egui::EguiContexts;
+
+use super::{ProcGenInputContext, ProcGenKeyBindings};
+
+/// Inputs of the debug plugin that can be bound to keyboard keys and gamepad buttons/axes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProcGenInput {
+    /// Move the selection cursor to the previous node on the current axis
+    PrevNode,
+    /// Move the selection cursor to the next node on the current axis
+    NextNode,
+    /// Enable the X axis selection
+    CursorXAxis,
+    /// Enable the Y axis selection
+    CursorYAxis,
+    /// Enable the Z axis selection
+    CursorZAxis,
+    /// Deselect the current selection
+    Deselect,
+    /// Extend the selection as a box when moving the selection cursor
+    BoxSelection,
+    /// Move the selection cursor to another grid
+    SwitchGrid,
+    /// Pause/unpause the current [`super::GenerationControlStatus`]
+    PauseToggle,
+    /// Step once per press, only used with [`super::GenerationViewMode::StepByStepManual`]
+    Step,
+    /// Step continuously as long as pressed, only used with [`super::GenerationViewMode::StepByStepManual`]
+    ContinuousStep,
+}
+
+impl ProcGenInput {
+    /// Returns the [ProcGenInputContext] this input belongs to
+    pub fn context(&self) -> ProcGenInputContext {
+        match self {
+            ProcGenInput::PrevNode
+            | ProcGenInput::NextNode
+            | ProcGenInput::CursorXAxis
+            | ProcGenInput::CursorYAxis
+            | ProcGenInput::CursorZAxis
+            | ProcGenInput::Deselect
+            | ProcGenInput::BoxSelection
+            | ProcGenInput::SwitchGrid => ProcGenInputContext::Cursor,
+            ProcGenInput::PauseToggle | ProcGenInput::Step | ProcGenInput::ContinuousStep => {
+                ProcGenInputContext::Generation
+            }
+        }
+    }
+}
+
+impl ProcGenKeyBindings {
+    /// Returns the [KeyCode] bound to `input`
+    pub fn key(&self, input: ProcGenInput) -> KeyCode {
+        match input {
+            ProcGenInput::PrevNode => self.cursor.prev_node,
+            ProcGenInput::NextNode => self.cursor.next_node,
+            ProcGenInput::CursorXAxis => self.cursor.cursor_x_axis,
+            ProcGenInput::CursorYAxis => self.cursor.cursor_y_axis,
+            ProcGenInput::CursorZAxis => self.cursor.cursor_z_axis,
+            ProcGenInput::Deselect => self.cursor.deselect,
+            ProcGenInput::BoxSelection => self.cursor.box_selection,
+            ProcGenInput::SwitchGrid => self.cursor.switch_grid,
+            ProcGenInput::PauseToggle => self.generation.pause_toggle,
+            ProcGenInput::Step => self.generation.step,
+            ProcGenInput::ContinuousStep => self.generation.continuous_step,
+        }
+    }
+}
+
+/// Resource available to override the default gamepad bindings used by the [`super::ProcGenDebugPlugin`]. Gamepad bindings are used in addition to the [ProcGenKeyBindings].
+///
+/// Any gamepad connected can drive the debug plugin. A binding set to [None] is disabled, and the bindings of an input context disabled in the [ProcGenKeyBindings] are ignored.
+#[derive(Resource)]
+#[cfg_attr(
+    feature = "bindings-config",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ProcGenGamepadBindings {
+    /// Button to move the selection cursor to the previous node on the current axis
+    pub prev_node: Option<GamepadButtonType>,
+    /// Button to move the selection cursor to the next node on the current axis
+    pub next_node: Option<GamepadButtonType>,
+    /// Axis that can also move the selection cursor to the previous (negative values) or next (positive values) node on the current axis
+    pub cursor_movement_axis: Option<GamepadAxisType>,
+    /// Absolute value above which the `cursor_movement_axis` is considered pressed
+    pub axis_threshold: f32,
+    /// Button pressed to enable the X axis selection
+    pub cursor_x_axis: Option<GamepadButtonType>,
+    /// Button pressed to enable the Y axis selection
+    pub cursor_y_axis: Option<GamepadButtonType>,
+    /// Button pressed to enable the Z axis selection
+    pub cursor_z_axis: Option<GamepadButtonType>,
+    /// Button to deselect the current selection
+    pub deselect: Option<GamepadButtonType>,
+    /// Button held to extend the selection as a box when moving the selection cursor
+    pub box_selection: Option<GamepadButtonType>,
+    /// Button to move the selection cursor to another grid
+    pub switch_grid: Option<GamepadButtonType>,
+    /// Button to pause/unpause the current [`super::GenerationControlStatus`]
+    pub pause_toggle: Option<GamepadButtonType>,
+    /// Button used only with [`super::GenerationViewMode::StepByStepManual`] to step once per press
+    pub step: Option<GamepadButtonType>,
+    /// Button used only with [`super::GenerationViewMode::StepByStepManual`] to step continuously as long as pressed
+    pub continuous_step: Option<GamepadButtonType>,
+}
+
+impl Default for ProcGenGamepadBindings {
+    fn default() -> Self {
+        Self {
+            prev_node: Some(GamepadButtonType::DPadLeft),
+            next_node: Some(GamepadButtonType::DPadRight),
+            cursor_movement_axis: Some(GamepadAxisType::LeftStickX),
+            axis_threshold: 0.5,
+            cursor_x_axis: Some(GamepadButtonType::West),
+            cursor_y_axis: Some(GamepadButtonType::North),
+            cursor_z_axis: Some(GamepadButtonType::East),
+            deselect: Some(GamepadButtonType::Select),
+            box_selection: Some(GamepadButtonType::LeftTrigger),
+            switch_grid: Some(GamepadButtonType::RightTrigger),
+            pause_toggle: Some(GamepadButtonType::Start),
+            step: Some(GamepadButtonType::South),
+            continuous_step: Some(GamepadButtonType::RightTrigger2),
+        }
+    }
+}
+
+impl ProcGenGamepadBindings {
+    /// Returns the [GamepadButtonType] bound to `input`, if any
+    pub fn button(&self, input: ProcGenInput) -> Option<GamepadButtonType> {
+        match input {
+            ProcGenInput::PrevNode => self.prev_node,
+            ProcGenInput::NextNode => self.next_node,
+            ProcGenInput::CursorXAxis => self.cursor_x_axis,
+            ProcGenInput::CursorYAxis => self.cursor_y_axis,
+            ProcGenInput::CursorZAxis => self.cursor_z_axis,
+            ProcGenInput::Deselect => self.deselect,
+            ProcGenInput::BoxSelection => self.box_selection,
+            ProcGenInput::SwitchGrid => self.switch_grid,
+            ProcGenInput::PauseToggle => self.pause_toggle,
+            ProcGenInput::Step => self.step,
+            ProcGenInput::ContinuousStep => self.continuous_step,
+        }
+    }
+}
+
+/// [SystemParam] used to know whether or not egui wants the keyboard input, for example while a text field is edited. Egui never wants it without the `egui-edit` feature
+#[cfg(feature = "egui-edit")]
+#[derive(SystemParam)]
+pub struct EguiKeyboardFocus<'w, 's> {
+    contexts: EguiContexts<'w, 's>,
+}
+
+#[cfg(feature = "egui-edit")]
+impl<'w, 's> EguiKeyboardFocus<'w, 's> {
+    /// Returns whether or not egui wants the keyboard input
+    pub fn wants_keyboard_input(&mut self) -> bool {
+        self.contexts
+            .try_ctx_mut()
+            .is_some_and(|ctx| ctx.wants_keyboard_input())
+    }
+}
+
+/// [SystemParam] used to know whether or not egui wants the keyboard input, for example while a text field is edited. Egui never wants it without the `egui-edit` feature
+#[cfg(not(feature = "egui-edit"))]
+#[derive(SystemParam)]
+pub struct EguiKeyboardFocus<'w, 's> {
+    _marker: PhantomData<(&'w (), &'s ())>,
+}
+
+#[cfg(not(feature = "egui-edit"))]
+impl<'w, 's> EguiKeyboardFocus<'w, 's> {
+    /// Returns whether or not egui wants the keyboard input
+    pub fn wants_keyboard_input(&mut self) -> bool {
+        false
+    }
+}
+
+/// [SystemParam] used to read the [ProcGenInput] from the keyboard and from all the connected gamepads, using the [ProcGenKeyBindings] and [ProcGenGamepadBindings] `Resources`.
+///
+/// Inputs of a [ProcGenInputContext] disabled in the [ProcGenKeyBindings] are never pressed, and no input is pressed while egui wants the keyboard input (with the `egui-edit` feature), so that typing in a text field does not also drive the debug plugin.
+#[derive(SystemParam)]
+pub struct ProcGenInputs<'w, 's> {
+    egui_focus: EguiKeyboardFocus<'w, 's>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    key_bindings: Res<'w, ProcGenKeyBindings>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+    gamepad_bindings: Res<'w, ProcGenGamepadBindings>,
+}
+
+impl<'w, 's> ProcGenInputs<'w, 's> {
+    /// Returns whether or not `input` is currently pressed on the keyboard or on any gamepad
+    pub fn pressed(&mut self, input: ProcGenInput) -> bool {
+        if !self.key_bindings.is_enabled(input.context()) || self.egui_focus.wants_keyboard_input()
+        {
+            return false;
+        }
+        self.keys.pressed(self.key_bindings.key(input))
+            || self.gamepad_button_matches(input, |button| self.gamepad_buttons.pressed(button))
+            || self.gamepad_axis_pressed(input)
+    }
+
+    /// Returns whether or not `input` was just pressed on the keyboard or on any gamepad.
+    ///
+    /// Gamepad axes are only considered by [ProcGenInputs::pressed]
+    pub fn just_pressed(&mut self, input: ProcGenInput) -> bool {
+        if !self.key_bindings.is_enabled(input.context()) || self.egui_focus.wants_keyboard_input()
+        {
+            return false;
+        }
+        self.keys.just_pressed(self.key_bindings.key(input))
+            || self
+                .gamepad_button_matches(input, |button| self.gamepad_buttons.just_pressed(button))
+    }
+
+    fn gamepad_button_matches(
+        &self,
+        input: ProcGenInput,
+        predicate: impl Fn(GamepadButton) -> bool,
+    ) -> bool {
+        let Some(button_type) = self.gamepad_bindings.button(input) else {
+            return false;
+        };
+        self.gamepads
+            .iter()
+            .any(|gamepad| predicate(GamepadButton::new(gamepad, button_type)))
+    }
+
+    fn gamepad_axis_pressed(&self, input: ProcGenInput) -> bool {
+        let Some(axis_type) = self.gamepad_bindings.cursor_movement_axis else {
+            return false;
+        };
+        let threshold = self.gamepad_bindings.axis_threshold;
+        self.gamepads.iter().any(|gamepad| {
+            let value = self
+                .gamepad_axes
+                .get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or(0.);
+            match input {
+                ProcGenInput::PrevNode => value < -threshold,
+                ProcGenInput::NextNode => value > threshold,
+                _ => false,
+            }
+        })
+    }
+}
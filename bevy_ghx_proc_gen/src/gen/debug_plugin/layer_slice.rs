@@ -0,0 +1,118 @@
+use bevy::{
+    ecs::{
+        query::{Added, With, Without},
+        system::{Query, Res, ResMut, Resource},
+    },
+    hierarchy::Parent,
+    input::{keyboard::KeyCode, ButtonInput},
+    log::info,
+    render::view::Visibility,
+};
+use ghx_proc_gen::{
+    generator::Generator,
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+};
+
+use crate::gen::{debug_plugin::picking::CursorTarget, node_generation, GridNode, NodeLayer};
+
+use super::ProcGenKeyBindings;
+
+/// Axis along which the layers are sliced
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliceAxis {
+    /// Only the nodes with the selected Y coordinate are displayed
+    #[default]
+    Y,
+    /// Only the nodes with the selected Z coordinate are displayed
+    Z,
+}
+
+/// Resource used to hide all the spawned nodes except the ones on a selected layer
+#[derive(Resource, Default, Debug)]
+pub struct LayerSlice {
+    /// Whether or not the slice view is enabled
+    pub enabled: bool,
+    /// Axis of the slice
+    pub axis: SliceAxis,
+    /// Displayed layer, as a coordinate on the slice axis
+    pub layer: u32,
+}
+
+impl LayerSlice {
+    fn contains<C: CartesianCoordinates>(&self, grid: &CartesianGrid<C>, node: &GridNode) -> bool {
+        let position = grid.pos_from_index(node.0);
+        match self.axis {
+            SliceAxis::Y => position.y == self.layer,
+            SliceAxis::Z => position.z == self.layer,
+        }
+    }
+}
+
+/// System that updates the [LayerSlice] on a keypress:
+/// - the slice toggle key cycles between disabled, Y axis and Z axis slicing
+/// - the slice up/down keys move the slice to the next/previous layer
+///
+/// The keybinds are read from the [`ProcGenKeyBindings`] `Resource`
+pub fn update_layer_slice_from_keybinds<C: CartesianCoordinates>(
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut layer_slice: ResMut<LayerSlice>,
+    grids: Query<&CartesianGrid<C>, With<Generator<C, CartesianGrid<C>>>>,
+) {
+    let key_bindings = &proc_gen_key_bindings.editor;
+    if key_bindings.just_pressed(&keys, key_bindings.toggle_layer_slice) {
+        (layer_slice.enabled, layer_slice.axis) = match (layer_slice.enabled, layer_slice.axis) {
+            (false, _) => (true, SliceAxis::Y),
+            (true, SliceAxis::Y) => (true, SliceAxis::Z),
+            (true, SliceAxis::Z) => (false, SliceAxis::Y),
+        };
+        match layer_slice.enabled {
+            true => info!("Layer slice on axis {:?}", layer_slice.axis),
+            false => info!("Layer slice disabled"),
+        }
+    }
+    if !layer_slice.enabled {
+        return;
+    }
+
+    let layers_count = grids
+        .iter()
+        .map(|grid| match layer_slice.axis {
+            SliceAxis::Y => grid.size_y(),
+            SliceAxis::Z => grid.size_z(),
+        })
+        .max()
+        .unwrap_or(1);
+    if key_bindings.just_pressed(&keys, key_bindings.slice_up) {
+        layer_slice.layer = (layer_slice.layer + 1).min(layers_count.saturating_sub(1));
+    } else if key_bindings.just_pressed(&keys, key_bindings.slice_down) {
+        layer_slice.layer = layer_slice.layer.saturating_sub(1);
+    } else if layer_slice.layer >= layers_count {
+        layer_slice.layer = layers_count.saturating_sub(1);
+    }
+}
+
+/// System that updates the [Visibility] of the spawned nodes according to the [LayerSlice]
+pub fn update_layer_slice_visibility<C: CartesianCoordinates>(
+    layer_slice: Res<LayerSlice>,
+    grids: Query<&CartesianGrid<C>>,
+    mut all_nodes: Query<(&GridNode, &Parent, &mut Visibility), Without<CursorTarget>>,
+    spawned_nodes: Query<(), (Added<GridNode>, Without<CursorTarget>)>,
+    node_layers: Query<&Parent, With<NodeLayer>>,
+) {
+    if !layer_slice.is_changed() && spawned_nodes.is_empty() {
+        return;
+    }
+    for (node, parent, mut visibility) in all_nodes.iter_mut() {
+        let Ok(grid) = grids.get(node_generation(parent, &node_layers)) else {
+            continue;
+        };
+        let new_visibility = match !layer_slice.enabled || layer_slice.contains(grid, node) {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+        if *visibility != new_visibility {
+            *visibility = new_visibility;
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use bevy::{
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{With, Without},
+        system::{Commands, Local, Query, Res, ResMut, Resource},
+    },
+    hierarchy::{BuildChildren, DespawnRecursiveExt},
+    input::{keyboard::KeyCode, ButtonInput},
+    log::{info, warn},
+    math::Vec3,
+    render::{camera::Camera, spatial_bundle::SpatialBundle},
+    text::{BreakLineOn, Text, TextSection, TextStyle},
+    transform::components::{GlobalTransform, Transform},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        BackgroundColor, Display, PositionType, Style, Val,
+    },
+    utils::default,
+};
+use ghx_proc_gen::{
+    generator::{
+        model::{ModelInstance, ALL_MODEL_ROTATIONS},
+        Generator,
+    },
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+};
+
+#[cfg(feature = "picking")]
+use bevy_mod_picking::picking_core::Pickable;
+
+use crate::gen::{
+    assets::{AssetSpawner, AssetsBundleSpawner, ComponentSpawner},
+    spawn_model_assets,
+};
+
+use super::{
+    cursor::{Flag, GridCursorsOverlayCamera},
+    generation::ActiveGeneration,
+    GridCursorsUiSettings, ProcGenKeyBindings,
+};
+
+/// Resource used to customize the model gallery
+#[derive(Resource, Debug)]
+pub struct ModelGallerySettings {
+    /// How many empty nodes are left between two models of the gallery
+    pub spacing: u32,
+    /// Whether or not each model of the gallery is labeled with its name and rotation
+    pub labels: bool,
+}
+
+impl Default for ModelGallerySettings {
+    fn default() -> Self {
+        Self {
+            spacing: 1,
+            labels: true,
+        }
+    }
+}
+
+/// Resource holding the currently spawned model gallery, if any
+#[derive(Resource, Default, Debug)]
+pub struct ModelGallery(pub Option<ModelGalleryEntities>);
+
+/// Root entities of a spawned model gallery
+#[derive(Clone, Copy, Debug)]
+pub struct ModelGalleryEntities {
+    /// Generation entity the gallery was spawned from
+    pub generation: Entity,
+    /// Root entity of the gallery models, child of the generation entity
+    pub root: Entity,
+    /// Root entity of the gallery labels UI
+    pub labels_root: Entity,
+}
+
+/// Component put on the center of each model variation displayed in the model gallery
+#[derive(Component, Clone, Debug)]
+pub struct ModelGalleryItem {
+    /// Model variation displayed
+    pub instance: ModelInstance,
+    /// Label of the model variation
+    pub label: String,
+}
+
+/// Marker component put on the assets entities spawned by the model gallery
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ModelGalleryAsset;
+
+/// Component put on the UI text overlay of a [ModelGalleryItem]
+#[derive(Component, Debug)]
+pub struct ModelGalleryLabel {
+    /// Entity of the labeled [ModelGalleryItem]
+    pub item: Entity,
+}
+
+/// System that spawns or despawns the [ModelGallery] of the [ActiveGeneration] on a keypress.
+///
+/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+pub fn toggle_model_gallery_from_keybinds<
+    C: CartesianCoordinates,
+    A: AssetsBundleSpawner,
+    T: ComponentSpawner,
+>(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    gallery_settings: Res<ModelGallerySettings>,
+    active_generation: Res<ActiveGeneration>,
+    mut model_gallery: ResMut<ModelGallery>,
+    generations: Query<(&Generator<C, CartesianGrid<C>>, &AssetSpawner<A, T>)>,
+) {
+    if !proc_gen_key_bindings
+        .editor
+        .just_pressed(&keys, proc_gen_key_bindings.editor.toggle_model_gallery)
+    {
+        return;
+    }
+    if let Some(gallery) = model_gallery.0.take() {
+        despawn_model_gallery(&mut commands, gallery);
+        return;
+    }
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    let Ok((generator, asset_spawner)) = generations.get(active_generation) else {
+        return;
+    };
+    model_gallery.0 = Some(spawn_model_gallery(
+        &mut commands,
+        active_generation,
+        generator,
+        asset_spawner,
+        &gallery_settings,
+    ));
+}
+
+/// Spawns every model variation of the rules of `generator` once, using the assets of `asset_spawner`.
+///
+/// Each original model is displayed on its own row with all its rotations, next to the generation grid (rows are laid along the Z axis for 3d grids, and along the Y axis for 2d grids). The gallery is spawned as a child of the generation entity.
+pub fn spawn_model_gallery<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner>(
+    commands: &mut Commands,
+    gen_entity: Entity,
+    generator: &Generator<C, CartesianGrid<C>>,
+    asset_spawner: &AssetSpawner<A, T>,
+    settings: &ModelGallerySettings,
+) -> ModelGalleryEntities {
+    let grid = generator.grid();
+    let rules = generator.rules();
+    let step = (settings.spacing + 1) as f32;
+    let origin_x = (grid.size_x() + settings.spacing + 1) as f32;
+    let row_direction = match grid.size_z() > 1 {
+        true => Vec3::Z,
+        false => Vec3::Y,
+    };
+
+    let root = commands
+        .spawn((SpatialBundle::default(), Name::new("ModelGallery")))
+        .id();
+    commands.entity(gen_entity).add_child(root);
+    let labels_root = commands
+        .spawn((NodeBundle::default(), Name::new("ModelGalleryLabels")))
+        .id();
+    #[cfg(feature = "picking")]
+    commands.entity(labels_root).insert(Pickable::IGNORE);
+
+    let mut variations_count = 0;
+    for model_index in 0..rules.original_models_count() {
+        let mut column = 0;
+        for rotation in ALL_MODEL_ROTATIONS {
+            let Some(variant_index) = rules.variant_index(model_index, *rotation) else {
+                continue;
+            };
+            let instance = ModelInstance::new(model_index, *rotation);
+            let node_pos = Vec3::new(origin_x + column as f32 * step, 0., 0.)
+                + row_direction * (model_index as f32 * step);
+            spawn_model_assets(
+                commands,
+                root,
+                asset_spawner,
+                &instance,
+                node_pos,
+                ModelGalleryAsset,
+            );
+
+            let label = format!(
+                "{} {}°",
+                rules.name_str(variant_index).unwrap_or("None"),
+                rotation.value()
+            );
+            let item = commands
+                .spawn((
+                    SpatialBundle::from_transform(Transform::from_translation(
+                        asset_spawner.node_size * (node_pos + Vec3::splat(0.5)),
+                    )),
+                    ModelGalleryItem { instance, label },
+                ))
+                .id();
+            commands.entity(root).add_child(item);
+            if settings.labels {
+                let label_entity = commands
+                    .spawn((ModelGalleryLabel { item }, TextBundle::default()))
+                    .id();
+                #[cfg(feature = "picking")]
+                commands.entity(label_entity).insert(Pickable::IGNORE);
+                commands.entity(labels_root).add_child(label_entity);
+            }
+            column += 1;
+            variations_count += 1;
+        }
+    }
+    info!(
+        "Spawned a model gallery of {} models ({} variations) for generation {:?}",
+        rules.original_models_count(),
+        variations_count,
+        gen_entity
+    );
+
+    ModelGalleryEntities {
+        generation: gen_entity,
+        root,
+        labels_root,
+    }
+}
+
+/// Despawns a model gallery spawned by [spawn_model_gallery]
+pub fn despawn_model_gallery(commands: &mut Commands, gallery: ModelGalleryEntities) {
+    if let Some(root) = commands.get_entity(gallery.root) {
+        root.despawn_recursive();
+    }
+    if let Some(labels_root) = commands.get_entity(gallery.labels_root) {
+        labels_root.despawn_recursive();
+    }
+}
+
+/// System updating the position of the [ModelGalleryLabel] overlays
+pub fn update_model_gallery_labels(
+    mut camera_warning_flag: Local<Flag>,
+    ui_config: Res<GridCursorsUiSettings>,
+    just_one_camera: Query<(&Camera, &GlobalTransform), Without<GridCursorsOverlayCamera>>,
+    overlay_camera: Query<(&Camera, &GlobalTransform), With<GridCursorsOverlayCamera>>,
+    mut labels: Query<(
+        &ModelGalleryLabel,
+        &mut Text,
+        &mut Style,
+        &mut BackgroundColor,
+    )>,
+    items: Query<(&ModelGalleryItem, &GlobalTransform)>,
+) {
+    if labels.is_empty() {
+        return;
+    }
+    let (camera, cam_gtransform) = match just_one_camera.get_single() {
+        Ok(found) => found,
+        Err(_) => match overlay_camera.get_single() {
+            Ok(found) => found,
+            Err(_) => {
+                if !camera_warning_flag.0 {
+                    warn!("None (or too many) Camera(s) found with 'GridCursorsOverlayCamera' component to display the model gallery labels. Add `GridCursorsOverlayCamera` component to a Camera or disable the model gallery labels.");
+                    camera_warning_flag.0 = true;
+                }
+                return;
+            }
+        },
+    };
+
+    for (label, mut text, mut style, mut background_color) in labels.iter_mut() {
+        let Ok((item, item_gtransform)) = items.get(label.item) else {
+            continue;
+        };
+        let Some(viewport_pos) =
+            camera.world_to_viewport(cam_gtransform, item_gtransform.translation())
+        else {
+            style.display = Display::None;
+            continue;
+        };
+        if text.sections.is_empty() {
+            *text = Text {
+                linebreak_behavior: BreakLineOn::NoWrap,
+                sections: vec![TextSection {
+                    value: item.label.clone(),
+                    style: TextStyle {
+                        font_size: ui_config.font_size,
+                        color: ui_config.text_color,
+                        ..default()
+                    },
+                }],
+                ..default()
+            };
+            *background_color = BackgroundColor(ui_config.background_color);
+        }
+        *style = Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(viewport_pos.x + ui_config.overlay_offset.x),
+            top: Val::Px(viewport_pos.y + ui_config.overlay_offset.y),
+            padding: ui_config.panel_padding,
+            ..default()
+        };
+    }
+}
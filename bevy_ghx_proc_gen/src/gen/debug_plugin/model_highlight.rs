@@ -0,0 +1,181 @@
+use bevy::{
+    color::{palettes::css::GOLD, Color},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        query::With,
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    input::{keyboard::KeyCode, ButtonInput},
+    log::info,
+    prelude::{Deref, DerefMut},
+};
+use bevy_ghx_grid::debug_plugin::markers::{spawn_marker, MarkerDespawnEvent};
+use ghx_proc_gen::{
+    generator::{model::ModelInstance, Generator},
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        grid::Grid,
+    },
+};
+
+use super::{
+    cursor::{Cursor, SelectCursor},
+    generation::GenerationEvent,
+    ProcGenKeyBindings,
+};
+
+/// Resource used to customize the highlight of the instances of a model
+#[derive(Resource, Debug)]
+pub struct ModelHighlightSettings {
+    /// Color of the markers spawned on the highlighted nodes
+    pub color: Color,
+    /// If `true`, only the nodes using the exact same variation (rotation & facing) of the model are highlighted. Else, all the variations of the model are highlighted
+    pub match_variation: bool,
+}
+
+impl Default for ModelHighlightSettings {
+    fn default() -> Self {
+        Self {
+            color: Color::Srgba(GOLD),
+            match_variation: false,
+        }
+    }
+}
+
+impl ModelHighlightSettings {
+    /// Returns whether or not `instance` should be highlighted when `model` is
+    pub fn matches(&self, model: &ModelInstance, instance: &ModelInstance) -> bool {
+        match self.match_variation {
+            true => model == instance,
+            false => model.model_index == instance.model_index,
+        }
+    }
+}
+
+/// Resource holding the currently highlighted model, if any
+#[derive(Resource, Default, Debug)]
+pub struct HighlightedModel(pub Option<HighlightedModelInfo>);
+
+/// Model highlighted in a generation
+#[derive(Clone, Copy, Debug)]
+pub struct HighlightedModelInfo {
+    /// Generation entity the model is highlighted in
+    pub generation: Entity,
+    /// Highlighted model
+    pub model: ModelInstance,
+}
+
+/// Component used to store the [`bevy_ghx_grid::debug_plugin::markers::GridMarker`] entities of the highlighted nodes of a generation
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct ModelHighlightMarkers(pub Vec<Entity>);
+
+/// System that highlights all the instances of the model generated on the node pointed by the selection cursor, on a keypress. Pressing it again on an instance of the highlighted model clears the highlight.
+///
+/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+pub fn toggle_model_highlight_from_keybinds<C: CartesianCoordinates>(
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    highlight_settings: Res<ModelHighlightSettings>,
+    mut highlighted_model: ResMut<HighlightedModel>,
+    selection_cursor: Query<&Cursor, With<SelectCursor>>,
+    generators: Query<&Generator<C, CartesianGrid<C>>>,
+) {
+    if !proc_gen_key_bindings
+        .editor
+        .just_pressed(&keys, proc_gen_key_bindings.editor.toggle_model_highlight)
+    {
+        return;
+    }
+    let Ok(cursor) = selection_cursor.get_single() else {
+        return;
+    };
+    let targeted_model = cursor.0.as_ref().and_then(|targeted_node| {
+        let generator = generators.get(targeted_node.grid).ok()?;
+        match generator.get_models_on(targeted_node.node_index).as_slice() {
+            [model] => Some(HighlightedModelInfo {
+                generation: targeted_node.grid,
+                model: *model,
+            }),
+            _ => None,
+        }
+    });
+
+    highlighted_model.0 = match (highlighted_model.0, targeted_model) {
+        (Some(highlighted), Some(targeted))
+            if highlighted.generation == targeted.generation
+                && highlight_settings.matches(&highlighted.model, &targeted.model) =>
+        {
+            None
+        }
+        (_, targeted) => targeted,
+    };
+}
+
+/// System spawning a marker on each node of the highlighted generation using the [`HighlightedModel`], when the highlight changes or when the generation is updated
+pub fn update_model_highlight_markers<C: CartesianCoordinates>(
+    mut commands: Commands,
+    highlight_settings: Res<ModelHighlightSettings>,
+    highlighted_model: Res<HighlightedModel>,
+    mut generation_events: EventReader<GenerationEvent>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    mut generations: Query<(
+        Entity,
+        &Generator<C, CartesianGrid<C>>,
+        Option<&mut ModelHighlightMarkers>,
+    )>,
+) {
+    let highlighted_generation_updated = generation_events.read().fold(false, |updated, event| {
+        let gen_entity = match event {
+            GenerationEvent::Reinitialized(gen_entity) => gen_entity,
+            GenerationEvent::Updated(gen_entity, _) => gen_entity,
+        };
+        updated || highlighted_model.0.map(|info| info.generation) == Some(*gen_entity)
+    });
+    if !highlight_settings.is_changed()
+        && !highlighted_model.is_changed()
+        && !highlighted_generation_updated
+    {
+        return;
+    }
+
+    for (gen_entity, generator, highlight_markers) in generations.iter_mut() {
+        if let Some(mut highlight_markers) = highlight_markers {
+            for marker in highlight_markers.drain(..) {
+                marker_events.send(MarkerDespawnEvent::Marker(marker));
+            }
+        }
+        let Some(info) = highlighted_model.0 else {
+            continue;
+        };
+        if info.generation != gen_entity {
+            continue;
+        }
+
+        let mut markers = Vec::new();
+        for node_index in 0..generator.grid().total_size() {
+            if let [model] = generator.get_models_on(node_index).as_slice() {
+                if highlight_settings.matches(&info.model, model) {
+                    markers.push(spawn_marker(
+                        &mut commands,
+                        gen_entity,
+                        highlight_settings.color,
+                        generator.grid().pos_from_index(node_index),
+                    ));
+                }
+            }
+        }
+        if highlighted_model.is_changed() {
+            info!(
+                "Highlighted {} instance(s) of model {} in generation {:?}",
+                markers.len(),
+                info.model,
+                gen_entity
+            );
+        }
+        commands
+            .entity(gen_entity)
+            .insert(ModelHighlightMarkers(markers));
+    }
+}
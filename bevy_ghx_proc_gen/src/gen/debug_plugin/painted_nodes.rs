@@ -0,0 +1,196 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use bevy::{
+    ecs::system::{Query, Res, ResMut, Resource},
+    log::{info, warn},
+};
+use bevy_egui::{
+    egui::{self, Color32, Pos2},
+    EguiContexts,
+};
+use ghx_proc_gen::{
+    generator::{model::ModelVariantIndex, GenerationStatus, Generator},
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        grid::Grid,
+    },
+    NodeIndex,
+};
+
+use super::{generation::ActiveGeneration, GenerationControl, GenerationControlStatus};
+
+/// Resource holding the state of the egui painted nodes panel
+#[derive(Resource)]
+pub struct PaintedNodesFile {
+    /// Path of the file the painted nodes are saved to and loaded from
+    pub path: String,
+}
+
+impl Default for PaintedNodesFile {
+    fn default() -> Self {
+        Self {
+            path: "painted_nodes.txt".to_string(),
+        }
+    }
+}
+
+/// Saves `nodes` to the file at `path`, one `node_index model_variant_index` pair per line.
+///
+/// The file can be read back with [load_painted_nodes], and its content given to a generator builder as initial nodes.
+pub fn save_painted_nodes<P: AsRef<Path>>(
+    path: P,
+    nodes: &Vec<(NodeIndex, ModelVariantIndex)>,
+) -> io::Result<()> {
+    let content: String = nodes
+        .iter()
+        .map(|(node_index, model_variant_index)| {
+            format!("{} {}\n", node_index, model_variant_index)
+        })
+        .collect();
+    fs::write(path, content)
+}
+
+/// Loads painted nodes from a file written by [save_painted_nodes].
+///
+/// Empty lines and lines starting with `#` are ignored. Returns an [io::Error] of kind [ErrorKind::InvalidData] if a line can't be parsed.
+pub fn load_painted_nodes<P: AsRef<Path>>(
+    path: P,
+) -> io::Result<Vec<(NodeIndex, ModelVariantIndex)>> {
+    let content = fs::read_to_string(path)?;
+    let mut nodes = Vec::new();
+    for (line_index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut values = line.split_whitespace().map(|value| value.parse::<usize>());
+        match (values.next(), values.next(), values.next()) {
+            (Some(Ok(node_index)), Some(Ok(model_variant_index)), None) => {
+                nodes.push((node_index, model_variant_index))
+            }
+            _ => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "invalid painted node on line {}: '{}'",
+                        line_index + 1,
+                        line
+                    ),
+                ))
+            }
+        }
+    }
+    Ok(nodes)
+}
+
+/// System used to draw the painted nodes egui window.
+///
+/// Allows to save the initial nodes of the [ActiveGeneration] (which contain the nodes painted in the editor) to a file, and to load them back as initial nodes, reinitializing the generation.
+pub fn draw_painted_nodes_panel<C: CartesianCoordinates>(
+    mut contexts: EguiContexts,
+    mut painted_nodes_file: ResMut<PaintedNodesFile>,
+    active_generation: Res<ActiveGeneration>,
+    mut generation_control: ResMut<GenerationControl>,
+    mut generations: Query<&mut Generator<C, CartesianGrid<C>>>,
+) {
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    let Ok(mut generator) = generations.get_mut(active_generation) else {
+        return;
+    };
+
+    let (mut save_requested, mut load_requested, mut clear_requested) = (false, false, false);
+    egui::Window::new("Painted nodes")
+        .title_bar(true)
+        .default_open(false)
+        .default_pos(Pos2::new(400., 10.))
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("🖊 Painted nodes:");
+                ui.colored_label(
+                    Color32::WHITE,
+                    format!("{}", generator.initial_nodes().len()),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label("📁 File:");
+                ui.text_edit_singleline(&mut painted_nodes_file.path);
+            });
+            ui.horizontal(|ui| {
+                save_requested = ui.button("💾 Save").clicked();
+                load_requested = ui.button("📂 Load").clicked();
+                clear_requested = ui.button("Clear").clicked();
+            });
+        });
+
+    if save_requested {
+        match save_painted_nodes(&painted_nodes_file.path, generator.initial_nodes()) {
+            Ok(()) => info!(
+                "Saved {} painted nodes to '{}'",
+                generator.initial_nodes().len(),
+                painted_nodes_file.path
+            ),
+            Err(err) => warn!(
+                "Failed to save painted nodes to '{}': {}",
+                painted_nodes_file.path, err
+            ),
+        }
+    }
+
+    let new_initial_nodes = if load_requested {
+        match load_painted_nodes(&painted_nodes_file.path) {
+            Ok(nodes) => {
+                let (nodes_count, models_count) = (
+                    generator.grid().total_size(),
+                    generator.rules().models_count(),
+                );
+                let (valid_nodes, invalid_nodes): (Vec<_>, Vec<_>) =
+                    nodes.into_iter().partition(|(node_index, model_index)| {
+                        *node_index < nodes_count && *model_index < models_count
+                    });
+                if !invalid_nodes.is_empty() {
+                    warn!(
+                        "Ignored {} painted nodes not matching the grid or the rules",
+                        invalid_nodes.len()
+                    );
+                }
+                info!(
+                    "Loaded {} painted nodes from '{}'",
+                    valid_nodes.len(),
+                    painted_nodes_file.path
+                );
+                Some(valid_nodes)
+            }
+            Err(err) => {
+                warn!(
+                    "Failed to load painted nodes from '{}': {}",
+                    painted_nodes_file.path, err
+                );
+                None
+            }
+        }
+    } else if clear_requested {
+        Some(Vec::new())
+    } else {
+        None
+    };
+
+    if let Some(initial_nodes) = new_initial_nodes {
+        if let Err(err) = generator.set_initial_nodes(initial_nodes) {
+            warn!("Failed to set the painted nodes: {}", err);
+            return;
+        }
+        generation_control.need_reinit = false;
+        match generator.reinitialize() {
+            GenerationStatus::Ongoing => {
+                generation_control.status = GenerationControlStatus::Ongoing;
+            }
+            GenerationStatus::Done => generation_control.need_reinit = true,
+        }
+    }
+}
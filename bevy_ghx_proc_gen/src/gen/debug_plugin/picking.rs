@@ -2,6 +2,7 @@ use bevy::{
     asset::{Assets, Handle},
     color::{Alpha, Color},
     ecs::{
+        change_detection::Ref,
         component::Component,
         entity::Entity,
         event::{Event, EventReader, EventWriter},
@@ -9,27 +10,32 @@ use bevy::{
         system::{Commands, Local, Query, Res, ResMut, Resource},
     },
     hierarchy::{BuildChildren, DespawnRecursiveExt, Parent},
-    input::{keyboard::KeyCode, ButtonInput},
     math::{primitives::Cuboid, Vec2, Vec3},
     pbr::{NotShadowCaster, PbrBundle, StandardMaterial},
     prelude::{AlphaMode, Deref, DerefMut},
-    render::mesh::Mesh,
+    render::{mesh::Mesh, view::Visibility},
     sprite::{Sprite, SpriteBundle},
     text::Text,
     transform::components::Transform,
     utils::default,
 };
 
+#[cfg(feature = "reflect")]
+use bevy::{
+    ecs::reflect::{ReflectComponent, ReflectResource},
+    reflect::Reflect,
+};
 use bevy_ghx_grid::{
     debug_plugin::{
         get_translation_from_grid_coords_3d,
-        markers::{GridMarker, MarkerDespawnEvent},
+        markers::{spawn_marker, GridMarker, MarkerDespawnEvent},
         view::{DebugGridView, DebugGridView2d, DebugGridView3d},
     },
     ghx_grid::{coordinate_system::CoordinateSystem, direction::Direction},
 };
 use bevy_mod_picking::{
     events::Out,
+    picking_core::Pickable,
     prelude::{Down, ListenerInput, On, Over, Pointer},
     PickableBundle,
 };
@@ -39,19 +45,21 @@ use ghx_proc_gen::{
     NodeIndex,
 };
 
-use crate::gen::GridNode;
+use crate::gen::{node_generation, GridNode, NodeLayer};
 
 use super::{
     cursor::{
-        cursor_info_to_string, Cursor, CursorBehavior, CursorInfo, CursorMarkerSettings,
-        CursorsPanelText, SelectCursor, TargetedNode, OVER_CURSOR_SECTION_INDEX,
+        cursor_info_to_string, most_constrained_neighbour, neighbours_info, Cursor, CursorBehavior,
+        CursorInfo, CursorMarkerSettings, CursorsPanelText, SelectCursor, TargetedNode,
+        OVER_CURSOR_SECTION_INDEX,
     },
     generation::{ActiveGeneration, GenerationEvent},
-    ProcGenKeyBindings,
+    input::{ProcGenInput, ProcGenInputs},
 };
 
 /// Used to customize the color of the Over cursor [GridMarker]
 #[derive(Resource)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Resource))]
 pub struct OverCursorMarkerSettings(pub Color);
 impl Default for OverCursorMarkerSettings {
     fn default() -> Self {
@@ -64,8 +72,29 @@ impl CursorMarkerSettings for OverCursorMarkerSettings {
     }
 }
 
+/// Used to customize the display of the neighbours of the node hovered by the Over cursor
+#[derive(Resource)]
+pub struct OverCursorNeighboursSettings {
+    /// Whether or not the possibilities of the neighbours of the hovered node are displayed
+    pub enabled: bool,
+    /// Color of the [GridMarker] spawned on the ungenerated neighbours
+    pub color: Color,
+    /// Color of the [GridMarker] spawned on the most constrained neighbour
+    pub most_constrained_color: Color,
+}
+impl Default for OverCursorNeighboursSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Color::srgb(0.4, 0.6, 0.85),
+            most_constrained_color: Color::srgb(0.9, 0.45, 0.2),
+        }
+    }
+}
+
 /// Main component for the Over cursor
 #[derive(Component, Debug)]
+#[cfg_attr(feature = "reflect", derive(Reflect), reflect(Component))]
 pub struct OverCursor;
 impl CursorBehavior for OverCursor {
     fn new() -> Self {
@@ -98,6 +127,10 @@ impl From<ListenerInput<Pointer<Out>>> for NodeOutEvent {
 #[derive(Event, Deref, DerefMut)]
 pub struct NodeSelectedEvent(pub Entity);
 
+/// Event raised when a context menu is requested on a node by a mouse pointer (secondary button)
+#[derive(Event, Deref, DerefMut)]
+pub struct NodeContextMenuEvent(pub Entity);
+
 /// System that inserts picking event handlers to entites with an added [GridNode] component
 pub fn insert_cursor_picking_handlers_to_grid_nodes<C: CoordinateSystem>(
     mut commands: Commands,
@@ -111,9 +144,16 @@ pub fn insert_cursor_picking_handlers_to_grid_nodes<C: CoordinateSystem>(
             On::<Pointer<Out>>::send_event::<NodeOutEvent>(),
             On::<Pointer<Down>>::run(
                 move |event: ListenerMut<Pointer<Down>>,
-                      mut selection_events: EventWriter<NodeSelectedEvent>| {
-                    if event.button == PointerButton::Primary {
-                        selection_events.send(NodeSelectedEvent(event.listener()));
+                      mut selection_events: EventWriter<NodeSelectedEvent>,
+                      mut context_menu_events: EventWriter<NodeContextMenuEvent>| {
+                    match event.button {
+                        PointerButton::Primary => {
+                            selection_events.send(NodeSelectedEvent(event.listener()));
+                        }
+                        PointerButton::Secondary => {
+                            context_menu_events.send(NodeContextMenuEvent(event.listener()));
+                        }
+                        _ => (),
                     }
                 },
             ),
@@ -167,6 +207,65 @@ pub fn update_over_cursor_from_generation_events<C: CoordinateSystem>(
     }
 }
 
+/// System updating the neighbours information of the Over [Cursor] and their [GridMarker], when the Over cursor moves or when a generation is updated.
+///
+/// Does nothing while [OverCursorNeighboursSettings] is disabled.
+///
+/// Should run after update_cursors_info_from_generation_events
+pub fn update_over_cursor_neighbours<C: CartesianCoordinates>(
+    mut commands: Commands,
+    mut neighbours_markers: Local<Vec<Entity>>,
+    neighbours_settings: Res<OverCursorNeighboursSettings>,
+    mut generation_events: EventReader<GenerationEvent>,
+    mut marker_events: EventWriter<MarkerDespawnEvent>,
+    generators: Query<(&Generator<C, CartesianGrid<C>>, &CartesianGrid<C>)>,
+    mut over_cursor: Query<(Ref<Cursor>, &mut CursorInfo), With<OverCursor>>,
+) {
+    let generation_updated = generation_events.read().count() > 0;
+    let Ok((cursor, mut cursor_info)) = over_cursor.get_single_mut() else {
+        return;
+    };
+    if !generation_updated && !cursor.is_changed() && !neighbours_settings.is_changed() {
+        return;
+    }
+
+    for marker in neighbours_markers.drain(..) {
+        marker_events.send(MarkerDespawnEvent::Marker(marker));
+    }
+    if !cursor_info.neighbours.is_empty() {
+        cursor_info.neighbours.clear();
+    }
+    if !neighbours_settings.enabled {
+        return;
+    }
+    let Some(overed_node) = &cursor.0 else {
+        return;
+    };
+    let Ok((generator, grid)) = generators.get(overed_node.grid) else {
+        return;
+    };
+
+    cursor_info.neighbours = neighbours_info(generator, overed_node.node_index);
+
+    let most_constrained =
+        most_constrained_neighbour(&cursor_info.neighbours).map(|n| n.node_index);
+    for neighbour in cursor_info.neighbours.iter() {
+        if neighbour.possible_models_count <= 1 {
+            continue;
+        }
+        let color = match Some(neighbour.node_index) == most_constrained {
+            true => neighbours_settings.most_constrained_color,
+            false => neighbours_settings.color,
+        };
+        neighbours_markers.push(spawn_marker(
+            &mut commands,
+            overed_node.grid,
+            color,
+            grid.pos_from_index(neighbour.node_index),
+        ));
+    }
+}
+
 /// System used to update cursor positions from picking events
 pub fn picking_update_cursors_position<
     C: CartesianCoordinates,
@@ -180,6 +279,7 @@ pub fn picking_update_cursors_position<
     mut events: EventReader<PE>,
     mut marker_events: EventWriter<MarkerDespawnEvent>,
     grid_nodes: Query<(&GridNode, &Parent)>,
+    node_layers: Query<&Parent, With<NodeLayer>>,
     mut cursor: Query<&mut Cursor, With<CB>>,
     generations: Query<(Entity, &CartesianGrid<C>), With<Generator<C, CartesianGrid<C>>>>,
 ) {
@@ -191,7 +291,7 @@ pub fn picking_update_cursors_position<
             return;
         };
 
-        let picked_grid_entity = node_parent.get();
+        let picked_grid_entity = node_generation(node_parent, &node_layers);
         let update_cursor = match &cursor.0 {
             Some(targeted_node) => {
                 if (targeted_node.grid != picked_grid_entity)
@@ -293,13 +393,26 @@ pub struct ActiveCursorTargets {
     pub from_node: NodeIndex,
 }
 
-/// System that spawn & depsanw the cursor targets
+/// Local system resource used to pool the cursor targets entities.
+///
+/// Cursor targets are moved and shown/hidden when the selection or the axis changes, instead of being despawned and respawned.
+#[derive(Default)]
+pub struct CursorTargetsPool {
+    /// Grid entity the pooled cursor targets are children of
+    pub grid: Option<Entity>,
+    /// Pooled cursor targets entities
+    pub targets: Vec<Entity>,
+    /// Count of cursor targets currently displayed, they are the first ones in `targets`
+    pub active_count: usize,
+}
+
+/// System that places, shows & hides the cursor targets
 pub fn update_cursor_targets_nodes<C: CartesianCoordinates>(
     mut local_active_cursor_targets: Local<Option<ActiveCursorTargets>>,
+    mut targets_pool: Local<CursorTargetsPool>,
     mut commands: Commands,
-    keys: Res<ButtonInput<KeyCode>>,
+    mut inputs: ProcGenInputs,
     cursor_target_assets: Res<CursorTargetAssets>,
-    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
     mut marker_events: EventWriter<MarkerDespawnEvent>,
     selection_cursor: Query<&Cursor, With<SelectCursor>>,
     mut over_cursor: Query<&mut Cursor, (With<OverCursor>, Without<SelectCursor>)>,
@@ -317,47 +430,35 @@ pub fn update_cursor_targets_nodes<C: CartesianCoordinates>(
         return;
     };
 
-    let axis_selection = if keys.pressed(proc_gen_key_bindings.cursor_x_axis) {
+    let axis_selection = if inputs.pressed(ProcGenInput::CursorXAxis) {
         Some(Direction::XForward)
-    } else if keys.pressed(proc_gen_key_bindings.cursor_y_axis) {
+    } else if inputs.pressed(ProcGenInput::CursorYAxis) {
         Some(Direction::YForward)
-    } else if keys.pressed(proc_gen_key_bindings.cursor_z_axis) {
+    } else if inputs.pressed(ProcGenInput::CursorZAxis) {
         Some(Direction::ZForward)
     } else {
         None
     };
 
     if let Some(axis) = axis_selection {
-        if let Some(active_targets) = local_active_cursor_targets.as_mut() {
-            if selected_node.node_index != active_targets.from_node {
-                despawn_cursor_targets(
-                    &mut commands,
-                    &mut marker_events,
-                    &cursor_targets,
-                    &mut over_cursor,
-                );
-                spawn_cursor_targets(
-                    &mut commands,
-                    &cursor_target_assets,
-                    selected_node,
-                    axis,
-                    &grids_with_cam3d,
-                    &grids_with_cam2d,
-                );
-
-                active_targets.from_node = selected_node.node_index;
-                active_targets.axis = axis;
+        let targets_outdated = match local_active_cursor_targets.as_ref() {
+            Some(active_targets) => {
+                selected_node.node_index != active_targets.from_node || axis != active_targets.axis
             }
-        } else {
-            spawn_cursor_targets(
+            None => true,
+        };
+        if targets_outdated {
+            clear_over_cursor(&mut marker_events, &mut over_cursor);
+            place_cursor_targets(
                 &mut commands,
+                &mut targets_pool,
                 &cursor_target_assets,
+                &cursor_targets,
                 selected_node,
                 axis,
                 &grids_with_cam3d,
                 &grids_with_cam2d,
             );
-
             *local_active_cursor_targets = Some(ActiveCursorTargets {
                 axis,
                 from_node: selected_node.node_index,
@@ -365,27 +466,19 @@ pub fn update_cursor_targets_nodes<C: CartesianCoordinates>(
         }
     } else if local_active_cursor_targets.is_some() {
         *local_active_cursor_targets = None;
-        despawn_cursor_targets(
-            &mut commands,
-            &mut marker_events,
-            &cursor_targets,
-            &mut over_cursor,
-        );
+        hide_cursor_targets(&mut commands, &mut targets_pool);
+        clear_over_cursor(&mut marker_events, &mut over_cursor);
     }
 }
 
-/// Function used to despawn all cursor targets and eventually the attached over cursor
-pub fn despawn_cursor_targets(
-    commands: &mut Commands,
+/// Function used to despawn the Over cursor, if any.
+///
+/// Used when the cursor targets are moved or hidden: the node under the Over cursor may not be there anymore, and there won't be any NodeOutEvent.
+pub fn clear_over_cursor(
     marker_events: &mut EventWriter<MarkerDespawnEvent>,
-    cursor_targets: &Query<Entity, With<CursorTarget>>,
     over_cursor: &mut Query<&mut Cursor, (With<OverCursor>, Without<SelectCursor>)>,
 ) {
-    for cursor_target in cursor_targets.iter() {
-        commands.entity(cursor_target).despawn_recursive();
-    }
     if let Ok(mut over_cursor) = over_cursor.get_single_mut() {
-        // If there is an Over cursor, force despawn it, since we will despawn the underlying node there won't be any NodeOutEvent.
         if let Some(grid_cursor) = &over_cursor.0 {
             marker_events.send(MarkerDespawnEvent::Marker(grid_cursor.marker));
             over_cursor.0 = None;
@@ -393,10 +486,24 @@ pub fn despawn_cursor_targets(
     };
 }
 
-/// Function used to spawn cursor targets
-pub fn spawn_cursor_targets<C: CartesianCoordinates>(
+/// Function used to hide all the displayed cursor targets of the pool
+pub fn hide_cursor_targets(commands: &mut Commands, targets_pool: &mut CursorTargetsPool) {
+    for cursor_target in targets_pool.targets.iter().take(targets_pool.active_count) {
+        if let Some(mut cursor_target) = commands.get_entity(*cursor_target) {
+            cursor_target.insert((Visibility::Hidden, Pickable::IGNORE));
+        }
+    }
+    targets_pool.active_count = 0;
+}
+
+/// Function used to place the cursor targets along `axis` from the selected node.
+///
+/// Reuses the pooled cursor targets, and only spawns new ones when the pool is too small.
+pub fn place_cursor_targets<C: CartesianCoordinates>(
     commands: &mut Commands,
+    targets_pool: &mut CursorTargetsPool,
     cursor_target_assets: &Res<CursorTargetAssets>,
+    cursor_targets: &Query<Entity, With<CursorTarget>>,
     selected_node: &TargetedNode,
     axis: Direction,
     grids_with_cam3d: &Query<(&CartesianGrid<C>, &DebugGridView), With<DebugGridView3d>>,
@@ -405,141 +512,157 @@ pub fn spawn_cursor_targets<C: CartesianCoordinates>(
         (With<DebugGridView2d>, Without<DebugGridView3d>),
     >,
 ) {
-    if let Ok((grid, grid_view)) = grids_with_cam3d.get(selected_node.grid) {
-        spawn_cursor_targets_3d(
-            commands,
-            &cursor_target_assets,
-            axis,
-            selected_node,
-            grid,
-            &grid_view.node_size,
-        );
-    } else if let Ok((grid, grid_view)) = grids_with_cam2d.get(selected_node.grid) {
-        spawn_cursor_targets_2d(
-            commands,
-            &cursor_target_assets,
-            axis,
-            selected_node,
-            grid,
-            &grid_view.node_size,
-        );
+    let (grid, grid_view, view_3d) =
+        if let Ok((grid, grid_view)) = grids_with_cam3d.get(selected_node.grid) {
+            (grid, grid_view, true)
+        } else if let Ok((grid, grid_view)) = grids_with_cam2d.get(selected_node.grid) {
+            (grid, grid_view, false)
+        } else {
+            return;
+        };
+
+    if targets_pool.grid != Some(selected_node.grid) {
+        for cursor_target in targets_pool.targets.drain(..) {
+            if let Some(cursor_target) = commands.get_entity(cursor_target) {
+                cursor_target.despawn_recursive();
+            }
+        }
+        targets_pool.grid = Some(selected_node.grid);
+    }
+    // Pooled targets may have been despawned with their grid children
+    targets_pool
+        .targets
+        .retain(|cursor_target| cursor_targets.contains(*cursor_target));
+
+    let coords = cursor_targets_coords(axis, selected_node, grid, view_3d);
+    for (target_rank, (x, y, z)) in coords.iter().enumerate() {
+        let node = GridNode(grid.index_from_coords(*x, *y, *z));
+        let mut translation = get_translation_from_grid_coords_3d(*x, *y, *z, &grid_view.node_size);
+        if !view_3d {
+            translation.z += grid_view.node_size.z;
+        }
+        let transform = Transform::from_translation(translation).with_scale(grid_view.node_size);
+        match targets_pool.targets.get(target_rank) {
+            Some(cursor_target) => {
+                commands.entity(*cursor_target).insert((
+                    node,
+                    transform,
+                    Visibility::Inherited,
+                    Pickable::default(),
+                ));
+            }
+            None => {
+                let cursor_target = match view_3d {
+                    true => spawn_cursor_target_3d(commands, cursor_target_assets, node, transform),
+                    false => {
+                        spawn_cursor_target_2d(commands, cursor_target_assets, node, transform)
+                    }
+                };
+                commands.entity(selected_node.grid).add_child(cursor_target);
+                targets_pool.targets.push(cursor_target);
+            }
+        }
+    }
+    for cursor_target in targets_pool
+        .targets
+        .iter()
+        .take(targets_pool.active_count)
+        .skip(coords.len())
+    {
+        commands
+            .entity(*cursor_target)
+            .insert((Visibility::Hidden, Pickable::IGNORE));
     }
+    targets_pool.active_count = coords.len();
 }
 
-/// Function used to spawn cursor targets when using a 3d camera
-pub fn spawn_cursor_targets_3d<C: CartesianCoordinates>(
-    commands: &mut Commands,
-    cursor_target_assets: &Res<CursorTargetAssets>,
+/// Returns the grid coordinates of the cursor targets to display along `axis` from the selected node: the row of nodes along the axis, and the plane of nodes orthogonal to it
+pub fn cursor_targets_coords<C: CartesianCoordinates>(
     axis: Direction,
     selected_node: &TargetedNode,
     grid: &CartesianGrid<C>,
-    node_size: &Vec3,
-) {
-    let mut spawn_cursor_target = |x: u32, y: u32, z: u32| {
-        let translation = get_translation_from_grid_coords_3d(x, y, z, node_size);
-        let helper_node_entity = commands
-            .spawn((
-                GridNode(grid.index_from_coords(x, y, z)),
-                CursorTarget,
-                NotShadowCaster,
-                PbrBundle {
-                    transform: Transform::from_translation(translation).with_scale(*node_size),
-                    mesh: cursor_target_assets.target_mesh_3d.clone(),
-                    material: cursor_target_assets.target_mat_3d.clone(),
-                    ..default()
-                },
-            ))
-            .id();
-        commands
-            .entity(selected_node.grid)
-            .add_child(helper_node_entity);
-    };
-
-    match axis {
-        Direction::XForward => {
-            for x in 0..grid.size_x() {
-                spawn_cursor_target(x, selected_node.position.y, selected_node.position.z);
-            }
+    view_3d: bool,
+) -> Vec<(u32, u32, u32)> {
+    let pos = &selected_node.position;
+    let mut coords = Vec::new();
+    match (axis, view_3d) {
+        (Direction::XForward, true) => {
+            coords.extend((0..grid.size_x()).map(|x| (x, pos.y, pos.z)));
             for y in 0..grid.size_y() {
-                for z in 0..grid.size_z() {
-                    spawn_cursor_target(selected_node.position.x, y, z);
-                }
+                coords.extend((0..grid.size_z()).map(|z| (pos.x, y, z)));
             }
         }
-        Direction::YForward => {
-            for y in 0..grid.size_y() {
-                spawn_cursor_target(selected_node.position.x, y, selected_node.position.z);
-            }
+        (Direction::YForward, true) => {
+            coords.extend((0..grid.size_y()).map(|y| (pos.x, y, pos.z)));
             for x in 0..grid.size_x() {
-                for z in 0..grid.size_z() {
-                    spawn_cursor_target(x, selected_node.position.y, z);
-                }
+                coords.extend((0..grid.size_z()).map(|z| (x, pos.y, z)));
             }
         }
-        Direction::ZForward => {
-            for z in 0..grid.size_z() {
-                spawn_cursor_target(selected_node.position.x, selected_node.position.y, z);
+        (Direction::XForward, false) | (Direction::YForward, false) => {
+            coords.extend((0..grid.size_x()).map(|x| (x, pos.y, pos.z)));
+            coords.extend((0..grid.size_y()).map(|y| (pos.x, y, pos.z)));
+        }
+        (Direction::ZForward, true) => {
+            coords.extend((0..grid.size_z()).map(|z| (pos.x, pos.y, z)));
+            for x in 0..grid.size_x() {
+                coords.extend((0..grid.size_y()).map(|y| (x, y, pos.z)));
             }
+        }
+        (Direction::ZForward, false) => {
             for x in 0..grid.size_x() {
-                for y in 0..grid.size_y() {
-                    spawn_cursor_target(x, y, selected_node.position.z);
-                }
+                coords.extend((0..grid.size_y()).map(|y| (x, y, pos.z)));
             }
         }
         _ => {}
     }
+    coords
 }
 
-/// Function used to spawn cursor targets when using a 2d camera
-pub fn spawn_cursor_targets_2d<C: CartesianCoordinates>(
+/// Function used to spawn a cursor target when using a 3d camera
+pub fn spawn_cursor_target_3d(
     commands: &mut Commands,
     cursor_target_assets: &Res<CursorTargetAssets>,
-    axis: Direction,
-    selected_node: &TargetedNode,
-    grid: &CartesianGrid<C>,
-    node_size: &Vec3,
-) {
-    let mut spawn_cursor_target = |x: u32, y: u32, z: u32| {
-        let mut translation = get_translation_from_grid_coords_3d(x, y, z, node_size);
-        translation.z += node_size.z;
-        let helper_node_entity = commands
-            .spawn((
-                GridNode(grid.index_from_coords(x, y, z)),
-                CursorTarget,
-                // TODO: Here MaterialMesh2dBundle + PickableBundle::default() did not interact with picking. Not sure why yet. Using Sprite instead.
-                SpriteBundle {
-                    transform: Transform::from_translation(translation).with_scale(*node_size),
-                    sprite: Sprite {
-                        color: cursor_target_assets.color,
-                        custom_size: Some(Vec2::splat(cursor_target_assets.base_size)),
-                        ..default()
-                    },
+    node: GridNode,
+    transform: Transform,
+) -> Entity {
+    commands
+        .spawn((
+            node,
+            CursorTarget,
+            NotShadowCaster,
+            PbrBundle {
+                transform,
+                mesh: cursor_target_assets.target_mesh_3d.clone(),
+                material: cursor_target_assets.target_mat_3d.clone(),
+                ..default()
+            },
+            Pickable::default(),
+        ))
+        .id()
+}
+
+/// Function used to spawn a cursor target when using a 2d camera
+pub fn spawn_cursor_target_2d(
+    commands: &mut Commands,
+    cursor_target_assets: &Res<CursorTargetAssets>,
+    node: GridNode,
+    transform: Transform,
+) -> Entity {
+    commands
+        .spawn((
+            node,
+            CursorTarget,
+            // TODO: Here MaterialMesh2dBundle + PickableBundle::default() did not interact with picking. Not sure why yet. Using Sprite instead.
+            SpriteBundle {
+                transform,
+                sprite: Sprite {
+                    color: cursor_target_assets.color,
+                    custom_size: Some(Vec2::splat(cursor_target_assets.base_size)),
                     ..default()
                 },
-                PickableBundle::default(),
-            ))
-            .id();
-        commands
-            .entity(selected_node.grid)
-            .add_child(helper_node_entity);
-    };
-
-    match axis {
-        Direction::XForward | Direction::YForward => {
-            for x in 0..grid.size_x() {
-                spawn_cursor_target(x, selected_node.position.y, selected_node.position.z);
-            }
-            for y in 0..grid.size_y() {
-                spawn_cursor_target(selected_node.position.x, y, selected_node.position.z);
-            }
-        }
-        Direction::ZForward => {
-            for x in 0..grid.size_x() {
-                for y in 0..grid.size_y() {
-                    spawn_cursor_target(x, y, selected_node.position.z);
-                }
-            }
-        }
-        _ => {}
-    }
+                ..default()
+            },
+            PickableBundle::default(),
+        ))
+        .id()
 }
@@ -0,0 +1,467 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use bevy::{
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        event::EventReader,
+        system::{Query, Res},
+        world::Ref,
+    },
+    time::Time,
+};
+use ghx_proc_gen::{
+    generator::{GenerationStep, Generator},
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        grid::Grid,
+    },
+    NodeIndex,
+};
+
+#[cfg(feature = "egui-edit")]
+use bevy::{
+    ecs::system::{Commands, ResMut, Resource},
+    log::{info, warn},
+};
+#[cfg(feature = "egui-edit")]
+use bevy_egui::{
+    egui::{self, Color32, Pos2},
+    EguiContexts,
+};
+
+use super::contradiction::ContradictionEvent;
+#[cfg(feature = "egui-edit")]
+use super::{
+    generation::ActiveGeneration, timeline::GenerationTimeline, GenerationControl,
+    GenerationControlStatus,
+};
+
+/// An event of a generation, as recorded in a [GenerationRecording]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecordedEvent {
+    /// The generator was reinitialized with the given seed
+    Reinitialized(u64),
+    /// A step was added to the trace of the generator
+    Step(GenerationStep),
+    /// The generation was rewound to the given step of its trace
+    Rewound(usize),
+    /// The generation failed due to a contradiction on the given node
+    Failed(NodeIndex),
+}
+
+/// A [RecordedEvent] with the time at which it was recorded
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecordedEntry {
+    /// Time in seconds since the start of the recording
+    pub time: f32,
+    /// Recorded event
+    pub event: RecordedEvent,
+}
+
+/// Default maximum number of entries kept by a [GenerationRecording]
+pub const DEFAULT_MAX_RECORDED_ENTRIES: usize = 100_000;
+
+/// Component used to record the events of a generation: its reinitializations, the steps of its trace, its rewinds and its failures, with timestamps.
+///
+/// Recording is opt-in: only the generations with this component are recorded by [record_generations]. Events are detected once per frame, so multiple tries done in the same frame (such as with [`super::GenerationViewMode::Final`]) only record the last one.
+///
+/// The recording keeps at most `max_entries` entries: once full, the oldest entries are dropped.
+///
+/// A recording can be saved with [save_recording] and loaded back with [load_recording]. [GenerationRecording::last_generation] can then be used to replay the recorded generation in a [`super::timeline::GenerationTimeline`].
+#[derive(Component, Clone, Debug)]
+pub struct GenerationRecording {
+    /// Size of the grid of the recorded generation
+    pub nodes_count: usize,
+    /// Models count of the rules of the recorded generation
+    pub models_count: usize,
+    /// Recorded entries, in chronological order
+    pub entries: VecDeque<RecordedEntry>,
+    /// Maximum number of entries kept. When a new entry is recorded on a full recording, the oldest entry is dropped
+    pub max_entries: usize,
+    /// Whether or not the new events of the generation are recorded. Disabled on recordings loaded from a file, so that replaying them does not record them again
+    pub recording: bool,
+    start_time: Option<f32>,
+    seed: Option<u64>,
+    recorded_steps: usize,
+}
+
+impl Default for GenerationRecording {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RECORDED_ENTRIES)
+    }
+}
+
+impl GenerationRecording {
+    /// Creates an empty recording, keeping at most `max_entries` entries. Insert it on a generation entity to start recording it
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            nodes_count: 0,
+            models_count: 0,
+            entries: VecDeque::new(),
+            max_entries,
+            recording: true,
+            start_time: None,
+            seed: None,
+            recorded_steps: 0,
+        }
+    }
+
+    /// Returns the seed of the last recorded generation and the steps of its trace, rewinds applied.
+    ///
+    /// Returns `None` if no reinitialization was recorded, or if it was dropped because the recording was full.
+    pub fn last_generation(&self) -> Option<(u64, Vec<GenerationStep>)> {
+        let mut last_generation = None;
+        for entry in self.entries.iter() {
+            match entry.event {
+                RecordedEvent::Reinitialized(seed) => last_generation = Some((seed, Vec::new())),
+                RecordedEvent::Step(step) => {
+                    if let Some((_, steps)) = &mut last_generation {
+                        steps.push(step);
+                    }
+                }
+                RecordedEvent::Rewound(steps_count) => {
+                    if let Some((_, steps)) = &mut last_generation {
+                        steps.truncate(steps_count);
+                    }
+                }
+                RecordedEvent::Failed(_) => (),
+            }
+        }
+        last_generation
+    }
+
+    /// Removes all the entries and resumes the recording, the next recorded events will be timestamped from `now`
+    pub fn clear(&mut self, now: f32) {
+        self.entries.clear();
+        self.recording = true;
+        self.start_time = Some(now);
+        self.seed = None;
+        self.recorded_steps = 0;
+    }
+
+    fn push(&mut self, now: f32, event: RecordedEvent) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let start_time = *self.start_time.get_or_insert(now);
+        if self.entries.len() >= self.max_entries {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(RecordedEntry {
+            time: now - start_time,
+            event,
+        });
+    }
+}
+
+/// System that records the new events of the generations which have a [GenerationRecording]
+pub fn record_generations<C: CartesianCoordinates>(
+    time: Res<Time>,
+    mut contradiction_events: EventReader<ContradictionEvent>,
+    mut generations: Query<(
+        Ref<Generator<C, CartesianGrid<C>>>,
+        &mut GenerationRecording,
+    )>,
+) {
+    let now = time.elapsed_seconds();
+    for (generator, mut recording) in generations.iter_mut() {
+        if generator.is_changed() || recording.is_added() {
+            record_generator_changes(&mut recording, &generator, now);
+        }
+    }
+
+    for event in contradiction_events.read() {
+        if let Ok((_, mut recording)) = generations.get_mut(event.generation) {
+            if recording.recording {
+                recording.push(now, RecordedEvent::Failed(event.node_index));
+            }
+        }
+    }
+}
+
+fn record_generator_changes<C: CartesianCoordinates>(
+    recording: &mut GenerationRecording,
+    generator: &Generator<C, CartesianGrid<C>>,
+    now: f32,
+) {
+    if !recording.recording {
+        return;
+    }
+    recording.nodes_count = generator.grid().total_size();
+    recording.models_count = generator.rules().models_count();
+    let trace = generator.trace();
+    if recording.seed != Some(generator.seed()) {
+        recording.seed = Some(generator.seed());
+        recording.recorded_steps = 0;
+        recording.push(now, RecordedEvent::Reinitialized(generator.seed()));
+    } else if trace.len() < recording.recorded_steps {
+        recording.recorded_steps = trace.len();
+        recording.push(now, RecordedEvent::Rewound(trace.len()));
+    }
+    for step in trace[recording.recorded_steps..].iter() {
+        recording.push(now, RecordedEvent::Step(*step));
+    }
+    recording.recorded_steps = trace.len();
+}
+
+/// Saves `recording` to the file at `path`, as a text file with one entry per line.
+///
+/// The first line holds the nodes count and the models count of the generation, each following line holds the time of an entry followed by the event: `reinit <seed>`, `select|set|ban <node_index> <model_variant_index>`, `rewind <steps_count>` or `fail <node_index>`.
+pub fn save_recording<P: AsRef<Path>>(path: P, recording: &GenerationRecording) -> io::Result<()> {
+    let mut content = format!(
+        "# nodes models\n{} {}\n# time event\n",
+        recording.nodes_count, recording.models_count
+    );
+    for entry in recording.entries.iter() {
+        let event = match entry.event {
+            RecordedEvent::Reinitialized(seed) => format!("reinit {}", seed),
+            RecordedEvent::Step(GenerationStep::Selected {
+                node_index,
+                model_variant_index,
+            }) => format!("select {} {}", node_index, model_variant_index),
+            RecordedEvent::Step(GenerationStep::Set {
+                node_index,
+                model_variant_index,
+            }) => format!("set {} {}", node_index, model_variant_index),
+            RecordedEvent::Step(GenerationStep::Banned {
+                node_index,
+                model_variant_index,
+            }) => format!("ban {} {}", node_index, model_variant_index),
+            RecordedEvent::Rewound(steps_count) => format!("rewind {}", steps_count),
+            RecordedEvent::Failed(node_index) => format!("fail {}", node_index),
+        };
+        content.push_str(&format!("{:.3} {}\n", entry.time, event));
+    }
+    fs::write(path, content)
+}
+
+/// Loads a recording from a file written by [save_recording].
+///
+/// Empty lines and lines starting with `#` are ignored. Returns an [io::Error] of kind [ErrorKind::InvalidData] if a line can't be parsed.
+pub fn load_recording<P: AsRef<Path>>(path: P) -> io::Result<GenerationRecording> {
+    let content = fs::read_to_string(path)?;
+    let mut recording = GenerationRecording::new(usize::MAX);
+    recording.recording = false;
+    let mut header_read = false;
+    for (line_index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid_line = || {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "invalid recording entry on line {}: '{}'",
+                    line_index + 1,
+                    line
+                ),
+            )
+        };
+        let values: Vec<&str> = line.split_whitespace().collect();
+        let parse = |index: usize| -> io::Result<usize> {
+            values
+                .get(index)
+                .and_then(|value| value.parse::<usize>().ok())
+                .ok_or_else(invalid_line)
+        };
+        if !header_read {
+            if values.len() != 2 {
+                return Err(invalid_line());
+            }
+            (recording.nodes_count, recording.models_count) = (parse(0)?, parse(1)?);
+            header_read = true;
+            continue;
+        }
+        let time = values
+            .first()
+            .and_then(|value| value.parse::<f32>().ok())
+            .ok_or_else(invalid_line)?;
+        let event = match (values.get(1).copied(), values.len()) {
+            (Some("reinit"), 3) => {
+                RecordedEvent::Reinitialized(values[2].parse::<u64>().map_err(|_| invalid_line())?)
+            }
+            (Some("select"), 4) => RecordedEvent::Step(GenerationStep::Selected {
+                node_index: parse(2)?,
+                model_variant_index: parse(3)?,
+            }),
+            (Some("set"), 4) => RecordedEvent::Step(GenerationStep::Set {
+                node_index: parse(2)?,
+                model_variant_index: parse(3)?,
+            }),
+            (Some("ban"), 4) => RecordedEvent::Step(GenerationStep::Banned {
+                node_index: parse(2)?,
+                model_variant_index: parse(3)?,
+            }),
+            (Some("rewind"), 3) => RecordedEvent::Rewound(parse(2)?),
+            (Some("fail"), 3) => RecordedEvent::Failed(parse(2)?),
+            _ => return Err(invalid_line()),
+        };
+        recording.entries.push_back(RecordedEntry { time, event });
+    }
+    Ok(recording)
+}
+
+/// Resource holding the state of the egui recording panel
+#[cfg(feature = "egui-edit")]
+#[derive(Resource)]
+pub struct RecordingFile {
+    /// Path of the file the recording is saved to and loaded from
+    pub path: String,
+}
+
+#[cfg(feature = "egui-edit")]
+impl Default for RecordingFile {
+    fn default() -> Self {
+        Self {
+            path: "generation_recording.txt".to_string(),
+        }
+    }
+}
+
+/// System used to draw the recording egui window.
+///
+/// Allows to start recording the [ActiveGeneration], to save its [GenerationRecording] to a file, and to load a recording back for replay: the generator is reinitialized with the seed of the last recorded generation, and its steps are loaded in the [GenerationTimeline], to be scrubbed through.
+#[cfg(feature = "egui-edit")]
+pub fn draw_recording_panel<C: CartesianCoordinates>(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    mut recording_file: ResMut<RecordingFile>,
+    active_generation: Res<ActiveGeneration>,
+    mut generation_control: ResMut<GenerationControl>,
+    mut generations: Query<(
+        &mut Generator<C, CartesianGrid<C>>,
+        Option<&mut GenerationRecording>,
+    )>,
+) {
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    let Ok((mut generator, recording)) = generations.get_mut(active_generation) else {
+        return;
+    };
+    let Some(mut recording) = recording else {
+        egui::Window::new("Recording")
+            .title_bar(true)
+            .default_open(false)
+            .default_pos(Pos2::new(400., 100.))
+            .show(contexts.ctx_mut(), |ui| {
+                if ui.button("⏺ Start recording").clicked() {
+                    commands
+                        .entity(active_generation)
+                        .insert(GenerationRecording::default());
+                }
+            });
+        return;
+    };
+
+    let (mut save_requested, mut load_requested, mut clear_requested) = (false, false, false);
+    egui::Window::new("Recording")
+        .title_bar(true)
+        .default_open(false)
+        .default_pos(Pos2::new(400., 100.))
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(match recording.recording {
+                    true => "⏺ Recording:",
+                    false => "▶ Replaying:",
+                });
+                ui.colored_label(
+                    Color32::WHITE,
+                    format!("{} entries", recording.entries.len()),
+                );
+            });
+            for entry in recording
+                .entries
+                .iter()
+                .filter(|entry| matches!(entry.event, RecordedEvent::Failed(_)))
+                .rev()
+                .take(5)
+            {
+                if let RecordedEvent::Failed(node_index) = entry.event {
+                    ui.colored_label(
+                        Color32::RED,
+                        format!("{:.2}s: failed on node {}", entry.time, node_index),
+                    );
+                }
+            }
+            ui.horizontal(|ui| {
+                ui.label("📁 File:");
+                ui.text_edit_singleline(&mut recording_file.path);
+            });
+            ui.horizontal(|ui| {
+                save_requested = ui.button("💾 Save").clicked();
+                load_requested = ui.button("📂 Load").clicked();
+                clear_requested = ui.button("Clear").clicked();
+            });
+        });
+
+    if save_requested {
+        match save_recording(&recording_file.path, &recording) {
+            Ok(()) => info!(
+                "Saved {} recorded entries to '{}'",
+                recording.entries.len(),
+                recording_file.path
+            ),
+            Err(err) => warn!(
+                "Failed to save the recording to '{}': {}",
+                recording_file.path, err
+            ),
+        }
+    }
+
+    if clear_requested {
+        recording.clear(time.elapsed_seconds());
+    }
+
+    if !load_requested {
+        return;
+    }
+    let loaded_recording = match load_recording(&recording_file.path) {
+        Ok(loaded_recording) => loaded_recording,
+        Err(err) => {
+            warn!(
+                "Failed to load the recording from '{}': {}",
+                recording_file.path, err
+            );
+            return;
+        }
+    };
+    if loaded_recording.nodes_count != generator.grid().total_size()
+        || loaded_recording.models_count != generator.rules().models_count()
+    {
+        warn!(
+            "Recording '{}' does not match the grid or the rules of the active generation",
+            recording_file.path
+        );
+        return;
+    }
+    let Some((seed, steps)) = loaded_recording.last_generation() else {
+        warn!(
+            "Recording '{}' does not contain any generation",
+            recording_file.path
+        );
+        return;
+    };
+    info!(
+        "Loaded {} recorded entries from '{}', replaying seed {} ({} steps)",
+        loaded_recording.entries.len(),
+        recording_file.path,
+        seed,
+        steps.len()
+    );
+    generator.reinitialize_with_seed(seed);
+    generation_control.status = GenerationControlStatus::Paused;
+    generation_control.need_reinit = false;
+    *recording = loaded_recording;
+    commands
+        .entity(active_generation)
+        .insert(GenerationTimeline(steps));
+}
@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res},
+    },
+    hierarchy::{DespawnRecursiveExt, Parent},
+    input::{keyboard::KeyCode, ButtonInput},
+    log::{info, warn},
+};
+use ghx_proc_gen::{
+    generator::Generator,
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    GeneratorError,
+};
+
+use crate::gen::{node_generation, GridNode, NodeLayer};
+
+use super::{box_selection::SelectionBox, ProcGenKeyBindings};
+
+/// System that regenerates the nodes of the current [`SelectionBox`] on a keypress, see [`Generator::regenerate_nodes`]. All the other nodes of the generation are kept as they are.
+///
+/// Only the entities of the regenerated nodes are despawned. The new nodes are spawned by the debug plugin when its [`ghx_proc_gen::generator::observer::QueuedObserver`] receives them.
+///
+/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+pub fn reroll_selection_box_from_keybinds<C: CartesianCoordinates>(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    selection_box: Res<SelectionBox>,
+    mut generations: Query<&mut Generator<C, CartesianGrid<C>>>,
+    existing_nodes: Query<(Entity, &GridNode, &Parent)>,
+    node_layers: Query<&Parent, With<NodeLayer>>,
+) {
+    if !proc_gen_key_bindings
+        .editor
+        .just_pressed(&keys, proc_gen_key_bindings.editor.reroll_selection)
+    {
+        return;
+    }
+    let Some(box_selection) = &selection_box.0 else {
+        return;
+    };
+    let Ok(mut generator) = generations.get_mut(box_selection.grid) else {
+        return;
+    };
+
+    let node_indexes = box_selection.node_indexes(generator.grid());
+    match generator.regenerate_nodes(&node_indexes) {
+        Ok((gen_info, regenerated_nodes)) => {
+            info!(
+                "Rerolled {} nodes of generation {:?}, try_count: {}, seed: {}",
+                regenerated_nodes.len(),
+                box_selection.grid,
+                gen_info.try_count,
+                generator.seed()
+            );
+            let regenerated_indexes: HashSet<_> = regenerated_nodes
+                .iter()
+                .map(|node| node.node_index)
+                .collect();
+            for (node_entity, GridNode(node_index), parent) in existing_nodes.iter() {
+                if node_generation(parent, &node_layers) == box_selection.grid
+                    && regenerated_indexes.contains(node_index)
+                {
+                    commands.entity(node_entity).despawn_recursive();
+                }
+            }
+        }
+        Err(GeneratorError { node_index }) => warn!(
+            "Failed to reroll the selection of generation {:?}, contradiction at node {}",
+            box_selection.grid, node_index
+        ),
+    }
+}
@@ -0,0 +1,504 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        system::{Commands, Query, Res, ResMut, Resource, SystemParam},
+    },
+    log::{info, warn},
+};
+use bevy_egui::{
+    egui::{self, Color32, Pos2},
+    EguiContexts,
+};
+use ghx_proc_gen::{
+    generator::{
+        model::{ModelCollection, ModelIndex, ModelTemplate, ALL_MODEL_ROTATIONS},
+        rules::{Rules, RulesBuilder, CARTESIAN_2D_ROTATION_AXIS},
+        socket::SocketCollection,
+        Generator,
+    },
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        coordinate_system::CoordinateSystem,
+        direction::Direction,
+        grid::Grid,
+    },
+    RulesBuilderError,
+};
+
+use super::{
+    generation::{ActiveGeneration, VoidNodes},
+    timeline::GenerationTimeline,
+    GenerationControl, GenerationControlStatus,
+};
+
+/// A socket of a [RulesDescription]
+#[derive(Clone, Debug)]
+pub struct SocketDescription {
+    /// Name of the socket, only used for display
+    pub name: String,
+}
+
+/// Type of a connection between two sockets of a [RulesDescription]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionKind {
+    /// Simple connection, see [`SocketCollection::add_connection`]
+    Simple,
+    /// Connection between all the rotations of the sockets, see [`SocketCollection::add_rotated_connection`]
+    Rotated,
+}
+
+/// A model of a [RulesDescription]
+#[derive(Clone, Debug)]
+pub struct ModelDescription {
+    /// Name of the model
+    pub name: String,
+    /// Weight of the model
+    pub weight: f32,
+    /// Whether the model can be rotated in every way, or only used with [`ghx_proc_gen::generator::model::ModelRotation::Rot0`]
+    pub all_rotations: bool,
+    /// `sockets[direction]` is the index of the socket of the model in `direction`, if any
+    pub sockets: Vec<Option<usize>>,
+}
+
+/// Editable description of [Rules]: sockets, connections between them, and models.
+///
+/// [Rules] discard their sockets once built, so a description cannot be extracted from existing [Rules]. It can be filled from code with the same sockets and models as the [Rules] of a generation, to then be tweaked in the rules editor.
+///
+/// Models are indexed in their order of creation: the model at index `i` uses the assets registered for the [`ghx_proc_gen::generator::model::ModelIndex`] `i` in the [`crate::gen::assets::AssetSpawner`] of the generation.
+#[derive(Clone, Default, Debug)]
+pub struct RulesDescription {
+    /// Sockets of the description
+    pub sockets: Vec<SocketDescription>,
+    /// Connections between sockets, indexed by pairs of socket indexes `(a, b)` with `a <= b`
+    pub connections: BTreeMap<(usize, usize), ConnectionKind>,
+    /// Models of the description
+    pub models: Vec<ModelDescription>,
+}
+
+impl RulesDescription {
+    /// Adds a new socket named `name` and returns its index
+    pub fn add_socket(&mut self, name: impl Into<String>) -> usize {
+        self.sockets.push(SocketDescription { name: name.into() });
+        self.sockets.len() - 1
+    }
+
+    /// Removes the socket at `socket_index`, as well as its connections and its uses by the models
+    pub fn remove_socket(&mut self, socket_index: usize) {
+        if socket_index >= self.sockets.len() {
+            return;
+        }
+        self.sockets.remove(socket_index);
+        let shift = |index: usize| match index > socket_index {
+            true => index - 1,
+            false => index,
+        };
+        self.connections = self
+            .connections
+            .iter()
+            .filter(|((a, b), _)| *a != socket_index && *b != socket_index)
+            .map(|((a, b), kind)| ((shift(*a), shift(*b)), *kind))
+            .collect();
+        for model in self.models.iter_mut() {
+            for socket in model.sockets.iter_mut() {
+                *socket = match *socket {
+                    Some(index) if index == socket_index => None,
+                    Some(index) => Some(shift(index)),
+                    None => None,
+                };
+            }
+        }
+    }
+
+    /// Returns the connection between the sockets `a` and `b`, if any
+    pub fn connection(&self, a: usize, b: usize) -> Option<ConnectionKind> {
+        self.connections.get(&(a.min(b), a.max(b))).copied()
+    }
+
+    /// Sets the connection between the sockets `a` and `b`. Removes it if `kind` is [None]
+    pub fn set_connection(&mut self, a: usize, b: usize, kind: Option<ConnectionKind>) {
+        let key = (a.min(b), a.max(b));
+        match kind {
+            Some(kind) => self.connections.insert(key, kind),
+            None => self.connections.remove(&key),
+        };
+    }
+
+    /// Adds a new model named `name` without any socket, for a coordinate system with `directions_count` directions, and returns its index
+    pub fn add_model(&mut self, name: impl Into<String>, directions_count: usize) -> usize {
+        self.models.push(ModelDescription {
+            name: name.into(),
+            weight: 1.0,
+            all_rotations: false,
+            sockets: vec![None; directions_count],
+        });
+        self.models.len() - 1
+    }
+
+    /// Builds [Rules] from this description, for the coordinate system `coord_system`.
+    ///
+    /// The rotation axis of the models is [`CARTESIAN_2D_ROTATION_AXIS`] in 2d and [`Direction::YForward`] in 3d.
+    pub fn build_rules<C: CartesianCoordinates>(
+        &self,
+        coord_system: C,
+    ) -> Result<Rules<C>, RulesBuilderError> {
+        let mut socket_collection = SocketCollection::new();
+        let sockets: Vec<_> = self
+            .sockets
+            .iter()
+            .map(|_| socket_collection.create())
+            .collect();
+        for ((a, b), kind) in self.connections.iter() {
+            match kind {
+                ConnectionKind::Simple => {
+                    socket_collection.add_connection(sockets[*a], vec![sockets[*b]]);
+                }
+                ConnectionKind::Rotated => {
+                    socket_collection.add_rotated_connection(sockets[*a], vec![sockets[*b]]);
+                }
+            }
+        }
+
+        let mut models = ModelCollection::<C>::new();
+        for model_description in self.models.iter() {
+            let model_sockets = (0..coord_system.directions_count())
+                .map(|direction| match model_description.sockets.get(direction) {
+                    Some(Some(socket_index)) => vec![sockets[*socket_index]],
+                    _ => Vec::new(),
+                })
+                .collect();
+            let model = models
+                .create(ModelTemplate::<C>::from_raw_sockets(model_sockets))
+                .with_name(model_description.name.clone())
+                .with_weight(model_description.weight);
+            if model_description.all_rotations {
+                model.with_all_rotations();
+            }
+        }
+
+        let rotation_axis = match coord_system.directions_count() {
+            4 => CARTESIAN_2D_ROTATION_AXIS,
+            _ => Direction::YForward,
+        };
+        RulesBuilder::new_raw(models, socket_collection, rotation_axis, coord_system).build()
+    }
+}
+
+/// Resource holding the state of the egui rules editor
+#[derive(Resource, Default)]
+pub struct RulesEditor {
+    /// Description of the edited [Rules]
+    pub description: RulesDescription,
+    /// Index of the model currently edited, if any
+    pub selected_model: Option<usize>,
+}
+
+/// System used to draw the rules editor egui window.
+///
+/// The edited [RulesDescription] can be rebuilt into [Rules], which are then applied to the [Generator] of the [ActiveGeneration] to restart its generation.
+pub fn draw_rules_editor<C: CartesianCoordinates>(
+    mut contexts: EguiContexts,
+    mut rules_editor: ResMut<RulesEditor>,
+    active_generation: Res<ActiveGeneration>,
+    mut generation_restart: GenerationRestart,
+    mut generations: Query<&mut Generator<C, CartesianGrid<C>>>,
+) {
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    let Ok(mut generator) = generations.get_mut(active_generation) else {
+        return;
+    };
+    let directions = generator.grid().coord_system().directions();
+
+    let mut rebuild_requested = false;
+    let RulesEditor {
+        description,
+        selected_model,
+    } = &mut *rules_editor;
+    egui::Window::new("Rules editor")
+        .title_bar(true)
+        .default_open(false)
+        .default_pos(Pos2::new(400., 10.))
+        .show(contexts.ctx_mut(), |ui| {
+            egui::CollapsingHeader::new(format!("🔌 Sockets ({})", description.sockets.len()))
+                .default_open(true)
+                .show(ui, |ui| {
+                    let mut removed_socket = None;
+                    for (index, socket) in description.sockets.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{}:", index));
+                            ui.text_edit_singleline(&mut socket.name);
+                            if ui.button("🗑").clicked() {
+                                removed_socket = Some(index);
+                            }
+                        });
+                    }
+                    if let Some(index) = removed_socket {
+                        description.remove_socket(index);
+                    }
+                    if ui.button("➕ Add socket").clicked() {
+                        let name = format!("socket_{}", description.sockets.len());
+                        description.add_socket(name);
+                    }
+                });
+
+            egui::CollapsingHeader::new("🔗 Connections").show(ui, |ui| {
+                ui.label("Click a cell to cycle between: none, connected (✔), connected in all rotations (⟳)");
+                egui::ScrollArea::both().max_height(250.).show(ui, |ui| {
+                    egui::Grid::new("rules_editor_connections")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("");
+                            for socket in description.sockets.iter() {
+                                ui.label(socket.name.as_str());
+                            }
+                            ui.end_row();
+                            for a in 0..description.sockets.len() {
+                                ui.label(description.sockets[a].name.as_str());
+                                for b in 0..description.sockets.len() {
+                                    let connection = description.connection(a, b);
+                                    let cell = match connection {
+                                        None => " ",
+                                        Some(ConnectionKind::Simple) => "✔",
+                                        Some(ConnectionKind::Rotated) => "⟳",
+                                    };
+                                    if ui.button(cell).clicked() {
+                                        let next = match connection {
+                                            None => Some(ConnectionKind::Simple),
+                                            Some(ConnectionKind::Simple) => {
+                                                Some(ConnectionKind::Rotated)
+                                            }
+                                            Some(ConnectionKind::Rotated) => None,
+                                        };
+                                        description.set_connection(a, b, next);
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+            egui::CollapsingHeader::new(format!("🧱 Models ({})", description.models.len()))
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        for (index, model) in description.models.iter().enumerate() {
+                            if ui
+                                .selectable_label(*selected_model == Some(index), model.name.as_str())
+                                .clicked()
+                            {
+                                *selected_model = Some(index);
+                            }
+                        }
+                    });
+                    if ui.button("➕ Add model").clicked() {
+                        let name = format!("model_{}", description.models.len());
+                        *selected_model = Some(description.add_model(name, directions.len()));
+                    }
+
+                    let Some(model_index) = *selected_model else {
+                        return;
+                    };
+                    let Some(model) = description.models.get_mut(model_index) else {
+                        *selected_model = None;
+                        return;
+                    };
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut model.name);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Weight:");
+                        ui.add(
+                            egui::DragValue::new(&mut model.weight)
+                                .speed(0.05)
+                                .clamp_range(0.001..=f32::MAX),
+                        );
+                        ui.checkbox(&mut model.all_rotations, "All rotations");
+                    });
+                    model.sockets.resize(directions.len(), None);
+                    for (direction_index, direction) in directions.iter().enumerate() {
+                        let socket = &mut model.sockets[direction_index];
+                        let selected_text = match socket {
+                            Some(socket_index) => description.sockets[*socket_index].name.clone(),
+                            None => "None".to_owned(),
+                        };
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{:?}:", direction));
+                            egui::ComboBox::from_id_source(("rules_editor_face", direction_index))
+                                .selected_text(selected_text)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(socket, None, "None");
+                                    for (socket_index, socket_description) in
+                                        description.sockets.iter().enumerate()
+                                    {
+                                        ui.selectable_value(
+                                            socket,
+                                            Some(socket_index),
+                                            socket_description.name.as_str(),
+                                        );
+                                    }
+                                });
+                        });
+                    }
+                    if ui.button("🗑 Remove model").clicked() {
+                        description.models.remove(model_index);
+                        *selected_model = None;
+                    }
+                });
+
+            ui.separator();
+            if ui
+                .button("🔨 Rebuild rules & restart")
+                .on_hover_text("Replaces the rules of the active generation")
+                .clicked()
+            {
+                rebuild_requested = true;
+            }
+            ui.colored_label(
+                Color32::GRAY,
+                format!(
+                    "{} sockets, {} connections, {} models",
+                    description.sockets.len(),
+                    description.connections.len(),
+                    description.models.len()
+                ),
+            );
+        });
+
+    if !rebuild_requested {
+        return;
+    }
+    let rules = match rules_editor
+        .description
+        .build_rules(generator.grid().coord_system().clone())
+    {
+        Ok(rules) => rules,
+        Err(err) => {
+            warn!("Failed to build the edited rules: {}", err);
+            return;
+        }
+    };
+    generation_restart.apply_rules(active_generation, &mut generator, rules);
+}
+
+/// [SystemParam] used to restart a generation with new [Rules]
+#[derive(SystemParam)]
+pub struct GenerationRestart<'w, 's> {
+    commands: Commands<'w, 's>,
+    generation_control: ResMut<'w, GenerationControl>,
+}
+
+impl<'w, 's> GenerationRestart<'w, 's> {
+    /// Applies `rules` to the [Generator] of the generation `gen_entity` with [`Generator::set_rules`], which keeps its configuration (heuristics, initial nodes and bans, weights modifiers, observers, ...) and reinitializes it.
+    ///
+    /// The spawned nodes and error markers of the generation are despawned by the debug plugin once its observer receives the reinitialization.
+    pub fn apply_rules<C: CartesianCoordinates>(
+        &mut self,
+        gen_entity: Entity,
+        generator: &mut Generator<C, CartesianGrid<C>>,
+        rules: Rules<C>,
+    ) {
+        info!("Restarting generation {:?} with new rules", gen_entity);
+        if let Err(err) = generator.set_rules(Arc::new(rules)) {
+            warn!(
+                "Generation {:?} failed to reinitialize with the new rules: {}",
+                gen_entity, err
+            );
+        }
+        self.commands
+            .entity(gen_entity)
+            .remove::<(VoidNodes, GenerationTimeline)>();
+        self.generation_control.need_reinit = false;
+        self.generation_control.status = GenerationControlStatus::Ongoing;
+    }
+}
+
+/// Resource holding the state of the egui models weights panel
+#[derive(Resource, Default)]
+pub struct WeightsPanel {
+    /// Weight currently edited, applied once the edition ends
+    pub pending_weight: Option<(ModelIndex, f32)>,
+}
+
+/// System used to draw the models weights egui window.
+///
+/// Lists the original models of the [ActiveGeneration] with a weight slider. Once a weight is modified, the [Rules] are updated and the generation is restarted.
+pub fn draw_weights_panel<C: CartesianCoordinates>(
+    mut contexts: EguiContexts,
+    mut weights_panel: ResMut<WeightsPanel>,
+    active_generation: Res<ActiveGeneration>,
+    mut generation_restart: GenerationRestart,
+    mut generations: Query<&mut Generator<C, CartesianGrid<C>>>,
+) {
+    let Some(active_generation) = active_generation.0 else {
+        return;
+    };
+    let Ok(mut generator) = generations.get_mut(active_generation) else {
+        return;
+    };
+    let rules = generator.rules();
+
+    let mut applied_weight = None;
+    egui::Window::new("Models weights")
+        .title_bar(true)
+        .default_open(false)
+        .default_pos(Pos2::new(400., 300.))
+        .show(contexts.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(400.)
+                .show(ui, |ui| {
+                    egui::Grid::new("weights_panel_models")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for model_index in 0..rules.original_models_count() {
+                                let variants: Vec<_> = ALL_MODEL_ROTATIONS
+                                    .iter()
+                                    .filter_map(|rot| rules.variant_index(model_index, *rot))
+                                    .collect();
+                                let Some(first_variant) = variants.first() else {
+                                    continue;
+                                };
+                                let mut weight = match weights_panel.pending_weight {
+                                    Some((pending_index, weight))
+                                        if pending_index == model_index =>
+                                    {
+                                        weight
+                                    }
+                                    _ => rules.weight(*first_variant).unwrap_or_default(),
+                                };
+                                ui.label(rules.name_str(*first_variant).unwrap_or("None"));
+                                ui.colored_label(
+                                    Color32::GRAY,
+                                    format!("{} variations", variants.len()),
+                                );
+                                let response = ui.add(
+                                    egui::Slider::new(&mut weight, 0.01..=100.)
+                                        .logarithmic(true)
+                                        .clamp_to_range(false),
+                                );
+                                if response.changed() {
+                                    weights_panel.pending_weight = Some((model_index, weight));
+                                }
+                                if response.drag_stopped()
+                                    || (response.changed() && !response.dragged())
+                                {
+                                    applied_weight = weights_panel.pending_weight.take();
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+    let Some((model_index, weight)) = applied_weight else {
+        return;
+    };
+    let mut new_rules = rules.clone();
+    new_rules.set_weight(model_index, weight);
+    generation_restart.apply_rules(active_generation, &mut generator, new_rules);
+}
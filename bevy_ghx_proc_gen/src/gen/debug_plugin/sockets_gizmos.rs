@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use bevy::{
+    color::{Color, Hsla},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{keyboard::KeyCode, ButtonInput},
+    math::Vec3,
+    prelude::{Deref, DerefMut},
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_ghx_grid::debug_plugin::view::DebugGridView;
+use ghx_proc_gen::{
+    generator::{model::ModelVariantIndex, socket::SocketId, Generator},
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        coordinate_system::CoordinateSystem,
+        direction::Direction,
+        grid::Grid,
+    },
+    NodeIndex,
+};
+
+use super::{generation::GenerationEvent, ProcGenKeyBindings};
+
+/// Resource used to customize the sockets gizmos
+#[derive(Resource, Debug)]
+pub struct SocketsGizmosSettings {
+    /// Whether or not the sockets of the generated nodes are currently displayed
+    pub enabled: bool,
+    /// Size of a socket gizmo, relatively to the node size
+    pub size: f32,
+    /// Colors used for specific sockets. Sockets without a color here get a color derived from their [SocketId]
+    pub colors: HashMap<SocketId, Color>,
+}
+
+impl Default for SocketsGizmosSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            size: 0.12,
+            colors: HashMap::new(),
+        }
+    }
+}
+
+impl SocketsGizmosSettings {
+    /// Returns the color used to display `socket`
+    pub fn socket_color(&self, socket: SocketId) -> Color {
+        match self.colors.get(&socket) {
+            Some(color) => *color,
+            None => {
+                // Rotated sockets (upper bits) get a different color than their original socket.
+                let index = (socket as u32).wrapping_add((socket >> 32) as u32 * 7919);
+                // Golden angle hue spacing, so that consecutive sockets get distinct colors
+                let hue = ((index % 4096) as f32 * 137.508) % 360.;
+                Color::Hsla(Hsla::new(hue, 0.85, 0.55, 1.))
+            }
+        }
+    }
+}
+
+/// Component used to store the generated model variant of each generated node of a generation, for the sockets gizmos
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct GeneratedVariants(pub HashMap<NodeIndex, ModelVariantIndex>);
+
+/// System that toggles the sockets gizmos on a keypress.
+///
+/// The keybind is read from the [`ProcGenKeyBindings`] `Resource`
+pub fn toggle_sockets_gizmos_from_keybinds(
+    keys: Res<ButtonInput<KeyCode>>,
+    proc_gen_key_bindings: Res<ProcGenKeyBindings>,
+    mut gizmos_settings: ResMut<SocketsGizmosSettings>,
+) {
+    if proc_gen_key_bindings
+        .editor
+        .just_pressed(&keys, proc_gen_key_bindings.editor.toggle_sockets_gizmos)
+    {
+        gizmos_settings.enabled = !gizmos_settings.enabled;
+    }
+}
+
+/// System that tracks the [GeneratedVariants] of the generations from the [`GenerationEvent`]
+pub fn update_generated_variants<C: CartesianCoordinates>(
+    mut commands: Commands,
+    mut generation_events: EventReader<GenerationEvent>,
+    mut generations: Query<(
+        &Generator<C, CartesianGrid<C>>,
+        Option<&mut GeneratedVariants>,
+    )>,
+) {
+    for event in generation_events.read() {
+        let (gen_entity, node_index) = match event {
+            GenerationEvent::Reinitialized(gen_entity) => (*gen_entity, None),
+            GenerationEvent::Updated(gen_entity, node_index) => (*gen_entity, Some(*node_index)),
+        };
+        let Ok((generator, generated_variants)) = generations.get_mut(gen_entity) else {
+            continue;
+        };
+        let Some(mut generated_variants) = generated_variants else {
+            commands
+                .entity(gen_entity)
+                .insert(GeneratedVariants(generated_variants_of(generator)));
+            continue;
+        };
+        match node_index {
+            Some(node_index) => {
+                if let Some(variant_index) = generated_variant_of(generator, node_index) {
+                    generated_variants.insert(node_index, variant_index);
+                }
+            }
+            None => **generated_variants = generated_variants_of(generator),
+        }
+    }
+}
+
+fn generated_variants_of<C: CartesianCoordinates>(
+    generator: &Generator<C, CartesianGrid<C>>,
+) -> HashMap<NodeIndex, ModelVariantIndex> {
+    (0..generator.grid().total_size())
+        .filter_map(|node_index| {
+            generated_variant_of(generator, node_index).map(|variant| (node_index, variant))
+        })
+        .collect()
+}
+
+fn generated_variant_of<C: CartesianCoordinates>(
+    generator: &Generator<C, CartesianGrid<C>>,
+    node_index: NodeIndex,
+) -> Option<ModelVariantIndex> {
+    match generator.get_models_on(node_index).as_slice() {
+        [instance] => generator
+            .rules()
+            .variant_index(instance.model_index, instance.rotation),
+        _ => None,
+    }
+}
+
+/// System drawing the sockets of each generated node as colored gizmos on the node faces, when enabled in the [SocketsGizmosSettings]
+pub fn draw_sockets_gizmos<C: CartesianCoordinates>(
+    mut gizmos: Gizmos,
+    gizmos_settings: Res<SocketsGizmosSettings>,
+    generations: Query<(
+        &Generator<C, CartesianGrid<C>>,
+        &GeneratedVariants,
+        &DebugGridView,
+        &GlobalTransform,
+    )>,
+) {
+    if !gizmos_settings.enabled {
+        return;
+    }
+    for (generator, generated_variants, grid_view, grid_gtransform) in generations.iter() {
+        let grid = generator.grid();
+        let directions = grid.coord_system().directions();
+        let node_size = grid_view.node_size;
+        let gizmo_size = node_size * gizmos_settings.size;
+        for (node_index, variant_index) in generated_variants.iter() {
+            let Some(sockets) = generator.rules().sockets(*variant_index) else {
+                continue;
+            };
+            let pos = grid.pos_from_index(*node_index);
+            let node_center =
+                node_size * (Vec3::new(pos.x as f32, pos.y as f32, pos.z as f32) + 0.5);
+            for (direction_index, direction_sockets) in sockets.iter().enumerate() {
+                let Some(direction) = directions.get(direction_index) else {
+                    continue;
+                };
+                let normal = direction_vector(*direction);
+                // Spreads the sockets of a same face along one of its tangents
+                let tangent = match normal.x != 0. {
+                    true => Vec3::Y,
+                    false => Vec3::X,
+                };
+                let count = direction_sockets.len() as f32;
+                for (socket_rank, socket) in direction_sockets.iter().enumerate() {
+                    let spread = (socket_rank as f32 - (count - 1.) / 2.) * 1.5;
+                    let local_position =
+                        node_center + node_size * normal * 0.45 + gizmo_size * tangent * spread;
+                    gizmos.cuboid(
+                        grid_gtransform.mul_transform(
+                            Transform::from_translation(local_position).with_scale(gizmo_size),
+                        ),
+                        gizmos_settings.socket_color(*socket),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn direction_vector(direction: Direction) -> Vec3 {
+    match direction {
+        Direction::XForward => Vec3::X,
+        Direction::XBackward => Vec3::NEG_X,
+        Direction::YForward => Vec3::Y,
+        Direction::YBackward => Vec3::NEG_Y,
+        Direction::ZForward => Vec3::Z,
+        Direction::ZBackward => Vec3::NEG_Z,
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use bevy::{
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    hierarchy::BuildChildren,
+    text::{Text, TextStyle},
+    time::{Time, Timer, TimerMode},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        BackgroundColor,
+    },
+    utils::default,
+};
+use ghx_proc_gen::{
+    generator::Generator,
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        grid::Grid,
+    },
+};
+
+#[cfg(feature = "picking")]
+use bevy_mod_picking::picking_core::Pickable;
+
+use super::{generation::ActiveGeneration, GridCursorsUiSettings};
+
+/// Root marker for the generation stats panel UI
+#[derive(Component)]
+pub struct GenerationStatsPanelRoot;
+
+/// Text component marker for the generation stats panel UI
+#[derive(Component)]
+pub struct GenerationStatsPanelText;
+
+const STATS_REFRESH_INTERVAL_MS: u64 = 500;
+
+/// Resource used to track the steps rate of the [ActiveGeneration]
+#[derive(Resource)]
+pub struct GenerationStatsTracker {
+    /// Generation entity the tracked values belong to
+    pub generation: Option<Entity>,
+    /// Timer used to refresh the measured rates
+    pub refresh_timer: Timer,
+    /// Steps count of the generation at the previous refresh
+    pub previous_steps: u64,
+    /// Last measured steps per second
+    pub steps_per_second: f32,
+}
+
+impl Default for GenerationStatsTracker {
+    fn default() -> Self {
+        Self {
+            generation: None,
+            refresh_timer: Timer::new(
+                Duration::from_millis(STATS_REFRESH_INTERVAL_MS),
+                TimerMode::Repeating,
+            ),
+            previous_steps: 0,
+            steps_per_second: 0.,
+        }
+    }
+}
+
+/// Setup system used to spawn the generation stats UI panel
+pub fn setup_generation_stats_panel(mut commands: Commands, ui_config: Res<GridCursorsUiSettings>) {
+    let root = commands
+        .spawn((
+            GenerationStatsPanelRoot,
+            Name::new("GenerationStatsPanelRoot"),
+            NodeBundle {
+                background_color: BackgroundColor(ui_config.background_color),
+                style: ui_config.panel_style(ui_config.stats_panel_anchor),
+                ..default()
+            },
+        ))
+        .id();
+    let text = commands
+        .spawn((
+            GenerationStatsPanelText,
+            TextBundle {
+                text: Text::from_section(
+                    "No active generation",
+                    TextStyle {
+                        font_size: ui_config.font_size,
+                        color: ui_config.text_color,
+                        ..default()
+                    },
+                ),
+                ..default()
+            },
+        ))
+        .id();
+    commands.entity(root).add_child(text);
+
+    #[cfg(feature = "picking")]
+    commands.entity(root).insert(Pickable::IGNORE);
+    #[cfg(feature = "picking")]
+    commands.entity(text).insert(Pickable::IGNORE);
+}
+
+/// System updating the generation stats UI panel with the stats of the [ActiveGeneration]
+pub fn update_generation_stats_panel_text<C: CartesianCoordinates>(
+    time: Res<Time>,
+    active_generation: Res<ActiveGeneration>,
+    mut stats_tracker: ResMut<GenerationStatsTracker>,
+    generators: Query<&Generator<C, CartesianGrid<C>>>,
+    mut stats_panel_text: Query<&mut Text, With<GenerationStatsPanelText>>,
+) {
+    stats_tracker.refresh_timer.tick(time.delta());
+    if !stats_tracker.refresh_timer.just_finished() {
+        return;
+    }
+
+    let Some(gen_entity) = active_generation.0 else {
+        return;
+    };
+    let Ok(generator) = generators.get(gen_entity) else {
+        return;
+    };
+    let stats = generator.stats();
+
+    if stats_tracker.generation != Some(gen_entity) {
+        stats_tracker.generation = Some(gen_entity);
+        stats_tracker.steps_per_second = 0.;
+    } else {
+        let elapsed = stats_tracker.refresh_timer.duration().as_secs_f32();
+        stats_tracker.steps_per_second =
+            stats.steps.saturating_sub(stats_tracker.previous_steps) as f32 / elapsed;
+    }
+    stats_tracker.previous_steps = stats.steps;
+
+    for mut text in &mut stats_panel_text {
+        text.sections[0].value = format!(
+            "Generation {:?}\n\
+            Steps/s: {:.1}\n\
+            Steps: {}\n\
+            Bans: {}\n\
+            Contradictions: {}\n\
+            Max propagation queue: {}\n\
+            Nodes remaining: {}/{}",
+            gen_entity,
+            stats_tracker.steps_per_second,
+            stats.steps,
+            stats.bans,
+            stats.contradictions,
+            stats.max_propagation_queue_len,
+            generator.nodes_left(),
+            generator.grid().total_size(),
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::Changed,
+        system::{Commands, Query, ResMut},
+    },
+    log::warn,
+    prelude::{Deref, DerefMut},
+};
+use ghx_proc_gen::{
+    generator::{GenerationStatus, GenerationStep, Generator},
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+};
+
+use super::{GenerationControl, GenerationControlStatus};
+
+/// Component used to store the known history of a generation: the steps of its trace, as well as the steps that were undone when scrubbing backward.
+///
+/// The current position of the generation in its timeline is the length of its [`Generator::trace`].
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct GenerationTimeline(pub Vec<GenerationStep>);
+
+/// System that keeps the [GenerationTimeline] of the generations in sync with their trace, and inserts it on generations which don't have one yet.
+///
+/// The undone steps of a timeline are kept as long as the trace of the generation is a prefix of the timeline.
+pub fn update_generation_timelines<C: CartesianCoordinates>(
+    mut commands: Commands,
+    mut generations: Query<
+        (
+            Entity,
+            &Generator<C, CartesianGrid<C>>,
+            Option<&mut GenerationTimeline>,
+        ),
+        Changed<Generator<C, CartesianGrid<C>>>,
+    >,
+) {
+    for (gen_entity, generator, timeline) in generations.iter_mut() {
+        match timeline {
+            Some(mut timeline) => {
+                if !timeline.starts_with(generator.trace()) {
+                    timeline.0 = generator.trace().clone();
+                }
+            }
+            None => {
+                commands
+                    .entity(gen_entity)
+                    .insert(GenerationTimeline(generator.trace().clone()));
+            }
+        }
+    }
+}
+
+/// Moves the generation to the position `target_step` of its [GenerationTimeline], by rewinding the generator or by replaying the recorded steps.
+///
+/// Pauses the [GenerationControl] so that the generation systems do not immediately step the generation again.
+pub fn scrub_generation<C: CartesianCoordinates>(
+    generator: &mut Generator<C, CartesianGrid<C>>,
+    timeline: &GenerationTimeline,
+    generation_control: &mut ResMut<GenerationControl>,
+    target_step: usize,
+) {
+    let target_step = target_step.min(timeline.len());
+    let current_step = generator.trace().len();
+    if target_step == current_step {
+        return;
+    }
+    generation_control.status = GenerationControlStatus::Paused;
+    generation_control.need_reinit = false;
+
+    let res = if target_step < current_step {
+        generator.rewind(target_step)
+    } else {
+        let mut res = Ok(GenerationStatus::Ongoing);
+        for step in timeline[current_step..target_step].iter() {
+            res = generator.replay_step(*step);
+            if res.is_err() {
+                break;
+            }
+        }
+        res
+    };
+    match res {
+        Ok(GenerationStatus::Ongoing) => (),
+        Ok(GenerationStatus::Done) => generation_control.need_reinit = true,
+        Err(err) => {
+            warn!(
+                "Failed to scrub the generation to step {}: {}",
+                target_step, err
+            );
+            generation_control.need_reinit = true;
+        }
+    }
+}
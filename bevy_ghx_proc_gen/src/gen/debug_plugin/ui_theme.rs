@@ -0,0 +1,70 @@
+use std::any::TypeId;
+
+use bevy::{
+    ecs::system::{Res, ResMut},
+    gizmos::config::GizmoConfigStore,
+};
+use bevy_ghx_grid::debug_plugin::markers::MarkersGroup;
+
+#[cfg(feature = "picking")]
+use super::picking::OverCursorMarkerSettings;
+#[cfg(feature = "egui-edit")]
+use bevy::color::{Color, ColorToPacked};
+#[cfg(feature = "egui-edit")]
+use bevy_egui::{
+    egui::{Color32, Visuals},
+    EguiContexts,
+};
+
+use super::{cursor::SelectionCursorMarkerSettings, GridCursorsUiSettings};
+
+/// System applying the markers style of the [GridCursorsUiSettings] (markers line width and cursors colors) when it changes.
+///
+/// Cursors colors only apply to the cursors markers spawned after the change.
+pub fn apply_ui_theme_to_markers(
+    ui_config: Res<GridCursorsUiSettings>,
+    mut config_store: ResMut<GizmoConfigStore>,
+    mut selection_cursor_settings: ResMut<SelectionCursorMarkerSettings>,
+    #[cfg(feature = "picking")] over_cursor_settings: Option<ResMut<OverCursorMarkerSettings>>,
+) {
+    if !ui_config.is_changed() {
+        return;
+    }
+    if let Some(line_width) = ui_config.marker_line_width {
+        if let Some((markers_config, _)) =
+            config_store.get_config_mut_dyn(&TypeId::of::<MarkersGroup>())
+        {
+            markers_config.line_width = line_width;
+        }
+    }
+    if let Some(color) = ui_config.selection_cursor_color {
+        selection_cursor_settings.0 = color;
+    }
+    #[cfg(feature = "picking")]
+    if let (Some(color), Some(mut over_cursor_settings)) =
+        (ui_config.over_cursor_color, over_cursor_settings)
+    {
+        over_cursor_settings.0 = color;
+    }
+}
+
+/// System applying the background & text colors of the [GridCursorsUiSettings] to the egui windows when it changes, if enabled by [GridCursorsUiSettings::style_egui]
+#[cfg(feature = "egui-edit")]
+pub fn apply_ui_theme_to_egui(ui_config: Res<GridCursorsUiSettings>, mut contexts: EguiContexts) {
+    if !ui_config.is_changed() {
+        return;
+    }
+    let mut visuals = Visuals::dark();
+    if ui_config.style_egui {
+        visuals.window_fill = to_color32(ui_config.background_color);
+        visuals.panel_fill = to_color32(ui_config.background_color);
+        visuals.override_text_color = Some(to_color32(ui_config.text_color));
+    }
+    contexts.ctx_mut().set_visuals(visuals);
+}
+
+#[cfg(feature = "egui-edit")]
+fn to_color32(color: Color) -> Color32 {
+    let [r, g, b, a] = color.to_srgba().to_u8_array();
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
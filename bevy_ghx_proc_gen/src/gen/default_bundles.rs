@@ -1,19 +1,28 @@
+#[cfg(feature = "reflect")]
+use bevy::reflect::TypePath;
+use std::collections::HashMap;
+
 use bevy::{
-    asset::Handle,
-    ecs::system::EntityCommands,
-    math::{Quat, Vec3},
+    app::{App, Plugin, Update},
+    asset::{AssetId, Assets, Handle},
+    color::{Color, LinearRgba},
+    ecs::{
+        query::Added,
+        system::{EntityCommands, Local, Query, ResMut},
+    },
+    math::Vec3,
     pbr::{Material, MaterialMeshBundle, PbrBundle, StandardMaterial},
     render::{mesh::Mesh, texture::Image},
     scene::{Scene, SceneBundle},
-    sprite::SpriteBundle,
+    sprite::{Sprite, SpriteBundle},
     transform::components::Transform,
     utils::default,
 };
-use ghx_proc_gen::generator::model::ModelRotation;
+use ghx_proc_gen::generator::model::{ModelFacing, ModelRotation};
 
-use super::assets::AssetsBundleSpawner;
+use super::assets::{AssetsBundleSpawner, AssetsOrientation, TintColor};
 
-/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Z+
+/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Z+, unless the [`super::assets::AssetSpawner`] has some [`super::assets::AssetSpawner::assets_orientation`]
 impl AssetsBundleSpawner for Handle<Image> {
     fn insert_bundle(
         &self,
@@ -21,18 +30,41 @@ impl AssetsBundleSpawner for Handle<Image> {
         translation: Vec3,
         scale: Vec3,
         rotation: ModelRotation,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            ModelFacing::Up,
+            &AssetsOrientation::Z_UP,
+        );
+    }
+
+    fn assumed_orientation(&self) -> AssetsOrientation {
+        AssetsOrientation::Z_UP
+    }
+
+    fn insert_bundle_with_orientation(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+        orientation: &AssetsOrientation,
     ) {
         commands.insert(SpriteBundle {
             texture: self.clone(),
             transform: Transform::from_translation(translation)
                 .with_scale(scale)
-                .with_rotation(Quat::from_rotation_z(rotation.rad())),
+                .with_rotation(orientation.rotation(rotation, facing)),
             ..default()
         });
     }
 }
 
-/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Y+
+/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Y+, unless the [`super::assets::AssetSpawner`] has some [`super::assets::AssetSpawner::assets_orientation`]
 impl AssetsBundleSpawner for Handle<Scene> {
     fn insert_bundle(
         &self,
@@ -40,12 +72,49 @@ impl AssetsBundleSpawner for Handle<Scene> {
         translation: Vec3,
         scale: Vec3,
         rotation: ModelRotation,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            ModelFacing::Up,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_oriented_bundle(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            facing,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_bundle_with_orientation(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+        orientation: &AssetsOrientation,
     ) {
         commands.insert(SceneBundle {
             scene: self.clone(),
             transform: Transform::from_translation(translation)
                 .with_scale(scale)
-                .with_rotation(Quat::from_rotation_y(rotation.rad())),
+                .with_rotation(orientation.rotation(rotation, facing)),
             ..default()
         });
     }
@@ -53,6 +122,7 @@ impl AssetsBundleSpawner for Handle<Scene> {
 
 /// Custom type to store [`Handle`] to a [`Mesh`] asset and its [`Material`]
 #[derive(Clone)]
+#[cfg_attr(feature = "reflect", derive(TypePath))]
 pub struct MaterialMesh<M: Material> {
     /// Mesh handle
     pub mesh: Handle<Mesh>,
@@ -64,6 +134,7 @@ pub struct MaterialMesh<M: Material> {
 ///
 /// Specialization of [`MaterialMesh`] with [`StandardMaterial`]
 #[derive(Clone)]
+#[cfg_attr(feature = "reflect", derive(TypePath))]
 pub struct PbrMesh {
     /// Mesh handle
     pub mesh: Handle<Mesh>,
@@ -71,7 +142,7 @@ pub struct PbrMesh {
     pub material: Handle<StandardMaterial>,
 }
 
-/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Y+
+/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Y+, unless the [`super::assets::AssetSpawner`] has some [`super::assets::AssetSpawner::assets_orientation`]
 impl<M: Material> AssetsBundleSpawner for MaterialMesh<M> {
     fn insert_bundle(
         &self,
@@ -79,19 +150,56 @@ impl<M: Material> AssetsBundleSpawner for MaterialMesh<M> {
         translation: Vec3,
         scale: Vec3,
         rotation: ModelRotation,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            ModelFacing::Up,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_oriented_bundle(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            facing,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_bundle_with_orientation(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+        orientation: &AssetsOrientation,
     ) {
         commands.insert(MaterialMeshBundle {
             mesh: self.mesh.clone(),
             material: self.material.clone(),
             transform: Transform::from_translation(translation)
                 .with_scale(scale)
-                .with_rotation(Quat::from_rotation_y(rotation.rad())),
+                .with_rotation(orientation.rotation(rotation, facing)),
             ..default()
         });
     }
 }
 
-/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Y+
+/// **WARNING**: Assumes a specific `Rotation Axis` for the `Models`: Y+, unless the [`super::assets::AssetSpawner`] has some [`super::assets::AssetSpawner::assets_orientation`]
 impl AssetsBundleSpawner for PbrMesh {
     fn insert_bundle(
         &self,
@@ -99,14 +207,108 @@ impl AssetsBundleSpawner for PbrMesh {
         translation: Vec3,
         scale: Vec3,
         rotation: ModelRotation,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            ModelFacing::Up,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_oriented_bundle(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+    ) {
+        self.insert_bundle_with_orientation(
+            commands,
+            translation,
+            scale,
+            rotation,
+            facing,
+            &AssetsOrientation::Y_UP,
+        );
+    }
+
+    fn insert_bundle_with_orientation(
+        &self,
+        commands: &mut EntityCommands,
+        translation: Vec3,
+        scale: Vec3,
+        rotation: ModelRotation,
+        facing: ModelFacing,
+        orientation: &AssetsOrientation,
     ) {
         commands.insert(PbrBundle {
             mesh: self.mesh.clone(),
             material: self.material.clone(),
             transform: Transform::from_translation(translation)
                 .with_scale(scale)
-                .with_rotation(Quat::from_rotation_y(rotation.rad())),
+                .with_rotation(orientation.rotation(rotation, facing)),
             ..default()
         });
     }
 }
+
+/// [`Plugin`] applying the [`TintColor`] of the spawned assets (see [`super::assets::InstanceTint`]) to their [`Sprite`] or [`StandardMaterial`]
+pub struct ProcGenTintsPlugin;
+
+impl Plugin for ProcGenTintsPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "reflect")]
+        app.register_type::<TintColor>();
+        app.add_systems(Update, apply_tints);
+    }
+}
+
+/// System used by [`ProcGenTintsPlugin`] to tint the newly spawned sprites and standard materials.
+///
+/// Tinted materials are shared by all the instances with the same material and tint. Materials which are not loaded yet when their entity is spawned are not tinted.
+pub fn apply_tints(
+    mut tinted_materials: Local<
+        HashMap<(AssetId<StandardMaterial>, [u32; 4]), Handle<StandardMaterial>>,
+    >,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut tinted_sprites: Query<(&TintColor, &mut Sprite), Added<TintColor>>,
+    mut tinted_meshes: Query<(&TintColor, &mut Handle<StandardMaterial>), Added<TintColor>>,
+) {
+    for (tint, mut sprite) in tinted_sprites.iter_mut() {
+        sprite.color = tinted(sprite.color, tint.0);
+    }
+    for (tint, mut material) in tinted_meshes.iter_mut() {
+        let tint = tint.0.to_linear();
+        let key = (
+            material.id(),
+            [tint.red, tint.green, tint.blue, tint.alpha].map(f32::to_bits),
+        );
+        if let Some(tinted_material) = tinted_materials.get(&key) {
+            *material = tinted_material.clone();
+            continue;
+        }
+        let Some(base_material) = materials.get(material.id()) else {
+            continue;
+        };
+        let mut tinted_material = base_material.clone();
+        tinted_material.base_color = tinted(tinted_material.base_color, Color::LinearRgba(tint));
+        let tinted_material = materials.add(tinted_material);
+        tinted_materials.insert(key, tinted_material.clone());
+        *material = tinted_material;
+    }
+}
+
+/// Multiplies `color` by `tint`, in linear space
+fn tinted(color: Color, tint: Color) -> Color {
+    let (color, tint) = (color.to_linear(), tint.to_linear());
+    Color::LinearRgba(LinearRgba::new(
+        color.red * tint.red,
+        color.green * tint.green,
+        color.blue * tint.blue,
+        color.alpha * tint.alpha,
+    ))
+}
@@ -0,0 +1,241 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        query::Without,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query},
+    },
+    log::{info, warn},
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+use ghx_proc_gen::{
+    generator::{
+        builder::{GeneratorBuilder, DEFAULT_RETRY_COUNT},
+        model::ModelCollection,
+        node_heuristic::NodeSelectionHeuristic,
+        observer::QueuedObserver,
+        rules::{Rules, RulesBuilder},
+        socket::SocketCollection,
+        Generator, ModelSelectionHeuristic, RngMode,
+    },
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        direction::Direction,
+    },
+};
+
+/// Source of the [`Rules`] of a [`GeneratorSettings`]
+#[derive(Clone)]
+pub enum GeneratorRules<C: CartesianCoordinates> {
+    /// Rules already built, possibly shared with other generators
+    Shared(Arc<Rules<C>>),
+    /// Models and sockets from which the [`Rules`] are compiled, off the main thread, see [`RulesBuilder::new_raw`]
+    Collections {
+        /// Models of the rules
+        models: ModelCollection<C>,
+        /// Sockets used by the models
+        sockets: SocketCollection,
+        /// Rotation axis of the models
+        rotation_axis: Direction,
+    },
+}
+
+/// Component describing a [`Generator`] to be built by the [`ProcGenDeferredBuildPlugin`].
+///
+/// Once the generator is built, it is inserted on the same entity (with a [`QueuedObserver`] if [`GeneratorSettings::observed`] is set) and a [`GeneratorBuildEvent`] is sent. The settings component is left on the entity, unless the build fails, in which case it is removed.
+#[derive(Component, Clone)]
+pub struct GeneratorSettings<C: CartesianCoordinates> {
+    /// Rules of the generator
+    pub rules: GeneratorRules<C>,
+    /// Grid of the generator
+    pub grid: CartesianGrid<C>,
+    /// Seed configuration of the generator, see [`GeneratorBuilder::with_rng`]
+    pub rng_mode: RngMode,
+    /// See [`GeneratorBuilder::with_max_retry_count`]
+    pub max_retry_count: u32,
+    /// See [`GeneratorBuilder::with_node_heuristic`]
+    pub node_heuristic: NodeSelectionHeuristic,
+    /// See [`GeneratorBuilder::with_model_heuristic`]
+    pub model_heuristic: ModelSelectionHeuristic,
+    /// Whether or not a [`QueuedObserver`] is inserted with the generator. Required by the [`super::debug_plugin::ProcGenDebugPlugin`]
+    pub observed: bool,
+}
+
+impl<C: CartesianCoordinates> GeneratorSettings<C> {
+    /// Creates settings for a generator using `rules` on `grid`, with the other values set to the defaults of [`GeneratorBuilder`]
+    pub fn new(rules: GeneratorRules<C>, grid: CartesianGrid<C>) -> Self {
+        Self {
+            rules,
+            grid,
+            rng_mode: RngMode::RandomSeed,
+            max_retry_count: DEFAULT_RETRY_COUNT,
+            node_heuristic: NodeSelectionHeuristic::MinimumRemainingValue,
+            model_heuristic: ModelSelectionHeuristic::WeightedProbability,
+            observed: false,
+        }
+    }
+
+    /// Sets the [`RngMode`] of the generator
+    pub fn with_rng(mut self, rng_mode: RngMode) -> Self {
+        self.rng_mode = rng_mode;
+        self
+    }
+
+    /// Sets the max retry count of the generator
+    pub fn with_max_retry_count(mut self, max_retry_count: u32) -> Self {
+        self.max_retry_count = max_retry_count;
+        self
+    }
+
+    /// Sets the [`NodeSelectionHeuristic`] of the generator
+    pub fn with_node_heuristic(mut self, heuristic: NodeSelectionHeuristic) -> Self {
+        self.node_heuristic = heuristic;
+        self
+    }
+
+    /// Sets the [`ModelSelectionHeuristic`] of the generator
+    pub fn with_model_heuristic(mut self, heuristic: ModelSelectionHeuristic) -> Self {
+        self.model_heuristic = heuristic;
+        self
+    }
+
+    /// Inserts a [`QueuedObserver`] with the generator once built
+    pub fn with_queued_observer(mut self) -> Self {
+        self.observed = true;
+        self
+    }
+
+    fn build(self) -> Result<(Generator<C, CartesianGrid<C>>, Option<QueuedObserver>), String> {
+        let rules = match self.rules {
+            GeneratorRules::Shared(rules) => rules,
+            GeneratorRules::Collections {
+                models,
+                sockets,
+                rotation_axis,
+            } => Arc::new(
+                RulesBuilder::new_raw(
+                    models,
+                    sockets,
+                    rotation_axis,
+                    self.grid.coord_system().clone(),
+                )
+                .build()
+                .map_err(|err| err.to_string())?,
+            ),
+        };
+        let mut builder = GeneratorBuilder::new()
+            .with_shared_rules(rules)
+            .with_grid(self.grid)
+            .with_rng(self.rng_mode)
+            .with_max_retry_count(self.max_retry_count)
+            .with_node_heuristic(self.node_heuristic)
+            .with_model_heuristic(self.model_heuristic);
+        let observer = match self.observed {
+            true => Some(builder.add_queued_observer()),
+            false => None,
+        };
+        let generator = builder.build().map_err(|err| err.to_string())?;
+        Ok((generator, observer))
+    }
+}
+
+/// Component holding the task building the [`Generator`] of a [`GeneratorSettings`]
+#[derive(Component)]
+pub struct GeneratorBuildTask<C: CartesianCoordinates>(
+    Task<Result<(Generator<C, CartesianGrid<C>>, Option<QueuedObserver>), String>>,
+);
+
+/// Event sent by the [`ProcGenDeferredBuildPlugin`] when the build of a [`Generator`] ends
+#[derive(Event, Clone, Copy, Debug)]
+pub enum GeneratorBuildEvent {
+    /// The [`Generator`] of this entity was built and inserted
+    Ready(Entity),
+    /// The [`Generator`] of this entity failed to build, the error was logged
+    Failed(Entity),
+}
+
+/// [`Plugin`] building [`Generator`]s from [`GeneratorSettings`] components on the [`AsyncComputeTaskPool`], since compiling the rules and initializing the generator can be slow for big rules and grids.
+pub struct ProcGenDeferredBuildPlugin<C: CartesianCoordinates> {
+    typestate: PhantomData<C>,
+}
+
+impl<C: CartesianCoordinates> ProcGenDeferredBuildPlugin<C> {
+    /// Constructor
+    pub fn new() -> Self {
+        Self {
+            typestate: PhantomData,
+        }
+    }
+}
+
+impl<C: CartesianCoordinates> Plugin for ProcGenDeferredBuildPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GeneratorBuildEvent>().add_systems(
+            Update,
+            (
+                spawn_generator_build_tasks::<C>,
+                poll_generator_build_tasks::<C>,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// System used by [`ProcGenDeferredBuildPlugin`] to start a build task for each entity with [`GeneratorSettings`] and no [`Generator`] yet
+pub fn spawn_generator_build_tasks<C: CartesianCoordinates>(
+    mut commands: Commands,
+    new_settings: Query<
+        (Entity, &GeneratorSettings<C>),
+        (
+            Without<Generator<C, CartesianGrid<C>>>,
+            Without<GeneratorBuildTask<C>>,
+        ),
+    >,
+) {
+    let task_pool = AsyncComputeTaskPool::get();
+    for (gen_entity, settings) in new_settings.iter() {
+        let settings = settings.clone();
+        let task = task_pool.spawn(async move { settings.build() });
+        commands.entity(gen_entity).insert(GeneratorBuildTask(task));
+    }
+}
+
+/// System used by [`ProcGenDeferredBuildPlugin`] to insert the [`Generator`]s whose build task is finished
+pub fn poll_generator_build_tasks<C: CartesianCoordinates>(
+    mut commands: Commands,
+    mut build_events: EventWriter<GeneratorBuildEvent>,
+    mut build_tasks: Query<(Entity, &mut GeneratorBuildTask<C>)>,
+) {
+    for (gen_entity, mut task) in build_tasks.iter_mut() {
+        let Some(result) = block_on(future::poll_once(&mut task.0)) else {
+            continue;
+        };
+        let mut entity_commands = commands.entity(gen_entity);
+        entity_commands.remove::<GeneratorBuildTask<C>>();
+        match result {
+            Ok((generator, observer)) => {
+                info!(
+                    "Generator {:?} built, seed: {}; grid: {}",
+                    gen_entity,
+                    generator.seed(),
+                    generator.grid()
+                );
+                entity_commands.insert(generator);
+                if let Some(observer) = observer {
+                    entity_commands.insert(observer);
+                }
+                build_events.send(GeneratorBuildEvent::Ready(gen_entity));
+            }
+            Err(err) => {
+                warn!("Failed to build generator {:?}: {}", gen_entity, err);
+                entity_commands.remove::<GeneratorSettings<C>>();
+                build_events.send(GeneratorBuildEvent::Failed(gen_entity));
+            }
+        }
+    }
+}
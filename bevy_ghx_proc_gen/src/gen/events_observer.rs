@@ -0,0 +1,112 @@
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        system::{Commands, Query},
+    },
+};
+use ghx_proc_gen::{
+    generator::{
+        model::ModelInstance,
+        observer::{GenerationUpdate, QueuedObserver},
+        Generator,
+    },
+    ghx_grid::{coordinate_system::CoordinateSystem, grid::Grid},
+    NodeIndex,
+};
+
+/// Component observing the [`Generator`] of its entity. Its [`GenerationUpdate`]s are translated by [`emit_generation_events`] into [`NodeGenerated`], [`GenerationFailedAt`] and [`GenerationReinitialized`] events.
+///
+/// Each event is both sent as a buffered event (to be read with an `EventReader`) and triggered on the generation entity (to be received by Bevy observers, such as `commands.entity(gen_entity).observe(...)`).
+#[derive(Component)]
+pub struct GenerationEventsObserver(QueuedObserver);
+
+impl GenerationEventsObserver {
+    /// Creates a new [`GenerationEventsObserver`] for a given [`Generator`]
+    pub fn new<C: CoordinateSystem, G: Grid<C>>(generator: &mut Generator<C, G>) -> Self {
+        Self(QueuedObserver::new(generator))
+    }
+}
+
+/// A node of a generation was generated
+#[derive(Event, Clone, Copy, Debug)]
+pub struct NodeGenerated {
+    /// Entity of the generation
+    pub gen_entity: Entity,
+    /// Index of the node in the grid
+    pub node_index: NodeIndex,
+    /// Model generated on the node
+    pub model_instance: ModelInstance,
+}
+
+/// A generation failed due to a contradiction
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GenerationFailedAt {
+    /// Entity of the generation
+    pub gen_entity: Entity,
+    /// Index of the node where the contradiction occured
+    pub node_index: NodeIndex,
+}
+
+/// A generation was reinitialized to its initial state
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GenerationReinitialized {
+    /// Entity of the generation
+    pub gen_entity: Entity,
+    /// Seed of the new generation
+    pub seed: u64,
+}
+
+/// [`Plugin`] registering the typed generation events and the [`emit_generation_events`] system.
+///
+/// Generations opt in by inserting a [`GenerationEventsObserver`] next to their [`Generator`].
+pub struct ProcGenEventsPlugin;
+
+impl Plugin for ProcGenEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NodeGenerated>()
+            .add_event::<GenerationFailedAt>()
+            .add_event::<GenerationReinitialized>()
+            .add_systems(PostUpdate, emit_generation_events);
+    }
+}
+
+/// System used by [`ProcGenEventsPlugin`] to dequeue the updates of every [`GenerationEventsObserver`], and send and trigger their typed events
+pub fn emit_generation_events(
+    mut commands: Commands,
+    mut observers: Query<(Entity, &mut GenerationEventsObserver)>,
+    mut generated_events: EventWriter<NodeGenerated>,
+    mut failed_events: EventWriter<GenerationFailedAt>,
+    mut reinitialized_events: EventWriter<GenerationReinitialized>,
+) {
+    for (gen_entity, mut observer) in observers.iter_mut() {
+        for update in observer.0.dequeue_all() {
+            match update {
+                GenerationUpdate::Generated(node) => {
+                    let event = NodeGenerated {
+                        gen_entity,
+                        node_index: node.node_index,
+                        model_instance: node.model_instance,
+                    };
+                    generated_events.send(event);
+                    commands.trigger_targets(event, gen_entity);
+                }
+                GenerationUpdate::Failed(node_index) => {
+                    let event = GenerationFailedAt {
+                        gen_entity,
+                        node_index,
+                    };
+                    failed_events.send(event);
+                    commands.trigger_targets(event, gen_entity);
+                }
+                GenerationUpdate::Reinitializing(seed) => {
+                    let event = GenerationReinitialized { gen_entity, seed };
+                    reinitialized_events.send(event);
+                    commands.trigger_targets(event, gen_entity);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        system::Query,
+    },
+    log::warn,
+};
+use ghx_proc_gen::{
+    generator::{seam::seam_bans, Generator},
+    ghx_grid::{
+        cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+        direction::Direction,
+    },
+};
+
+/// Component linking a generation entity to an adjacent generation entity, so that both agree at their seam.
+///
+/// Each time the generation holding this component is done, its border nodes on the `face` side are pushed as initial bans into the `target` generator (see [`seam_bans`]), which is then reinitialized. Chained links (`A -> B -> C`) are regenerated in order.
+///
+/// Both generators should use the same rules, and their grids should have the same size on the axis parallel to the seam.
+///
+/// **Note**: the initial bans of the target generator are replaced by the seam bans.
+#[derive(Component, Clone, Debug)]
+pub struct LinkedGeneration {
+    /// Generation entity adjacent to this one
+    pub target: Entity,
+    /// Face of this generation grid touching the grid of `target`
+    pub face: Direction,
+    /// Seed of the last generation whose border was pushed into `target`
+    pushed_seed: Option<u64>,
+}
+
+impl LinkedGeneration {
+    /// Links to the `target` generation entity, whose grid touches the `face` side of this generation grid
+    pub fn new(target: Entity, face: Direction) -> Self {
+        Self {
+            target,
+            face,
+            pushed_seed: None,
+        }
+    }
+}
+
+/// Event sent by the [`ProcGenLinkedGenerationsPlugin`] when the seam constraints of a generation entity were updated and its generator reinitialized.
+///
+/// The [`super::simple_plugin::ProcGenSimplePlugin`] reacts to it by despawning the nodes of the generation and generating it again.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SeamConstraintsUpdatedEvent(pub Entity);
+
+/// [`Plugin`] pushing the border nodes of the generations with a [`LinkedGeneration`] into their target generations
+pub struct ProcGenLinkedGenerationsPlugin<C: CartesianCoordinates> {
+    typestate: PhantomData<C>,
+}
+
+impl<C: CartesianCoordinates> ProcGenLinkedGenerationsPlugin<C> {
+    /// Constructor
+    pub fn new() -> Self {
+        Self {
+            typestate: PhantomData,
+        }
+    }
+}
+
+impl<C: CartesianCoordinates> Plugin for ProcGenLinkedGenerationsPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SeamConstraintsUpdatedEvent>()
+            .add_systems(Update, push_seam_constraints::<C>);
+    }
+}
+
+/// System used by [`ProcGenLinkedGenerationsPlugin`] to push the seam constraints of the done generations into their linked generations
+pub fn push_seam_constraints<C: CartesianCoordinates>(
+    mut updated_events: EventWriter<SeamConstraintsUpdatedEvent>,
+    mut links: Query<(Entity, &mut LinkedGeneration)>,
+    mut generators: Query<&mut Generator<C, CartesianGrid<C>>>,
+) {
+    for (source_entity, mut link) in links.iter_mut() {
+        let Ok(source) = generators.get(source_entity) else {
+            continue;
+        };
+        if source.nodes_left() != 0 || link.pushed_seed == Some(source.seed()) {
+            continue;
+        }
+        link.pushed_seed = Some(source.seed());
+
+        let Ok([source, mut target]) = generators.get_many_mut([source_entity, link.target]) else {
+            warn!(
+                "Linked generation {:?} of {:?} has no generator",
+                link.target, source_entity
+            );
+            continue;
+        };
+        let Some(bans) = seam_bans(&source, link.face, target.grid()) else {
+            warn!(
+                "Grids of {:?} and {:?} do not match along their seam",
+                source_entity, link.target
+            );
+            continue;
+        };
+        if let Err(err) = target.set_initial_bans(bans) {
+            warn!("Failed to set the seam bans of {:?}: {}", link.target, err);
+            continue;
+        }
+        target.reinitialize();
+        updated_events.send(SeamConstraintsUpdatedEvent(link.target));
+    }
+}
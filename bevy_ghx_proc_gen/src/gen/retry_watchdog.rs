@@ -0,0 +1,82 @@
+use bevy::ecs::{component::Component, entity::Entity, event::Event};
+use ghx_proc_gen::GeneratorError;
+
+/// Opt-in component limiting how many times the [`super::simple_plugin::ProcGenSimplePlugin`] runs a failing generation. It is ignored by the [`super::debug_plugin::ProcGenDebugPlugin`].
+///
+/// Without it, a failing generation is run again each frame, forever. With it, after each failed attempt, the generation waits for a number of frames that doubles at each attempt (starting at `backoff_frames`), then runs again with the next seed. Once `max_attempts` attempts failed, a [`GenerationAbandonedEvent`] is sent and the generation is not run anymore, until it is restarted.
+///
+/// **Note**: an attempt is a call to [`ghx_proc_gen::generator::Generator::generate_grid`], which can already retry the generation internally, see [`ghx_proc_gen::generator::Generator::max_retry_count`].
+#[derive(Component, Clone, Debug)]
+pub struct RetryWatchdog {
+    /// Maximum number of failed attempts before the generation is abandoned
+    pub max_attempts: u32,
+    /// Number of frames waited after the first failed attempt. Doubled after each failed attempt
+    pub backoff_frames: u32,
+    /// Failed attempts since the last success
+    attempts: u32,
+    /// Frames left to wait before the next attempt
+    frames_to_wait: u32,
+}
+
+impl RetryWatchdog {
+    /// Creates a [`RetryWatchdog`] abandoning a generation after `max_attempts` failed attempts, with a backoff of 1 frame
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            backoff_frames: 1,
+            attempts: 0,
+            frames_to_wait: 0,
+        }
+    }
+
+    /// Sets the number of frames waited after the first failed attempt
+    pub fn with_backoff_frames(mut self, backoff_frames: u32) -> Self {
+        self.backoff_frames = backoff_frames;
+        self
+    }
+
+    /// Returns how many attempts failed since the last success
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns whether or not the generation can be attempted this frame. Counts down the backoff frames
+    pub fn tick(&mut self) -> bool {
+        match self.frames_to_wait {
+            0 => true,
+            _ => {
+                self.frames_to_wait -= 1;
+                false
+            }
+        }
+    }
+
+    /// Records a failed attempt. Returns `true` if the generation should be abandoned
+    pub fn record_failure(&mut self) -> bool {
+        self.attempts += 1;
+        if self.attempts >= self.max_attempts {
+            return true;
+        }
+        self.frames_to_wait = self
+            .backoff_frames
+            .saturating_mul(2u32.saturating_pow(self.attempts - 1));
+        false
+    }
+
+    /// Resets the failed attempts, after a success or a restart of the generation
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+        self.frames_to_wait = 0;
+    }
+}
+
+/// Event sent when a generation with a [`RetryWatchdog`] failed all of its attempts
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GenerationAbandonedEvent {
+    /// Generation entity
+    pub generation: Entity,
+    /// Number of failed attempts
+    pub attempts: u32,
+    /// Error of the last attempt
+    pub error: GeneratorError,
+}
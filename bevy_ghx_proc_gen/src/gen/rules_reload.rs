@@ -0,0 +1,108 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::{Asset, AssetApp, AssetEvent, Assets, Handle},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        system::{Query, Res},
+    },
+    log::{info, warn},
+};
+use ghx_proc_gen::{
+    generator::{rules::Rules, Generator},
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    RulesBuilderError,
+};
+
+/// An [`Asset`] from which [`Rules`] can be built, such as a rules description file read by an asset loader.
+pub trait RulesAsset<C: CartesianCoordinates>: Asset {
+    /// Builds new [`Rules`] from the current content of the asset
+    fn build_rules(&self) -> Result<Rules<C>, RulesBuilderError>;
+}
+
+/// Component linking a generation entity to the [`RulesAsset`] its [`Generator`] rules were built from.
+///
+/// When the asset is modified, the rules are rebuilt and swapped into the [`Generator`] by [`reload_modified_rules`].
+#[derive(Component, Clone, Debug)]
+pub struct RulesSource<A: Asset>(pub Handle<A>);
+
+/// Event sent by [`reload_modified_rules`] once the [`Generator`] of a generation entity uses new [`Rules`] and was reinitialized
+#[derive(Event, Clone, Copy, Debug)]
+pub struct RulesReloadedEvent(pub Entity);
+
+/// [`Plugin`] reloading the [`Rules`] of the generators with a [`RulesSource`] when their [`RulesAsset`] is modified.
+///
+/// Modified events are only sent by Bevy when its asset hot-reloading is enabled (`file_watcher` feature and [`bevy::asset::AssetPlugin::watch_for_changes_override`]).
+/// The [`super::simple_plugin::ProcGenSimplePlugin`] and [`super::debug_plugin::ProcGenDebugPlugin`] restart the generations from their new rules.
+pub struct ProcGenRulesReloadPlugin<C: CartesianCoordinates, A: RulesAsset<C>> {
+    typestate: PhantomData<(C, A)>,
+}
+
+impl<C: CartesianCoordinates, A: RulesAsset<C>> ProcGenRulesReloadPlugin<C, A> {
+    /// Constructor
+    pub fn new() -> Self {
+        Self {
+            typestate: PhantomData,
+        }
+    }
+}
+
+impl<C: CartesianCoordinates, A: RulesAsset<C>> Plugin for ProcGenRulesReloadPlugin<C, A> {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<A>()
+            .add_event::<RulesReloadedEvent>()
+            .add_systems(Update, reload_modified_rules::<C, A>);
+    }
+}
+
+/// System used by [`ProcGenRulesReloadPlugin`] to rebuild the [`Rules`] of the generations whose [`RulesAsset`] was modified, and swap them into their [`Generator`].
+///
+/// The generators are reinitialized with their current seed, see [`Generator::set_rules`]. If the new rules cannot be built, the generator keeps its current rules.
+pub fn reload_modified_rules<C: CartesianCoordinates, A: RulesAsset<C>>(
+    mut asset_events: EventReader<AssetEvent<A>>,
+    mut reloaded_events: EventWriter<RulesReloadedEvent>,
+    rules_assets: Res<Assets<A>>,
+    mut generations: Query<(Entity, &mut Generator<C, CartesianGrid<C>>, &RulesSource<A>)>,
+) {
+    for event in asset_events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        let Some(rules_asset) = rules_assets.get(*id) else {
+            continue;
+        };
+        let mut rules = None;
+        for (gen_entity, mut generator, source) in generations.iter_mut() {
+            if source.0.id() != *id {
+                continue;
+            }
+            if rules.is_none() {
+                rules = match rules_asset.build_rules() {
+                    Ok(new_rules) => Some(Arc::new(new_rules)),
+                    Err(err) => {
+                        warn!("Failed to rebuild the modified rules {:?}: {}", id, err);
+                        break;
+                    }
+                };
+            }
+            let Some(rules) = &rules else {
+                break;
+            };
+            match generator.set_rules(Arc::clone(rules)) {
+                Ok(_) => info!(
+                    "Reloaded rules of generation {:?}, seed: {}",
+                    gen_entity,
+                    generator.seed()
+                ),
+                Err(err) => warn!(
+                    "Reloaded rules of generation {:?}, but its initial nodes failed to generate: {}",
+                    gen_entity, err
+                ),
+            }
+            reloaded_events.send(RulesReloadedEvent(gen_entity));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        system::{Commands, Query},
+    },
+    log::{info, warn},
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+#[cfg(feature = "debug-plugin")]
+use bevy::{
+    color::Color,
+    core::Name,
+    ecs::{query::Added, schedule::IntoSystemConfigs},
+    hierarchy::BuildChildren,
+    text::{Text, TextStyle},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        BackgroundColor, FlexDirection, PositionType, Style, UiRect, Val,
+    },
+    utils::default,
+};
+use ghx_proc_gen::{
+    generator::{
+        analysis::{analyze_seeds, SeedsAnalysis},
+        builder::{GeneratorBuilder, Set},
+    },
+    ghx_grid::cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    GeneratorBuilderError,
+};
+
+/// Component holding the task running a [`SeedsAnalysis`], see [`spawn_seeds_analysis`]
+#[derive(Component)]
+pub struct SeedsAnalysisTask(Task<Result<SeedsAnalysis, GeneratorBuilderError>>);
+
+/// Component holding the result of a finished [`SeedsAnalysisTask`]
+#[derive(Component, Clone, Debug)]
+pub struct SeedsAnalysisReport(pub SeedsAnalysis);
+
+/// Event sent by the [`ProcGenSeedsAnalysisPlugin`] when a [`SeedsAnalysisReport`] is inserted on the specified entity
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SeedsAnalysisDoneEvent(pub Entity);
+
+/// Spawns an entity running [`analyze_seeds`] for `builder` and `seeds` on the [`AsyncComputeTaskPool`], and returns it.
+///
+/// Once the analysis is done, the [`ProcGenSeedsAnalysisPlugin`] replaces its [`SeedsAnalysisTask`] by a [`SeedsAnalysisReport`].
+pub fn spawn_seeds_analysis<C: CartesianCoordinates>(
+    commands: &mut Commands,
+    builder: GeneratorBuilder<Set, Set, C, CartesianGrid<C>>,
+    seeds: Vec<u64>,
+) -> Entity {
+    let task = AsyncComputeTaskPool::get().spawn(async move { analyze_seeds(&builder, seeds) });
+    commands.spawn(SeedsAnalysisTask(task)).id()
+}
+
+/// [`Plugin`] running the [`SeedsAnalysisTask`]s spawned by [`spawn_seeds_analysis`], without needing any rendering.
+///
+/// The summary of each analysis is logged. With the `debug-plugin` feature, [`ProcGenSeedsAnalysisPlugin::with_ui_table`] also displays the reports in a UI table.
+pub struct ProcGenSeedsAnalysisPlugin {
+    #[cfg(feature = "debug-plugin")]
+    ui_table: bool,
+}
+
+impl ProcGenSeedsAnalysisPlugin {
+    /// Constructor
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "debug-plugin")]
+            ui_table: false,
+        }
+    }
+
+    /// Displays each [`SeedsAnalysisReport`] in a UI table
+    #[cfg(feature = "debug-plugin")]
+    pub fn with_ui_table(mut self) -> Self {
+        self.ui_table = true;
+        self
+    }
+}
+
+impl Plugin for ProcGenSeedsAnalysisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SeedsAnalysisDoneEvent>()
+            .add_systems(Update, poll_seeds_analysis_tasks);
+        #[cfg(feature = "debug-plugin")]
+        if self.ui_table {
+            app.add_systems(
+                Update,
+                spawn_seeds_analysis_tables.after(poll_seeds_analysis_tasks),
+            );
+        }
+    }
+}
+
+/// System used by [`ProcGenSeedsAnalysisPlugin`] to insert the [`SeedsAnalysisReport`]s of the finished [`SeedsAnalysisTask`]s
+pub fn poll_seeds_analysis_tasks(
+    mut commands: Commands,
+    mut done_events: EventWriter<SeedsAnalysisDoneEvent>,
+    mut analysis_tasks: Query<(Entity, &mut SeedsAnalysisTask)>,
+) {
+    for (entity, mut task) in analysis_tasks.iter_mut() {
+        let Some(result) = block_on(future::poll_once(&mut task.0)) else {
+            continue;
+        };
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<SeedsAnalysisTask>();
+        match result {
+            Ok(analysis) => {
+                info!("Seeds analysis {:?} done: {}", entity, analysis);
+                entity_commands.insert(SeedsAnalysisReport(analysis));
+                done_events.send(SeedsAnalysisDoneEvent(entity));
+            }
+            Err(err) => warn!("Failed to run seeds analysis {:?}: {}", entity, err),
+        }
+    }
+}
+
+/// Maximum number of failed seeds listed in a seeds analysis UI table
+#[cfg(feature = "debug-plugin")]
+pub const SEEDS_ANALYSIS_TABLE_MAX_FAILURES: usize = 10;
+
+/// System used by [`ProcGenSeedsAnalysisPlugin`] to spawn a UI table for each new [`SeedsAnalysisReport`]
+#[cfg(feature = "debug-plugin")]
+pub fn spawn_seeds_analysis_tables(
+    mut commands: Commands,
+    new_reports: Query<(Entity, &SeedsAnalysisReport), Added<SeedsAnalysisReport>>,
+) {
+    let text_style = TextStyle {
+        font_size: 14.,
+        color: Color::WHITE,
+        ..default()
+    };
+    for (entity, report) in new_reports.iter() {
+        let analysis = &report.0;
+        let mut rows = vec![
+            format!("Seeds analysis {:?}", entity),
+            format!(
+                "Success rate: {}/{} ({:.1}%)",
+                analysis.successes_count(),
+                analysis.runs_count(),
+                100. * analysis.success_rate()
+            ),
+            format!("Average try count: {:.2}", analysis.average_try_count()),
+            format!("Average duration: {:?}", analysis.average_duration()),
+            format!("Total duration: {:?}", analysis.total_duration()),
+        ];
+        let failed_seeds = analysis.failed_seeds();
+        for seed in failed_seeds.iter().take(SEEDS_ANALYSIS_TABLE_MAX_FAILURES) {
+            rows.push(format!("Failed seed: {}", seed));
+        }
+        if failed_seeds.len() > SEEDS_ANALYSIS_TABLE_MAX_FAILURES {
+            rows.push(format!(
+                "... and {} more failed seeds",
+                failed_seeds.len() - SEEDS_ANALYSIS_TABLE_MAX_FAILURES
+            ));
+        }
+
+        let table = commands
+            .spawn((
+                Name::new("SeedsAnalysisTable"),
+                NodeBundle {
+                    background_color: BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        flex_direction: FlexDirection::Column,
+                        bottom: Val::Percent(1.),
+                        left: Val::Percent(1.),
+                        padding: UiRect::all(Val::Px(6.)),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .id();
+        for row in rows {
+            let text = commands
+                .spawn(TextBundle {
+                    text: Text::from_section(row, text_style.clone()),
+                    ..default()
+                })
+                .id();
+            commands.entity(table).add_child(text);
+        }
+    }
+}
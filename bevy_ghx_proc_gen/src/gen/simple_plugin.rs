@@ -1,13 +1,16 @@
 use std::marker::PhantomData;
 
 use bevy::{
-    app::{App, Plugin, Update},
+    app::{App, Plugin, PostUpdate, Update},
     ecs::{
         entity::Entity,
-        query::Added,
+        event::{EventReader, EventWriter},
+        query::{Added, Or, With},
+        removal_detection::RemovedComponents,
         schedule::IntoSystemConfigs,
         system::{Commands, Query, ResMut, Resource},
     },
+    hierarchy::{Children, DespawnRecursiveExt, Parent},
     log::{info, warn},
     utils::HashSet,
 };
@@ -17,13 +20,20 @@ use ghx_proc_gen::{
     GeneratorError,
 };
 
-use crate::gen::spawn_node;
+#[cfg(feature = "rules-hot-reload")]
+use crate::gen::rules_reload::RulesReloadedEvent;
+use crate::gen::{
+    group_spawned_nodes_into_layers,
+    linked_generations::SeamConstraintsUpdatedEvent,
+    retry_watchdog::{GenerationAbandonedEvent, RetryWatchdog},
+    spawn_node, GridNode, NodeLayer,
+};
 
 use super::{assets::NoComponents, AssetSpawner, AssetsBundleSpawner, ComponentSpawner};
 
 /// A simple [`Plugin`] that automatically detects any [`Entity`] with a [`Generator`] `Component` and tries to run the contained generator once per frame until it succeeds.
 ///
-/// Once the generation is successful, the plugin will spawn the generated nodes assets.
+/// Once the generation is successful, the plugin will spawn the generated nodes assets. The retries of a failing generation can be limited with a [`RetryWatchdog`].
 pub struct ProcGenSimplePlugin<
     C: CartesianCoordinates,
     A: AssetsBundleSpawner,
@@ -37,9 +47,26 @@ impl<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner> Plugi
 {
     fn build(&self, app: &mut App) {
         app.insert_resource(PendingGenerations::default());
-        app.add_systems(
+        #[cfg(feature = "reflect")]
+        app.register_type::<crate::gen::GridNode>()
+            .register_type::<NodeLayer>();
+        app.add_event::<SeamConstraintsUpdatedEvent>()
+            .add_event::<GenerationAbandonedEvent>()
+            .add_systems(
+                Update,
+                (
+                    register_new_generations::<C>,
+                    cleanup_removed_generations::<C>,
+                    restart_linked_generations,
+                    generate_and_spawn::<C, A, T>,
+                )
+                    .chain(),
+            )
+            .add_systems(PostUpdate, group_spawned_nodes_into_layers::<C, A, T>);
+        #[cfg(feature = "rules-hot-reload")]
+        app.add_event::<RulesReloadedEvent>().add_systems(
             Update,
-            (register_new_generations::<C>, generate_and_spawn::<C, A, T>).chain(),
+            restart_reloaded_generations.before(generate_and_spawn::<C, A, T>),
         );
     }
 }
@@ -79,15 +106,108 @@ pub fn register_new_generations<C: CartesianCoordinates>(
     }
 }
 
+/// System used by [`ProcGenSimplePlugin`] to forget the generations whose [`Generator`] component was removed (or whose entity was despawned), and to despawn their spawned nodes, which would otherwise be left orphaned
+pub fn cleanup_removed_generations<C: CartesianCoordinates>(
+    mut commands: Commands,
+    mut removed_generators: RemovedComponents<Generator<C, CartesianGrid<C>>>,
+    mut pending_generations: ResMut<PendingGenerations>,
+    spawned_nodes: Query<(Entity, &Parent), Or<(With<GridNode>, With<NodeLayer>)>>,
+) {
+    let removed: HashSet<Entity> = removed_generators.read().collect();
+    if removed.is_empty() {
+        return;
+    }
+    for gen_entity in removed.iter() {
+        pending_generations.pendings.remove(gen_entity);
+    }
+    // Children of a generation despawned without `despawn_recursive` keep a dangling `Parent`
+    for (node, parent) in spawned_nodes.iter() {
+        if removed.contains(&parent.get()) {
+            commands.entity(node).despawn_recursive();
+        }
+    }
+}
+
+/// System used by [`ProcGenSimplePlugin`] to despawn the nodes of the generations whose rules were reloaded, and to generate them again
+#[cfg(feature = "rules-hot-reload")]
+pub fn restart_reloaded_generations(
+    mut commands: Commands,
+    mut reloaded_events: EventReader<RulesReloadedEvent>,
+    mut pending_generations: ResMut<PendingGenerations>,
+    generations: Query<&Children>,
+    existing_nodes: Query<Entity, Or<(With<GridNode>, With<NodeLayer>)>>,
+) {
+    for RulesReloadedEvent(gen_entity) in reloaded_events.read() {
+        restart_generation(
+            &mut commands,
+            &mut pending_generations,
+            &generations,
+            &existing_nodes,
+            *gen_entity,
+        );
+    }
+}
+
+/// System used by [`ProcGenSimplePlugin`] to despawn the nodes of the generations whose seam constraints were updated (see [`crate::gen::linked_generations::LinkedGeneration`]), and to generate them again
+pub fn restart_linked_generations(
+    mut commands: Commands,
+    mut updated_events: EventReader<SeamConstraintsUpdatedEvent>,
+    mut pending_generations: ResMut<PendingGenerations>,
+    generations: Query<&Children>,
+    existing_nodes: Query<Entity, Or<(With<GridNode>, With<NodeLayer>)>>,
+) {
+    for SeamConstraintsUpdatedEvent(gen_entity) in updated_events.read() {
+        restart_generation(
+            &mut commands,
+            &mut pending_generations,
+            &generations,
+            &existing_nodes,
+            *gen_entity,
+        );
+    }
+}
+
+/// Despawns the nodes (and node layers) of `gen_entity` and marks it as pending
+fn restart_generation(
+    commands: &mut Commands,
+    pending_generations: &mut PendingGenerations,
+    generations: &Query<&Children>,
+    existing_nodes: &Query<Entity, Or<(With<GridNode>, With<NodeLayer>)>>,
+    gen_entity: Entity,
+) {
+    if let Ok(children) = generations.get(gen_entity) {
+        for &child in children.iter() {
+            if let Ok(node) = existing_nodes.get(child) {
+                commands.entity(node).despawn_recursive();
+            }
+        }
+    }
+    pending_generations.pendings.insert(gen_entity);
+}
+
 /// System used by [`ProcGenSimplePlugin`] to run generators and spawn their node's assets
 pub fn generate_and_spawn<C: CartesianCoordinates, A: AssetsBundleSpawner, T: ComponentSpawner>(
     mut commands: Commands,
     mut pending_generations: ResMut<PendingGenerations>,
-    mut generations: Query<(&mut Generator<C, CartesianGrid<C>>, &AssetSpawner<A, T>)>,
+    mut abandoned_events: EventWriter<GenerationAbandonedEvent>,
+    mut generations: Query<(
+        &mut Generator<C, CartesianGrid<C>>,
+        &AssetSpawner<A, T>,
+        Option<&mut RetryWatchdog>,
+    )>,
 ) {
     let mut generations_done = vec![];
     for &gen_entity in pending_generations.pendings.iter() {
-        if let Ok((mut generation, asset_spawner)) = generations.get_mut(gen_entity) {
+        if let Ok((mut generation, asset_spawner, mut watchdog)) = generations.get_mut(gen_entity) {
+            if let Some(watchdog) = watchdog.as_mut() {
+                // An abandoned generation is only pending again if it was restarted
+                if watchdog.attempts() >= watchdog.max_attempts {
+                    watchdog.reset();
+                }
+                if !watchdog.tick() {
+                    continue;
+                }
+            }
             match generation.generate_grid() {
                 Ok((gen_info, grid_data)) => {
                     info!(
@@ -107,9 +227,12 @@ pub fn generate_and_spawn<C: CartesianCoordinates, A: AssetsBundleSpawner, T: Co
                             node_index,
                         );
                     }
+                    if let Some(watchdog) = watchdog.as_mut() {
+                        watchdog.reset();
+                    }
                     generations_done.push(gen_entity);
                 }
-                Err(GeneratorError { node_index }) => {
+                Err(error @ GeneratorError { node_index }) => {
                     warn!(
                         "Generation {:?} failed at node {}, seed: {}; grid: {}",
                         gen_entity,
@@ -117,6 +240,22 @@ pub fn generate_and_spawn<C: CartesianCoordinates, A: AssetsBundleSpawner, T: Co
                         generation.seed(),
                         generation.grid()
                     );
+                    let Some(watchdog) = watchdog.as_mut() else {
+                        continue;
+                    };
+                    if watchdog.record_failure() {
+                        warn!(
+                            "Generation {:?} abandoned after {} failed attempts",
+                            gen_entity,
+                            watchdog.attempts()
+                        );
+                        abandoned_events.send(GenerationAbandonedEvent {
+                            generation: gen_entity,
+                            attempts: watchdog.attempts(),
+                            error,
+                        });
+                        generations_done.push(gen_entity);
+                    }
                 }
             }
         }
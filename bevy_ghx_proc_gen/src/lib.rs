@@ -6,6 +6,10 @@
 /// Utilities & debug tools/plugins for using the ghx_proc_gen generator
 pub mod gen;
 
+/// Pan-orbit camera & helpers to frame the generation grids
+#[cfg(feature = "camera-utils")]
+pub mod camera;
+
 pub use bevy_ghx_grid;
 pub use ghx_proc_gen as proc_gen;
 
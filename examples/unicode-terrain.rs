@@ -5,17 +5,15 @@ use std::{
 
 use ghx_proc_gen::{
     generator::{
-        model::{ModelCollection, ModelInstance},
-        node_heuristic::NodeSelectionHeuristic,
-        observer::QueuedStatefulObserver,
-        rules::RulesBuilder,
-        socket::{SocketCollection, SocketsCartesian2D},
+        model::ModelInstance, node_heuristic::NodeSelectionHeuristic,
+        observer::QueuedStatefulObserver, rules::RulesBuilder, socket::SocketsCartesian2D,
         GenerationStatus, ModelSelectionHeuristic,
     },
     ghx_grid::{
         cartesian::{coordinates::Cartesian2D, grid::CartesianGrid},
         grid::GridData,
     },
+    rules_table,
 };
 
 use ghx_proc_gen::generator::{builder::GeneratorBuilder, RngMode};
@@ -35,52 +33,29 @@ fn main() {
         .with_max_level(tracing::Level::DEBUG)
         .init();
 
-    let mut sockets = SocketCollection::new();
-    let mountain = sockets.create();
-    let forest = sockets.create();
-    let meadows = sockets.create();
-    let beach = sockets.create();
-    let sea = sockets.create();
-    let deep_sea = sockets.create();
-
-    let mut models = ModelCollection::<Cartesian2D>::new();
-    let mut icons = Vec::new();
-
-    icons.push("🗻");
-    models.create(SocketsCartesian2D::Mono(mountain));
-
-    icons.push("🌲"); // Variation 1
-    models
-        .create(SocketsCartesian2D::Mono(forest))
-        .with_weight(0.5);
-
-    icons.push("🌳"); // Variation 2
-    models
-        .create(SocketsCartesian2D::Mono(forest))
-        .with_weight(0.5);
-
-    icons.push("🟩");
-    models.create(SocketsCartesian2D::Mono(meadows));
-
-    icons.push("🟨");
-    models.create(SocketsCartesian2D::Mono(beach));
-
-    icons.push("🟦");
-    models.create(SocketsCartesian2D::Mono(sea));
-
-    icons.push("🟦");
-    models
-        .create(SocketsCartesian2D::Mono(deep_sea))
-        .with_weight(2.);
-
-    sockets.add_connections(vec![
-        (mountain, vec![mountain, forest]),
-        (forest, vec![forest, meadows]),
-        (meadows, vec![meadows, beach]),
-        (beach, vec![beach, sea]),
-        (sea, vec![sea]),
-        (deep_sea, vec![sea]),
-    ]);
+    let (models, sockets) = rules_table! {
+        sockets: [mountain, forest, meadows, beach, sea, deep_sea],
+        connections: [
+            mountain => [mountain, forest],
+            forest => [forest, meadows],
+            meadows => [meadows, beach],
+            beach => [beach, sea],
+            sea => [sea],
+            deep_sea => [sea],
+        ],
+        models: [
+            "mountain": SocketsCartesian2D::Mono(mountain),
+            // Two variations of forest
+            "forest_1": SocketsCartesian2D::Mono(forest) => with_weight(0.5),
+            "forest_2": SocketsCartesian2D::Mono(forest) => with_weight(0.5),
+            "meadows": SocketsCartesian2D::Mono(meadows),
+            "beach": SocketsCartesian2D::Mono(beach),
+            "sea": SocketsCartesian2D::Mono(sea),
+            "deep_sea": SocketsCartesian2D::Mono(deep_sea) => with_weight(2.),
+        ],
+    };
+    // One icon per model, in the models declaration order
+    let icons = vec!["🗻", "🌲", "🌳", "🟩", "🟨", "🟦", "🟦"];
 
     let rules = RulesBuilder::new_cartesian_2d(models, sockets)
         .build()
@@ -0,0 +1,101 @@
+//! Generation benchmarks on rules with hundreds of model variants, where the model selection dominates.
+//!
+//! The `cached` benchmarks use the cumulative weights cached by the generator. The `uncached` benchmarks add neighbour weights that always return `1.`: the models weights are unchanged, but they are computed from all the possible models on each selection, as the generator did before caching them.
+//!
+//! To compare with another revision, run `cargo bench -p ghx_proc_gen -- --save-baseline <name>` on it, then `cargo bench -p ghx_proc_gen -- --baseline <name>` on this one.
+
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ghx_proc_gen::{
+    generator::{
+        builder::GeneratorBuilder,
+        model::{ModelCollection, ModelIndex, ModelInstance},
+        rules::{Rules, RulesBuilder},
+        socket::{SocketCollection, SocketsCartesian2D},
+        RngMode,
+    },
+    ghx_grid::cartesian::{coordinates::Cartesian2D, grid::CartesianGrid},
+};
+
+const GRID_SIZE: u32 = 32;
+const SEED: u64 = 1234;
+
+/// Rules with `models_count` models and all their rotations, loosely connected so that the generations rarely fail
+fn many_variants_rules(models_count: usize, fixed_point_weights: bool) -> Rules<Cartesian2D> {
+    let mut sockets = SocketCollection::new();
+    let side_sockets: Vec<_> = (0..4).map(|_| sockets.create()).collect();
+    for (index, socket) in side_sockets.iter().enumerate() {
+        sockets.add_connection(
+            *socket,
+            vec![*socket, side_sockets[(index + 1) % side_sockets.len()]],
+        );
+    }
+
+    let mut models = ModelCollection::<Cartesian2D>::new();
+    for model_index in 0..models_count {
+        let socket = |shift: usize| side_sockets[(model_index >> (2 * shift)) % side_sockets.len()];
+        models
+            .create(SocketsCartesian2D::Simple {
+                x_pos: socket(0),
+                x_neg: socket(1),
+                y_pos: socket(2),
+                y_neg: socket(3),
+            })
+            .with_all_rotations()
+            .with_weight(1. + (model_index % 7) as f32);
+    }
+
+    let mut builder = RulesBuilder::new_cartesian_2d(models, sockets);
+    if fixed_point_weights {
+        builder = builder.with_fixed_point_weights();
+    }
+    builder.build().unwrap()
+}
+
+fn generate(rules: &Arc<Rules<Cartesian2D>>, uncached: bool) {
+    let builder = GeneratorBuilder::new()
+        .with_shared_rules(Arc::clone(rules))
+        .with_grid(CartesianGrid::new_cartesian_2d(
+            GRID_SIZE, GRID_SIZE, false, false,
+        ))
+        .with_rng(RngMode::Seeded(SEED));
+    let builder = match uncached {
+        true => builder.with_neighbour_weights(|_: ModelIndex, _: &[Option<ModelInstance>]| 1f32),
+        false => builder,
+    };
+    let mut generator = builder.build().unwrap();
+    let _ = generator.generate();
+}
+
+fn model_selection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("model_selection");
+    group.sample_size(20);
+    for models_count in [16, 64, 128] {
+        for fixed_point_weights in [false, true] {
+            let rules = Arc::new(many_variants_rules(models_count, fixed_point_weights));
+            let weights = match fixed_point_weights {
+                true => "fixed_point",
+                false => "float",
+            };
+            for uncached in [false, true] {
+                let cache = match uncached {
+                    true => "uncached",
+                    false => "cached",
+                };
+                group.bench_with_input(
+                    BenchmarkId::new(
+                        format!("{weights}/{cache}"),
+                        format!("{} variants", rules.models_count()),
+                    ),
+                    &rules,
+                    |b, rules| b.iter(|| generate(rules, uncached)),
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, model_selection);
+criterion_main!(benches);
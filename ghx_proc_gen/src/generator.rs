@@ -1,5 +1,8 @@
 use core::fmt;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, mem::size_of, sync::Arc};
+
+#[cfg(feature = "metrics")]
+use std::time::Duration;
 
 #[cfg(feature = "bevy")]
 use bevy::ecs::component::Component;
@@ -9,17 +12,24 @@ use ghx_grid::{
     grid::{Grid, GridData, NodeRef},
 };
 
-use crate::{GeneratorError, NodeIndex, NodeSetError};
+use crate::{
+    GenerationFailedError, GeneratorError, NodeIndex, NodeSetError, SnapshotMismatchError,
+};
 
 use self::{
     builder::{GeneratorBuilder, Unset},
-    internal_generator::{InternalGenerator, InternalGeneratorStatus},
+    internal_generator::{
+        remap_model_variants, InternalGenerator, InternalGeneratorSnapshot, InternalGeneratorStatus,
+    },
     model::{ModelIndex, ModelInstance, ModelRotation, ModelVariantIndex},
-    node_heuristic::NodeSelectionHeuristic,
-    observer::GenerationUpdate,
+    model_weights::ModelWeightsCache,
+    node_heuristic::{InternalNodeSelectionHeuristic, NodeSelectionHeuristic},
+    observer::{GenerationUpdate, StampedUpdate},
     rules::{ModelInfo, ModelVariantRef, Rules},
 };
 
+/// Defines [`analysis::analyze_seeds`] to measure the robustness of some rules over multiple seeds
+pub mod analysis;
 /// Defines a [`GeneratorBuilder`] used to create a generator
 pub mod builder;
 /// Defines [`crate::generator::model::Model`] and their associated type & utilities
@@ -28,12 +38,23 @@ pub mod model;
 pub mod node_heuristic;
 /// Defines different possible observers to view the results:execution of a [`Generator`]
 pub mod observer;
+/// Defines a [`pipeline::GenerationPipeline`] chaining multiple generations
+pub mod pipeline;
+/// Ready-made sockets and models for common structures, to instantiate and extend. Also serves as examples of socket design patterns
+pub mod presets;
+/// Defines a [`region::SparseRegion`] to only generate the undetermined region of a mostly predetermined grid
+pub mod region;
 /// Defines the [`Rules`] used by a [`Generator`]
 pub mod rules;
+/// Defines [`seam::seam_bans`] to make adjacent generations agree at their seam
+pub mod seam;
 /// Defines [`crate::generator::socket::Socket`] and their associated type & utilities
 pub mod socket;
+/// Defines helpers to infer sockets from the pixels of 2d tiles or the voxels of 3d tiles, and to load voxel tiles from `.vox` files
+pub mod tiles;
 
 pub(crate) mod internal_generator;
+pub(crate) mod model_weights;
 
 /// Defines a heuristic for the choice of a model among the possible ones when a node has been selected for generation.
 
@@ -42,6 +63,48 @@ pub enum ModelSelectionHeuristic {
     /// Choses a random model among the possible ones, weighted by each model weight.
     #[default]
     WeightedProbability,
+    /// Choses a random model among the possible ones, ignoring the models weights. Useful to stress-test some rules.
+    UniformRandom,
+    /// Choses the possible model with the highest weight (the first one in case of a tie). The model selection is deterministic, which gives reproducible "canonical" outputs, for example in tests.
+    ///
+    /// Note that the node selection may still be random, depending on the [`NodeSelectionHeuristic`].
+    HighestWeight,
+}
+
+/// Position-dependent weights for the models, used by the [`ModelSelectionHeuristic`] when selecting a model for a node. Allows weights to follow noise functions, biome maps or gameplay heatmaps.
+///
+/// With the [`ModelSelectionHeuristic::WeightedProbability`] (and no [`NeighbourWeights`]), the weight field is evaluated for each node and model when the generator (re)initializes and the resulting weights are cached. Else, it is evaluated lazily when selecting a model for a node.
+///
+/// Implemented for closures `Fn(NodeIndex, ModelIndex) -> f32`. See [`GeneratorBuilder::with_weight_field`]
+pub trait WeightField: Send + Sync {
+    /// Returns the factor applied to the [`Rules`] weight of the model with index `model_index` (and all its rotations) on the node with index `node_index`.
+    ///
+    /// Should be positive. A factor of `0.` prevents the model from being selected on this node, unless all the possible models have a weight of `0.`, in which case the [`Rules`] weights are used.
+    fn weight(&self, node_index: NodeIndex, model_index: ModelIndex) -> f32;
+}
+
+impl<F: Fn(NodeIndex, ModelIndex) -> f32 + Send + Sync> WeightField for F {
+    fn weight(&self, node_index: NodeIndex, model_index: ModelIndex) -> f32 {
+        self(node_index, model_index)
+    }
+}
+
+/// Weights adjustments depending on the models already generated on the neighbours of a node, evaluated lazily by the [`ModelSelectionHeuristic`] when selecting a model for the node. Allows cellular-automata-like textures (clumps, streaks, ...) while keeping the generation constraint-correct.
+///
+/// Implemented for closures `Fn(ModelIndex, &[Option<ModelInstance>]) -> f32`. See [`GeneratorBuilder::with_neighbour_weights`]
+pub trait NeighbourWeights: Send + Sync {
+    /// Returns the factor applied to the weight of the model with index `model_index` (and all its rotations) on a node.
+    ///
+    /// `neighbours[direction]` is the [`ModelInstance`] generated on the neighbour of the node in `direction` (as an index of the grid coordinate system directions), or `None` if there is no neighbour in this direction or if it is not generated yet.
+    ///
+    /// Should be positive. A factor of `0.` prevents the model from being selected, unless all the possible models have a weight of `0.`, in which case the adjustments are ignored.
+    fn weight(&self, model_index: ModelIndex, neighbours: &[Option<ModelInstance>]) -> f32;
+}
+
+impl<F: Fn(ModelIndex, &[Option<ModelInstance>]) -> f32 + Send + Sync> NeighbourWeights for F {
+    fn weight(&self, model_index: ModelIndex, neighbours: &[Option<ModelInstance>]) -> f32 {
+        self(model_index, neighbours)
+    }
 }
 
 /// Different ways to seed the RNG of the generator.
@@ -87,6 +150,241 @@ pub struct GenInfo {
     pub try_count: u32,
 }
 
+/// Detailed report of a successful generation, returned by [`Generator::generate_reported`]
+#[derive(Clone, Debug)]
+pub struct GenReport {
+    /// How many tries the generation took before succeeding
+    pub try_count: u32,
+    /// Tries that failed before the successful one, in order. Empty if the first try succeeded
+    pub failed_tries: Vec<FailedTry>,
+}
+
+/// A failed try of a generation, see [`Generator::generate_reported`]
+#[derive(Clone, Copy, Debug)]
+pub struct FailedTry {
+    /// Seed used by this try
+    pub seed: u64,
+    /// Contradiction that ended this try
+    pub error: GeneratorError,
+}
+
+/// Statistics about the execution of a [`Generator`], accumulated since its creation.
+#[derive(Default, Clone, Debug)]
+pub struct GenStats {
+    /// How many generation steps were executed (node selections, node sets and model bans)
+    pub steps: u64,
+    /// How many models were banned from nodes during propagation
+    pub bans: u64,
+    /// How many contradictions were encountered
+    pub contradictions: u32,
+    /// How many contradictions were encountered on each node, across all the tries. Nodes where no contradiction occured are absent.
+    ///
+    /// Nodes often appearing here point to the part of the rules that is under-constrained.
+    pub contradiction_sites: HashMap<NodeIndex, u32>,
+    /// How many times the generator was reinitialized
+    pub reinitializations: u32,
+    /// Maximum length reached by the propagation queue
+    pub max_propagation_queue_len: usize,
+}
+
+/// Performance metrics of a [`Generator`], accumulated since its creation or since the last call to [`Generator::reset_metrics`]. Only available with the `metrics` feature.
+///
+/// Each generation step (node selection, node set or model ban) is also emitted as a `tracing` span at the `TRACE` level.
+#[cfg(feature = "metrics")]
+#[derive(Default, Clone, Copy, Debug)]
+pub struct GenMetrics {
+    /// How many models were banned from nodes, during initialization and propagation
+    pub bans: u64,
+    /// How many bans were popped from the propagation queue
+    pub propagation_pops: u64,
+    /// How many supports counts were decremented during propagation
+    pub support_decrements: u64,
+    /// How many generation steps were measured
+    pub steps: u64,
+    /// Total duration of the measured generation steps
+    pub total_steps_duration: Duration,
+    /// Duration of the longest generation step
+    pub max_step_duration: Duration,
+    /// Duration of the last generation step
+    pub last_step_duration: Duration,
+}
+
+#[cfg(feature = "metrics")]
+impl GenMetrics {
+    /// Returns the average duration of a generation step
+    pub fn average_step_duration(&self) -> Duration {
+        match self.steps {
+            0 => Duration::ZERO,
+            steps => self.total_steps_duration / steps as u32,
+        }
+    }
+
+    pub(crate) fn record_step(&mut self, duration: Duration) {
+        self.steps += 1;
+        self.total_steps_duration += duration;
+        self.max_step_duration = self.max_step_duration.max(duration);
+        self.last_step_duration = duration;
+    }
+}
+
+/// Sizes in bytes of the main buffers of a [`Generator`]. See [`Generator::memory_footprint`] and [`MemoryFootprint::estimate`]
+#[derive(Default, Clone, Copy, Debug)]
+pub struct MemoryFootprint {
+    /// Possible models of each node
+    pub nodes: usize,
+    /// Count of possible models of each node
+    pub possible_models_counts: usize,
+    /// Supports count of each model of each node, in each direction. Usually the largest buffer by far
+    pub supports_count: usize,
+    /// Buffers of the [`NodeSelectionHeuristic`]
+    pub node_heuristic: usize,
+    /// Weight jitter factor of each model
+    pub jitter_factors: usize,
+    /// Cumulative weights of the possible models of each node, cached by the [`ModelSelectionHeuristic::WeightedProbability`] unless [`NeighbourWeights`] are used
+    pub model_weights: usize,
+    /// Steps of the current generation, see [`Generator::trace`]. Grows during the generation
+    pub trace: usize,
+}
+
+impl MemoryFootprint {
+    /// Returns the total size in bytes of the buffers
+    pub fn total(&self) -> usize {
+        self.nodes
+            + self.possible_models_counts
+            + self.supports_count
+            + self.node_heuristic
+            + self.jitter_factors
+            + self.model_weights
+            + self.trace
+    }
+
+    /// Estimates the footprint of a [`Generator`] with the given `rules`, `grid` and `node_heuristic`, without allocating anything. Useful to size huge grids.
+    ///
+    /// The [`MemoryFootprint::trace`] is estimated as empty, and the [`MemoryFootprint::model_weights`] as used by the default [`ModelSelectionHeuristic::WeightedProbability`].
+    pub fn estimate<C: CoordinateSystem, G: Grid<C>>(
+        rules: &Rules<C>,
+        grid: &G,
+        node_heuristic: NodeSelectionHeuristic,
+    ) -> Self {
+        let nodes_count = grid.total_size();
+        let models_count = rules.models_count();
+        let directions_count = grid.directions_count();
+        Self {
+            nodes: (nodes_count * models_count).div_ceil(usize::BITS as usize) * size_of::<usize>(),
+            possible_models_counts: nodes_count * size_of::<usize>(),
+            supports_count: nodes_count * models_count * directions_count * size_of::<usize>(),
+            node_heuristic: InternalNodeSelectionHeuristic::estimate_memory_footprint(
+                node_heuristic,
+                nodes_count,
+                models_count,
+                directions_count,
+            ),
+            jitter_factors: models_count * size_of::<f32>(),
+            model_weights: ModelWeightsCache::estimate_memory_footprint(nodes_count, models_count),
+            trace: 0,
+        }
+    }
+}
+
+/// Instances count of an original model in a generation, compared to the count expected from the models weights. See [`Generator::model_census`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModelCensusEntry {
+    /// Index of the original model
+    pub model_index: ModelIndex,
+    /// How many generated nodes use a variation of this model
+    pub count: usize,
+    /// How many generated nodes would use a variation of this model if only the weights were taken into account (no constraints from the sockets)
+    pub expected_count: f32,
+}
+
+impl ModelCensusEntry {
+    /// Returns the relative deviation of [`ModelCensusEntry::count`] from [`ModelCensusEntry::expected_count`]: `0` when they are equal, `-1` when the model is never used although expected.
+    ///
+    /// Returns `0` if no instance is expected.
+    pub fn deviation(&self) -> f32 {
+        match self.expected_count > 0. {
+            true => (self.count as f32 - self.expected_count) / self.expected_count,
+            false => 0.,
+        }
+    }
+}
+
+/// A generation step, as recorded in the trace of a [`Generator`]. See [`Generator::trace`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationStep {
+    /// A node and a model were selected by the heuristics
+    Selected {
+        /// Index of the selected node
+        node_index: NodeIndex,
+        /// Index of the selected model variation
+        model_variant_index: ModelVariantIndex,
+    },
+    /// A model was set on a node by a call to [`Generator::set_and_propagate`]
+    Set {
+        /// Index of the node that was set
+        node_index: NodeIndex,
+        /// Index of the model variation that was set
+        model_variant_index: ModelVariantIndex,
+    },
+    /// A model was banned from a node by a call to [`Generator::ban_and_propagate`]
+    Banned {
+        /// Index of the node the model was banned from
+        node_index: NodeIndex,
+        /// Index of the model variation that was banned
+        model_variant_index: ModelVariantIndex,
+    },
+}
+
+/// Copy of the generation state of a [`Generator`]. See [`Generator::snapshot`]
+#[derive(Clone)]
+pub struct GeneratorSnapshot {
+    internal: InternalGeneratorSnapshot,
+}
+
+/// A node and a model selected by the heuristics of a [`Generator`] but not committed yet. See [`Generator::select_next`]
+#[derive(Clone, Debug)]
+pub struct PendingSelection {
+    /// Index of the selected node
+    pub node_index: NodeIndex,
+    /// Model proposed by the [`ModelSelectionHeuristic`] for the node
+    pub proposed_model: ModelInstance,
+    /// All the models still possible on the node, the proposed model included
+    pub candidates: Vec<ModelInstance>,
+}
+
+/// Two adjacent nodes whose models are not allowed next to each other by the [`Rules`]. See [`Generator::adjacency_violations`]
+#[derive(Clone, Copy, Debug)]
+pub struct AdjacencyViolation {
+    /// First node of the pair, with the lowest node index
+    pub from: GeneratedNode,
+    /// Second node of the pair
+    pub to: GeneratedNode,
+}
+
+/// Snapshot of the remaining possibilities on a node of a [`Generator`]
+#[derive(Default, Clone, Copy, Debug)]
+pub struct NodeEntropy {
+    /// How many model variations are still possible on the node
+    pub possible_models_count: usize,
+    /// Shannon entropy of the node, computed from the weights of the model variations still possible on the node.
+    ///
+    /// Is `0.` when the node is generated
+    pub entropy: f32,
+}
+
+/// A model variant still possible on a node of a [`Generator`], see [`Generator::possible_variants_on`]
+#[derive(Clone, Copy, Debug)]
+pub struct PossibleVariant<D> {
+    /// Index of the model variant in the [`Rules`]
+    pub variant_index: ModelVariantIndex,
+    /// Model instance of the variant
+    pub instance: ModelInstance,
+    /// Weight that the model selection would currently use for this variant on this node: its weight in the [`Rules`], with the weight jitter, the [`WeightField`] and the [`NeighbourWeights`] applied
+    pub weight: f32,
+    /// Direction (from the node) of the neighbour which leaves the fewest supports to this variant, with the count of neighbour variants still supporting it. `None` if the node has no neighbours
+    pub most_constraining: Option<(D, usize)>,
+}
+
 enum NodeSetStatus {
     AlreadySet,
     CanBeSet,
@@ -116,11 +414,15 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         rules: Arc<Rules<C>>,
         grid: G,
         initial_nodes: Vec<(NodeIndex, ModelVariantIndex)>,
+        initial_bans: Vec<(NodeIndex, ModelVariantIndex)>,
         max_retry_count: u32,
         node_selection_heuristic: NodeSelectionHeuristic,
         model_selection_heuristic: ModelSelectionHeuristic,
+        weight_field: Option<Arc<dyn WeightField>>,
+        neighbour_weights: Option<Arc<dyn NeighbourWeights>>,
+        weight_jitter: f32,
         rng_mode: RngMode,
-        observers: Vec<crossbeam_channel::Sender<GenerationUpdate>>,
+        observers: Vec<crossbeam_channel::Sender<StampedUpdate>>,
         collector: &mut Collector,
     ) -> Result<Self, NodeSetError> {
         let mut generator = Self {
@@ -129,8 +431,12 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
             internal: InternalGenerator::new(
                 rules,
                 grid,
+                initial_bans,
                 node_selection_heuristic,
                 model_selection_heuristic,
+                weight_field,
+                neighbour_weights,
+                weight_jitter,
                 rng_mode,
                 observers,
             ),
@@ -154,6 +460,50 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         self.max_retry_count = max_retry_count;
     }
 
+    /// Returns the weight jitter of the generator: the amplitude of the random factors applied to the models weights. See [`GeneratorBuilder::with_weight_jitter`]
+    pub fn weight_jitter(&self) -> f32 {
+        self.internal.weight_jitter
+    }
+
+    /// Specifies the weight jitter of the generator. It will only be applied from the next reinitialization of the generator. See [`GeneratorBuilder::with_weight_jitter`]
+    pub fn set_weight_jitter(&mut self, weight_jitter: f32) {
+        self.internal.weight_jitter = weight_jitter;
+    }
+
+    /// Returns the initial nodes of the generator: the nodes registered in the [`GeneratorBuilder`] and the nodes memorized by [`Generator::set_and_propagate`]. They are spawned each time the generator reinitializes.
+    pub fn initial_nodes(&self) -> &Vec<(NodeIndex, ModelVariantIndex)> {
+        &self.initial_nodes
+    }
+
+    /// Replaces the initial nodes of the generator. They will only be spawned on the next reinitialization of the generator.
+    ///
+    /// Returns [`NodeSetError::InvalidNodeIndex`] or [`NodeSetError::InvalidModelIndex`] if a node or a model variant does not exist in the grid or the rules. The initial nodes are then left unchanged.
+    pub fn set_initial_nodes(
+        &mut self,
+        initial_nodes: Vec<(NodeIndex, ModelVariantIndex)>,
+    ) -> Result<(), NodeSetError> {
+        self.internal.check_nodes_entries(&initial_nodes)?;
+        self.initial_nodes = initial_nodes;
+        Ok(())
+    }
+
+    /// Returns the initial bans of the generator: the models banned from nodes each time the generator reinitializes, as registered in the [`GeneratorBuilder`].
+    pub fn initial_bans(&self) -> &Vec<(NodeIndex, ModelVariantIndex)> {
+        &self.internal.initial_bans
+    }
+
+    /// Replaces the initial bans of the generator. They will only be applied on the next reinitialization of the generator.
+    ///
+    /// Returns [`NodeSetError::InvalidNodeIndex`] or [`NodeSetError::InvalidModelIndex`] if a node or a model variant does not exist in the grid or the rules. The initial bans are then left unchanged.
+    pub fn set_initial_bans(
+        &mut self,
+        initial_bans: Vec<(NodeIndex, ModelVariantIndex)>,
+    ) -> Result<(), NodeSetError> {
+        self.internal.check_nodes_entries(&initial_bans)?;
+        self.internal.initial_bans = initial_bans;
+        Ok(())
+    }
+
     /// Returns the seed that was used to initialize the generator RNG for this generation. See [`RngMode`] for more information.
     pub fn seed(&self) -> u64 {
         self.internal.seed
@@ -169,11 +519,59 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         &self.internal.rules
     }
 
+    /// Replaces the [`Rules`] used by the generator, for example after their source asset was modified, and reinitializes the generator with its current seed.
+    ///
+    /// The initial nodes and initial bans are converted to the model variants of the new rules: entries whose model (with the same rotation and facing) does not exist in the new rules are dropped. Models are matched by their [`ModelIndex`], so the new rules should be built from the same models, in the same order.
+    ///
+    /// Returns an error if an initial node could not be generated with the new rules. The generator is still usable and can be reinitialized.
+    pub fn set_rules(&mut self, rules: Arc<Rules<C>>) -> Result<GenerationStatus, NodeSetError> {
+        self.initial_nodes =
+            remap_model_variants(&self.internal.rules, &rules, &self.initial_nodes);
+        self.internal
+            .replace_rules(rules, &mut None, &self.initial_nodes)
+    }
+
     /// Returns how many nodes are left to generate
     pub fn nodes_left(&self) -> usize {
         self.internal.nodes_left_to_generate
     }
 
+    /// Returns the [`GenStats`] accumulated by the generator since its creation
+    pub fn stats(&self) -> &GenStats {
+        &self.internal.stats
+    }
+
+    /// Returns the [`GenMetrics`] accumulated by the generator. Only available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> GenMetrics {
+        self.internal.metrics
+    }
+
+    /// Resets the [`GenMetrics`] of the generator, to measure a specific part of a generation. Only available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&mut self) {
+        self.internal.metrics = GenMetrics::default();
+    }
+
+    /// Returns the [`MemoryFootprint`] of the main buffers of the generator. See [`MemoryFootprint::estimate`] to predict it before creating a generator.
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        self.internal.memory_footprint()
+    }
+
+    /// Returns a [`ModelCensusEntry`] for each original model of the rules, counting its instances among the nodes generated so far.
+    ///
+    /// Useful to tune the models weights and to spot the effects of the sockets constraints: a model far from its expected count is favored or hindered by its neighbours. Can be called at any time during the generation.
+    pub fn model_census(&self) -> Vec<ModelCensusEntry> {
+        self.internal.model_census()
+    }
+
+    /// Returns a [`GridData`] of [`NodeEntropy`] for all the nodes of the grid, in their current state.
+    ///
+    /// Can be called at any time during the generation, even when the generation is done or failed.
+    pub fn entropy_snapshot(&self) -> GridData<C, NodeEntropy, G> {
+        self.internal.entropy_snapshot()
+    }
+
     /// Returns a [`GridData`] of [`ModelInstance`] with all the nodes generated if the generation is done
     ///
     /// Returns `None` if the generation is still ongoing or currently failed
@@ -185,6 +583,13 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         }
     }
 
+    /// Returns a [`GridData`] with the [`ModelInstance`] of each generated node, and `None` for the nodes that are not generated yet (or left with no possible model by a contradiction).
+    ///
+    /// Unlike [`Generator::to_grid_data`], can be called at any time during the generation: useful to periodically sync the current state of a generation (networking, minimaps, ...) without replaying the updates of an observer.
+    pub fn to_partial_grid_data(&self) -> GridData<C, Option<ModelInstance>, G> {
+        self.internal.to_partial_grid_data()
+    }
+
     /// Tries to generate the whole grid. If the generation fails due to a contradiction, it will retry `max_retry_count` times before returning the last encountered [`GeneratorError`]
     ///
     /// If the generation is currently done or failed, calling this method will reinitialize the generator with the next seed before starting the generation.
@@ -193,9 +598,12 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
     pub fn generate_grid(
         &mut self,
     ) -> Result<(GenInfo, GridData<C, ModelInstance, G>), GeneratorError> {
-        let gen_info =
-            self.internal
-                .generate(&mut None, self.max_retry_count, &self.initial_nodes)?;
+        let gen_info = self.internal.generate(
+            &mut None,
+            &mut None,
+            self.max_retry_count,
+            &self.initial_nodes,
+        )?;
         Ok((gen_info, self.internal.to_grid_data()))
     }
 
@@ -203,12 +611,35 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
     ///
     /// [`Generator::to_grid_data`] can still be called to retrieve a [`GridData`] afterwards.
     pub fn generate(&mut self) -> Result<GenInfo, GeneratorError> {
-        let gen_info =
-            self.internal
-                .generate(&mut None, self.max_retry_count, &self.initial_nodes)?;
+        let gen_info = self.internal.generate(
+            &mut None,
+            &mut None,
+            self.max_retry_count,
+            &self.initial_nodes,
+        )?;
         Ok(gen_info)
     }
 
+    /// Same as [`Generator::generate`] but also records the seed and the contradiction of each failed try.
+    ///
+    /// - Returns a [`GenReport`] with the tries that failed before the successful one
+    /// - Returns a [`GenerationFailedError`] with all the failed tries if every try failed
+    pub fn generate_reported(&mut self) -> Result<GenReport, GenerationFailedError> {
+        let mut failed_tries = Vec::new();
+        match self.internal.generate(
+            &mut None,
+            &mut Some(&mut failed_tries),
+            self.max_retry_count,
+            &self.initial_nodes,
+        ) {
+            Ok(gen_info) => Ok(GenReport {
+                try_count: gen_info.try_count,
+                failed_tries,
+            }),
+            Err(_) => Err(GenerationFailedError { failed_tries }),
+        }
+    }
+
     /// Advances the generation by one "step": select a node and a model via the heuristics and propagate the changes.
     /// - Returns the [`GenerationStatus`] if the step executed successfully
     /// - Returns a [`GeneratorError`] if the generation fails due to a contradiction.
@@ -276,6 +707,218 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         Ok((status, generated_nodes))
     }
 
+    /// Tries to ban the model refrenced by `model_variant_ref` from the node referenced by `node_ref`. Then tries to propagate the change.
+    /// - Returns `Ok` and the current [`GenerationStatus`] if successful. Banning a model which is already not possible on the node does nothing.
+    /// - Returns a [`NodeSetError`] if it fails.
+    ///
+    /// If the generation is currently done or failed, this method will just return the done or failed status/error.
+    ///
+    /// **Note**: One call to this method **can** lead to one or more nodes generated if the propagation phase forces some node(s) into a definite state (due to only one possible model remaining on a node)
+    pub fn ban_and_propagate<N: NodeRef<C>, M: ModelVariantRef<C>>(
+        &mut self,
+        node_ref: N,
+        model_variant_ref: M,
+    ) -> Result<GenerationStatus, NodeSetError> {
+        let node_index = node_ref.to_index(&self.internal.grid);
+        let model_variant_index = model_variant_ref.to_index(&self.internal.rules)?;
+        self.internal
+            .ban_and_propagate(node_index, model_variant_index, &mut None)
+    }
+
+    /// Same as [`Generator::ban_and_propagate`] but also returns all the [`GeneratedNode`] generated by this generation operation if successful.
+    pub fn ban_and_propagate_collected<N: NodeRef<C>, M: ModelVariantRef<C>>(
+        &mut self,
+        node_ref: N,
+        model_variant_ref: M,
+    ) -> Result<(GenerationStatus, Vec<GeneratedNode>), NodeSetError> {
+        let mut generated_nodes = Vec::new();
+        let node_index = node_ref.to_index(&self.internal.grid);
+        let model_variant_index = model_variant_ref.to_index(&self.internal.rules)?;
+        let status = self.internal.ban_and_propagate(
+            node_index,
+            model_variant_index,
+            &mut Some(&mut generated_nodes),
+        )?;
+        Ok((status, generated_nodes))
+    }
+
+    /// Returns an independent deep copy of the generator, in the same generation state and sharing the same [`Rules`]. Observers are not forked: the fork starts without any observer.
+    ///
+    /// The fork uses a copy of the RNG, so it continues the generation exactly as the original would. See [`Generator::fork_with_seed`] to explore alternative continuations.
+    pub fn fork(&self) -> Generator<C, G> {
+        Generator {
+            max_retry_count: self.max_retry_count,
+            initial_nodes: self.initial_nodes.clone(),
+            internal: self.internal.fork(),
+        }
+    }
+
+    /// Same as [`Generator::fork`], but the RNG of the fork is seeded with `rng_seed` so that it explores a different continuation of the current partial generation. Forks with different seeds can be generated in parallel to present alternative options.
+    ///
+    /// [`Generator::seed`] of the fork still returns the seed of the current generation, and any pending selection (see [`Generator::select_next`]) is discarded in the fork.
+    pub fn fork_with_seed(&self, rng_seed: u64) -> Generator<C, G> {
+        let mut fork = self.fork();
+        fork.internal.reseed_rng(rng_seed);
+        fork
+    }
+
+    /// Returns a [`GeneratorSnapshot`] of the current generation state (nodes, supports, RNG, heuristics, trace), which can be restored later with [`Generator::restore`] to resume a generation or to roll back a speculative change.
+    ///
+    /// The configuration of the generator (rules, grid, initial nodes, retry count, ...), its observers and its stats are not part of the snapshot.
+    pub fn snapshot(&self) -> GeneratorSnapshot {
+        GeneratorSnapshot {
+            internal: self.internal.snapshot(),
+        }
+    }
+
+    /// Restores a generation state from a [`GeneratorSnapshot`] taken on this generator, or on a generator with the same rules and grid.
+    ///
+    /// Observers are sent a [`GenerationUpdate::Reinitializing`] followed by all the nodes generated in the restored state.
+    ///
+    /// Returns a [`SnapshotMismatchError`] and leaves the generator unchanged if the snapshot comes from a generator with a different grid size or models count.
+    pub fn restore(&mut self, snapshot: &GeneratorSnapshot) -> Result<(), SnapshotMismatchError> {
+        match self.internal.restore(&snapshot.internal) {
+            true => Ok(()),
+            false => Err(SnapshotMismatchError),
+        }
+    }
+
+    /// Regenerates the nodes in `node_indexes` with the next seed, while keeping all the other generated nodes as they are. For example to reroll a region of a generated level which does not look right.
+    ///
+    /// The initial nodes inside of the region are still enforced, and the nodes outside of the region that were not generated yet are generated too. Observers only receive a [`GenerationUpdate::Generated`] for each of the regenerated nodes, without any reinitialization.
+    ///
+    /// - Returns the [`GenInfo`] of the regeneration and the regenerated nodes if successful
+    /// - Returns a [`GeneratorError`] and leaves the generation unchanged if the region could not be regenerated in [`Generator::max_retry_count`] tries
+    ///
+    /// **Note**: the [`Generator::trace`] then only contains the steps of the regeneration, which cannot be rewound.
+    pub fn regenerate_nodes(
+        &mut self,
+        node_indexes: &[NodeIndex],
+    ) -> Result<(GenInfo, Vec<GeneratedNode>), GeneratorError> {
+        let nodes_count = self.internal.grid.total_size();
+        let mut in_region = vec![false; nodes_count];
+        for &node_index in node_indexes {
+            if node_index < nodes_count {
+                in_region[node_index] = true;
+            }
+        }
+
+        // Every generated node outside of the region is kept as an initial node of the regeneration
+        let mut kept_nodes = Vec::new();
+        for node_index in 0..nodes_count {
+            if in_region[node_index] {
+                continue;
+            }
+            let mut possible_models = self.internal.possible_model_indexes(node_index);
+            if let (Some(model_variant_index), None) =
+                (possible_models.next(), possible_models.next())
+            {
+                kept_nodes.push((node_index, model_variant_index));
+            } else {
+                in_region[node_index] = true;
+            }
+        }
+        let mut regeneration = self.fork();
+        regeneration.initial_nodes = self
+            .initial_nodes
+            .iter()
+            .filter(|(node_index, _)| in_region[*node_index])
+            .copied()
+            .chain(kept_nodes)
+            .collect();
+        // The region is reset whatever the current status: an ongoing generation would otherwise be continued from its current nodes
+        let gen_info = match regeneration
+            .internal
+            .reinitialize(&mut None, &regeneration.initial_nodes)
+        {
+            GenerationStatus::Done => GenInfo { try_count: 1 },
+            GenerationStatus::Ongoing => regeneration.generate()?,
+        };
+
+        let regenerated_nodes: Vec<GeneratedNode> = (0..nodes_count)
+            .filter(|node_index| in_region[*node_index])
+            .map(|node_index| GeneratedNode {
+                node_index,
+                model_instance: *regeneration
+                    .internal
+                    .rules
+                    .model(regeneration.internal.get_model_index(node_index)),
+            })
+            .collect();
+        let observers = std::mem::take(&mut self.internal.observers);
+        let update_step = self.internal.update_step;
+        self.internal = regeneration.internal;
+        self.internal.observers = observers;
+        self.internal.update_step = update_step;
+        for node in regenerated_nodes.iter() {
+            self.internal
+                .notify_observers(GenerationUpdate::Generated(*node));
+        }
+        Ok((gen_info, regenerated_nodes))
+    }
+
+    /// First phase of a two-phase step: selects a node and a model via the heuristics, as [`Generator::select_and_propagate`] would, but does not commit them. This allows interactive tools (or a player) to intercept the choice, before calling [`Generator::commit`] or [`Generator::auto_commit`].
+    ///
+    /// - Returns `Ok(None)` if the generation is done
+    /// - Returns a [`GeneratorError`] if the generation is currently failed
+    ///
+    /// Calling this method again returns the same [`PendingSelection`], until it is committed or invalidated by any other change to the nodes (set, ban, reinitialization, ...).
+    pub fn select_next(&mut self) -> Result<Option<PendingSelection>, GeneratorError> {
+        let Some((node_index, proposed_model_index)) = self.internal.select_next()? else {
+            return Ok(None);
+        };
+        Ok(Some(PendingSelection {
+            node_index,
+            proposed_model: *self.internal.rules.model(proposed_model_index),
+            candidates: self
+                .internal
+                .possible_model_indexes(node_index)
+                .map(|model_index| *self.internal.rules.model(model_index))
+                .collect(),
+        }))
+    }
+
+    /// Second phase of a two-phase step: generates the model referenced by `model_variant_ref` on the node of the pending selection returned by [`Generator::select_next`], and propagates the change.
+    ///
+    /// - Returns `Ok` and the current [`GenerationStatus`] if successful.
+    /// - Returns [`NodeSetError::NoPendingSelection`] if there is no pending selection, or another [`NodeSetError`] if the model is not one of the candidates or if the propagation fails.
+    ///
+    /// Committing the proposed model is equivalent to a call to [`Generator::select_and_propagate`]. Committing another candidate is equivalent to a (non memorized) call to [`Generator::set_and_propagate`].
+    pub fn commit<M: ModelVariantRef<C>>(
+        &mut self,
+        model_variant_ref: M,
+    ) -> Result<GenerationStatus, NodeSetError> {
+        let model_variant_index = model_variant_ref.to_index(&self.internal.rules)?;
+        self.internal.commit(model_variant_index, &mut None)
+    }
+
+    /// Same as [`Generator::commit`] but collects and return the generated [`GeneratedNode`] when successful.
+    pub fn commit_collected<M: ModelVariantRef<C>>(
+        &mut self,
+        model_variant_ref: M,
+    ) -> Result<(GenerationStatus, Vec<GeneratedNode>), NodeSetError> {
+        let mut generated_nodes = Vec::new();
+        let model_variant_index = model_variant_ref.to_index(&self.internal.rules)?;
+        let status = self
+            .internal
+            .commit(model_variant_index, &mut Some(&mut generated_nodes))?;
+        Ok((status, generated_nodes))
+    }
+
+    /// Commits the proposed model of the pending selection returned by [`Generator::select_next`]. If there is no pending selection, this is the same as [`Generator::select_and_propagate`].
+    pub fn auto_commit(&mut self) -> Result<GenerationStatus, GeneratorError> {
+        self.internal.auto_commit(&mut None)
+    }
+
+    /// Same as [`Generator::auto_commit`] but collects and return the generated [`GeneratedNode`] when successful.
+    pub fn auto_commit_collected(
+        &mut self,
+    ) -> Result<(GenerationStatus, Vec<GeneratedNode>), GeneratorError> {
+        let mut generated_nodes = Vec::new();
+        let status = self.internal.auto_commit(&mut Some(&mut generated_nodes))?;
+        Ok((status, generated_nodes))
+    }
+
     /// Reinitalizes the generator with the next seed (a seed is generated from the current seed)
     pub fn reinitialize(&mut self) -> GenerationStatus {
         self.internal.reinitialize(&mut None, &self.initial_nodes)
@@ -290,6 +933,61 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         (res, generated_nodes)
     }
 
+    /// Reinitalizes the generator with the given `seed`, discarding the current generation state.
+    ///
+    /// Can be used to reproduce a previous generation from its seed, see [`Generator::seed`].
+    pub fn reinitialize_with_seed(&mut self, seed: u64) -> GenerationStatus {
+        self.internal
+            .reinitialize_with_seed(seed, &mut None, &self.initial_nodes)
+    }
+
+    /// Same as [`Generator::reinitialize_with_seed`] but also returns all the [`GeneratedNode`] generated by this generation operation.
+    pub fn reinitialize_with_seed_collected(
+        &mut self,
+        seed: u64,
+    ) -> (GenerationStatus, Vec<GeneratedNode>) {
+        let mut generated_nodes = Vec::new();
+        let res = self.internal.reinitialize_with_seed(
+            seed,
+            &mut Some(&mut generated_nodes),
+            &self.initial_nodes,
+        );
+        (res, generated_nodes)
+    }
+
+    /// Returns the trace of the current generation: all the [`GenerationStep`] executed since the last (re)initialization, in order.
+    ///
+    /// Since the generator is deterministic for a given seed, the trace can be used with [`Generator::rewind`] and [`Generator::replay_step`] to move backward and forward in the generation history.
+    pub fn trace(&self) -> &Vec<GenerationStep> {
+        &self.internal.trace
+    }
+
+    /// Reinitalizes the generator with its current seed and replays the first `steps_count` steps of its [`Generator::trace`].
+    ///
+    /// Selected steps are replayed with their recorded node and model, so the nodes end in the exact same state as they were after those steps, even if the RNG was reseeded since the reinitialization (see [`Generator::fork_with_seed`]). The RNG is advanced as if the heuristics were run again: a generator whose RNG was never reseeded continues exactly as it did after those steps. If `steps_count` is greater than the trace length, the whole trace is replayed.
+    pub fn rewind(&mut self, steps_count: usize) -> Result<GenerationStatus, NodeSetError> {
+        let steps_count = steps_count.min(self.internal.trace.len());
+        let steps = self.internal.trace[..steps_count].to_vec();
+        let mut status = self.internal.reinitialize_with_seed(
+            self.internal.seed,
+            &mut None,
+            &self.initial_nodes,
+        );
+        for step in steps {
+            status = self.internal.replay_recorded_step(step, &mut None)?;
+        }
+        Ok(status)
+    }
+
+    /// Replays a [`GenerationStep`], usually taken from a previously recorded [`Generator::trace`].
+    ///
+    /// A [`GenerationStep::Selected`] step is replayed by selecting a node and a model via the heuristics, it will only select the same node and model if the generation is in the same state as when the step was recorded.
+    ///
+    /// If the generation is currently done or failed, this method will just return the done or failed status/error.
+    pub fn replay_step(&mut self, step: GenerationStep) -> Result<GenerationStatus, NodeSetError> {
+        self.internal.replay_step(step, &mut None)
+    }
+
     /// Returns all the current possible model instances on `node_index`
     pub fn get_models_on(&self, node_index: NodeIndex) -> Vec<ModelInstance> {
         let mut models = Vec::new();
@@ -303,6 +1001,91 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         models
     }
 
+    /// Validates some nodes against the [`Rules`], and returns every pair of adjacent nodes among `nodes` whose model variants are not allowed next to each other.
+    ///
+    /// The propagation keeps the internal state of the generator consistent, so this is meant for nodes coming from outside of it: initial nodes forced or painted by the user (see [`Generator::initial_nodes`]), nodes spawned by an application, ... Nodes with an invalid node index or model variant index are ignored. If a node appears more than once, its last model variant is checked.
+    pub fn adjacency_violations(
+        &self,
+        nodes: &[(NodeIndex, ModelVariantIndex)],
+    ) -> Vec<AdjacencyViolation> {
+        let grid = &self.internal.grid;
+        let rules = &self.internal.rules;
+        let models: HashMap<NodeIndex, ModelVariantIndex> = nodes
+            .iter()
+            .filter(|(node_index, model)| {
+                self.internal.is_valid_node_index(*node_index) && *model < rules.models_count()
+            })
+            .copied()
+            .collect();
+
+        let mut violations = Vec::new();
+        let mut neighbours = vec![None; grid.directions_count()];
+        for (&node_index, &model) in models.iter() {
+            grid.get_neighbours_in_all_directions(node_index, &mut neighbours);
+            for direction in grid.coord_system().directions() {
+                let Some(neighbour_index) = neighbours[(*direction).into()] else {
+                    continue;
+                };
+                // Allowed adjacencies are symmetric, each pair only needs to be checked once
+                if neighbour_index <= node_index {
+                    continue;
+                }
+                let Some(&neighbour_model) = models.get(&neighbour_index) else {
+                    continue;
+                };
+                if !rules.is_neighbour_allowed(model, *direction, neighbour_model) {
+                    violations.push(AdjacencyViolation {
+                        from: GeneratedNode {
+                            node_index,
+                            model_instance: *rules.model(model),
+                        },
+                        to: GeneratedNode {
+                            node_index: neighbour_index,
+                            model_instance: *rules.model(neighbour_model),
+                        },
+                    });
+                }
+            }
+        }
+        violations.sort_by_key(|violation| (violation.from.node_index, violation.to.node_index));
+        violations
+    }
+
+    /// Returns the [`NodeEntropy`] of the node at `node_index` in its current state, or `None` if `node_index` is not a valid node index
+    pub fn node_entropy(&self, node_index: NodeIndex) -> Option<NodeEntropy> {
+        match self.internal.is_valid_node_index(node_index) {
+            true => Some(self.internal.node_entropy(node_index)),
+            false => None,
+        }
+    }
+
+    /// Returns an iterator over the indexes of the nodes that are not generated yet: nodes on which more than one model variant is still possible, in increasing order.
+    ///
+    /// Nodes left with no possible model by a contradiction are neither generated nor returned.
+    pub fn ungenerated_nodes(&self) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.internal.ungenerated_nodes()
+    }
+
+    /// Returns `true` if the node referenced by `node_ref` is generated: exactly one model variant is possible on it. Returns `false` if the node is not generated yet, or if `node_ref` does not reference a valid node.
+    pub fn is_generated<N: NodeRef<C>>(&self, node_ref: N) -> bool {
+        let node_index = node_ref.to_index(&self.internal.grid);
+        self.internal.is_valid_node_index(node_index)
+            && self.internal.possible_models_count(node_index) == 1
+    }
+
+    /// Returns all the model variants still possible on `node_index`, with their current weight and the neighbour constraining them the most. Returns an empty `Vec` if `node_index` is not a valid node index.
+    ///
+    /// Meant for debugging and inspection: it is much more costly than [`Generator::get_models_on`].
+    pub fn possible_variants_on(
+        &self,
+        node_index: NodeIndex,
+    ) -> Vec<PossibleVariant<C::Direction>> {
+        match self.internal.is_valid_node_index(node_index) {
+            true => self.internal.possible_variants(node_index),
+            false => Vec::new(),
+        }
+    }
+
     /// Returns all the current possible model on `node_index` grouped by variations, as well as the total number of possible models
     pub fn get_models_variations_on(&self, node_index: NodeIndex) -> (Vec<ModelVariations>, u32) {
         let mut model_variations = Vec::new();
@@ -333,7 +1116,7 @@ impl<C: CoordinateSystem, G: Grid<C>> Generator<C, G> {
         (model_variations, total_models_count)
     }
 
-    fn create_observer_queue(&mut self) -> crossbeam_channel::Receiver<GenerationUpdate> {
+    fn create_observer_queue(&mut self) -> crossbeam_channel::Receiver<StampedUpdate> {
         // We can't simply bound to the number of nodes since we might retry some generations. (and send more than number_of_nodes updates)
         let (sender, receiver) = crossbeam_channel::unbounded();
         self.internal.observers.push(sender);
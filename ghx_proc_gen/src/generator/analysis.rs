@@ -0,0 +1,151 @@
+use core::fmt;
+use std::time::{Duration, Instant};
+
+use ghx_grid::{coordinate_system::CoordinateSystem, grid::Grid};
+
+use crate::{GeneratorBuilderError, GeneratorError, NodeIndex};
+
+use super::{
+    builder::{GeneratorBuilder, Set},
+    RngMode,
+};
+
+/// Result of the generation of one seed, see [`analyze_seeds`]
+#[derive(Clone, Copy, Debug)]
+pub struct SeedRun {
+    /// Seed the generation was started with
+    pub seed: u64,
+    /// How many tries the generation took. Equals the max retry count + 1 when the generation failed
+    pub try_count: u32,
+    /// Duration of the generation, all tries included
+    pub duration: Duration,
+    /// Node of the last contradiction if the generation failed, `None` if it succeeded
+    pub failed_at: Option<NodeIndex>,
+}
+
+impl SeedRun {
+    /// Returns whether or not the generation succeeded
+    pub fn succeeded(&self) -> bool {
+        self.failed_at.is_none()
+    }
+}
+
+/// Report of the generations of the same rules and grid over multiple seeds, see [`analyze_seeds`].
+///
+/// Its [`fmt::Display`] implementation prints a short summary, followed by the failed seeds.
+#[derive(Default, Clone, Debug)]
+pub struct SeedsAnalysis {
+    /// Result of each seed, in the order of the analyzed seeds
+    pub runs: Vec<SeedRun>,
+}
+
+impl SeedsAnalysis {
+    /// Returns how many seeds were generated
+    pub fn runs_count(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Returns how many seeds were generated successfully
+    pub fn successes_count(&self) -> usize {
+        self.runs.iter().filter(|run| run.succeeded()).count()
+    }
+
+    /// Returns the ratio (between `0.` and `1.`) of seeds generated successfully. `0.` if no seed was analyzed
+    pub fn success_rate(&self) -> f32 {
+        match self.runs.len() {
+            0 => 0.,
+            runs_count => self.successes_count() as f32 / runs_count as f32,
+        }
+    }
+
+    /// Returns the average try count of the generations, failed ones included. `0.` if no seed was analyzed
+    pub fn average_try_count(&self) -> f32 {
+        match self.runs.len() {
+            0 => 0.,
+            runs_count => {
+                self.runs.iter().map(|run| run.try_count).sum::<u32>() as f32 / runs_count as f32
+            }
+        }
+    }
+
+    /// Returns the average duration of the generations, failed ones included
+    pub fn average_duration(&self) -> Duration {
+        match self.runs.len() {
+            0 => Duration::ZERO,
+            runs_count => self.total_duration() / runs_count as u32,
+        }
+    }
+
+    /// Returns the total duration of the generations
+    pub fn total_duration(&self) -> Duration {
+        self.runs.iter().map(|run| run.duration).sum()
+    }
+
+    /// Returns the seeds whose generation failed
+    pub fn failed_seeds(&self) -> Vec<u64> {
+        self.runs
+            .iter()
+            .filter(|run| !run.succeeded())
+            .map(|run| run.seed)
+            .collect()
+    }
+}
+
+impl fmt::Display for SeedsAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}/{} seeds generated ({:.1}%), average try count: {:.2}, average duration: {:?}",
+            self.successes_count(),
+            self.runs_count(),
+            100. * self.success_rate(),
+            self.average_try_count(),
+            self.average_duration()
+        )?;
+        for run in self.runs.iter().filter(|run| !run.succeeded()) {
+            writeln!(
+                f,
+                "  seed {} failed at node {} after {} tries",
+                run.seed,
+                run.failed_at.unwrap_or_default(),
+                run.try_count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Generates the rules and grid of `builder` once per seed in `seeds`, and reports the success rate, try counts and durations of the generations. Does not need any rendering, which makes it usable in tests and CI jobs to check that some rules are robust.
+///
+/// The [`RngMode`] of `builder` is overridden by each seed, all its other parameters (retry count, heuristics, initial nodes, ...) are used as is.
+///
+/// ### Example
+///
+/// ```ignore
+/// let analysis = analyze_seeds(&builder, 0..100)?;
+/// println!("{}", analysis);
+/// assert!(analysis.success_rate() > 0.95);
+/// ```
+pub fn analyze_seeds<C: CoordinateSystem, G: Grid<C>, I: IntoIterator<Item = u64>>(
+    builder: &GeneratorBuilder<Set, Set, C, G>,
+    seeds: I,
+) -> Result<SeedsAnalysis, GeneratorBuilderError> {
+    let mut analysis = SeedsAnalysis::default();
+    for seed in seeds {
+        let start = Instant::now();
+        let mut generator = builder.clone().with_rng(RngMode::Seeded(seed)).build()?;
+        let (try_count, failed_at) = match generator.generate() {
+            Ok(gen_info) => (gen_info.try_count, None),
+            Err(GeneratorError { node_index }) => {
+                (generator.max_retry_count() + 1, Some(node_index))
+            }
+        };
+        analysis.runs.push(SeedRun {
+            seed,
+            try_count,
+            duration: start.elapsed(),
+            failed_at,
+        });
+    }
+    Ok(analysis)
+}
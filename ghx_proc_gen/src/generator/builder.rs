@@ -1,6 +1,7 @@
 use std::{marker::PhantomData, sync::Arc};
 
 use ghx_grid::{
+    cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
     coordinate_system::CoordinateSystem,
     grid::{Grid, GridData, NodeRef},
 };
@@ -8,11 +9,12 @@ use ghx_grid::{
 use crate::{GeneratorBuilderError, NodeIndex};
 
 use super::{
-    model::ModelVariantIndex,
+    model::{ModelIndex, ModelSet, ModelVariantIndex},
     node_heuristic::NodeSelectionHeuristic,
-    observer::{GenerationUpdate, QueuedObserver, QueuedStatefulObserver},
+    observer::{QueuedObserver, QueuedStatefulObserver, StampedUpdate},
     rules::{ModelVariantRef, Rules},
-    Collector, GeneratedNode, Generator, ModelSelectionHeuristic, RngMode,
+    Collector, GeneratedNode, Generator, ModelSelectionHeuristic, NeighbourWeights, RngMode,
+    WeightField,
 };
 
 /// Default retry count for the generator
@@ -59,8 +61,12 @@ pub struct GeneratorBuilder<G, R, C: CoordinateSystem, T: Grid<C>> {
     node_selection_heuristic: NodeSelectionHeuristic,
     model_selection_heuristic: ModelSelectionHeuristic,
     rng_mode: RngMode,
-    observers: Vec<crossbeam_channel::Sender<GenerationUpdate>>,
+    observers: Vec<crossbeam_channel::Sender<StampedUpdate>>,
     initial_nodes: Vec<(NodeIndex, ModelVariantIndex)>,
+    initial_bans: Vec<(NodeIndex, ModelVariantIndex)>,
+    weight_field: Option<Arc<dyn WeightField>>,
+    neighbour_weights: Option<Arc<dyn NeighbourWeights>>,
+    weight_jitter: f32,
     typestate: PhantomData<(G, R)>,
 }
 
@@ -76,6 +82,10 @@ impl<C: CoordinateSystem, G: Grid<C>> GeneratorBuilder<Unset, Unset, C, G> {
             rng_mode: RngMode::RandomSeed,
             observers: Vec::new(),
             initial_nodes: Vec::new(),
+            initial_bans: Vec::new(),
+            weight_field: None,
+            neighbour_weights: None,
+            weight_jitter: 0.,
             typestate: PhantomData,
         }
     }
@@ -94,6 +104,10 @@ impl<C: CoordinateSystem, G: Grid<C>> GeneratorBuilder<Unset, Unset, C, G> {
             rng_mode: self.rng_mode,
             observers: self.observers,
             initial_nodes: self.initial_nodes,
+            initial_bans: self.initial_bans,
+            weight_field: self.weight_field,
+            neighbour_weights: self.neighbour_weights,
+            weight_jitter: self.weight_jitter,
 
             typestate: PhantomData,
         }
@@ -111,6 +125,10 @@ impl<C: CoordinateSystem, G: Grid<C>> GeneratorBuilder<Unset, Unset, C, G> {
             rng_mode: self.rng_mode,
             observers: self.observers,
             initial_nodes: self.initial_nodes,
+            initial_bans: self.initial_bans,
+            weight_field: self.weight_field,
+            neighbour_weights: self.neighbour_weights,
+            weight_jitter: self.weight_jitter,
 
             typestate: PhantomData,
         }
@@ -130,6 +148,10 @@ impl<C: CoordinateSystem, G: Grid<C>> GeneratorBuilder<Unset, Set, C, G> {
             rng_mode: self.rng_mode,
             observers: self.observers,
             initial_nodes: self.initial_nodes,
+            initial_bans: self.initial_bans,
+            weight_field: self.weight_field,
+            neighbour_weights: self.neighbour_weights,
+            weight_jitter: self.weight_jitter,
 
             typestate: PhantomData,
         }
@@ -152,6 +174,54 @@ impl<G, R, C: CoordinateSystem, T: Grid<C>> GeneratorBuilder<G, R, C, T> {
         self.model_selection_heuristic = heuristic;
         self
     }
+    /// Specifies a [`WeightField`] to make the models weights depend on the nodes positions. Only used by the [`ModelSelectionHeuristic`], the [`NodeSelectionHeuristic`] still uses the [`Rules`] weights. No weight field by default.
+    ///
+    /// ### Example
+    ///
+    /// More trees where the noise is high:
+    /// ```ignore
+    /// let builder = builder.with_weight_field(move |node_index, model_index| match model_index {
+    ///     TREE => noise.get(node_index),
+    ///     _ => 1.,
+    /// });
+    /// ```
+    pub fn with_weight_field<W: WeightField + 'static>(mut self, weight_field: W) -> Self {
+        self.weight_field = Some(Arc::new(weight_field));
+        self
+    }
+    /// Same as [`GeneratorBuilder::with_weight_field`] but with a [`WeightField`] which can be shared by multiple generators
+    pub fn with_shared_weight_field(mut self, weight_field: Arc<dyn WeightField>) -> Self {
+        self.weight_field = Some(weight_field);
+        self
+    }
+    /// Specifies some [`NeighbourWeights`] to adjust the models weights from the models already generated around a node. Only used by the [`ModelSelectionHeuristic`]. None by default.
+    ///
+    /// ### Example
+    ///
+    /// Make forests grow in clumps:
+    /// ```ignore
+    /// let builder = builder.with_neighbour_weights(|model_index, neighbours: &[Option<ModelInstance>]| {
+    ///     match model_index {
+    ///         FOREST => 1. + 2. * neighbours.iter().flatten().filter(|n| n.model_index == FOREST).count() as f32,
+    ///         _ => 1.,
+    ///     }
+    /// });
+    /// ```
+    pub fn with_neighbour_weights<W: NeighbourWeights + 'static>(
+        mut self,
+        neighbour_weights: W,
+    ) -> Self {
+        self.neighbour_weights = Some(Arc::new(neighbour_weights));
+        self
+    }
+
+    /// Specifies the amplitude of a random factor (in `[1 - weight_jitter, 1 + weight_jitter]`) applied to each model weight by the [`ModelSelectionHeuristic`]. Clamped to `[0, 0.9]`, `0.` (no jitter) by default.
+    ///
+    /// The factors are drawn again from the seed on each retry, so that consecutive retries explore different distributions instead of failing the same way repeatedly on near-deterministic rules. Generations stay reproducible from their seed.
+    pub fn with_weight_jitter(mut self, weight_jitter: f32) -> Self {
+        self.weight_jitter = weight_jitter;
+        self
+    }
     /// Specifies the [`RngMode`] to be used by the [`Generator`]. Defaults to [`RngMode::RandomSeed`].
     pub fn with_rng(mut self, rng_mode: RngMode) -> Self {
         self.rng_mode = rng_mode;
@@ -168,6 +238,17 @@ impl<G, R, C: CoordinateSystem, T: Grid<C>> GeneratorBuilder<G, R, C, T> {
         self.initial_nodes.extend(initial_nodes);
         self
     }
+
+    /// Registers some [`NodeIndex`] [`ModelVariantIndex`] pairs to be banned initially by the [`Generator`]: these models will never be generated on these nodes. These bans are applied when the generator reinitializes too.
+    ///
+    /// See [`GeneratorBuilder::with_coarse_grid`] to derive the bans from a coarser generation.
+    pub fn with_initial_bans_raw(
+        mut self,
+        initial_bans: Vec<(NodeIndex, ModelVariantIndex)>,
+    ) -> Self {
+        self.initial_bans.extend(initial_bans);
+        self
+    }
 }
 
 // For functions in this impl, we know that self.grid is `Some` thanks to the typing.
@@ -216,6 +297,79 @@ impl<C: CoordinateSystem, R, G: Grid<C>> GeneratorBuilder<Set, R, C, G> {
             Ok(self)
         }
     }
+
+    /// Specifies a [`NodeSelectionHeuristic::DistanceFromNode`] heuristic growing the generation outward from the node at `origin` (or toward it if `furthest` is `true`). `origin` can be a grid position, it is converted to a [`NodeIndex`] with the grid of the builder.
+    ///
+    /// Replaces any heuristic previously given to [`GeneratorBuilder::with_node_heuristic`].
+    pub fn with_distance_heuristic<N: NodeRef<C>>(mut self, origin: N, furthest: bool) -> Self {
+        let grid = self.grid.as_ref().unwrap();
+        self.node_selection_heuristic = NodeSelectionHeuristic::DistanceFromNode {
+            node_index: origin.to_index(grid),
+            furthest,
+        };
+        self
+    }
+}
+
+// For functions in this impl, we know that self.rules and self.grid are `Some` thanks to the typing.
+impl<C: CartesianCoordinates> GeneratorBuilder<Set, Set, C, CartesianGrid<C>> {
+    /// Constrains this generation with the result of a coarser generation, for hierarchical coarse-to-fine generation: a coarse grid is generated first (biomes, rooms, ...) and each of its nodes restricts the models allowed in the corresponding block of nodes of this (fine) grid. This keeps large maps globally coherent.
+    ///
+    /// Each node of this grid is mapped to the node of `coarse` at the same relative position, so the fine grid size does not need to be a multiple of the coarse grid size. `allowed_models` returns the [`ModelIndex`] (all their rotations included) allowed in the block of a coarse node, or `None` if the block is not constrained.
+    ///
+    /// The constraints are registered as initial bans, see [`GeneratorBuilder::with_initial_bans_raw`].
+    ///
+    /// ### Example
+    ///
+    /// ```ignore
+    /// let (_, biomes) = coarse_generator.generate_grid()?;
+    /// let fine_generator = GeneratorBuilder::new()
+    ///     .with_rules(fine_rules)
+    ///     .with_grid(CartesianGrid::new_cartesian_2d(128, 128, false, false))
+    ///     .with_coarse_grid(&biomes, |biome| match biome.model_index {
+    ///         FOREST => Some(forest_models.clone()),
+    ///         _ => None,
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn with_coarse_grid<D, F: Fn(&D) -> Option<Vec<ModelIndex>>>(
+        mut self,
+        coarse: &GridData<C, D, CartesianGrid<C>>,
+        allowed_models: F,
+    ) -> Self {
+        let grid = self.grid.as_ref().unwrap();
+        let rules = self.rules.as_ref().unwrap();
+
+        // Banned model variations for each coarse node, if constrained
+        let coarse_bans: Vec<Option<Vec<ModelVariantIndex>>> = coarse
+            .iter()
+            .map(|coarse_node| {
+                allowed_models(coarse_node).map(|allowed| {
+                    (0..rules.models_count())
+                        .filter(|variant| !allowed.contains(&rules.model(*variant).model_index))
+                        .collect()
+                })
+            })
+            .collect();
+
+        let coarse_grid = coarse.grid();
+        let coarse_coord = |fine: u32, fine_size: u32, coarse_size: u32| {
+            (fine as u64 * coarse_size as u64 / fine_size as u64) as u32
+        };
+        for node_index in 0..grid.total_size() {
+            let pos = grid.pos_from_index(node_index);
+            let coarse_index = coarse_grid.index_from_coords(
+                coarse_coord(pos.x, grid.size_x(), coarse_grid.size_x()),
+                coarse_coord(pos.y, grid.size_y(), coarse_grid.size_y()),
+                coarse_coord(pos.z, grid.size_z(), coarse_grid.size_z()),
+            );
+            if let Some(bans) = &coarse_bans[coarse_index] {
+                self.initial_bans
+                    .extend(bans.iter().map(|variant| (node_index, *variant)));
+            }
+        }
+        self
+    }
 }
 
 // For functions in this impl, we know that self.rules and self.grid are `Some` thanks to the typing.
@@ -263,6 +417,54 @@ impl<C: CoordinateSystem, G: Grid<C>> GeneratorBuilder<Set, Set, C, G> {
         }
     }
 
+    /// Restricts the models allowed on each node of the grid to the [`ModelSet`] given by `data`, before the generation starts. Unlike initial nodes, the models are not forced: the generator still picks one of the allowed models on each node. Allows for example to theme some regions of a map.
+    ///
+    /// The constraints are registered as initial bans, see [`GeneratorBuilder::with_initial_bans_raw`].
+    ///
+    /// ### Example
+    ///
+    /// ```ignore
+    /// let desert: ModelSet = desert_models.iter().copied().collect();
+    /// let constraints = GridData::new(
+    ///     grid.clone(),
+    ///     (0..grid.total_size())
+    ///         .map(|node| match grid.pos_from_index(node).x < grid.size_x() / 2 {
+    ///             true => desert.clone(),
+    ///             false => ModelSet::All,
+    ///         })
+    ///         .collect(),
+    /// );
+    /// let generator = GeneratorBuilder::new()
+    ///     .with_rules(rules)
+    ///     .with_grid(grid)
+    ///     .with_node_constraints(constraints)?
+    ///     .build()?;
+    /// ```
+    pub fn with_node_constraints(
+        mut self,
+        data: GridData<C, ModelSet, G>,
+    ) -> Result<Self, GeneratorBuilderError> {
+        let grid = self.grid.as_ref().unwrap();
+        let rules = self.rules.as_ref().unwrap();
+        if grid.total_size() != data.grid().total_size() {
+            return Err(GeneratorBuilderError::InvalidGridSize(
+                data.grid().total_size(),
+                grid.total_size(),
+            ));
+        }
+        for (node_index, allowed_models) in data.iter().enumerate() {
+            if let ModelSet::All = allowed_models {
+                continue;
+            }
+            self.initial_bans.extend(
+                (0..rules.models_count())
+                    .filter(|variant| !allowed_models.contains(rules.model(*variant).model_index))
+                    .map(|variant| (node_index, variant)),
+            );
+        }
+        Ok(self)
+    }
+
     /// Instantiates a [`Generator`] as specified by the various builder parameters.
     pub fn build(self) -> Result<Generator<C, G>, GeneratorBuilderError> {
         self.internal_build(&mut None)
@@ -288,9 +490,13 @@ impl<C: CoordinateSystem, G: Grid<C>> GeneratorBuilder<Set, Set, C, G> {
             rules,
             grid,
             self.initial_nodes,
+            self.initial_bans,
             self.max_retry_count,
             self.node_selection_heuristic,
             self.model_selection_heuristic,
+            self.weight_field,
+            self.neighbour_weights,
+            self.weight_jitter,
             self.rng_mode,
             self.observers,
             collector,
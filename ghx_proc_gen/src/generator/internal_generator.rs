@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{mem::size_of, sync::Arc};
 
 use bitvec::{bitvec, order::LocalBits, slice::IterOnes, vec::BitVec};
 use ghx_grid::{
@@ -8,23 +8,32 @@ use ghx_grid::{
 };
 use ndarray::{Array, Ix3};
 use rand::{
-    distributions::{Distribution, WeightedIndex},
+    distributions::{Distribution, Uniform},
     rngs::StdRng,
     Rng, SeedableRng,
 };
 
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+#[cfg(feature = "metrics")]
+use tracing::trace_span;
 #[cfg(feature = "debug-traces")]
 use tracing::{debug, info, trace};
 
+#[cfg(feature = "metrics")]
+use super::GenMetrics;
+
 use crate::{GeneratorError, NodeIndex, NodeSetError};
 
 use super::{
     model::{ModelInstance, ModelVariantIndex},
+    model_weights::{CumulativeWeights, ModelWeightsCache},
     node_heuristic::{InternalNodeSelectionHeuristic, NodeSelectionHeuristic},
-    observer::GenerationUpdate,
+    observer::{GenerationUpdate, StampedUpdate},
     rules::Rules,
-    Collector, GenInfo, GeneratedNode, GenerationStatus, ModelSelectionHeuristic, NodeSetStatus,
-    RngMode,
+    Collector, FailedTry, GenInfo, GenStats, GeneratedNode, GenerationStatus, GenerationStep,
+    MemoryFootprint, ModelCensusEntry, ModelSelectionHeuristic, NeighbourWeights, NodeEntropy,
+    NodeSetStatus, PossibleVariant, RngMode, WeightField,
 };
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -43,24 +52,85 @@ struct PropagationEntry {
     model_index: ModelVariantIndex,
 }
 
+/// Converts model variants indexes of `old_rules` into the indexes of the same [`ModelInstance`] in `new_rules`.
+///
+/// Entries whose model instance does not exist anymore in `new_rules` are dropped.
+pub(crate) fn remap_model_variants<C: CoordinateSystem>(
+    old_rules: &Rules<C>,
+    new_rules: &Rules<C>,
+    entries: &Vec<(NodeIndex, ModelVariantIndex)>,
+) -> Vec<(NodeIndex, ModelVariantIndex)> {
+    entries
+        .iter()
+        .filter(|(_, variant_index)| *variant_index < old_rules.models_count())
+        .filter_map(|(node_index, variant_index)| {
+            let instance = old_rules.model(*variant_index);
+            new_rules
+                .instance_variant_index(instance)
+                .map(|new_index| (*node_index, new_index))
+        })
+        .collect()
+}
+
+/// Copy of the generation state of an [`InternalGenerator`]
+#[derive(Clone)]
+pub(crate) struct InternalGeneratorSnapshot {
+    nodes_count: usize,
+    models_count: usize,
+    status: InternalGeneratorStatus,
+    nodes_left_to_generate: usize,
+    seed: u64,
+    trace: Vec<GenerationStep>,
+    pending_selection: Option<(NodeIndex, ModelVariantIndex)>,
+    rng: StdRng,
+    nodes: BitVec<usize>,
+    possible_models_counts: Vec<usize>,
+    node_selection_heuristic: InternalNodeSelectionHeuristic,
+    jitter_factors: Vec<f32>,
+    supports_count: Array<usize, Ix3>,
+}
+
 pub(crate) struct InternalGenerator<C: CoordinateSystem, G: Grid<C>> {
     // === Read-only configuration ===
     pub(crate) grid: G,
     pub(crate) rules: Arc<Rules<C>>,
+    /// Models banned from nodes before any generation, each time the generator (re)initializes
+    pub(crate) initial_bans: Vec<(NodeIndex, ModelVariantIndex)>,
 
     // === Generation state ===
     pub(crate) status: InternalGeneratorStatus,
     pub(crate) nodes_left_to_generate: usize,
     /// Observers signaled with updates of the nodes.
-    pub(crate) observers: Vec<crossbeam_channel::Sender<GenerationUpdate>>,
+    pub(crate) observers: Vec<crossbeam_channel::Sender<StampedUpdate>>,
+    /// Step number of the next update sent to the observers. Never reset, so that the updates of a generator are totally ordered
+    pub(crate) update_step: u64,
     pub(crate) seed: u64,
+    pub(crate) stats: GenStats,
+    #[cfg(feature = "metrics")]
+    pub(crate) metrics: GenMetrics,
+    /// Steps executed since the last reinitialization
+    pub(crate) trace: Vec<GenerationStep>,
+    /// Node and model selected by the heuristics but not committed yet, see [`InternalGenerator::select_next`]
+    pending_selection: Option<(NodeIndex, ModelVariantIndex)>,
     rng: StdRng,
+    /// Heuristic as configured by the user, kept to rebuild [`InternalGenerator::node_selection_heuristic`] when the rules change
+    node_selection_config: NodeSelectionHeuristic,
     /// `nodes[node_index * self.rules.models_count() + model_index]` is true (1) if model with index `model_index` is still allowed on node with index `node_index`
     nodes: BitVec<usize>,
     /// Stores how many models are still possible for a given node
     possible_models_counts: Vec<usize>,
     node_selection_heuristic: InternalNodeSelectionHeuristic,
     model_selection_heuristic: ModelSelectionHeuristic,
+    /// Optional position-dependent factors applied to the models weights during model selection
+    weight_field: Option<Arc<dyn WeightField>>,
+    /// Optional factors applied to the models weights during model selection, from the models generated on the neighbours
+    neighbour_weights: Option<Arc<dyn NeighbourWeights>>,
+    /// Amplitude of the random factors applied to the models weights, drawn again on each (re)initialization
+    pub(crate) weight_jitter: f32,
+    /// `jitter_factors[model_index]` is the random factor currently applied to the weight of `model_index`
+    jitter_factors: Vec<f32>,
+    /// Cumulative weights of the possible models of each node, updated on each ban so that [`ModelSelectionHeuristic::WeightedProbability`] does not iterate all the possible models of a node on each selection
+    weights_cache: ModelWeightsCache,
 
     // === Constraint satisfaction algorithm data ===
     /// Stack of bans to propagate
@@ -73,10 +143,14 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
     pub(crate) fn new(
         rules: Arc<Rules<C>>,
         grid: G,
+        initial_bans: Vec<(NodeIndex, ModelVariantIndex)>,
         node_selection_heuristic: NodeSelectionHeuristic,
         model_selection_heuristic: ModelSelectionHeuristic,
+        weight_field: Option<Arc<dyn WeightField>>,
+        neighbour_weights: Option<Arc<dyn NeighbourWeights>>,
+        weight_jitter: f32,
         rng_mode: RngMode,
-        observers: Vec<crossbeam_channel::Sender<GenerationUpdate>>,
+        observers: Vec<crossbeam_channel::Sender<StampedUpdate>>,
     ) -> Self {
         let models_count = rules.models_count();
         let nodes_count = grid.total_size();
@@ -87,21 +161,33 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             RngMode::RandomSeed => rand::thread_rng().gen::<u64>(),
         };
 
-        let node_selection_heuristic = InternalNodeSelectionHeuristic::from_external(
-            node_selection_heuristic,
-            &rules,
-            grid.total_size(),
-        );
+        let node_selection_config = node_selection_heuristic;
+        let node_selection_heuristic =
+            InternalNodeSelectionHeuristic::from_external(node_selection_heuristic, &rules, &grid);
 
-        Self {
+        let jitter_factors = draw_jitter_factors(seed, weight_jitter, models_count);
+
+        let mut generator = Self {
             grid,
             rules,
+            initial_bans,
 
+            node_selection_config,
             node_selection_heuristic,
             model_selection_heuristic,
+            weight_field,
+            neighbour_weights,
+            weight_jitter,
+            jitter_factors,
+            weights_cache: ModelWeightsCache::None,
 
             rng: StdRng::seed_from_u64(seed),
             seed,
+            stats: GenStats::default(),
+            #[cfg(feature = "metrics")]
+            metrics: GenMetrics::default(),
+            trace: Vec::new(),
+            pending_selection: None,
 
             status: InternalGeneratorStatus::Ongoing,
             nodes: bitvec![1; nodes_count * models_count],
@@ -109,10 +195,13 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             possible_models_counts: vec![models_count; nodes_count],
 
             observers,
+            update_step: 0,
 
             propagation_stack: Vec::new(),
             supports_count: Array::zeros((nodes_count, models_count, direction_count)),
-        }
+        };
+        generator.rebuild_weights_cache();
+        generator
     }
 }
 
@@ -123,18 +212,178 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
     }
 
     #[inline]
-    fn get_model_index(&self, node_index: NodeIndex) -> ModelVariantIndex {
+    pub(crate) fn get_model_index(&self, node_index: NodeIndex) -> ModelVariantIndex {
         self.nodes[node_index * self.rules.models_count()
             ..node_index * self.rules.models_count() + self.rules.models_count()]
             .first_one()
             .unwrap_or(0)
     }
 
+    pub(crate) fn memory_footprint(&self) -> MemoryFootprint {
+        MemoryFootprint {
+            nodes: self.nodes.as_raw_slice().len() * size_of::<usize>(),
+            possible_models_counts: self.possible_models_counts.capacity() * size_of::<usize>(),
+            supports_count: self.supports_count.len() * size_of::<usize>(),
+            node_heuristic: self.node_selection_heuristic.memory_footprint(),
+            jitter_factors: self.jitter_factors.capacity() * size_of::<f32>(),
+            model_weights: self.weights_cache.memory_footprint(),
+            trace: self.trace.capacity() * size_of::<GenerationStep>(),
+        }
+    }
+
+    /// Deep copy of the generator, sharing the same [`Rules`], without any observer.
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            grid: self.grid.clone(),
+            rules: Arc::clone(&self.rules),
+            initial_bans: self.initial_bans.clone(),
+
+            node_selection_config: self.node_selection_config,
+            node_selection_heuristic: self.node_selection_heuristic.clone(),
+            model_selection_heuristic: self.model_selection_heuristic,
+            weight_field: self.weight_field.clone(),
+            neighbour_weights: self.neighbour_weights.clone(),
+            weight_jitter: self.weight_jitter,
+            jitter_factors: self.jitter_factors.clone(),
+            weights_cache: self.weights_cache.clone(),
+
+            rng: self.rng.clone(),
+            seed: self.seed,
+            stats: self.stats.clone(),
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics,
+            trace: self.trace.clone(),
+            pending_selection: self.pending_selection,
+
+            status: self.status,
+            nodes: self.nodes.clone(),
+            nodes_left_to_generate: self.nodes_left_to_generate,
+            possible_models_counts: self.possible_models_counts.clone(),
+
+            observers: Vec::new(),
+            update_step: self.update_step,
+
+            propagation_stack: Vec::new(),
+            supports_count: self.supports_count.clone(),
+        }
+    }
+
+    /// Replaces the RNG state with a new one seeded from `seed`, without reinitializing the generation. [`InternalGenerator::seed`] still references the seed of the generation.
+    pub(crate) fn reseed_rng(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.pending_selection = None;
+    }
+
+    /// Copies the generation state. The propagation stack is always empty between two public API calls and is not part of the snapshot.
+    pub(crate) fn snapshot(&self) -> InternalGeneratorSnapshot {
+        InternalGeneratorSnapshot {
+            nodes_count: self.grid.total_size(),
+            models_count: self.rules.models_count(),
+            status: self.status,
+            nodes_left_to_generate: self.nodes_left_to_generate,
+            seed: self.seed,
+            trace: self.trace.clone(),
+            pending_selection: self.pending_selection,
+            rng: self.rng.clone(),
+            nodes: self.nodes.clone(),
+            possible_models_counts: self.possible_models_counts.clone(),
+            node_selection_heuristic: self.node_selection_heuristic.clone(),
+            jitter_factors: self.jitter_factors.clone(),
+            supports_count: self.supports_count.clone(),
+        }
+    }
+
+    /// Restores a generation state copied by [`InternalGenerator::snapshot`]. The observers are sent a reinitialization followed by all the generated nodes.
+    ///
+    /// Returns `false` and does nothing if the snapshot does not match the grid size and rules models count.
+    pub(crate) fn restore(&mut self, snapshot: &InternalGeneratorSnapshot) -> bool {
+        if snapshot.nodes_count != self.grid.total_size()
+            || snapshot.models_count != self.rules.models_count()
+        {
+            return false;
+        }
+        self.status = snapshot.status;
+        self.nodes_left_to_generate = snapshot.nodes_left_to_generate;
+        self.seed = snapshot.seed;
+        self.trace = snapshot.trace.clone();
+        self.pending_selection = snapshot.pending_selection;
+        self.rng = snapshot.rng.clone();
+        self.nodes = snapshot.nodes.clone();
+        self.possible_models_counts = snapshot.possible_models_counts.clone();
+        self.node_selection_heuristic = snapshot.node_selection_heuristic.clone();
+        self.jitter_factors = snapshot.jitter_factors.clone();
+        self.supports_count = snapshot.supports_count.clone();
+        self.propagation_stack.clear();
+        // The cached weights are derived from the possible models, no need to store them in the snapshot
+        self.rebuild_weights_cache();
+
+        if !self.observers.is_empty() {
+            self.notify_observers(GenerationUpdate::Reinitializing(self.seed));
+            for node_index in 0..self.grid.total_size() {
+                if self.possible_models_counts[node_index] == 1 {
+                    self.notify_observers(GenerationUpdate::Generated(GeneratedNode {
+                        node_index,
+                        model_instance: *self.rules.model(self.get_model_index(node_index)),
+                    }));
+                }
+            }
+        }
+        true
+    }
+
+    /// Swaps the [`Rules`] used by the generator and reinitializes it with its current seed.
+    ///
+    /// The initial bans are remapped to the variants of the new rules, see [`remap_model_variants`].
+    pub(crate) fn replace_rules(
+        &mut self,
+        rules: Arc<Rules<C>>,
+        collector: &mut Collector,
+        initial_nodes: &Vec<(NodeIndex, ModelVariantIndex)>,
+    ) -> Result<GenerationStatus, NodeSetError> {
+        self.initial_bans = remap_model_variants(&self.rules, &rules, &self.initial_bans);
+        self.node_selection_heuristic = InternalNodeSelectionHeuristic::from_external(
+            self.node_selection_config,
+            &rules,
+            &self.grid,
+        );
+        self.supports_count = Array::zeros((
+            self.grid.total_size(),
+            rules.models_count(),
+            self.grid.directions_count(),
+        ));
+        self.rules = rules;
+
+        self.reset_with_seed(self.seed);
+        self.stats.reinitializations += 1;
+
+        #[cfg(feature = "debug-traces")]
+        info!(
+            "Replacing rules of generator, reinitializing with seed {}",
+            self.seed
+        );
+
+        self.notify_observers(GenerationUpdate::Reinitializing(self.seed));
+        self.pregen(collector, initial_nodes)
+    }
+
     #[inline]
     pub(crate) fn is_valid_node_index(&self, node_index: NodeIndex) -> bool {
         node_index < self.possible_models_counts.len()
     }
 
+    #[inline]
+    pub(crate) fn possible_models_count(&self, node_index: NodeIndex) -> usize {
+        self.possible_models_counts[node_index]
+    }
+
+    pub(crate) fn ungenerated_nodes(&self) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.possible_models_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 1)
+            .map(|(node_index, _)| node_index)
+    }
+
     pub(crate) fn possible_model_indexes(
         &self,
         node_index: NodeIndex,
@@ -157,6 +406,8 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
     fn reset_with_seed(&mut self, seed: u64) {
         self.seed = seed;
         self.rng = StdRng::seed_from_u64(seed);
+        self.jitter_factors =
+            draw_jitter_factors(seed, self.weight_jitter, self.rules.models_count());
 
         self.status = InternalGeneratorStatus::Ongoing;
 
@@ -165,7 +416,10 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
         self.nodes_left_to_generate = nodes_count;
         self.possible_models_counts = vec![self.rules.models_count(); nodes_count];
         self.propagation_stack = Vec::new();
+        self.trace.clear();
+        self.pending_selection = None;
         self.node_selection_heuristic.reinitialize();
+        self.rebuild_weights_cache();
     }
 
     /// Advances the seed
@@ -176,7 +430,18 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
     ) -> GenerationStatus {
         // Gen next seed from current rng
         let next_seed = self.rng.gen::<u64>();
-        self.reset_with_seed(next_seed);
+        self.reinitialize_with_seed(next_seed, collector, initial_nodes)
+    }
+
+    /// Reinitializes the generator with the given seed
+    pub(crate) fn reinitialize_with_seed(
+        &mut self,
+        seed: u64,
+        collector: &mut Collector,
+        initial_nodes: &Vec<(NodeIndex, ModelVariantIndex)>,
+    ) -> GenerationStatus {
+        self.reset_with_seed(seed);
+        self.stats.reinitializations += 1;
 
         #[cfg(feature = "debug-traces")]
         info!(
@@ -184,9 +449,7 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             self.seed, self.status
         );
 
-        for obs in &mut self.observers {
-            let _ = obs.send(GenerationUpdate::Reinitializing(self.seed));
-        }
+        self.notify_observers(GenerationUpdate::Reinitializing(self.seed));
 
         // Since Pre-gen succeeded. The following calls will always succeed.
         let _ = self.initialize_supports_count(collector);
@@ -236,6 +499,17 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             }
         }
 
+        // Apply the initial bans. Done once all the supports counts are initialized since a ban resets the supports of the banned model.
+        for ban_index in 0..self.initial_bans.len() {
+            let (node, model) = self.initial_bans[ban_index];
+            if self.is_model_possible(node, model) {
+                if let Err(err) = self.ban_model_from_node(node, model, collector) {
+                    self.signal_contradiction(node);
+                    return Err(err);
+                }
+            }
+        }
+
         // Propagate the potential bans that occurred during initialization
         if let Err(err) = self.propagate(collector) {
             self.signal_contradiction(err.node_index);
@@ -274,6 +548,7 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
         collector: &mut Collector,
         initial_nodes: &Vec<(NodeIndex, ModelVariantIndex)>,
     ) -> Result<GenerationStatus, NodeSetError> {
+        self.check_nodes_entries(&self.initial_bans)?;
         self.initialize_supports_count(collector)?;
         // If done already, we still try to set all nodes and succeed only if initial nodes spawn requests match the already generated nodes.
         self.pregen_initial_nodes(collector, initial_nodes)
@@ -303,6 +578,22 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
     /// - node_index is invalid
     /// - model_variant_index is invalid
     /// - model_variant_index is not possible on node_index
+    /// Returns an error if an entry references a node or a model variant that does not exist
+    pub(crate) fn check_nodes_entries(
+        &self,
+        entries: &Vec<(NodeIndex, ModelVariantIndex)>,
+    ) -> Result<(), NodeSetError> {
+        for (node_index, model_variant_index) in entries.iter() {
+            if *model_variant_index >= self.rules.models_count() {
+                return Err(NodeSetError::InvalidModelIndex(*model_variant_index));
+            }
+            if !self.is_valid_node_index(*node_index) {
+                return Err(NodeSetError::InvalidNodeIndex(*node_index));
+            }
+        }
+        Ok(())
+    }
+
     /// Returns [`Ok(NodeSetStatus::CanBeSet)`] if model_variant_index can be generated on node_index and [`Ok(NodeSetStatus::AlreadySet)`] if node_index is already generated to model_variant_index
     fn check_set_and_propagate_parameters(
         &self,
@@ -327,6 +618,7 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
     pub(crate) fn generate(
         &mut self,
         collector: &mut Collector,
+        failed_tries: &mut Option<&mut Vec<FailedTry>>,
         retry_count: u32,
         initial_nodes: &Vec<(NodeIndex, ModelVariantIndex)>,
     ) -> Result<GenInfo, GeneratorError> {
@@ -357,12 +649,18 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
                         try_count: try_index + 1,
                     })
                 }
-                Err(err) => {
-                    last_error = Some(err);
+                Err(error) => {
+                    if let Some(failed_tries) = failed_tries {
+                        failed_tries.push(FailedTry {
+                            seed: self.seed,
+                            error,
+                        });
+                    }
+                    last_error = Some(error);
                 }
             }
         }
-        Err(last_error.unwrap()) // We know that last_err is Some
+        Err(last_error.unwrap()) // We know that there is at least one failed try
     }
 
     /// Top-level handler of public API calls.
@@ -419,16 +717,216 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
         self.unchecked_select_and_propagate(collector)
     }
 
+    /// Top-level handler of public API calls.
+    ///
+    /// Selects a node and a model with the heuristics without committing them. Returns the same pending selection until it is committed or invalidated by another change to the nodes.
+    ///
+    /// Returns `Ok(None)` if the generation is done.
+    pub(crate) fn select_next(
+        &mut self,
+    ) -> Result<Option<(NodeIndex, ModelVariantIndex)>, GeneratorError> {
+        match self.status {
+            InternalGeneratorStatus::Ongoing => (),
+            InternalGeneratorStatus::Done => return Ok(None),
+            InternalGeneratorStatus::Failed(err) => return Err(err),
+        }
+        if self.pending_selection.is_none() {
+            self.pending_selection = self.select_node_and_model();
+        }
+        Ok(self.pending_selection)
+    }
+
+    /// Top-level handler of public API calls.
+    ///
+    /// Commits `model_variant_index` on the node of the pending selection. Committing another model than the proposed one is recorded as a [`GenerationStep::Set`].
+    pub(crate) fn commit(
+        &mut self,
+        model_variant_index: ModelVariantIndex,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, NodeSetError> {
+        match self.status {
+            InternalGeneratorStatus::Ongoing => (),
+            InternalGeneratorStatus::Done => return Ok(GenerationStatus::Done),
+            InternalGeneratorStatus::Failed(err) => return Err(err.into()),
+        }
+        let Some((node_index, proposed_model_index)) = self.pending_selection else {
+            return Err(NodeSetError::NoPendingSelection);
+        };
+        if model_variant_index == proposed_model_index {
+            return Ok(self.unchecked_commit_selection(
+                node_index,
+                model_variant_index,
+                collector,
+            )?);
+        }
+        // The node of a pending selection is never generated
+        self.check_set_and_propagate_parameters(node_index, model_variant_index)?;
+        Ok(self.unchecked_set_and_propagate(node_index, model_variant_index, collector)?)
+    }
+
+    /// Top-level handler of public API calls.
+    ///
+    /// Commits the proposed model of the pending selection, or selects and commits a new node and model if there is no pending selection.
+    pub(crate) fn auto_commit(
+        &mut self,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, GeneratorError> {
+        match self.status {
+            InternalGeneratorStatus::Ongoing => (),
+            InternalGeneratorStatus::Done => return Ok(GenerationStatus::Done),
+            InternalGeneratorStatus::Failed(err) => return Err(err),
+        }
+        match self.pending_selection {
+            Some((node_index, model_variant_index)) => {
+                self.unchecked_commit_selection(node_index, model_variant_index, collector)
+            }
+            None => self.unchecked_select_and_propagate(collector),
+        }
+    }
+
+    /// Top-level handler of public API calls.
+    pub(crate) fn ban_and_propagate(
+        &mut self,
+        node_index: NodeIndex,
+        model_variant_index: ModelVariantIndex,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, NodeSetError> {
+        match self.status {
+            InternalGeneratorStatus::Ongoing => (),
+            InternalGeneratorStatus::Done => return Ok(GenerationStatus::Done),
+            InternalGeneratorStatus::Failed(err) => return Err(err.into()),
+        }
+
+        if model_variant_index >= self.rules.models_count() {
+            return Err(NodeSetError::InvalidModelIndex(model_variant_index));
+        }
+        if !self.is_valid_node_index(node_index) {
+            return Err(NodeSetError::InvalidNodeIndex(node_index));
+        }
+        if !self.is_model_possible(node_index, model_variant_index) {
+            // Nothing to do. We can't be done here
+            return Ok(GenerationStatus::Ongoing);
+        }
+
+        Ok(self.unchecked_ban_and_propagate(node_index, model_variant_index, collector)?)
+    }
+
+    /// Top-level handler of public API calls.
+    pub(crate) fn replay_step(
+        &mut self,
+        step: GenerationStep,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, NodeSetError> {
+        match step {
+            GenerationStep::Selected { .. } => Ok(self.select_and_propagate(collector)?),
+            GenerationStep::Set {
+                node_index,
+                model_variant_index,
+            } => self.set_and_propagate(node_index, model_variant_index, collector),
+            GenerationStep::Banned {
+                node_index,
+                model_variant_index,
+            } => self.ban_and_propagate(node_index, model_variant_index, collector),
+        }
+    }
+
+    /// Top-level handler of public API calls.
+    ///
+    /// Same as [`InternalGenerator::replay_step`], but a [`GenerationStep::Selected`] step commits its recorded node and model instead of the ones selected by the heuristics, so that it does not depend on the RNG state. The heuristics are still run to advance the RNG as they did when the step was recorded.
+    pub(crate) fn replay_recorded_step(
+        &mut self,
+        step: GenerationStep,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, NodeSetError> {
+        let GenerationStep::Selected {
+            node_index,
+            model_variant_index,
+        } = step
+        else {
+            return self.replay_step(step, collector);
+        };
+        match self.status {
+            InternalGeneratorStatus::Ongoing => (),
+            InternalGeneratorStatus::Done => return Ok(GenerationStatus::Done),
+            InternalGeneratorStatus::Failed(err) => return Err(err.into()),
+        }
+        match self.check_set_and_propagate_parameters(node_index, model_variant_index)? {
+            NodeSetStatus::AlreadySet => return Ok(GenerationStatus::Ongoing),
+            NodeSetStatus::CanBeSet => (),
+        }
+        self.select_node_and_model();
+        Ok(self.unchecked_commit_selection(node_index, model_variant_index, collector)?)
+    }
+
+    /// Measured wrapper of [`InternalGenerator::unchecked_set_and_propagate_step`], with the same requirements
+    fn unchecked_set_and_propagate(
+        &mut self,
+        node_index: NodeIndex,
+        model_variant_index: ModelVariantIndex,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, GeneratorError> {
+        #[cfg(feature = "metrics")]
+        let (_span, step_start) = (
+            trace_span!("set_and_propagate", node_index).entered(),
+            Instant::now(),
+        );
+        let result =
+            self.unchecked_set_and_propagate_step(node_index, model_variant_index, collector);
+        #[cfg(feature = "metrics")]
+        self.metrics.record_step(step_start.elapsed());
+        result
+    }
+
+    /// Measured wrapper of [`InternalGenerator::unchecked_ban_and_propagate_step`], with the same requirements
+    fn unchecked_ban_and_propagate(
+        &mut self,
+        node_index: NodeIndex,
+        model_variant_index: ModelVariantIndex,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, GeneratorError> {
+        #[cfg(feature = "metrics")]
+        let (_span, step_start) = (
+            trace_span!("ban_and_propagate", node_index).entered(),
+            Instant::now(),
+        );
+        let result =
+            self.unchecked_ban_and_propagate_step(node_index, model_variant_index, collector);
+        #[cfg(feature = "metrics")]
+        self.metrics.record_step(step_start.elapsed());
+        result
+    }
+
+    /// Measured wrapper of [`InternalGenerator::unchecked_commit_selection_step`], with the same requirements
+    fn unchecked_commit_selection(
+        &mut self,
+        node_index: NodeIndex,
+        selected_model_index: ModelVariantIndex,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, GeneratorError> {
+        #[cfg(feature = "metrics")]
+        let (_span, step_start) = (
+            trace_span!("commit_selection", node_index).entered(),
+            Instant::now(),
+        );
+        let result =
+            self.unchecked_commit_selection_step(node_index, selected_model_index, collector);
+        #[cfg(feature = "metrics")]
+        self.metrics.record_step(step_start.elapsed());
+        result
+    }
+
     /// - node_index and model_variant_index must be valid
     /// - model_variant_index must be possible on node_index
     /// - node_index must not be generated yet
     /// - Generator internal status must be [InternalGeneratorStatus::Ongoing]
-    fn unchecked_set_and_propagate(
+    fn unchecked_set_and_propagate_step(
         &mut self,
         node_index: NodeIndex,
         model_variant_index: ModelVariantIndex,
         collector: &mut Collector,
     ) -> Result<GenerationStatus, GeneratorError> {
+        // Any change to the nodes invalidates a pending selection
+        self.pending_selection = None;
         #[cfg(feature = "debug-traces")]
         debug!(
             "Set model {:?} named '{}' for node {} at position {:?}",
@@ -442,6 +940,11 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             self.signal_selection(collector, node_index, model_variant_index);
         }
 
+        self.stats.steps += 1;
+        self.trace.push(GenerationStep::Set {
+            node_index,
+            model_variant_index,
+        });
         self.handle_selected(node_index, model_variant_index);
 
         if let Err(err) = self.propagate(collector) {
@@ -452,23 +955,75 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
         Ok(self.check_if_done())
     }
 
+    /// - node_index and model_variant_index must be valid
+    /// - model_variant_index must be possible on node_index
+    /// - Generator internal status must be [InternalGeneratorStatus::Ongoing]
+    fn unchecked_ban_and_propagate_step(
+        &mut self,
+        node_index: NodeIndex,
+        model_variant_index: ModelVariantIndex,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, GeneratorError> {
+        // Any change to the nodes invalidates a pending selection
+        self.pending_selection = None;
+        #[cfg(feature = "debug-traces")]
+        debug!(
+            "Ban model {:?} named '{}' from node {} at position {:?}",
+            self.rules.model(model_variant_index),
+            self.rules.name_unchecked_str(model_variant_index),
+            node_index,
+            self.grid.pos_from_index(node_index)
+        );
+
+        self.stats.steps += 1;
+        self.trace.push(GenerationStep::Banned {
+            node_index,
+            model_variant_index,
+        });
+
+        if let Err(err) = self.ban_model_from_node(node_index, model_variant_index, collector) {
+            self.signal_contradiction(err.node_index);
+            return Err(err);
+        }
+        if let Err(err) = self.propagate(collector) {
+            self.signal_contradiction(err.node_index);
+            return Err(err);
+        };
+
+        Ok(self.check_if_done())
+    }
+
     fn unchecked_select_and_propagate(
         &mut self,
         collector: &mut Collector,
     ) -> Result<GenerationStatus, GeneratorError> {
-        let node_index = match self
-            .node_selection_heuristic
-            .select_node(&self.possible_models_counts, &mut self.rng)
-        {
-            Some(index) => index,
-            None => {
-                // TODO Here, should not be able to find None anymore.
-                self.status = InternalGeneratorStatus::Done;
-                return Ok(GenerationStatus::Done);
-            }
+        let Some((node_index, selected_model_index)) = self.select_node_and_model() else {
+            // TODO Here, should not be able to find None anymore.
+            self.status = InternalGeneratorStatus::Done;
+            return Ok(GenerationStatus::Done);
         };
+        self.unchecked_commit_selection(node_index, selected_model_index, collector)
+    }
+
+    /// Selects a node not yet generated and a model for it with the heuristics. Returns `None` if all the nodes are generated.
+    fn select_node_and_model(&mut self) -> Option<(NodeIndex, ModelVariantIndex)> {
+        let node_index = self
+            .node_selection_heuristic
+            .select_node(&self.possible_models_counts, &mut self.rng)?;
         // We found a node not yet generated. "Observe/collapse" the node: select a model for the node
-        let selected_model_index = self.select_model(node_index);
+        Some((node_index, self.select_model(node_index)))
+    }
+
+    /// - node_index and selected_model_index must be valid
+    /// - selected_model_index must be possible on node_index
+    /// - Generator internal status must be [InternalGeneratorStatus::Ongoing]
+    fn unchecked_commit_selection_step(
+        &mut self,
+        node_index: NodeIndex,
+        selected_model_index: ModelVariantIndex,
+        collector: &mut Collector,
+    ) -> Result<GenerationStatus, GeneratorError> {
+        self.pending_selection = None;
 
         #[cfg(feature = "debug-traces")]
         debug!(
@@ -482,6 +1037,11 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             self.signal_selection(collector, node_index, selected_model_index);
         }
 
+        self.stats.steps += 1;
+        self.trace.push(GenerationStep::Selected {
+            node_index,
+            model_variant_index: selected_model_index,
+        });
         self.handle_selected(node_index, selected_model_index);
 
         if let Err(err) = self.propagate(collector) {
@@ -495,33 +1055,307 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
     /// There should at least be one possible model for this node index. May panic otherwise.
     fn select_model(&mut self, node_index: NodeIndex) -> usize {
         match self.model_selection_heuristic {
-            ModelSelectionHeuristic::WeightedProbability => {
-                let possible_models: Vec<ModelVariantIndex> = (0..self.rules.models_count())
-                    .filter(|&model_index| self.is_model_possible(node_index, model_index))
-                    .collect();
-
-                // TODO May cache the current sum of weights at each node.
-                let weighted_distribution = WeightedIndex::new(
-                    possible_models
-                        .iter()
-                        .map(|&model_index| self.rules.weight_unchecked(model_index)),
-                )
-                .unwrap();
-                possible_models[weighted_distribution.sample(&mut self.rng)]
+            ModelSelectionHeuristic::WeightedProbability => match &self.weights_cache {
+                ModelWeightsCache::Float(weights) => {
+                    let total_weight = weights.total(node_index) as f32;
+                    match total_weight > 0. && total_weight.is_finite() {
+                        true => {
+                            let sampled_weight =
+                                Uniform::new(0., total_weight).sample(&mut self.rng);
+                            let model_index = weights.sample(node_index, sampled_weight as f64);
+                            self.closest_possible_model(node_index, model_index)
+                        }
+                        // Fallback to the weights without the weight field if it zeroes all the possible models
+                        false => self.sample_unadjusted_weights(node_index),
+                    }
+                }
+                ModelWeightsCache::FixedPoint(weights) => {
+                    let total_weight = weights.total(node_index);
+                    match total_weight > 0 {
+                        true => weights.sample(node_index, self.rng.gen_range(0..total_weight)),
+                        // Fallback to the fixed-point weights as is if the factors zero all the possible models
+                        false => {
+                            let possible_models: Vec<ModelVariantIndex> =
+                                self.possible_model_indexes(node_index).collect();
+                            let fixed_point_weights: Vec<u64> = possible_models
+                                .iter()
+                                .map(|&model_index| {
+                                    self.rules.fixed_point_weight_unchecked(model_index) as u64
+                                })
+                                .collect();
+                            possible_models
+                                [sample_fixed_point_index(&mut self.rng, &fixed_point_weights)]
+                        }
+                    }
+                }
+                // Weights are not cached when they depend on the generated neighbours, see [`ModelWeightsCache`]
+                ModelWeightsCache::None if self.rules.has_fixed_point_weights() => {
+                    let possible_models: Vec<ModelVariantIndex> =
+                        self.possible_model_indexes(node_index).collect();
+                    let fixed_point_weights =
+                        self.fixed_point_weights(node_index, &possible_models);
+                    possible_models[sample_fixed_point_index(&mut self.rng, &fixed_point_weights)]
+                }
+                ModelWeightsCache::None => {
+                    let possible_models: Vec<ModelVariantIndex> =
+                        self.possible_model_indexes(node_index).collect();
+                    let models_weights = self.models_weights(&possible_models);
+                    let adjusted_weights =
+                        self.adjusted_weights(node_index, &possible_models, &models_weights);
+                    // Fallback to the weights without adjustments if they zero all the possible models
+                    let (weights, total_weight) =
+                        match weights_total(adjusted_weights.iter().copied()) {
+                            Some(total_weight) => (adjusted_weights, total_weight),
+                            None => {
+                                let total_weight =
+                                    weights_total(models_weights.iter().copied()).unwrap();
+                                (models_weights, total_weight)
+                            }
+                        };
+                    let sampled_weight = Uniform::new(0., total_weight).sample(&mut self.rng);
+                    sample_weighted_item(sampled_weight, possible_models.into_iter().zip(weights))
+                }
+            },
+            ModelSelectionHeuristic::UniformRandom => {
+                let selected = self
+                    .rng
+                    .gen_range(0..self.possible_models_counts[node_index]);
+                self.possible_model_indexes(node_index)
+                    .nth(selected)
+                    .unwrap()
+            }
+            ModelSelectionHeuristic::HighestWeight => {
+                let possible_models: Vec<ModelVariantIndex> =
+                    self.possible_model_indexes(node_index).collect();
+                let mut weights = self.models_weights(&possible_models);
+                if self.has_weights_adjustments() {
+                    let adjusted_weights =
+                        self.adjusted_weights(node_index, &possible_models, &weights);
+                    // Same fallback as the weighted probability
+                    if adjusted_weights.iter().any(|weight| *weight > 0.) {
+                        weights = adjusted_weights;
+                    }
+                }
+                // First model with the highest weight, so that ties are resolved deterministically
+                let mut selected = 0;
+                for (index, weight) in weights.iter().enumerate().skip(1) {
+                    if *weight > weights[selected] {
+                        selected = index;
+                    }
+                }
+                possible_models[selected]
             }
         }
     }
 
-    fn handle_selected(&mut self, node_index: usize, selected_model_index: ModelVariantIndex) {
-        // Iterate all the possible models because we don't have an easy way to iterate only the models possible at node_index. But we'll filter impossible models right away. TODO: benchmark iter_ones
-        for model_index in 0..self.rules.models_count() {
-            if model_index == selected_model_index {
-                continue;
+    /// Samples a possible model of a node with the weights of the models, weight jitter included but without any [`WeightField`] or [`NeighbourWeights`]
+    fn sample_unadjusted_weights(&mut self, node_index: NodeIndex) -> ModelVariantIndex {
+        // The possible models are iterated twice instead of collecting them and their weights
+        let total_weight = weights_total(
+            self.possible_model_indexes(node_index)
+                .map(|model_index| self.model_weight(model_index)),
+        )
+        .unwrap();
+        let sampled_weight = Uniform::new(0., total_weight).sample(&mut self.rng);
+        sample_weighted_item(
+            sampled_weight,
+            self.possible_model_indexes(node_index)
+                .map(|model_index| (model_index, self.model_weight(model_index))),
+        )
+    }
+
+    /// Returns `model_index` if it is still possible on the node, else the closest possible model.
+    ///
+    /// Guards the sampling of [`ModelWeightsCache::Float`] against the rounding errors accumulated by the bans, which could leave a tiny weight to a banned model.
+    fn closest_possible_model(
+        &self,
+        node_index: NodeIndex,
+        model_index: ModelVariantIndex,
+    ) -> ModelVariantIndex {
+        if self.is_model_possible(node_index, model_index) {
+            return model_index;
+        }
+        let models_count = self.rules.models_count();
+        let node_models = &self.nodes[node_index * models_count..(node_index + 1) * models_count];
+        node_models[model_index..]
+            .first_one()
+            .map(|offset| model_index + offset)
+            .or_else(|| node_models[..model_index].last_one())
+            .unwrap_or(model_index)
+    }
+
+    /// Recomputes the cached weights of the possible models of each node, see [`ModelWeightsCache`]
+    fn rebuild_weights_cache(&mut self) {
+        let cacheable = matches!(
+            self.model_selection_heuristic,
+            ModelSelectionHeuristic::WeightedProbability
+        ) && self.neighbour_weights.is_none();
+        if !cacheable {
+            self.weights_cache = ModelWeightsCache::None;
+            return;
+        }
+        let nodes_count = self.grid.total_size();
+        let models_count = self.rules.models_count();
+        self.weights_cache = match self.rules.has_fixed_point_weights() {
+            true => {
+                let mut weights = CumulativeWeights::new(nodes_count, models_count);
+                for node_index in 0..nodes_count {
+                    weights.set_node(
+                        node_index,
+                        (0..models_count).map(|model_index| {
+                            match self.is_model_possible(node_index, model_index) {
+                                true => self.cached_fixed_point_weight(node_index, model_index),
+                                false => 0,
+                            }
+                        }),
+                    );
+                }
+                ModelWeightsCache::FixedPoint(weights)
             }
-            if !self.is_model_possible(node_index, model_index) {
-                continue;
+            false => {
+                let mut weights = CumulativeWeights::new(nodes_count, models_count);
+                for node_index in 0..nodes_count {
+                    weights.set_node(
+                        node_index,
+                        (0..models_count).map(|model_index| {
+                            match self.is_model_possible(node_index, model_index) {
+                                true => self.cached_weight(node_index, model_index),
+                                false => 0.,
+                            }
+                        }),
+                    );
+                }
+                ModelWeightsCache::Float(weights)
             }
+        };
+    }
+
+    /// Returns the weight of a model on a node, weight jitter and [`WeightField`] included, as cached by [`ModelWeightsCache::Float`]
+    fn cached_weight(&self, node_index: NodeIndex, model_index: ModelVariantIndex) -> f64 {
+        let mut weight = self.model_weight(model_index);
+        if let Some(weight_field) = &self.weight_field {
+            weight *= weight_field.weight(node_index, self.rules.model(model_index).model_index);
+        }
+        weight.max(0.) as f64
+    }
 
+    /// Returns the fixed-point weight of a model on a node, weight jitter and [`WeightField`] included, as cached by [`ModelWeightsCache::FixedPoint`]. Rounded the same way as in [`InternalGenerator::fixed_point_weights`]
+    fn cached_fixed_point_weight(
+        &self,
+        node_index: NodeIndex,
+        model_index: ModelVariantIndex,
+    ) -> u64 {
+        let weight = self.rules.fixed_point_weight_unchecked(model_index) as u64;
+        if self.weight_jitter == 0. && self.weight_field.is_none() {
+            return weight;
+        }
+        let mut factor = self.jitter_factors[model_index];
+        if let Some(weight_field) = &self.weight_field {
+            factor *= weight_field.weight(node_index, self.rules.model(model_index).model_index);
+        }
+        (weight as f64 * factor.max(0.) as f64).round() as u64
+    }
+
+    #[inline]
+    fn has_weights_adjustments(&self) -> bool {
+        self.weight_field.is_some() || self.neighbour_weights.is_some()
+    }
+
+    /// Returns the weight of a model, weight jitter included
+    #[inline]
+    fn model_weight(&self, model_index: ModelVariantIndex) -> f32 {
+        self.rules.weight_unchecked(model_index) * self.jitter_factors[model_index]
+    }
+
+    /// Returns the weights of the given models, weight jitter included
+    fn models_weights(&self, models: &Vec<ModelVariantIndex>) -> Vec<f32> {
+        models
+            .iter()
+            .map(|&model_index| self.model_weight(model_index))
+            .collect()
+    }
+
+    /// Returns the fixed-point weights of the given models on a node.
+    ///
+    /// The weight jitter and weights adjustments are floating point factors: when used, they are applied to the fixed-point weights and rounded. If they zero all the models, the fixed-point weights are used as is.
+    fn fixed_point_weights(
+        &self,
+        node_index: NodeIndex,
+        models: &Vec<ModelVariantIndex>,
+    ) -> Vec<u64> {
+        let fixed_point_weights: Vec<u64> = models
+            .iter()
+            .map(|&model_index| self.rules.fixed_point_weight_unchecked(model_index) as u64)
+            .collect();
+        if self.weight_jitter == 0. && !self.has_weights_adjustments() {
+            return fixed_point_weights;
+        }
+        let mut factors: Vec<f32> = models
+            .iter()
+            .map(|&model_index| self.jitter_factors[model_index])
+            .collect();
+        if self.has_weights_adjustments() {
+            factors = self.adjusted_weights(node_index, models, &factors);
+        }
+        let adjusted_weights: Vec<u64> = fixed_point_weights
+            .iter()
+            .zip(factors)
+            .map(|(weight, factor)| (*weight as f64 * factor.max(0.) as f64).round() as u64)
+            .collect();
+        match adjusted_weights.iter().any(|weight| *weight > 0) {
+            true => adjusted_weights,
+            false => fixed_point_weights,
+        }
+    }
+
+    /// Applies the [`WeightField`] and the [`NeighbourWeights`] to the weights of the possible models of a node
+    fn adjusted_weights(
+        &self,
+        node_index: NodeIndex,
+        possible_models: &Vec<ModelVariantIndex>,
+        models_weights: &Vec<f32>,
+    ) -> Vec<f32> {
+        let neighbours_models = match self.neighbour_weights {
+            Some(_) => {
+                let mut neighbours = vec![None; self.grid.directions_count()];
+                self.grid
+                    .get_neighbours_in_all_directions(node_index, &mut neighbours);
+                neighbours
+                    .iter()
+                    .map(|neighbour| match neighbour {
+                        Some(neighbour) if self.possible_models_counts[*neighbour] == 1 => {
+                            Some(*self.rules.model(self.get_model_index(*neighbour)))
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        possible_models
+            .iter()
+            .zip(models_weights.iter())
+            .map(|(&model_variant_index, weight)| {
+                let model_index = self.rules.model(model_variant_index).model_index;
+                let mut weight = *weight;
+                if let Some(weight_field) = &self.weight_field {
+                    weight *= weight_field.weight(node_index, model_index);
+                }
+                if let Some(neighbour_weights) = &self.neighbour_weights {
+                    weight *= neighbour_weights.weight(model_index, &neighbours_models);
+                }
+                weight
+            })
+            .collect()
+    }
+
+    fn handle_selected(&mut self, node_index: usize, selected_model_index: ModelVariantIndex) {
+        // Only iterate the models still possible on the node. `iter_ones` skips whole blocks of banned models at once, which is much faster than testing each model when there are many models and few of them are left.
+        let removed_models: Vec<ModelVariantIndex> = self
+            .possible_model_indexes(node_index)
+            .filter(|&model_index| model_index != selected_model_index)
+            .collect();
+        for model_index in removed_models {
             // Enqueue removal for propagation
             self.enqueue_removal_to_propagate(node_index, model_index);
 
@@ -530,15 +1364,11 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
                 self.supports_count[(node_index, model_index, dir)] = 0;
             }
         }
-        // Remove eliminated possibilities (after enqueuing the propagation entries because we currently filter on the possible models)
-        // TODO Remove alias ?
+        self.weights_cache
+            .keep_only(node_index, selected_model_index);
+        // Remove eliminated possibilities
         let models_count = self.rules.models_count();
-        for mut bit in self.nodes
-            [node_index * models_count..node_index * models_count + models_count]
-            .iter_mut()
-        {
-            *bit = false;
-        }
+        self.nodes[node_index * models_count..node_index * models_count + models_count].fill(false);
         self.nodes
             .set(node_index * models_count + selected_model_index, true);
         self.possible_models_counts[node_index] = 1;
@@ -566,12 +1396,18 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
 
         let number_of_models_left = &mut self.possible_models_counts[node_index];
         *number_of_models_left = number_of_models_left.saturating_sub(1);
+        self.stats.bans += 1;
+        #[cfg(feature = "metrics")]
+        {
+            self.metrics.bans += 1;
+        }
 
         self.node_selection_heuristic.handle_ban(
             node_index,
             model,
             self.rules.weight_unchecked(model),
         );
+        self.weights_cache.remove(node_index, model);
 
         #[cfg(feature = "debug-traces")]
         trace!(
@@ -624,6 +1460,10 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             node_index,
             model_index,
         });
+        self.stats.max_propagation_queue_len = self
+            .stats
+            .max_propagation_queue_len
+            .max(self.propagation_stack.len());
     }
 
     /// Returns [`GeneratorError`] if a node has no possible models left. Else, returns `Ok`.
@@ -635,6 +1475,10 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
 
         let mut neighbours = vec![None; self.grid.directions_count()];
         while let Some(from) = self.propagation_stack.pop() {
+            #[cfg(feature = "metrics")]
+            {
+                self.metrics.propagation_pops += 1;
+            }
             #[cfg(feature = "debug-traces")]
             trace!(
                 "Propagate removal of model {:?} named '{}' for node {}",
@@ -654,6 +1498,10 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
                             &mut self.supports_count[(*neighbour_index, model, dir)];
                         if *supports_count > 0 {
                             *supports_count -= 1;
+                            #[cfg(feature = "metrics")]
+                            {
+                                self.metrics.support_decrements += 1;
+                            }
                             // When we find a model which is now unsupported, we queue a ban
                             // We check > 0  and for == because we only want to queue the event once.
                             if *supports_count == 0 {
@@ -677,10 +1525,7 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
             node_index,
             model_instance: self.rules.model(model_index).clone(),
         };
-        let update = GenerationUpdate::Generated(grid_node);
-        for obs in &mut self.observers {
-            let _ = obs.send(update);
-        }
+        self.notify_observers(GenerationUpdate::Generated(grid_node));
         if let Some(collector) = collector {
             collector.push(grid_node);
         }
@@ -692,11 +1537,147 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
         debug!("Generation failed due to a contradiction");
 
         self.status = InternalGeneratorStatus::Failed(GeneratorError { node_index });
+        self.stats.contradictions += 1;
+        *self
+            .stats
+            .contradiction_sites
+            .entry(node_index)
+            .or_default() += 1;
+        self.notify_observers(GenerationUpdate::Failed(node_index));
+    }
+
+    /// Sends `update` to all the observers, stamped with the next update step
+    pub(crate) fn notify_observers(&mut self, update: GenerationUpdate) {
+        if self.observers.is_empty() {
+            return;
+        }
+        let stamped_update = StampedUpdate {
+            step: self.update_step,
+            update,
+        };
+        self.update_step += 1;
         for obs in &mut self.observers {
-            let _ = obs.send(GenerationUpdate::Failed(node_index));
+            let _ = obs.send(stamped_update);
+        }
+    }
+
+    pub(crate) fn node_entropy(&self, node_index: NodeIndex) -> NodeEntropy {
+        let possible_models_count = self.possible_models_counts[node_index];
+        if possible_models_count <= 1 {
+            return NodeEntropy {
+                possible_models_count,
+                entropy: 0.,
+            };
+        }
+        let mut weight_sum = 0.;
+        let mut weight_log_weight_sum = 0.;
+        for model_index in self.possible_model_indexes(node_index) {
+            let weight = self.rules.weight_unchecked(model_index);
+            weight_sum += weight;
+            weight_log_weight_sum += weight * f32::ln(weight);
+        }
+        NodeEntropy {
+            possible_models_count,
+            entropy: f32::ln(weight_sum) - weight_log_weight_sum / weight_sum,
         }
     }
 
+    pub(crate) fn possible_variants(
+        &self,
+        node_index: NodeIndex,
+    ) -> Vec<PossibleVariant<C::Direction>> {
+        let possible_models: Vec<ModelVariantIndex> =
+            self.possible_model_indexes(node_index).collect();
+        let mut weights = self.models_weights(&possible_models);
+        if self.has_weights_adjustments() {
+            weights = self.adjusted_weights(node_index, &possible_models, &weights);
+        }
+
+        let mut neighbours = vec![None; self.grid.directions_count()];
+        self.grid
+            .get_neighbours_in_all_directions(node_index, &mut neighbours);
+
+        possible_models
+            .iter()
+            .zip(weights)
+            .map(|(&variant_index, weight)| {
+                let mut most_constraining: Option<(C::Direction, usize)> = None;
+                for direction in self.grid.coord_system().directions() {
+                    if neighbours[(*direction).into()].is_none() {
+                        continue;
+                    }
+                    // Supports from the neighbour in `direction` are counted in the opposite direction
+                    let supports = self.supports_count
+                        [(node_index, variant_index, direction.opposite().into())];
+                    if most_constraining.map_or(true, |(_, min_supports)| supports < min_supports) {
+                        most_constraining = Some((*direction, supports));
+                    }
+                }
+                PossibleVariant {
+                    variant_index,
+                    instance: *self.rules.model(variant_index),
+                    weight,
+                    most_constraining,
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn entropy_snapshot(&self) -> GridData<C, NodeEntropy, G> {
+        let mut entropies = Vec::with_capacity(self.grid.total_size());
+        for node_index in 0..self.grid.total_size() {
+            entropies.push(self.node_entropy(node_index));
+        }
+        GridData::new(self.grid.clone(), entropies)
+    }
+
+    pub(crate) fn model_census(&self) -> Vec<ModelCensusEntry> {
+        let mut census: Vec<ModelCensusEntry> = (0..self.rules.original_models_count())
+            .map(|model_index| ModelCensusEntry {
+                model_index,
+                count: 0,
+                expected_count: 0.,
+            })
+            .collect();
+
+        // Each variation is selected proportionally to its weight, so an original model is expected with the summed weights of its variations
+        let mut models_weights = vec![0.; census.len()];
+        for variant_index in 0..self.rules.models_count() {
+            models_weights[self.rules.model(variant_index).model_index] +=
+                self.rules.weight_unchecked(variant_index);
+        }
+        let total_weight: f32 = models_weights.iter().sum();
+
+        let mut generated_count = 0;
+        for node_index in 0..self.grid.total_size() {
+            if self.possible_models_counts[node_index] != 1 {
+                continue;
+            }
+            generated_count += 1;
+            let variant_index = self.get_model_index(node_index);
+            census[self.rules.model(variant_index).model_index].count += 1;
+        }
+
+        if total_weight > 0. {
+            for entry in census.iter_mut() {
+                entry.expected_count =
+                    generated_count as f32 * models_weights[entry.model_index] / total_weight;
+            }
+        }
+        census
+    }
+
+    pub(crate) fn to_partial_grid_data(&self) -> GridData<C, Option<ModelInstance>, G> {
+        let mut nodes = Vec::with_capacity(self.grid.total_size());
+        for node_index in 0..self.grid.total_size() {
+            nodes.push(match self.possible_models_counts[node_index] {
+                1 => Some(*self.rules.model(self.get_model_index(node_index))),
+                _ => None,
+            });
+        }
+        GridData::new(self.grid.clone(), nodes)
+    }
+
     /// Should only be called when the nodes are fully generated
     pub(crate) fn to_grid_data(&self) -> GridData<C, ModelInstance, G> {
         let mut generated_nodes = Vec::with_capacity(self.nodes.len());
@@ -708,3 +1689,69 @@ impl<C: CoordinateSystem, G: Grid<C>> InternalGenerator<C, G> {
         GridData::new(self.grid.clone(), generated_nodes)
     }
 }
+
+/// Returns the sum of `weights`, accumulated in the same order as [`rand::distributions::WeightedIndex`] does, so that a seed gives the same selections as when the weighted selection was done with it.
+///
+/// Returns `None` if there are no weights, if a weight is negative or NaN, or if they sum to zero or to infinity.
+fn weights_total(mut weights: impl Iterator<Item = f32>) -> Option<f32> {
+    let mut total_weight = weights.next()?;
+    if !(total_weight >= 0.) {
+        return None;
+    }
+    for weight in weights {
+        if !(weight >= 0.) {
+            return None;
+        }
+        total_weight += weight;
+    }
+    match total_weight > 0. && total_weight.is_finite() {
+        true => Some(total_weight),
+        false => None,
+    }
+}
+
+/// Returns the first item whose cumulative weight is greater than `sampled_weight`, or the last item if there is none. This is the item [`rand::distributions::WeightedIndex`] returns for the same sampled weight, without allocating its cumulative weights.
+///
+/// `items` should not be empty. May panic otherwise.
+fn sample_weighted_item<T>(sampled_weight: f32, mut items: impl Iterator<Item = (T, f32)>) -> T {
+    let (mut item, mut cumulative_weight) = items.next().unwrap();
+    for (next_item, weight) in items {
+        if cumulative_weight > sampled_weight {
+            return item;
+        }
+        item = next_item;
+        cumulative_weight += weight;
+    }
+    item
+}
+
+/// Samples an index of `weights` with a probability proportional to its weight, using integer arithmetic only so that the result is the same on every platform. `weights` should contain at least one strictly positive weight. May panic otherwise.
+fn sample_fixed_point_index(rng: &mut StdRng, weights: &[u64]) -> usize {
+    let total_weight: u64 = weights.iter().sum();
+    let mut remaining = rng.gen_range(0..total_weight);
+    for (index, weight) in weights.iter().enumerate() {
+        if remaining < *weight {
+            return index;
+        }
+        remaining -= weight;
+    }
+    unreachable!("The sampled weight is lower than the total weight")
+}
+
+/// Maximum weight jitter, so that jittered weights stay strictly positive
+pub(crate) const MAX_WEIGHT_JITTER: f32 = 0.9;
+
+/// Salt used to derive the jitter factors RNG from the generation seed, so that drawing them does not alter the generation RNG
+const JITTER_SEED_SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Draws a random factor in `[1 - jitter, 1 + jitter]` for each model, from the generation `seed`. All factors are `1.` if `jitter` is `0.`
+fn draw_jitter_factors(seed: u64, jitter: f32, models_count: usize) -> Vec<f32> {
+    let jitter = jitter.clamp(0., MAX_WEIGHT_JITTER);
+    if jitter == 0. {
+        return vec![1.; models_count];
+    }
+    let mut rng = StdRng::seed_from_u64(seed ^ JITTER_SEED_SALT);
+    (0..models_count)
+        .map(|_| 1. + rng.gen_range(-jitter..=jitter))
+        .collect()
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashSet, fmt, marker::PhantomData};
+use std::{any::Any, borrow::Cow, collections::HashSet, fmt, marker::PhantomData, sync::Arc};
 
 use ghx_grid::{
     cartesian::coordinates::{Cartesian2D, Cartesian3D},
@@ -15,7 +15,7 @@ use bevy::{ecs::reflect::ReflectComponent, reflect::Reflect};
 
 use super::{
     rules::CARTESIAN_2D_ROTATION_AXIS,
-    socket::{Socket, SocketId, SocketsCartesian2D, SocketsCartesian3D},
+    socket::{Socket, SocketCollection, SocketId, SocketsCartesian2D, SocketsCartesian3D},
 };
 
 /// Index of an original model
@@ -24,6 +24,35 @@ pub type ModelIndex = usize;
 /// Index of a model variation
 pub type ModelVariantIndex = usize;
 
+/// User data attached to a [`Model`], shared by all its variations. See [`Model::with_data`]
+pub type ModelData = Arc<dyn Any + Send + Sync>;
+
+/// Set of original models (all their variations included) allowed on a node, see [`super::builder::GeneratorBuilder::with_node_constraints`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ModelSet {
+    /// All the models are allowed
+    #[default]
+    All,
+    /// Only these models are allowed
+    Only(HashSet<ModelIndex>),
+}
+
+impl ModelSet {
+    /// Returns whether or not `model_index` is in the set
+    pub fn contains(&self, model_index: ModelIndex) -> bool {
+        match self {
+            ModelSet::All => true,
+            ModelSet::Only(models) => models.contains(&model_index),
+        }
+    }
+}
+
+impl FromIterator<ModelIndex> for ModelSet {
+    fn from_iter<I: IntoIterator<Item = ModelIndex>>(iter: I) -> Self {
+        ModelSet::Only(iter.into_iter().collect())
+    }
+}
+
 /// Default weight of [`Model`] and [`ModelTemplate`]
 pub const DEFAULT_MODEL_WEIGHT: f32 = 1.0;
 
@@ -38,6 +67,8 @@ pub struct ModelTemplate<C> {
     ///
     ///  Defaults to [`DEFAULT_MODEL_WEIGHT`]
     weight: f32,
+    /// Whether or not an invalid weight was given and clamped to `f32::MIN_POSITIVE`
+    weight_clamped: bool,
     /// Allowed rotations of this [`ModelTemplate`] in the output, around the rotation axis specified in the rules.
     ///
     /// Defaults to only [`ModelRotation::Rot0`].
@@ -46,6 +77,14 @@ pub struct ModelTemplate<C> {
     /// - In 3d, sockets of a model that are on the rotation axis are rotated into new sockets when the model itself is rotated. See [`crate::generator::socket::SocketCollection`] for how to define and/or constrain sockets connections on the rotation axis.
     /// - In 2d, the rotation axis cannot be modified and is set to [`Direction::ZForward`].
     allowed_rotations: HashSet<ModelRotation>,
+    /// Allowed facings of this [`ModelTemplate`] in the output. Each allowed facing is combined with each allowed rotation.
+    ///
+    /// Defaults to only [`ModelFacing::Up`]. Only used in 3d.
+    allowed_facings: HashSet<ModelFacing>,
+    /// Whether or not this [`ModelTemplate`] is also expanded into variations flipped along the rotation axis of the rules, in addition to its regular variations.
+    ///
+    /// Defaults to `false`. Only used in 3d.
+    flipped_variants: bool,
     typestate: PhantomData<C>,
 }
 
@@ -54,20 +93,96 @@ impl ModelTemplate<Cartesian3D> {
         Self {
             sockets: sockets.into(),
             allowed_rotations: HashSet::from([ModelRotation::Rot0]),
+            allowed_facings: HashSet::from([ModelFacing::Up]),
+            flipped_variants: false,
             weight: DEFAULT_MODEL_WEIGHT,
+            weight_clamped: false,
             typestate: PhantomData,
         }
     }
 
     /// Returns a clone of the [`Model`] with its sockets rotated by `rotation` around `axis`.
     pub fn rotated(&self, rotation: ModelRotation, axis: Direction) -> Self {
+        self.rotated_around(rotation, axis)
+    }
+
+    /// Returns a clone of the [`Model`] with its sockets rotated by `rotation` around `axis`, an axis which is **not** the rotation axis of the [`super::rules::Rules`].
+    ///
+    /// Unlike [`ModelTemplate::rotated`], the sockets on `axis` are only moved and not marked as rotated, since rotated sockets are only meaningful on the rotation axis of the rules.
+    ///
+    /// **Note**: sockets that end up on the rotation axis of the rules will be rotated with the model variations, their connections should be declared with [`super::socket::SocketCollection::add_rotated_connection`].
+    pub fn rotated_around_secondary_axis(&self, rotation: ModelRotation, axis: Direction) -> Self {
         Self {
-            sockets: self.rotated_sockets(rotation, axis),
+            sockets: self.permuted_sockets(rotation, axis, false),
             weight: self.weight,
+            weight_clamped: self.weight_clamped,
             allowed_rotations: self.allowed_rotations.clone(),
+            allowed_facings: self.allowed_facings.clone(),
+            flipped_variants: self.flipped_variants,
             typestate: PhantomData,
         }
     }
+
+    /// Specify that this [`ModelTemplate`] also has variations flipped along the rotation axis of the rules, for each of its allowed facings and rotations. See [`Model::with_flipped_variants`]
+    pub fn with_flipped_variants(mut self) -> Self {
+        self.flipped_variants = true;
+        self
+    }
+
+    /// Specify that this [`ModelTemplate`] can be oriented in every way specified in `facings`, each one combined with each of its allowed rotations.
+    pub fn with_facings<F: Into<HashSet<ModelFacing>>>(mut self, facings: F) -> Self {
+        self.allowed_facings = facings.into();
+        self
+    }
+
+    /// Specify that this [`ModelTemplate`] can be oriented in each of the 24 orientations of a cube: all the [`ModelFacing`] combined with all the [`ModelRotation`].
+    ///
+    /// See [`ModelFacing`] for how the sockets of the oriented variations are connected.
+    pub fn with_all_orientations(mut self) -> Self {
+        self.allowed_facings = ALL_MODEL_FACINGS.iter().cloned().collect();
+        self.with_all_rotations()
+    }
+
+    /// Returns a clone of the [`Model`] rotated (around any axis) so that its face that was towards `up` is now towards `facing`. See [`ModelTemplate::rotated_around_secondary_axis`]
+    pub fn facing(&self, up: Direction, facing: Direction) -> Self {
+        if up == facing {
+            return self.clone();
+        }
+        for axis in [
+            Direction::XForward,
+            Direction::YForward,
+            Direction::ZForward,
+        ] {
+            for rotation in [
+                ModelRotation::Rot90,
+                ModelRotation::Rot180,
+                ModelRotation::Rot270,
+            ] {
+                if rotated_direction(up, rotation, axis) == facing {
+                    return self.rotated_around_secondary_axis(rotation, axis);
+                }
+            }
+        }
+        unreachable!("any direction can be rotated onto any other direction")
+    }
+}
+
+/// Returns the direction that `direction` points to once rotated by `rotation` around `axis`
+fn rotated_direction<D: DirectionTrait + Copy + Into<usize>>(
+    direction: D,
+    rotation: ModelRotation,
+    axis: D,
+) -> D {
+    let basis = axis.rotation_basis();
+    let direction_index: usize = direction.into();
+    match basis
+        .iter()
+        .position(|dir| Into::<usize>::into(*dir) == direction_index)
+    {
+        Some(index) => basis[(index + rotation.index() as usize) % basis.len()],
+        // On the rotation axis
+        None => direction,
+    }
 }
 
 impl ModelTemplate<Cartesian2D> {
@@ -75,23 +190,36 @@ impl ModelTemplate<Cartesian2D> {
         Self {
             sockets: sockets.into(),
             allowed_rotations: HashSet::from([ModelRotation::Rot0]),
+            allowed_facings: HashSet::from([ModelFacing::Up]),
+            flipped_variants: false,
             weight: DEFAULT_MODEL_WEIGHT,
+            weight_clamped: false,
             typestate: PhantomData,
         }
     }
 
     /// Returns a clone of the [`Model`] with its sockets rotated by `rotation` around [`CARTESIAN_2D_ROTATION_AXIS`].
     pub fn rotated(&self, rotation: ModelRotation) -> Self {
+        self.rotated_around(rotation, CARTESIAN_2D_ROTATION_AXIS)
+    }
+}
+
+impl<C: CoordinateSystem> ModelTemplate<C> {
+    /// Creates a [`ModelTemplate`] from raw sockets, with default values for the other members: weight is [`DEFAULT_MODEL_WEIGHT`] and the model will not be rotated.
+    ///
+    /// `sockets[direction]` holds the sockets of the model in `direction`, with the directions indexed as in the coordinate system `C`. When the coordinate system is known, prefer [`SocketsCartesian2D`] or [`SocketsCartesian3D`].
+    pub fn from_raw_sockets(sockets: Vec<Vec<Socket>>) -> Self {
         Self {
-            sockets: self.rotated_sockets(rotation, CARTESIAN_2D_ROTATION_AXIS),
-            weight: self.weight,
-            allowed_rotations: self.allowed_rotations.clone(),
+            sockets,
+            allowed_rotations: HashSet::from([ModelRotation::Rot0]),
+            allowed_facings: HashSet::from([ModelFacing::Up]),
+            flipped_variants: false,
+            weight: DEFAULT_MODEL_WEIGHT,
+            weight_clamped: false,
             typestate: PhantomData,
         }
     }
-}
 
-impl<C: CoordinateSystem> ModelTemplate<C> {
     /// Specify that this [`ModelTemplate`] can be rotated in exactly one way: `rotation`
     ///
     /// Rotations are specified as counter-clockwise
@@ -138,6 +266,7 @@ impl<C: CoordinateSystem> ModelTemplate<C> {
     /// All the variations (rotations) of this [`ModelTemplate`] will use the same weight.
     pub fn with_weight<W: Into<f32>>(mut self, weight: W) -> Self {
         let mut checked_weight = weight.into();
+        self.weight_clamped = checked_weight <= 0.;
         if checked_weight <= 0. {
             #[cfg(feature = "debug-traces")]
             warn!(
@@ -151,16 +280,84 @@ impl<C: CoordinateSystem> ModelTemplate<C> {
         self
     }
 
+    fn rotated_around(&self, rotation: ModelRotation, axis: C::Direction) -> Self {
+        Self {
+            sockets: self.rotated_sockets(rotation, axis),
+            weight: self.weight,
+            weight_clamped: self.weight_clamped,
+            allowed_rotations: self.allowed_rotations.clone(),
+            allowed_facings: self.allowed_facings.clone(),
+            flipped_variants: self.flipped_variants,
+            typestate: PhantomData,
+        }
+    }
+
     fn rotated_sockets(&self, rotation: ModelRotation, rot_axis: C::Direction) -> Vec<Vec<Socket>> {
+        self.permuted_sockets(rotation, rot_axis, true)
+    }
+
+    /// Sockets of the model rotated by `rotation` around `rot_axis`, then reoriented to `facing`
+    fn oriented_sockets(
+        &self,
+        facing: ModelFacing,
+        rotation: ModelRotation,
+        rot_axis: C::Direction,
+    ) -> Vec<Vec<Socket>> {
+        match facing.reorientation(rot_axis) {
+            None => self.rotated_sockets(rotation, rot_axis),
+            Some((reorientation, reorientation_axis)) => {
+                let rotated = Self {
+                    sockets: self.permuted_sockets(rotation, rot_axis, false),
+                    ..self.clone()
+                };
+                rotated.permuted_sockets(reorientation, reorientation_axis, false)
+            }
+        }
+    }
+
+    /// Sockets of the model flipped along `rot_axis`: the sockets towards `rot_axis` and towards its opposite are swapped, and the other sockets are replaced by their mirror image declared in `socket_collection`
+    fn flipped_sockets(
+        &self,
+        rot_axis: C::Direction,
+        socket_collection: &SocketCollection,
+    ) -> Vec<Vec<Socket>> {
+        let mut flipped_sockets: Vec<Vec<Socket>> = self
+            .sockets
+            .iter()
+            .map(|sockets| {
+                sockets
+                    .iter()
+                    .map(|socket| socket_collection.mirrored(*socket))
+                    .collect()
+            })
+            .collect();
+        // Sockets on the rotation axis are only moved
+        if self.sockets.len() > rot_axis.into() {
+            for fixed_axis in [rot_axis, rot_axis.opposite()] {
+                flipped_sockets[fixed_axis.into()] =
+                    self.sockets[fixed_axis.opposite().into()].clone();
+            }
+        }
+        flipped_sockets
+    }
+
+    /// Permutes the sockets as if the model was rotated by `rotation` around `rot_axis`. Sockets on the rotation axis are only marked as rotated if `mark_rotated` is `true`
+    fn permuted_sockets(
+        &self,
+        rotation: ModelRotation,
+        rot_axis: C::Direction,
+        mark_rotated: bool,
+    ) -> Vec<Vec<Socket>> {
         let mut rotated_sockets = vec![Vec::new(); self.sockets.len()];
 
         // Not pretty: if the node sockets contain the rotation axis
         if self.sockets.len() > rot_axis.into() {
-            // Sockets on the rotation axis are marked as rotated
             for fixed_axis in [rot_axis, rot_axis.opposite()] {
                 rotated_sockets[fixed_axis.into()].extend(self.sockets[fixed_axis.into()].clone());
-                for socket in &mut rotated_sockets[fixed_axis.into()] {
-                    socket.rotate(rotation);
+                if mark_rotated {
+                    for socket in &mut rotated_sockets[fixed_axis.into()] {
+                        socket.rotate(rotation);
+                    }
                 }
             }
         }
@@ -222,24 +419,104 @@ impl<C: CoordinateSystem> ModelCollection<C> {
         self.models.last_mut()
     }
 
-    pub(crate) fn create_variations(&self, rotation_axis: C::Direction) -> Vec<ModelVariation> {
+    fn create_rotated_copies<R: IntoIterator<Item = ModelRotation>>(
+        &mut self,
+        template: ModelTemplate<C>,
+        rotations: R,
+        axis: C::Direction,
+    ) -> &mut [Model<C>] {
+        let first_created = self.models.len();
+        for rotation in rotations {
+            self.create(template.rotated_around(rotation, axis));
+        }
+        &mut self.models[first_created..]
+    }
+
+    fn name_rotated_copies(models: &mut [Model<C>], name: &str) {
+        for (model, rotation) in models.iter_mut().zip(ALL_MODEL_ROTATIONS) {
+            model.with_name(format!("{}_{}", name, rotation));
+        }
+    }
+
+    pub(crate) fn clamped_weights(&self) -> Vec<ModelIndex> {
+        self.models
+            .iter()
+            .filter(|model| model.template.weight_clamped)
+            .map(|model| model.index)
+            .collect()
+    }
+
+    pub(crate) fn models_data(&self) -> Vec<Option<ModelData>> {
+        self.models.iter().map(|model| model.data.clone()).collect()
+    }
+
+    /// Returns the index of the first model that can be reoriented to another facing than [`ModelFacing::Up`] while using a socket with rotated connections in `socket_collection`, if any. See [`ModelFacing`]
+    pub(crate) fn first_reoriented_model_with_rotated_sockets(
+        &self,
+        socket_collection: &SocketCollection,
+    ) -> Option<ModelIndex> {
+        self.models
+            .iter()
+            .find(|model| {
+                model
+                    .template
+                    .allowed_facings
+                    .iter()
+                    .any(|facing| *facing != ModelFacing::Up)
+                    && model
+                        .template
+                        .sockets
+                        .iter()
+                        .flatten()
+                        .any(|socket| socket_collection.has_rotated_connections(*socket))
+            })
+            .map(|model| model.index)
+    }
+
+    pub(crate) fn create_variations(
+        &self,
+        rotation_axis: C::Direction,
+        socket_collection: &SocketCollection,
+    ) -> Vec<ModelVariation> {
         let mut model_variations = Vec::new();
         for model in self.models.iter() {
-            // Iterate on a vec of all possible node rotations and filter with the set to have a deterministic insertion order of model variations.
-            for rotation in ALL_MODEL_ROTATIONS {
-                if model.template.allowed_rotations.contains(&rotation) {
-                    let rotated_sockets = model.template.rotated_sockets(*rotation, rotation_axis);
-                    model_variations.push(ModelVariation {
-                        sockets: rotated_sockets
-                            .iter()
-                            .map(|dir| dir.iter().map(|s| s.id()).collect())
-                            .collect(),
-                        weight: model.template.weight,
-                        original_index: model.index,
-                        rotation: *rotation,
-                        #[cfg(feature = "models-names")]
-                        name: model.name.clone(),
-                    });
+            // The flipped variations are oriented like the regular ones, from the flipped sockets
+            let flipped_template = match model.template.flipped_variants {
+                true => Some(ModelTemplate {
+                    sockets: model
+                        .template
+                        .flipped_sockets(rotation_axis, socket_collection),
+                    ..model.template.clone()
+                }),
+                false => None,
+            };
+            let templates = std::iter::once((&model.template, false))
+                .chain(flipped_template.as_ref().map(|template| (template, true)));
+            for (template, flipped) in templates {
+                // Iterate on vecs of all possible node facings & rotations and filter with the sets to have a deterministic insertion order of model variations.
+                for facing in ALL_MODEL_FACINGS {
+                    if !template.allowed_facings.contains(facing) {
+                        continue;
+                    }
+                    for rotation in ALL_MODEL_ROTATIONS {
+                        if template.allowed_rotations.contains(&rotation) {
+                            let rotated_sockets =
+                                template.oriented_sockets(*facing, *rotation, rotation_axis);
+                            model_variations.push(ModelVariation {
+                                sockets: rotated_sockets
+                                    .iter()
+                                    .map(|dir| dir.iter().map(|s| s.id()).collect())
+                                    .collect(),
+                                weight: template.weight,
+                                original_index: model.index,
+                                rotation: *rotation,
+                                facing: *facing,
+                                flipped,
+                                #[cfg(feature = "models-names")]
+                                name: model.name.clone(),
+                            });
+                        }
+                    }
                 }
             }
         }
@@ -247,11 +524,102 @@ impl<C: CoordinateSystem> ModelCollection<C> {
     }
 }
 
+impl ModelCollection<Cartesian3D> {
+    /// Creates one distinct [`Model`] per rotation in `rotations`, each one being a copy of `template` with its sockets rotated by this rotation around `axis`.
+    ///
+    /// Returns the created models, in the order of `rotations`.
+    pub fn create_all<
+        T: Into<ModelTemplate<Cartesian3D>>,
+        R: IntoIterator<Item = ModelRotation>,
+    >(
+        &mut self,
+        template: T,
+        rotations: R,
+        axis: Direction,
+    ) -> &mut [Model<Cartesian3D>] {
+        self.create_rotated_copies(template.into(), rotations, axis)
+    }
+
+    /// Creates 4 distinct [`Model`], copies of `template` with their sockets rotated around `axis` by each [`ModelRotation`] (in the order of [`ALL_MODEL_ROTATIONS`]).
+    ///
+    /// The models are named `{name}_{rotation}` (for example `corner_90`) if the `models-names` feature is enabled.
+    pub fn create_all_rotations<T: Into<ModelTemplate<Cartesian3D>>>(
+        &mut self,
+        name: &str,
+        template: T,
+        axis: Direction,
+    ) -> &mut [Model<Cartesian3D>] {
+        let models =
+            self.create_rotated_copies(template.into(), ALL_MODEL_ROTATIONS.iter().copied(), axis);
+        Self::name_rotated_copies(models, name);
+        models
+    }
+
+    /// Creates one distinct [`Model`] per direction in `facings`, each one being a copy of `template` rotated (around any axis) so that its face that was towards `up` is now towards this direction. For example to create pipes that can face any of the 6 directions.
+    ///
+    /// Each created model keeps the allowed rotations of `template` around the rotation axis of the [`super::rules::Rules`]. See [`ModelTemplate::facing`].
+    ///
+    /// The models are named `{name}_{facing:?}` (for example `pipe_XForward`) if the `models-names` feature is enabled. Returns the created models, in the order of `facings`.
+    pub fn create_all_facings<
+        T: Into<ModelTemplate<Cartesian3D>>,
+        F: IntoIterator<Item = Direction>,
+    >(
+        &mut self,
+        name: &str,
+        template: T,
+        up: Direction,
+        facings: F,
+    ) -> &mut [Model<Cartesian3D>] {
+        let template = template.into();
+        let first_created = self.models.len();
+        for facing in facings {
+            self.create(template.facing(up, facing))
+                .with_name(format!("{}_{:?}", name, facing));
+        }
+        &mut self.models[first_created..]
+    }
+}
+
+impl ModelCollection<Cartesian2D> {
+    /// Creates one distinct [`Model`] per rotation in `rotations`, each one being a copy of `template` with its sockets rotated by this rotation around [`CARTESIAN_2D_ROTATION_AXIS`].
+    ///
+    /// Returns the created models, in the order of `rotations`.
+    pub fn create_all<
+        T: Into<ModelTemplate<Cartesian2D>>,
+        R: IntoIterator<Item = ModelRotation>,
+    >(
+        &mut self,
+        template: T,
+        rotations: R,
+    ) -> &mut [Model<Cartesian2D>] {
+        self.create_rotated_copies(template.into(), rotations, CARTESIAN_2D_ROTATION_AXIS)
+    }
+
+    /// Creates 4 distinct [`Model`], copies of `template` with their sockets rotated by each [`ModelRotation`] (in the order of [`ALL_MODEL_ROTATIONS`]).
+    ///
+    /// The models are named `{name}_{rotation}` (for example `corner_90`) if the `models-names` feature is enabled.
+    pub fn create_all_rotations<T: Into<ModelTemplate<Cartesian2D>>>(
+        &mut self,
+        name: &str,
+        template: T,
+    ) -> &mut [Model<Cartesian2D>] {
+        let models = self.create_rotated_copies(
+            template.into(),
+            ALL_MODEL_ROTATIONS.iter().copied(),
+            CARTESIAN_2D_ROTATION_AXIS,
+        );
+        Self::name_rotated_copies(models, name);
+        models
+    }
+}
+
 /// Represents a model to be used by a [`crate::generator::Generator`] as a "building-block" to fill out the generated area.
 #[derive(Clone, Debug)]
 pub struct Model<C: CoordinateSystem> {
     index: ModelIndex,
     template: ModelTemplate<C>,
+    /// User data attached to this model
+    data: Option<ModelData>,
 
     /// Name given to this model for debug purposes.
     #[cfg(feature = "models-names")]
@@ -263,6 +631,7 @@ impl<C: CoordinateSystem> Model<C> {
         Self {
             index,
             template,
+            data: None,
             #[cfg(feature = "models-names")]
             name: None,
         }
@@ -321,6 +690,7 @@ impl<C: CoordinateSystem> Model<C> {
     /// All the variations (rotations) of this [`Model`] will use the same weight.
     pub fn with_weight<W: Into<f32>>(&mut self, weight: W) -> &mut Self {
         let mut checked_weight = weight.into();
+        self.template.weight_clamped = checked_weight <= 0.;
         if checked_weight <= 0. {
             #[cfg(feature = "debug-traces")]
             warn!(
@@ -346,6 +716,19 @@ impl<C: CoordinateSystem> Model<C> {
         self
     }
 
+    /// Attaches an arbitrary user `data` payload to this model (gameplay metadata, biome id, ...), replacing any previous one.
+    ///
+    /// It can then be retrieved from the [`super::rules::Rules`] with [`super::rules::Rules::model_data`], for example for the [`ModelInstance`] of a generated node.
+    pub fn with_data<T: Any + Send + Sync>(&mut self, data: T) -> &mut Self {
+        self.data = Some(Arc::new(data));
+        self
+    }
+
+    /// Returns the user data attached to this model if any and if it is of type `T`
+    pub fn data<T: Any>(&self) -> Option<&T> {
+        self.data.as_ref().and_then(|data| data.downcast_ref::<T>())
+    }
+
     pub(crate) fn first_rot(&self) -> ModelRotation {
         for rot in ALL_MODEL_ROTATIONS {
             if self.template.allowed_rotations.contains(rot) {
@@ -355,14 +738,82 @@ impl<C: CoordinateSystem> Model<C> {
         ModelRotation::Rot0
     }
 
+    pub(crate) fn first_facing(&self) -> ModelFacing {
+        for facing in ALL_MODEL_FACINGS {
+            if self.template.allowed_facings.contains(facing) {
+                return *facing;
+            }
+        }
+        ModelFacing::Up
+    }
+
     /// Creates a model instance from this model
     pub fn instance(&self) -> ModelInstance {
         ModelInstance {
             model_index: self.index,
             rotation: self.first_rot(),
+            facing: self.first_facing(),
+            flipped: false,
         }
     }
 }
+
+impl Model<Cartesian3D> {
+    /// Specify that this [`Model`] can be oriented in every way specified in `facings`, each one combined with each of its allowed rotations.
+    pub fn with_facings<F: Into<HashSet<ModelFacing>>>(&mut self, facings: F) -> &mut Self {
+        self.template.allowed_facings = facings.into();
+        self
+    }
+
+    /// Specify that this [`Model`] can be oriented in each of the 24 orientations of a cube: all the [`ModelFacing`] combined with all the [`ModelRotation`].
+    ///
+    /// See [`ModelFacing`] for how the sockets of the oriented variations are connected.
+    pub fn with_all_orientations(&mut self) -> &mut Self {
+        self.template.allowed_facings = ALL_MODEL_FACINGS.iter().cloned().collect();
+        self.with_all_rotations()
+    }
+
+    /// Specify that this [`Model`] also has variations flipped along the rotation axis of the rules, for each of its allowed facings and rotations. For example for models that are vertically symmetric in gameplay but not in authoring, such as a stalactite and a stalagmite.
+    ///
+    /// A flipped variation is the model flipped first, then rotated and reoriented like a regular variation: its sockets towards the rotation axis and towards its opposite are swapped, and its other sockets are replaced by their mirror image (see [`SocketCollection::add_mirror`]). Its [`ModelInstance`] is marked as [`ModelInstance::flipped`].
+    ///
+    /// ### Example
+    /// ```
+    /// use ghx_proc_gen::generator::{
+    ///     model::ModelCollection,
+    ///     rules::RulesBuilder,
+    ///     socket::{SocketCollection, SocketsCartesian3D},
+    /// };
+    ///
+    /// let mut sockets = SocketCollection::new();
+    /// let (side, side_mirrored, rock, air) =
+    ///     (sockets.create(), sockets.create(), sockets.create(), sockets.create());
+    /// sockets
+    ///     .add_mirror(side, side_mirrored)
+    ///     .add_connections(vec![(side, vec![side]), (side_mirrored, vec![side_mirrored])])
+    ///     .add_connection(rock, vec![air]);
+    ///
+    /// // Y+ is the rotation axis: a stalactite hangs from the rock, its flipped variation stands on it
+    /// let mut models = ModelCollection::new();
+    /// models
+    ///     .create(SocketsCartesian3D::Simple {
+    ///         x_pos: side,
+    ///         x_neg: side,
+    ///         z_pos: side,
+    ///         z_neg: side,
+    ///         y_pos: rock,
+    ///         y_neg: air,
+    ///     })
+    ///     .with_flipped_variants();
+    ///
+    /// let rules = RulesBuilder::new_cartesian_3d(models, sockets).build().unwrap();
+    /// assert_eq!(rules.models_count(), 2);
+    /// ```
+    pub fn with_flipped_variants(&mut self) -> &mut Self {
+        self.template.flipped_variants = true;
+        self
+    }
+}
 impl<C: CoordinateSystem> Into<ModelTemplate<C>> for Model<C> {
     fn into(self) -> ModelTemplate<C> {
         self.template.clone()
@@ -380,6 +831,10 @@ pub struct ModelVariation {
     original_index: ModelIndex,
     /// Rotation of the [`Model`]
     rotation: ModelRotation,
+    /// Facing of the [`Model`]
+    facing: ModelFacing,
+    /// Whether or not the [`Model`] is flipped along the rotation axis
+    flipped: bool,
 
     /// Debug name for this model
     #[cfg(feature = "models-names")]
@@ -403,12 +858,19 @@ impl ModelVariation {
     pub fn rotation(&self) -> ModelRotation {
         self.rotation
     }
+    /// Returns the facing applied to the original [``Model`] this model was expanded from
+    pub fn facing(&self) -> ModelFacing {
+        self.facing
+    }
+    /// Returns whether or not the original [``Model`] this model was expanded from is flipped along the rotation axis
+    pub fn flipped(&self) -> bool {
+        self.flipped
+    }
 
     pub(crate) fn to_instance(&self) -> ModelInstance {
-        ModelInstance {
-            model_index: self.original_index,
-            rotation: self.rotation,
-        }
+        ModelInstance::new(self.original_index, self.rotation)
+            .with_facing(self.facing)
+            .with_flipped(self.flipped)
     }
 }
 
@@ -419,16 +881,142 @@ impl ModelVariation {
 pub struct ModelInstance {
     /// Index of the original [`Model`]
     pub model_index: ModelIndex,
-    /// Rotation of the original [`Model`]
+    /// Rotation of the original [`Model`], applied before its facing
     pub rotation: ModelRotation,
+    /// Facing of the original [`Model`], applied after its rotation. Always [`ModelFacing::Up`] for models that are not oriented with [`Model::with_facings`]
+    pub facing: ModelFacing,
+    /// Whether or not the original [`Model`] is flipped along the rotation axis, before its rotation and facing. Always `false` for models without [`Model::with_flipped_variants`]
+    pub flipped: bool,
+}
+
+impl ModelInstance {
+    /// Creates an instance of the original model `model_index` rotated by `rotation`, facing [`ModelFacing::Up`]
+    pub fn new(model_index: ModelIndex, rotation: ModelRotation) -> Self {
+        Self {
+            model_index,
+            rotation,
+            facing: ModelFacing::Up,
+            flipped: false,
+        }
+    }
+
+    /// Returns this instance reoriented to `facing`
+    pub fn with_facing(mut self, facing: ModelFacing) -> Self {
+        self.facing = facing;
+        self
+    }
+
+    /// Returns this instance flipped along the rotation axis if `flipped` is `true`
+    pub fn with_flipped(mut self, flipped: bool) -> Self {
+        self.flipped = flipped;
+        self
+    }
+
+    /// Returns the facing of the original [`Model`], applied after its rotation. Always [`ModelFacing::Up`] for models that are not oriented with [`Model::with_facings`]
+    pub fn facing(&self) -> ModelFacing {
+        self.facing
+    }
 }
 
 impl fmt::Display for ModelInstance {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "id: {}, rot: {}", self.model_index, self.rotation)
+        write!(f, "id: {}, rot: {}", self.model_index, self.rotation)?;
+        if self.facing != ModelFacing::Up {
+            write!(f, ", facing: {:?}", self.facing)?;
+        }
+        if self.flipped {
+            write!(f, ", flipped")?;
+        }
+        Ok(())
     }
 }
 
+/// Direction faced by the "up" face of a model: its face which is towards the rotation axis of the [`super::rules::Rules`] in the original model.
+///
+/// An oriented model is first rotated by its [`ModelRotation`] around the rotation axis, then reoriented so that its "up" face is towards its [`ModelFacing`]. This gives the 24 orientations of a cube.
+///
+/// **Note**: when reoriented, the sockets of a model are only moved to other faces and not marked as rotated (see [`ModelTemplate::rotated_around_secondary_axis`]). The sockets of models using other facings than [`ModelFacing::Up`] cannot have rotated connections (see [`SocketCollection::add_rotated_connection`]): building such [`super::rules::Rules`] returns [`crate::RulesBuilderError::RotatedSocketsOnReorientedModel`].
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "reflect", derive(Reflect))]
+pub enum ModelFacing {
+    /// Original orientation, towards the rotation axis
+    #[default]
+    Up,
+    /// Upside-down, towards the opposite of the rotation axis
+    Down,
+    /// Towards the first direction of the rotation basis of the rotation axis
+    Side0,
+    /// Towards the direction of the rotation basis of the rotation axis reached by a rotation of 90° from [`ModelFacing::Side0`]
+    Side90,
+    /// Towards the direction of the rotation basis of the rotation axis reached by a rotation of 180° from [`ModelFacing::Side0`]
+    Side180,
+    /// Towards the direction of the rotation basis of the rotation axis reached by a rotation of 270° from [`ModelFacing::Side0`]
+    Side270,
+}
+
+impl ModelFacing {
+    /// Returns an index for the facing
+    pub fn index(&self) -> u8 {
+        match *self {
+            ModelFacing::Up => 0,
+            ModelFacing::Down => 1,
+            ModelFacing::Side0 => 2,
+            ModelFacing::Side90 => 3,
+            ModelFacing::Side180 => 4,
+            ModelFacing::Side270 => 5,
+        }
+    }
+
+    /// Returns the direction faced by the "up" face of the model, for the given `rotation_axis`
+    pub fn direction<D: DirectionTrait + Copy>(&self, rotation_axis: D) -> D {
+        match *self {
+            ModelFacing::Up => rotation_axis,
+            ModelFacing::Down => rotation_axis.opposite(),
+            ModelFacing::Side0 => rotation_axis.rotation_basis()[0],
+            ModelFacing::Side90 => rotation_axis.rotation_basis()[1],
+            ModelFacing::Side180 => rotation_axis.rotation_basis()[2],
+            ModelFacing::Side270 => rotation_axis.rotation_basis()[3],
+        }
+    }
+
+    /// Returns the rotation, and the axis of this rotation, that reorients a model from [`ModelFacing::Up`] to this facing. Returns [`None`] for [`ModelFacing::Up`].
+    ///
+    /// Can be used by assets spawners to orient the assets of a model.
+    pub fn reorientation<D: DirectionTrait + Copy + Into<usize>>(
+        &self,
+        rotation_axis: D,
+    ) -> Option<(ModelRotation, D)> {
+        if *self == ModelFacing::Up {
+            return None;
+        }
+        let target: usize = self.direction(rotation_axis).into();
+        // Axes perpendicular to the rotation axis
+        for axis in rotation_axis.rotation_basis() {
+            for rotation in [
+                ModelRotation::Rot90,
+                ModelRotation::Rot180,
+                ModelRotation::Rot270,
+            ] {
+                if Into::<usize>::into(rotated_direction(rotation_axis, rotation, *axis)) == target
+                {
+                    return Some((rotation, *axis));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// All the possible facings for a [`Model`]
+pub const ALL_MODEL_FACINGS: &'static [ModelFacing] = &[
+    ModelFacing::Up,
+    ModelFacing::Down,
+    ModelFacing::Side0,
+    ModelFacing::Side90,
+    ModelFacing::Side180,
+    ModelFacing::Side270,
+];
+
 /// Represents a rotation around an Axis, in the trigonometric(counterclockwise) direction
 #[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "bevy", derive(Component))]
@@ -0,0 +1,247 @@
+use std::{
+    mem::size_of,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+use crate::NodeIndex;
+
+use super::model::ModelVariantIndex;
+
+/// Weight type stored in [`CumulativeWeights`]
+pub(crate) trait CumulativeWeight:
+    Copy + Default + PartialOrd + Add<Output = Self> + Sub<Output = Self> + AddAssign + SubAssign
+{
+}
+
+impl CumulativeWeight for f64 {}
+impl CumulativeWeight for u64 {}
+
+/// Cumulative weights of the models of each node, stored as one Fenwick tree (binary indexed tree) per node.
+///
+/// Banning a model, getting the total weight of a node and sampling a model from a weight are all `O(log(models_count))`, instead of `O(models_count)` when iterating the possible models of a node.
+#[derive(Clone)]
+pub(crate) struct CumulativeWeights<W: CumulativeWeight> {
+    models_count: usize,
+    /// Greatest power of two lower or equal to `models_count`, where the searches in a tree start
+    search_start: usize,
+    /// `trees[node_index * models_count..(node_index + 1) * models_count]` is the Fenwick tree of the node with index `node_index`
+    trees: Vec<W>,
+}
+
+impl<W: CumulativeWeight> CumulativeWeights<W> {
+    pub(crate) fn new(nodes_count: usize, models_count: usize) -> Self {
+        Self {
+            models_count,
+            search_start: match models_count {
+                0 => 0,
+                _ => 1 << models_count.ilog2(),
+            },
+            trees: vec![W::default(); nodes_count * models_count],
+        }
+    }
+
+    /// Returns the size in bytes of the trees
+    pub(crate) fn memory_footprint(&self) -> usize {
+        self.trees.capacity() * size_of::<W>()
+    }
+
+    #[inline]
+    fn tree(&self, node_index: NodeIndex) -> &[W] {
+        &self.trees[node_index * self.models_count..(node_index + 1) * self.models_count]
+    }
+
+    #[inline]
+    fn tree_mut(&mut self, node_index: NodeIndex) -> &mut [W] {
+        &mut self.trees[node_index * self.models_count..(node_index + 1) * self.models_count]
+    }
+
+    /// Replaces the weights of all the models of a node. `weights` should yield exactly one weight per model. In `O(models_count)`.
+    pub(crate) fn set_node(&mut self, node_index: NodeIndex, weights: impl Iterator<Item = W>) {
+        let models_count = self.models_count;
+        let tree = self.tree_mut(node_index);
+        for (slot, weight) in tree.iter_mut().zip(weights) {
+            *slot = weight;
+        }
+        // Tree indexes are 1-based: tree[i - 1] holds the sum of the weights in ]i - lowbit(i), i]
+        for i in 1..=models_count {
+            let parent = i + lowbit(i);
+            if parent <= models_count {
+                let partial_sum = tree[i - 1];
+                tree[parent - 1] += partial_sum;
+            }
+        }
+    }
+
+    /// Returns the weight of a model on a node
+    pub(crate) fn weight(&self, node_index: NodeIndex, model_index: ModelVariantIndex) -> W {
+        let tree = self.tree(node_index);
+        let i = model_index + 1;
+        let mut weight = tree[i - 1];
+        let stop = i - lowbit(i);
+        let mut j = i - 1;
+        while j > stop {
+            weight -= tree[j - 1];
+            j -= lowbit(j);
+        }
+        weight
+    }
+
+    /// Sets the weight of a model on a node to zero
+    pub(crate) fn remove(&mut self, node_index: NodeIndex, model_index: ModelVariantIndex) {
+        let weight = self.weight(node_index, model_index);
+        let models_count = self.models_count;
+        let tree = self.tree_mut(node_index);
+        let mut i = model_index + 1;
+        while i <= models_count {
+            tree[i - 1] -= weight;
+            i += lowbit(i);
+        }
+    }
+
+    /// Sets the weights of all the models of a node to zero, except for `model_index`
+    pub(crate) fn keep_only(&mut self, node_index: NodeIndex, model_index: ModelVariantIndex) {
+        let weight = self.weight(node_index, model_index);
+        self.set_node(
+            node_index,
+            (0..self.models_count).map(|index| match index == model_index {
+                true => weight,
+                false => W::default(),
+            }),
+        );
+    }
+
+    /// Returns the sum of the weights of the models of a node
+    pub(crate) fn total(&self, node_index: NodeIndex) -> W {
+        let tree = self.tree(node_index);
+        let mut total = W::default();
+        let mut i = self.models_count;
+        while i > 0 {
+            total += tree[i - 1];
+            i -= lowbit(i);
+        }
+        total
+    }
+
+    /// Returns the first model whose cumulative weight is greater than `sampled_weight`, or the last model if there is none. Models are accumulated in their index order.
+    pub(crate) fn sample(&self, node_index: NodeIndex, sampled_weight: W) -> ModelVariantIndex {
+        let tree = self.tree(node_index);
+        let mut remaining = sampled_weight;
+        let mut position = 0;
+        let mut step = self.search_start;
+        while step > 0 {
+            let next = position + step;
+            if next <= self.models_count && tree[next - 1] <= remaining {
+                position = next;
+                remaining -= tree[next - 1];
+            }
+            step >>= 1;
+        }
+        position.min(self.models_count - 1)
+    }
+}
+
+#[inline]
+fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+/// Weights cached by the generator for its [`super::ModelSelectionHeuristic::WeightedProbability`], see [`CumulativeWeights`].
+///
+/// Only the weights that do not change during a generation can be cached: the [`super::NeighbourWeights`] depend on the generated neighbours and are applied on each selection instead.
+#[derive(Clone)]
+pub(crate) enum ModelWeightsCache {
+    /// No weights cached, the weights are computed on each selection
+    None,
+    /// Floating point weights, weight jitter and [`super::WeightField`] included
+    Float(CumulativeWeights<f64>),
+    /// Fixed-point weights, weight jitter and [`super::WeightField`] included. See [`super::rules::Rules::fixed_point_weight`]
+    FixedPoint(CumulativeWeights<u64>),
+}
+
+impl ModelWeightsCache {
+    /// Returns the size in bytes of the cached weights
+    pub(crate) fn memory_footprint(&self) -> usize {
+        match self {
+            ModelWeightsCache::None => 0,
+            ModelWeightsCache::Float(weights) => weights.memory_footprint(),
+            ModelWeightsCache::FixedPoint(weights) => weights.memory_footprint(),
+        }
+    }
+
+    /// Returns the expected size in bytes of the cached weights, without instantiating them
+    pub(crate) fn estimate_memory_footprint(nodes_count: usize, models_count: usize) -> usize {
+        // f64 and u64 weights have the same size
+        nodes_count * models_count * size_of::<u64>()
+    }
+
+    /// Sets the cached weight of a model on a node to zero
+    pub(crate) fn remove(&mut self, node_index: NodeIndex, model_index: ModelVariantIndex) {
+        match self {
+            ModelWeightsCache::None => (),
+            ModelWeightsCache::Float(weights) => weights.remove(node_index, model_index),
+            ModelWeightsCache::FixedPoint(weights) => weights.remove(node_index, model_index),
+        }
+    }
+
+    /// Sets the cached weights of all the models of a node to zero, except for `model_index`
+    pub(crate) fn keep_only(&mut self, node_index: NodeIndex, model_index: ModelVariantIndex) {
+        match self {
+            ModelWeightsCache::None => (),
+            ModelWeightsCache::Float(weights) => weights.keep_only(node_index, model_index),
+            ModelWeightsCache::FixedPoint(weights) => weights.keep_only(node_index, model_index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CumulativeWeights;
+
+    /// Index returned by a linear scan of the cumulative weights, as the generator did before caching them
+    fn linear_sample(weights: &[u64], sampled_weight: u64) -> usize {
+        let mut cumulative_weight = 0;
+        for (index, weight) in weights.iter().enumerate() {
+            cumulative_weight += weight;
+            if cumulative_weight > sampled_weight {
+                return index;
+            }
+        }
+        weights.len() - 1
+    }
+
+    #[test]
+    fn sample_matches_linear_scan_after_removals() {
+        let mut weights: Vec<u64> = (0..37).map(|index| (index * 7 % 11) as u64).collect();
+        let mut cumulative_weights = CumulativeWeights::new(2, weights.len());
+        cumulative_weights.set_node(1, weights.iter().copied());
+
+        for removed in [3, 0, 36, 17, 18, 5] {
+            cumulative_weights.remove(1, removed);
+            weights[removed] = 0;
+
+            let total: u64 = weights.iter().sum();
+            assert_eq!(cumulative_weights.total(1), total);
+            for (index, weight) in weights.iter().enumerate() {
+                assert_eq!(cumulative_weights.weight(1, index), *weight);
+            }
+            for sampled_weight in 0..total {
+                assert_eq!(
+                    cumulative_weights.sample(1, sampled_weight),
+                    linear_sample(&weights, sampled_weight)
+                );
+            }
+        }
+        // The other node is untouched
+        assert_eq!(cumulative_weights.total(0), 0);
+    }
+
+    #[test]
+    fn keep_only_leaves_a_single_weight() {
+        let mut cumulative_weights = CumulativeWeights::new(1, 5);
+        cumulative_weights.set_node(0, [1., 2., 3., 4., 5.].into_iter());
+        cumulative_weights.keep_only(0, 3);
+        assert_eq!(cumulative_weights.total(0), 4.);
+        assert_eq!(cumulative_weights.sample(0, 0.), 3);
+        assert_eq!(cumulative_weights.sample(0, 3.9), 3);
+    }
+}
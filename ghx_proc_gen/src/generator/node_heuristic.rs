@@ -1,4 +1,6 @@
-use ghx_grid::coordinate_system::CoordinateSystem;
+use std::{collections::VecDeque, mem::size_of};
+
+use ghx_grid::{coordinate_system::CoordinateSystem, grid::Grid};
 use rand::{rngs::StdRng, Rng};
 
 use crate::NodeIndex;
@@ -20,11 +22,27 @@ pub enum NodeSelectionHeuristic {
     ///
     /// Often causes a **very high generation failure rate**, except for very simple rules.
     Random,
+    /// Only the nodes adjacent to already generated nodes can be chosen, so that the generation grows a connected frontier from the initial nodes (or from the first selected node). Among them, the one with the minimum count of possible models remaining is chosen, as in `MinimumRemainingValue`.
+    ///
+    /// Tends to reduce contradictions for corridor/path-like rules, and makes a step-by-step generation more legible.
+    FrontierGrowth,
+    /// The node closest to the node `node_index` (or the furthest from it if `furthest` is `true`) will be chosen at each selection iteration, so that the generation visibly grows outward from (or toward) this node. Among the nodes at the same distance, the one with the minimum count of possible models remaining is chosen, as in `MinimumRemainingValue`.
+    ///
+    /// Distances are counted in nodes, following the grid connectivity. Nodes that cannot be reached from `node_index` are considered the furthest.
+    ///
+    /// See [`crate::generator::builder::GeneratorBuilder::with_distance_heuristic`] to specify the origin with a grid position instead of a [`NodeIndex`].
+    DistanceFromNode {
+        /// Index of the node from which the distances are computed
+        node_index: NodeIndex,
+        /// If `true`, the furthest nodes are chosen first
+        furthest: bool,
+    },
 }
 
 const MAX_NOISE_VALUE: f32 = 1E-2;
 
 /// Defines a heuristic for the choice of a node to generate.
+#[derive(Clone)]
 pub(crate) enum InternalNodeSelectionHeuristic {
     MinimumRemainingValue,
     MinimumEntropy {
@@ -36,6 +54,16 @@ pub(crate) enum InternalNodeSelectionHeuristic {
         models_weight_log_weights: Vec<f32>,
     },
     Random,
+    FrontierGrowth {
+        /// `neighbours[node_index * directions_count + direction]` is the neighbour of `node_index` in `direction`, if any
+        neighbours: Vec<Option<NodeIndex>>,
+        directions_count: usize,
+    },
+    DistanceFromNode {
+        /// Distance of each node from the origin node of the heuristic, [`u32::MAX`] if unreachable
+        distances: Vec<u32>,
+        furthest: bool,
+    },
 }
 
 #[derive(Clone, Copy)]
@@ -67,10 +95,10 @@ fn entropy(weight_sum: f32, weight_log_weight_sum: f32) -> f32 {
 }
 
 impl InternalNodeSelectionHeuristic {
-    pub(crate) fn from_external<T: CoordinateSystem + Clone>(
+    pub(crate) fn from_external<T: CoordinateSystem + Clone, G: Grid<T>>(
         heuristic: NodeSelectionHeuristic,
         rules: &Rules<T>,
-        node_count: usize,
+        grid: &G,
     ) -> Self {
         match heuristic {
             NodeSelectionHeuristic::MinimumRemainingValue => {
@@ -78,8 +106,28 @@ impl InternalNodeSelectionHeuristic {
             }
             NodeSelectionHeuristic::Random => InternalNodeSelectionHeuristic::Random,
             NodeSelectionHeuristic::MinimumEntropy => {
-                InternalNodeSelectionHeuristic::new_minimum_entropy(rules, node_count)
+                InternalNodeSelectionHeuristic::new_minimum_entropy(rules, grid.total_size())
             }
+            NodeSelectionHeuristic::FrontierGrowth => {
+                let directions_count = grid.directions_count();
+                let mut neighbours = Vec::with_capacity(grid.total_size() * directions_count);
+                let mut node_neighbours = vec![None; directions_count];
+                for node_index in 0..grid.total_size() {
+                    grid.get_neighbours_in_all_directions(node_index, &mut node_neighbours);
+                    neighbours.extend_from_slice(&node_neighbours);
+                }
+                InternalNodeSelectionHeuristic::FrontierGrowth {
+                    neighbours,
+                    directions_count,
+                }
+            }
+            NodeSelectionHeuristic::DistanceFromNode {
+                node_index,
+                furthest,
+            } => InternalNodeSelectionHeuristic::DistanceFromNode {
+                distances: nodes_distances(grid, node_index),
+                furthest,
+            },
         }
     }
 
@@ -107,6 +155,49 @@ impl InternalNodeSelectionHeuristic {
         }
     }
 
+    /// Returns the size in bytes of the buffers of the heuristic
+    pub(crate) fn memory_footprint(&self) -> usize {
+        match self {
+            InternalNodeSelectionHeuristic::MinimumEntropy {
+                initial_node_entropy_data: _,
+                node_entropies,
+                models_weight_log_weights,
+            } => {
+                node_entropies.capacity() * size_of::<NodeEntropyData>()
+                    + models_weight_log_weights.capacity() * size_of::<f32>()
+            }
+            InternalNodeSelectionHeuristic::FrontierGrowth {
+                neighbours,
+                directions_count: _,
+            } => neighbours.capacity() * size_of::<Option<NodeIndex>>(),
+            InternalNodeSelectionHeuristic::DistanceFromNode {
+                distances,
+                furthest: _,
+            } => distances.capacity() * size_of::<u32>(),
+            InternalNodeSelectionHeuristic::MinimumRemainingValue
+            | InternalNodeSelectionHeuristic::Random => 0,
+        }
+    }
+
+    /// Returns the expected size in bytes of the buffers of `heuristic`, without instantiating it
+    pub(crate) fn estimate_memory_footprint(
+        heuristic: NodeSelectionHeuristic,
+        nodes_count: usize,
+        models_count: usize,
+        directions_count: usize,
+    ) -> usize {
+        match heuristic {
+            NodeSelectionHeuristic::MinimumEntropy => {
+                nodes_count * size_of::<NodeEntropyData>() + models_count * size_of::<f32>()
+            }
+            NodeSelectionHeuristic::FrontierGrowth => {
+                nodes_count * directions_count * size_of::<Option<NodeIndex>>()
+            }
+            NodeSelectionHeuristic::DistanceFromNode { .. } => nodes_count * size_of::<u32>(),
+            NodeSelectionHeuristic::MinimumRemainingValue | NodeSelectionHeuristic::Random => 0,
+        }
+    }
+
     pub(crate) fn reinitialize(&mut self) {
         match self {
             InternalNodeSelectionHeuristic::MinimumEntropy {
@@ -195,6 +286,84 @@ impl InternalNodeSelectionHeuristic {
                 }
                 picked_node
             }
+            InternalNodeSelectionHeuristic::FrontierGrowth {
+                neighbours,
+                directions_count,
+            } => {
+                let mut min = f32::MAX;
+                let mut picked_node = None;
+                let mut frontier_found = false;
+                for (index, &possibilities_count) in possible_models_counts.iter().enumerate() {
+                    if possibilities_count > 1 {
+                        let on_frontier = neighbours
+                            [index * directions_count..(index + 1) * directions_count]
+                            .iter()
+                            .flatten()
+                            .any(|neighbour| possible_models_counts[*neighbour] <= 1);
+                        // Once a frontier node is found, only frontier nodes are candidates
+                        if frontier_found && !on_frontier {
+                            continue;
+                        }
+                        if on_frontier && !frontier_found {
+                            frontier_found = true;
+                            min = f32::MAX;
+                        }
+                        let noise = MAX_NOISE_VALUE * rng.gen::<f32>();
+                        if (possibilities_count as f32 + noise) < min {
+                            min = possibilities_count as f32 + noise;
+                            picked_node = Some(index);
+                        }
+                    }
+                }
+                picked_node
+            }
+            InternalNodeSelectionHeuristic::DistanceFromNode {
+                distances,
+                furthest,
+            } => {
+                let mut best = (u32::MAX, f32::MAX);
+                let mut picked_node = None;
+                for (index, &possibilities_count) in possible_models_counts.iter().enumerate() {
+                    if possibilities_count > 1 {
+                        // Closest nodes have the lowest key
+                        let distance = match furthest {
+                            true => u32::MAX - distances[index],
+                            false => distances[index],
+                        };
+                        if distance > best.0 {
+                            continue;
+                        }
+                        let noise = MAX_NOISE_VALUE * rng.gen::<f32>();
+                        let candidate = (distance, possibilities_count as f32 + noise);
+                        if candidate < best {
+                            best = candidate;
+                            picked_node = Some(index);
+                        }
+                    }
+                }
+                picked_node
+            }
+        }
+    }
+}
+
+/// Returns the distance (in nodes) of each node of the `grid` from the node at `origin`, [`u32::MAX`] if unreachable
+fn nodes_distances<T: CoordinateSystem, G: Grid<T>>(grid: &G, origin: NodeIndex) -> Vec<u32> {
+    let mut distances = vec![u32::MAX; grid.total_size()];
+    if origin >= grid.total_size() {
+        return distances;
+    }
+    let mut neighbours = vec![None; grid.directions_count()];
+    let mut queue = VecDeque::from([origin]);
+    distances[origin] = 0;
+    while let Some(node_index) = queue.pop_front() {
+        grid.get_neighbours_in_all_directions(node_index, &mut neighbours);
+        for neighbour in neighbours.iter().flatten() {
+            if distances[*neighbour] == u32::MAX {
+                distances[*neighbour] = distances[node_index] + 1;
+                queue.push_back(*neighbour);
+            }
         }
     }
+    distances
 }
@@ -18,13 +18,23 @@ pub enum GenerationUpdate {
     Failed(usize),
 }
 
+/// A [`GenerationUpdate`] with its step number, as received by the observers
+#[derive(Clone, Copy, Debug)]
+pub struct StampedUpdate {
+    /// Step number of the update. Starts at 0 and is increased by 1 for each update sent by the generator, even across reinitializations: all the observers of a generator see the same step for the same update, and a gap in the steps received by an observer means that it missed some updates.
+    pub step: u64,
+    /// The update itself
+    pub update: GenerationUpdate,
+}
+
 /// Observer with a queue of the [`GenerationUpdate`] sent by the [`crate::generator::Generator`] which also maintains a coherent state of the current generation in a [`GridData`]
 ///
 /// Can be used in a different thread than the generator's thread.
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub struct QueuedStatefulObserver<T: CoordinateSystem, G: Grid<T>> {
     grid_data: GridData<T, Option<ModelInstance>, G>,
-    receiver: crossbeam_channel::Receiver<GenerationUpdate>,
+    last_step: Option<u64>,
+    receiver: crossbeam_channel::Receiver<StampedUpdate>,
 }
 
 impl<T: CoordinateSystem, G: Grid<T>> QueuedStatefulObserver<T, G> {
@@ -34,12 +44,10 @@ impl<T: CoordinateSystem, G: Grid<T>> QueuedStatefulObserver<T, G> {
         QueuedStatefulObserver::create(receiver, generator.grid())
     }
 
-    pub(crate) fn create(
-        receiver: crossbeam_channel::Receiver<GenerationUpdate>,
-        grid: &G,
-    ) -> Self {
+    pub(crate) fn create(receiver: crossbeam_channel::Receiver<StampedUpdate>, grid: &G) -> Self {
         QueuedStatefulObserver {
             grid_data: GridData::new(grid.clone(), vec![None; grid.total_size()]),
+            last_step: None,
             receiver,
         }
     }
@@ -49,16 +57,15 @@ impl<T: CoordinateSystem, G: Grid<T>> QueuedStatefulObserver<T, G> {
         &self.grid_data
     }
 
+    /// Returns the step number of the last update processed by the observer, if any. See [`StampedUpdate::step`]
+    pub fn last_step(&self) -> Option<u64> {
+        self.last_step
+    }
+
     /// Updates the internal state of the observer by dequeuing all queued updates.
     pub fn dequeue_all(&mut self) {
-        while let Ok(update) = self.receiver.try_recv() {
-            match update {
-                GenerationUpdate::Generated(grid_node) => self
-                    .grid_data
-                    .set(grid_node.node_index, Some(grid_node.model_instance)),
-                GenerationUpdate::Reinitializing(_) => self.grid_data.reset(None),
-                GenerationUpdate::Failed(_) => self.grid_data.reset(None),
-            }
+        while let Ok(stamped_update) = self.receiver.try_recv() {
+            self.apply(stamped_update);
         }
     }
 
@@ -66,20 +73,31 @@ impl<T: CoordinateSystem, G: Grid<T>> QueuedStatefulObserver<T, G> {
     ///
     /// Returns [`Some(GenerationUpdate)`] if there was an update to process, else returns `None`.
     pub fn dequeue_one(&mut self) -> Option<GenerationUpdate> {
+        self.dequeue_one_stamped()
+            .map(|stamped_update| stamped_update.update)
+    }
+
+    /// Same as [`QueuedStatefulObserver::dequeue_one`], but also returns the step number of the update
+    pub fn dequeue_one_stamped(&mut self) -> Option<StampedUpdate> {
         match self.receiver.try_recv() {
-            Ok(update) => {
-                match update {
-                    GenerationUpdate::Generated(grid_node) => self
-                        .grid_data
-                        .set(grid_node.node_index, Some(grid_node.model_instance)),
-                    GenerationUpdate::Reinitializing(_) => self.grid_data.reset(None),
-                    GenerationUpdate::Failed(_) => self.grid_data.reset(None),
-                }
-                Some(update)
+            Ok(stamped_update) => {
+                self.apply(stamped_update);
+                Some(stamped_update)
             }
             Err(_) => None,
         }
     }
+
+    fn apply(&mut self, stamped_update: StampedUpdate) {
+        match stamped_update.update {
+            GenerationUpdate::Generated(grid_node) => self
+                .grid_data
+                .set(grid_node.node_index, Some(grid_node.model_instance)),
+            GenerationUpdate::Reinitializing(_) => self.grid_data.reset(None),
+            GenerationUpdate::Failed(_) => self.grid_data.reset(None),
+        }
+        self.last_step = Some(stamped_update.step);
+    }
 }
 
 /// Observer with just a queue of the [`GenerationUpdate`] sent by the [`crate::generator::Generator`]
@@ -87,7 +105,7 @@ impl<T: CoordinateSystem, G: Grid<T>> QueuedStatefulObserver<T, G> {
 /// Can be used in a different thread than the generator's thread.
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub struct QueuedObserver {
-    receiver: crossbeam_channel::Receiver<GenerationUpdate>,
+    receiver: crossbeam_channel::Receiver<StampedUpdate>,
 }
 
 impl QueuedObserver {
@@ -97,7 +115,7 @@ impl QueuedObserver {
         QueuedObserver { receiver }
     }
 
-    pub(crate) fn create(receiver: crossbeam_channel::Receiver<GenerationUpdate>) -> Self {
+    pub(crate) fn create(receiver: crossbeam_channel::Receiver<StampedUpdate>) -> Self {
         Self { receiver }
     }
 
@@ -107,8 +125,17 @@ impl QueuedObserver {
     /// The `Vec` may be empty if no update was queued.
     pub fn dequeue_all(&mut self) -> Vec<GenerationUpdate> {
         let mut updates = Vec::new();
-        while let Ok(update) = self.receiver.try_recv() {
-            updates.push(update);
+        while let Ok(stamped_update) = self.receiver.try_recv() {
+            updates.push(stamped_update.update);
+        }
+        updates
+    }
+
+    /// Same as [`QueuedObserver::dequeue_all`], but the updates come with their step number
+    pub fn dequeue_all_stamped(&mut self) -> Vec<StampedUpdate> {
+        let mut updates = Vec::new();
+        while let Ok(stamped_update) = self.receiver.try_recv() {
+            updates.push(stamped_update);
         }
         updates
     }
@@ -117,8 +144,14 @@ impl QueuedObserver {
     ///
     /// Returns [`Some(GenerationUpdate)`] if there was an update to process, else returns `None`.
     pub fn dequeue_one(&mut self) -> Option<GenerationUpdate> {
+        self.dequeue_one_stamped()
+            .map(|stamped_update| stamped_update.update)
+    }
+
+    /// Same as [`QueuedObserver::dequeue_one`], but also returns the step number of the update
+    pub fn dequeue_one_stamped(&mut self) -> Option<StampedUpdate> {
         match self.receiver.try_recv() {
-            Ok(update) => Some(update),
+            Ok(stamped_update) => Some(stamped_update),
             Err(_) => None,
         }
     }
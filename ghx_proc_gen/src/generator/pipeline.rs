@@ -0,0 +1,205 @@
+use std::{fmt, sync::Arc};
+
+use ghx_grid::{
+    coordinate_system::CoordinateSystem,
+    grid::{Grid, GridData},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{GenerationPipelineError, NodeIndex};
+
+use super::{
+    builder::{GeneratorBuilder, DEFAULT_RETRY_COUNT},
+    model::{ModelInstance, ModelVariantIndex},
+    rules::Rules,
+    RngMode, WeightField,
+};
+
+/// Constraints given to a pass of a [`GenerationPipeline`], derived from the output of the previous pass
+#[derive(Default, Clone)]
+pub struct PassConstraints {
+    /// Nodes to spawn initially, see [`GeneratorBuilder::with_initial_nodes_raw`]
+    pub initial_nodes: Vec<(NodeIndex, ModelVariantIndex)>,
+    /// Models to ban initially, see [`GeneratorBuilder::with_initial_bans_raw`]
+    pub initial_bans: Vec<(NodeIndex, ModelVariantIndex)>,
+    /// Weight map of the models, see [`GeneratorBuilder::with_weight_field`]. For example to favor some models near the output of the previous pass
+    pub weight_field: Option<Arc<dyn WeightField>>,
+}
+
+impl fmt::Debug for PassConstraints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PassConstraints")
+            .field("initial_nodes", &self.initial_nodes)
+            .field("initial_bans", &self.initial_bans)
+            .field("weight_field", &self.weight_field.is_some())
+            .finish()
+    }
+}
+
+/// Function mapping the output of a pass and the [`Rules`] of the next pass to the [`PassConstraints`] of the next pass
+pub type PassMapping<C, G> =
+    Box<dyn Fn(&GridData<C, ModelInstance, G>, &Rules<C>) -> PassConstraints + Send + Sync>;
+
+struct GenerationPass<C: CoordinateSystem, G: Grid<C>> {
+    rules: Arc<Rules<C>>,
+    grid: G,
+    mapping: Option<PassMapping<C, G>>,
+}
+
+/// Sequence of generations (terrain pass → structures pass → props pass, ...) where the output of each pass is mapped to the initial constraints of the next one.
+///
+/// ### Example
+///
+/// No houses on water:
+/// ```
+/// use std::sync::Arc;
+/// use ghx_proc_gen::generator::{
+///     model::{ModelCollection, ModelRotation},
+///     pipeline::{GenerationPipeline, PassConstraints},
+///     rules::RulesBuilder,
+///     socket::{SocketCollection, SocketsCartesian2D},
+///     RngMode,
+/// };
+/// use ghx_grid::cartesian::grid::CartesianGrid;
+///
+/// let mut sockets = SocketCollection::new();
+/// let (land, water) = (sockets.create(), sockets.create());
+/// sockets.add_connections(vec![(land, vec![land, water]), (water, vec![water])]);
+/// let mut models = ModelCollection::new();
+/// models.create(SocketsCartesian2D::Mono(land));
+/// let water_model = models.create(SocketsCartesian2D::Mono(water)).index();
+/// let terrain_rules = Arc::new(RulesBuilder::new_cartesian_2d(models, sockets).build().unwrap());
+///
+/// let mut sockets = SocketCollection::new();
+/// let any = sockets.create();
+/// sockets.add_connection(any, vec![any]);
+/// let mut models = ModelCollection::new();
+/// models.create(SocketsCartesian2D::Mono(any));
+/// let house_model = models.create(SocketsCartesian2D::Mono(any)).index();
+/// let structures_rules =
+///     Arc::new(RulesBuilder::new_cartesian_2d(models, sockets).build().unwrap());
+///
+/// let grid = CartesianGrid::new_cartesian_2d(10, 10, false, false);
+/// let outputs = GenerationPipeline::new()
+///     .with_rng(RngMode::Seeded(42))
+///     .with_pass(terrain_rules, grid.clone())
+///     .with_mapped_pass(structures_rules, grid, move |terrain, structures_rules| {
+///         let house = structures_rules.variant_index(house_model, ModelRotation::Rot0).unwrap();
+///         PassConstraints {
+///             initial_bans: terrain
+///                 .iter()
+///                 .enumerate()
+///                 .filter(|(_, instance)| instance.model_index == water_model)
+///                 .map(|(node_index, _)| (node_index, house))
+///                 .collect(),
+///             ..Default::default()
+///         }
+///     })
+///     .generate()
+///     .unwrap();
+/// assert_eq!(outputs.len(), 2);
+/// ```
+pub struct GenerationPipeline<C: CoordinateSystem, G: Grid<C>> {
+    passes: Vec<GenerationPass<C, G>>,
+    max_retry_count: u32,
+    rng_mode: RngMode,
+}
+
+impl<C: CoordinateSystem, G: Grid<C>> GenerationPipeline<C, G> {
+    /// Creates an empty [`GenerationPipeline`]
+    pub fn new() -> Self {
+        Self {
+            passes: Vec::new(),
+            max_retry_count: DEFAULT_RETRY_COUNT,
+            rng_mode: RngMode::RandomSeed,
+        }
+    }
+
+    /// Specifies how many time each pass should retry its generation when a contradiction is encountered. Set to [`DEFAULT_RETRY_COUNT`] by default.
+    pub fn with_max_retry_count(mut self, max_retry_count: u32) -> Self {
+        self.max_retry_count = max_retry_count;
+        self
+    }
+
+    /// Specifies the [`RngMode`] of the pipeline. The seeds of the passes are derived from it. Defaults to [`RngMode::RandomSeed`].
+    pub fn with_rng(mut self, rng_mode: RngMode) -> Self {
+        self.rng_mode = rng_mode;
+        self
+    }
+
+    /// Adds a pass which is not constrained by the previous pass
+    pub fn with_pass(mut self, rules: Arc<Rules<C>>, grid: G) -> Self {
+        self.passes.push(GenerationPass {
+            rules,
+            grid,
+            mapping: None,
+        });
+        self
+    }
+
+    /// Adds a pass constrained by the output of the previous pass through `mapping`.
+    ///
+    /// The first pass cannot be a mapped pass: [`GenerationPipeline::generate`] returns [`GenerationPipelineError::MappedFirstPass`] in that case.
+    pub fn with_mapped_pass<
+        F: Fn(&GridData<C, ModelInstance, G>, &Rules<C>) -> PassConstraints + Send + Sync + 'static,
+    >(
+        mut self,
+        rules: Arc<Rules<C>>,
+        grid: G,
+        mapping: F,
+    ) -> Self {
+        self.passes.push(GenerationPass {
+            rules,
+            grid,
+            mapping: Some(Box::new(mapping)),
+        });
+        self
+    }
+
+    /// Returns how many passes are in the pipeline
+    pub fn passes_count(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// Generates all the passes in order and returns their outputs.
+    ///
+    /// Stops at the first pass which fails to build or generate and returns a [`GenerationPipelineError`] with the index of this pass. Returns [`GenerationPipelineError::MappedFirstPass`] without generating anything if the first pass has a mapping.
+    pub fn generate(&self) -> Result<Vec<GridData<C, ModelInstance, G>>, GenerationPipelineError> {
+        if self
+            .passes
+            .first()
+            .is_some_and(|first_pass| first_pass.mapping.is_some())
+        {
+            return Err(GenerationPipelineError::MappedFirstPass);
+        }
+        let mut rng = match self.rng_mode {
+            RngMode::Seeded(seed) => StdRng::seed_from_u64(seed),
+            RngMode::RandomSeed => StdRng::seed_from_u64(rand::thread_rng().gen()),
+        };
+        let mut outputs: Vec<GridData<C, ModelInstance, G>> = Vec::with_capacity(self.passes.len());
+        for (pass_index, pass) in self.passes.iter().enumerate() {
+            let constraints = match (&pass.mapping, outputs.last()) {
+                (Some(mapping), Some(previous_output)) => mapping(previous_output, &pass.rules),
+                _ => PassConstraints::default(),
+            };
+            let mut builder = GeneratorBuilder::new()
+                .with_shared_rules(pass.rules.clone())
+                .with_grid(pass.grid.clone())
+                .with_max_retry_count(self.max_retry_count)
+                .with_rng(RngMode::Seeded(rng.gen()));
+            if let Some(weight_field) = constraints.weight_field {
+                builder = builder.with_shared_weight_field(weight_field);
+            }
+            let mut generator = builder
+                .with_initial_nodes_raw(constraints.initial_nodes)
+                .with_initial_bans_raw(constraints.initial_bans)
+                .build()
+                .map_err(|err| GenerationPipelineError::Build(pass_index, err))?;
+            let (_gen_info, output) = generator
+                .generate_grid()
+                .map_err(|err| GenerationPipelineError::Generation(pass_index, err))?;
+            outputs.push(output);
+        }
+        Ok(outputs)
+    }
+}
@@ -0,0 +1,411 @@
+use ghx_grid::cartesian::coordinates::{Cartesian2D, Cartesian3D};
+
+use super::{
+    model::{ModelCollection, ModelIndex, ModelRotation},
+    socket::{Socket, SocketCollection, SocketsCartesian2D, SocketsCartesian3D},
+};
+
+/// Horizontal layers of terrain stacked on the Y axis: ground layers, topped by a single surface layer, topped by void.
+///
+/// Each horizontal layer is uniform since the sides of a model can only connect to the sides of the same model.
+///
+/// ### Example
+/// ```
+/// use ghx_proc_gen::generator::{
+///     model::ModelCollection, presets::FlatTerrainLayers3D, rules::RulesBuilder,
+///     socket::SocketCollection,
+/// };
+///
+/// let (mut models, mut sockets) = (ModelCollection::new(), SocketCollection::new());
+/// let terrain = FlatTerrainLayers3D::new(&mut sockets, &mut models);
+/// let rules = RulesBuilder::new_cartesian_3d(models, sockets).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FlatTerrainLayers3D {
+    /// Socket on all the faces of the void model
+    pub void: Socket,
+    /// Socket on the sides of the ground model
+    pub ground_side: Socket,
+    /// Socket on the top of the ground model
+    pub ground_top: Socket,
+    /// Socket on the bottom of the ground model
+    pub ground_bottom: Socket,
+    /// Socket on the sides of the surface model
+    pub surface_side: Socket,
+    /// Socket on the top of the surface model
+    pub surface_top: Socket,
+    /// Socket on the bottom of the surface model
+    pub surface_bottom: Socket,
+
+    /// Empty model, above the surface
+    pub void_model: ModelIndex,
+    /// Ground model, stackable on itself
+    pub ground_model: ModelIndex,
+    /// Surface model, on top of the ground
+    pub surface_model: ModelIndex,
+}
+
+impl FlatTerrainLayers3D {
+    /// Creates the sockets, connections and models of this preset in the given collections
+    pub fn new(sockets: &mut SocketCollection, models: &mut ModelCollection<Cartesian3D>) -> Self {
+        let mut s = || -> Socket { sockets.create() };
+        let void = s();
+        let (ground_side, ground_top, ground_bottom) = (s(), s(), s());
+        let (surface_side, surface_top, surface_bottom) = (s(), s(), s());
+
+        sockets
+            .add_connections(vec![
+                (void, vec![void]),
+                (ground_side, vec![ground_side]),
+                (surface_side, vec![surface_side]),
+            ])
+            // Sockets on the rotation axis (Y) use rotated connections so that rotated models can still be stacked.
+            .add_rotated_connections(vec![
+                (ground_top, vec![ground_bottom, surface_bottom]),
+                (surface_top, vec![void]),
+            ]);
+
+        let void_model = models
+            .create(SocketsCartesian3D::Mono(void))
+            .with_name("void")
+            .index();
+        let ground_model = models
+            .create(SocketsCartesian3D::Simple {
+                x_pos: ground_side,
+                x_neg: ground_side,
+                z_pos: ground_side,
+                z_neg: ground_side,
+                y_pos: ground_top,
+                y_neg: ground_bottom,
+            })
+            .with_name("ground")
+            .index();
+        let surface_model = models
+            .create(SocketsCartesian3D::Simple {
+                x_pos: surface_side,
+                x_neg: surface_side,
+                z_pos: surface_side,
+                z_neg: surface_side,
+                y_pos: surface_top,
+                y_neg: surface_bottom,
+            })
+            .with_name("surface")
+            .index();
+
+        Self {
+            void,
+            ground_side,
+            ground_top,
+            ground_bottom,
+            surface_side,
+            surface_top,
+            surface_bottom,
+            void_model,
+            ground_model,
+            surface_model,
+        }
+    }
+}
+
+/// Pipes (or corridors) network in 2d: straight pipes, corners, T-junctions, crossings and dead ends, all connected by a single `pipe` socket.
+///
+/// ### Example
+/// ```
+/// use ghx_proc_gen::generator::{
+///     model::ModelCollection, presets::Pipes2D, rules::RulesBuilder, socket::SocketCollection,
+/// };
+///
+/// let (mut models, mut sockets) = (ModelCollection::new(), SocketCollection::new());
+/// let pipes = Pipes2D::new(&mut sockets, &mut models);
+/// // Dead ends are rare
+/// models.models_mut().nth(pipes.end_model).unwrap().with_weight(0.1);
+/// let rules = RulesBuilder::new_cartesian_2d(models, sockets).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pipes2D {
+    /// Socket on the faces without a pipe
+    pub void: Socket,
+    /// Socket on the faces crossed by a pipe
+    pub pipe: Socket,
+
+    /// Empty model
+    pub empty_model: ModelIndex,
+    /// Straight pipe, along X (rotated along Y)
+    pub straight_model: ModelIndex,
+    /// Corner pipe, between X+ and Y+ (and all its rotations)
+    pub corner_model: ModelIndex,
+    /// T-junction, between X+, X- and Y+ (and all its rotations)
+    pub tee_model: ModelIndex,
+    /// Crossing of two pipes
+    pub cross_model: ModelIndex,
+    /// Dead end, opened towards X+ (and all its rotations)
+    pub end_model: ModelIndex,
+}
+
+impl Pipes2D {
+    /// Creates the sockets, connections and models of this preset in the given collections
+    pub fn new(sockets: &mut SocketCollection, models: &mut ModelCollection<Cartesian2D>) -> Self {
+        let (void, pipe) = (sockets.create(), sockets.create());
+        sockets.add_connections(vec![(void, vec![void]), (pipe, vec![pipe])]);
+
+        let empty_model = models
+            .create(SocketsCartesian2D::Mono(void))
+            .with_name("empty")
+            .index();
+        let straight_model = models
+            .create(SocketsCartesian2D::Simple {
+                x_pos: pipe,
+                x_neg: pipe,
+                y_pos: void,
+                y_neg: void,
+            })
+            .with_rotations([ModelRotation::Rot0, ModelRotation::Rot90])
+            .with_name("straight")
+            .index();
+        let corner_model = models
+            .create(SocketsCartesian2D::Simple {
+                x_pos: pipe,
+                x_neg: void,
+                y_pos: pipe,
+                y_neg: void,
+            })
+            .with_all_rotations()
+            .with_name("corner")
+            .index();
+        let tee_model = models
+            .create(SocketsCartesian2D::Simple {
+                x_pos: pipe,
+                x_neg: pipe,
+                y_pos: pipe,
+                y_neg: void,
+            })
+            .with_all_rotations()
+            .with_name("tee")
+            .index();
+        let cross_model = models
+            .create(SocketsCartesian2D::Mono(pipe))
+            .with_name("cross")
+            .index();
+        let end_model = models
+            .create(SocketsCartesian2D::Simple {
+                x_pos: pipe,
+                x_neg: void,
+                y_pos: void,
+                y_neg: void,
+            })
+            .with_all_rotations()
+            .with_name("end")
+            .index();
+
+        Self {
+            void,
+            pipe,
+            empty_model,
+            straight_model,
+            corner_model,
+            tee_model,
+            cross_model,
+            end_model,
+        }
+    }
+}
+
+/// Pillars standing on the bottom of a 3d grid (Y axis is up): a base, any number of stacked cores, and a cap, surrounded by void.
+///
+/// ### Example
+/// ```
+/// use ghx_proc_gen::generator::{
+///     model::ModelCollection, presets::Pillars3D, rules::RulesBuilder, socket::SocketCollection,
+/// };
+///
+/// let (mut models, mut sockets) = (ModelCollection::new(), SocketCollection::new());
+/// let pillars = Pillars3D::new(&mut sockets, &mut models);
+/// let rules = RulesBuilder::new_cartesian_3d(models, sockets).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pillars3D {
+    /// Socket on all the faces of the void model
+    pub void: Socket,
+    /// Socket on the sides of all the pillar models
+    pub pillar_side: Socket,
+    /// Socket on the top of the pillar base
+    pub base_top: Socket,
+    /// Socket on the bottom of the pillar base. Not connected to anything, so that bases can only be on the bottom of the grid
+    pub base_bottom: Socket,
+    /// Socket on the top of the pillar core
+    pub core_top: Socket,
+    /// Socket on the bottom of the pillar core
+    pub core_bottom: Socket,
+    /// Socket on the top of the pillar cap
+    pub cap_top: Socket,
+    /// Socket on the bottom of the pillar cap
+    pub cap_bottom: Socket,
+
+    /// Empty model
+    pub void_model: ModelIndex,
+    /// Base of a pillar
+    pub base_model: ModelIndex,
+    /// Core of a pillar, stackable on itself
+    pub core_model: ModelIndex,
+    /// Top of a pillar
+    pub cap_model: ModelIndex,
+}
+
+impl Pillars3D {
+    /// Creates the sockets, connections and models of this preset in the given collections
+    pub fn new(sockets: &mut SocketCollection, models: &mut ModelCollection<Cartesian3D>) -> Self {
+        let mut s = || -> Socket { sockets.create() };
+        let (void, pillar_side) = (s(), s());
+        let (base_top, base_bottom) = (s(), s());
+        let (core_top, core_bottom) = (s(), s());
+        let (cap_top, cap_bottom) = (s(), s());
+
+        sockets
+            .add_connections(vec![
+                (void, vec![void]),
+                (pillar_side, vec![pillar_side, void]),
+            ])
+            .add_rotated_connections(vec![
+                (base_top, vec![core_bottom]),
+                (core_top, vec![core_bottom, cap_bottom]),
+                (cap_top, vec![void]),
+            ]);
+
+        let void_model = models
+            .create(SocketsCartesian3D::Mono(void))
+            .with_name("void")
+            .index();
+        let mut pillar = |top, bottom, name: &'static str| {
+            models
+                .create(SocketsCartesian3D::Simple {
+                    x_pos: pillar_side,
+                    x_neg: pillar_side,
+                    z_pos: pillar_side,
+                    z_neg: pillar_side,
+                    y_pos: top,
+                    y_neg: bottom,
+                })
+                .with_name(name)
+                .index()
+        };
+        let base_model = pillar(base_top, base_bottom, "pillar_base");
+        let core_model = pillar(core_top, core_bottom, "pillar_core");
+        let cap_model = pillar(cap_top, cap_bottom, "pillar_cap");
+
+        Self {
+            void,
+            pillar_side,
+            base_top,
+            base_bottom,
+            core_top,
+            core_bottom,
+            cap_top,
+            cap_bottom,
+            void_model,
+            base_model,
+            core_model,
+            cap_model,
+        }
+    }
+}
+
+/// Side-view platformer tiles (Y axis is up): a continuous ground made of dirt topped by a surface row, and floating one-tile-high platforms in the air.
+///
+/// Models are never rotated, since gravity matters in a side view.
+///
+/// ### Example
+/// ```
+/// use ghx_proc_gen::generator::{
+///     model::ModelCollection, presets::Platformer2D, rules::RulesBuilder,
+///     socket::SocketCollection,
+/// };
+///
+/// let (mut models, mut sockets) = (ModelCollection::new(), SocketCollection::new());
+/// let platformer = Platformer2D::new(&mut sockets, &mut models);
+/// let rules = RulesBuilder::new_cartesian_2d(models, sockets).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Platformer2D {
+    /// Socket on all the faces of the air model, and on the air-facing faces of the other models
+    pub air: Socket,
+    /// Socket on all the faces of the dirt model, and on the bottom of the surface model
+    pub dirt: Socket,
+    /// Socket on the sides of the surface model
+    pub surface_side: Socket,
+    /// Socket on the connected sides of the platform models
+    pub platform_side: Socket,
+
+    /// Empty model
+    pub air_model: ModelIndex,
+    /// Dirt model, below the surface
+    pub dirt_model: ModelIndex,
+    /// Walkable top row of the ground
+    pub surface_model: ModelIndex,
+    /// Middle part of a floating platform
+    pub platform_model: ModelIndex,
+    /// Left end of a floating platform
+    pub platform_left_model: ModelIndex,
+    /// Right end of a floating platform
+    pub platform_right_model: ModelIndex,
+}
+
+impl Platformer2D {
+    /// Creates the sockets, connections and models of this preset in the given collections
+    pub fn new(sockets: &mut SocketCollection, models: &mut ModelCollection<Cartesian2D>) -> Self {
+        let mut s = || -> Socket { sockets.create() };
+        let (air, dirt) = (s(), s());
+        let (surface_side, platform_side) = (s(), s());
+
+        sockets.add_connections(vec![
+            (air, vec![air]),
+            (dirt, vec![dirt]),
+            (surface_side, vec![surface_side]),
+            (platform_side, vec![platform_side]),
+        ]);
+
+        let air_model = models
+            .create(SocketsCartesian2D::Mono(air))
+            .with_name("air")
+            .index();
+        let dirt_model = models
+            .create(SocketsCartesian2D::Mono(dirt))
+            .with_name("dirt")
+            .index();
+        let surface_model = models
+            .create(SocketsCartesian2D::Simple {
+                x_pos: surface_side,
+                x_neg: surface_side,
+                y_pos: air,
+                y_neg: dirt,
+            })
+            .with_name("surface")
+            .index();
+        let mut platform = |x_pos, x_neg, name: &'static str| {
+            models
+                .create(SocketsCartesian2D::Simple {
+                    x_pos,
+                    x_neg,
+                    y_pos: air,
+                    y_neg: air,
+                })
+                .with_name(name)
+                .index()
+        };
+        let platform_model = platform(platform_side, platform_side, "platform");
+        let platform_left_model = platform(platform_side, air, "platform_left");
+        let platform_right_model = platform(air, platform_side, "platform_right");
+
+        Self {
+            air,
+            dirt,
+            surface_side,
+            platform_side,
+            air_model,
+            dirt_model,
+            surface_model,
+            platform_model,
+            platform_left_model,
+            platform_right_model,
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use ghx_grid::{
+    cartesian::{
+        coordinates::{Cartesian2D, Cartesian3D, CartesianCoordinates},
+        grid::CartesianGrid,
+    },
+    grid::{Grid, GridData},
+};
+
+use crate::NodeIndex;
+
+use super::model::ModelInstance;
+
+/// Undetermined region of a mostly predetermined grid, extracted as a smaller grid so that a [`super::Generator`] only allocates its propagation state for this region instead of the full grid. For example to re-generate a small damaged area of a huge saved map.
+///
+/// The region is the bounding box of the undetermined nodes, extended by one node on each side (when possible) so that the generation stays compatible with the predetermined nodes around it. Predetermined nodes in the region are given as initial nodes.
+///
+/// Regions are never looping, even when extracted from a looping grid.
+///
+/// ### Example
+///
+/// ```
+/// use ghx_proc_gen::{
+///     generator::{builder::GeneratorBuilder, model::ModelCollection, region::SparseRegion, rules::RulesBuilder, socket::{SocketCollection, SocketsCartesian2D}},
+///     ghx_grid::{cartesian::grid::CartesianGrid, grid::{Grid, GridData}},
+/// };
+///
+/// let mut sockets = SocketCollection::new();
+/// let grass = sockets.create();
+/// sockets.add_connection(grass, vec![grass]);
+/// let mut models = ModelCollection::new();
+/// let grass_model = models.create(SocketsCartesian2D::Mono(grass)).instance();
+/// let rules = RulesBuilder::new_cartesian_2d(models, sockets).build().unwrap();
+///
+/// // A saved map with a damaged area
+/// let map_grid = CartesianGrid::new_cartesian_2d(64, 64, false, false);
+/// let mut saved_map = GridData::new(map_grid.clone(), vec![Some(grass_model); map_grid.total_size()]);
+/// saved_map.set(map_grid.index_from_coords(20, 30, 0), None);
+///
+/// let region = SparseRegion::from_grid_data(&saved_map).unwrap();
+/// let mut generator = GeneratorBuilder::new()
+///     .with_rules(rules)
+///     .with_grid(region.grid().clone())
+///     .with_initial_nodes(region.initial_nodes().clone())
+///     .unwrap()
+///     .build()
+///     .unwrap();
+/// let (_gen_info, generated_region) = generator.generate_grid().unwrap();
+/// region.write_back(&generated_region, &mut saved_map);
+/// assert!(saved_map.iter().all(|node| node.is_some()));
+/// ```
+pub struct SparseRegion<C: CartesianCoordinates> {
+    /// Coordinates of the first node of the region in the full grid
+    offset: (u32, u32, u32),
+    grid: CartesianGrid<C>,
+    initial_nodes: Vec<(NodeIndex, ModelInstance)>,
+}
+
+impl SparseRegion<Cartesian2D> {
+    /// Extracts the region of the undetermined (`None`) nodes of `data`. Returns `None` if all the nodes are determined.
+    pub fn from_grid_data(
+        data: &GridData<Cartesian2D, Option<ModelInstance>, CartesianGrid<Cartesian2D>>,
+    ) -> Option<Self> {
+        Self::extract(data, |(size_x, size_y, _)| {
+            CartesianGrid::new_cartesian_2d(size_x, size_y, false, false)
+        })
+    }
+}
+
+impl SparseRegion<Cartesian3D> {
+    /// Extracts the region of the undetermined (`None`) nodes of `data`. Returns `None` if all the nodes are determined.
+    pub fn from_grid_data(
+        data: &GridData<Cartesian3D, Option<ModelInstance>, CartesianGrid<Cartesian3D>>,
+    ) -> Option<Self> {
+        Self::extract(data, |(size_x, size_y, size_z)| {
+            CartesianGrid::new_cartesian_3d(size_x, size_y, size_z, false, false, false)
+        })
+    }
+}
+
+impl<C: CartesianCoordinates> SparseRegion<C> {
+    fn extract<F: FnOnce((u32, u32, u32)) -> CartesianGrid<C>>(
+        data: &GridData<C, Option<ModelInstance>, CartesianGrid<C>>,
+        create_grid: F,
+    ) -> Option<Self> {
+        let full_grid = data.grid();
+        let mut min = (u32::MAX, u32::MAX, u32::MAX);
+        let mut max = (0, 0, 0);
+        for (node_index, node) in data.iter().enumerate() {
+            if node.is_none() {
+                let pos = full_grid.pos_from_index(node_index);
+                min = (min.0.min(pos.x), min.1.min(pos.y), min.2.min(pos.z));
+                max = (max.0.max(pos.x), max.1.max(pos.y), max.2.max(pos.z));
+            }
+        }
+        if min.0 == u32::MAX {
+            return None;
+        }
+
+        // Extend the bounding box by one node to include the predetermined neighbours of the region
+        let offset = (
+            min.0.saturating_sub(1),
+            min.1.saturating_sub(1),
+            min.2.saturating_sub(1),
+        );
+        let end = (
+            (max.0 + 1).min(full_grid.size_x() - 1),
+            (max.1 + 1).min(full_grid.size_y() - 1),
+            (max.2 + 1).min(full_grid.size_z() - 1),
+        );
+        let grid = create_grid((
+            end.0 - offset.0 + 1,
+            end.1 - offset.1 + 1,
+            end.2 - offset.2 + 1,
+        ));
+
+        let mut region = Self {
+            offset,
+            grid,
+            initial_nodes: Vec::new(),
+        };
+        for region_index in 0..region.grid.total_size() {
+            if let Some(model) = data.get(region.full_grid_index(full_grid, region_index)) {
+                region.initial_nodes.push((region_index, *model));
+            }
+        }
+        Some(region)
+    }
+
+    /// Returns the grid of the region, to give to a [`super::builder::GeneratorBuilder`]
+    pub fn grid(&self) -> &CartesianGrid<C> {
+        &self.grid
+    }
+
+    /// Returns the predetermined nodes of the region, indexed in the region grid, to give to a [`super::builder::GeneratorBuilder`]
+    pub fn initial_nodes(&self) -> &Vec<(NodeIndex, ModelInstance)> {
+        &self.initial_nodes
+    }
+
+    /// Returns the index in the full grid of the node with index `region_index` in the region grid
+    pub fn full_grid_index(
+        &self,
+        full_grid: &CartesianGrid<C>,
+        region_index: NodeIndex,
+    ) -> NodeIndex {
+        let pos = self.grid.pos_from_index(region_index);
+        full_grid.index_from_coords(
+            self.offset.0 + pos.x,
+            self.offset.1 + pos.y,
+            self.offset.2 + pos.z,
+        )
+    }
+
+    /// Writes the nodes of a generation of the region back into the full grid `target`
+    pub fn write_back(
+        &self,
+        generated_region: &GridData<C, ModelInstance, CartesianGrid<C>>,
+        target: &mut GridData<C, Option<ModelInstance>, CartesianGrid<C>>,
+    ) {
+        for (region_index, model) in generated_region.iter().enumerate() {
+            let full_grid_index = self.full_grid_index(target.grid(), region_index);
+            target.set(full_grid_index, Some(*model));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ghx_grid::{
+        cartesian::grid::CartesianGrid,
+        grid::{Grid, GridData},
+    };
+
+    use crate::generator::{
+        builder::GeneratorBuilder,
+        model::{ModelCollection, ModelInstance, ModelRotation},
+        rules::RulesBuilder,
+        socket::{SocketCollection, SocketsCartesian2D},
+    };
+
+    use super::SparseRegion;
+
+    #[test]
+    fn write_back_round_trip() {
+        let mut sockets = SocketCollection::new();
+        let a = sockets.create();
+        sockets.add_connection(a, vec![a]);
+        let mut models = ModelCollection::new();
+        models.create(SocketsCartesian2D::Mono(a));
+        models.create(SocketsCartesian2D::Mono(a));
+        let rules = RulesBuilder::new_cartesian_2d(models, sockets)
+            .build()
+            .unwrap();
+
+        let saved_model = ModelInstance::new(1, ModelRotation::Rot0);
+        let map_grid = CartesianGrid::new_cartesian_2d(6, 5, false, false);
+        let mut saved_map = GridData::new(
+            map_grid.clone(),
+            vec![Some(saved_model); map_grid.total_size()],
+        );
+        let undetermined = [
+            map_grid.index_from_coords(2, 2, 0),
+            map_grid.index_from_coords(3, 2, 0),
+        ];
+        for node_index in undetermined {
+            saved_map.set(node_index, None);
+        }
+
+        let region = SparseRegion::from_grid_data(&saved_map).unwrap();
+        // Bounding box of the undetermined nodes, extended by one node on each side
+        assert_eq!(region.grid().size_x(), 4);
+        assert_eq!(region.grid().size_y(), 3);
+        assert_eq!(
+            region.initial_nodes().len(),
+            region.grid().total_size() - undetermined.len()
+        );
+        assert_eq!(
+            region.full_grid_index(&map_grid, 0),
+            map_grid.index_from_coords(1, 1, 0)
+        );
+
+        let mut generator = GeneratorBuilder::new()
+            .with_rules(rules)
+            .with_grid(region.grid().clone())
+            .with_initial_nodes(region.initial_nodes().clone())
+            .unwrap()
+            .build()
+            .unwrap();
+        let (_gen_info, generated_region) = generator.generate_grid().unwrap();
+        region.write_back(&generated_region, &mut saved_map);
+
+        for (node_index, node) in saved_map.iter().enumerate() {
+            match undetermined.contains(&node_index) {
+                true => assert!(node.is_some()),
+                false => assert_eq!(*node, Some(saved_model)),
+            }
+        }
+    }
+
+    #[test]
+    fn region_is_clamped_to_the_grid() {
+        let map_grid = CartesianGrid::new_cartesian_2d(4, 4, true, true);
+        let saved_model = ModelInstance::new(0, ModelRotation::Rot0);
+        let mut saved_map = GridData::new(
+            map_grid.clone(),
+            vec![Some(saved_model); map_grid.total_size()],
+        );
+        assert!(SparseRegion::from_grid_data(&saved_map).is_none());
+
+        saved_map.set(map_grid.index_from_coords(0, 0, 0), None);
+        let region = SparseRegion::from_grid_data(&saved_map).unwrap();
+        assert_eq!(region.grid().size_x(), 2);
+        assert_eq!(region.grid().size_y(), 2);
+        assert_eq!(region.full_grid_index(&map_grid, 0), 0);
+    }
+}
@@ -1,13 +1,16 @@
 use std::{
+    any::Any,
     collections::{BTreeSet, HashMap, HashSet},
     fmt,
     marker::PhantomData,
+    sync::Arc,
 };
 
 use ghx_grid::{
     cartesian::coordinates::{Cartesian2D, Cartesian3D},
     coordinate_system::CoordinateSystem,
     direction::{Direction, DirectionTrait},
+    grid::Grid,
 };
 use ndarray::{Array, Ix1, Ix2};
 
@@ -16,16 +19,19 @@ use std::borrow::Cow;
 
 #[cfg(feature = "debug-traces")]
 use tracing::trace;
+use tracing::warn;
 
 #[cfg(feature = "bevy")]
 use bevy::ecs::component::Component;
 #[cfg(feature = "reflect")]
 use bevy::{ecs::reflect::ReflectComponent, reflect::Reflect};
 
+#[cfg(feature = "models-sockets")]
+use super::socket::SocketId;
 use super::{
     model::{
-        Model, ModelCollection, ModelIndex, ModelInstance, ModelRotation, ModelVariantIndex,
-        ALL_MODEL_ROTATIONS,
+        Model, ModelCollection, ModelData, ModelFacing, ModelIndex, ModelInstance, ModelRotation,
+        ModelVariantIndex, ALL_MODEL_ROTATIONS,
     },
     socket::SocketCollection,
 };
@@ -40,6 +46,7 @@ pub struct RulesBuilder<C: CoordinateSystem> {
     socket_collection: SocketCollection,
     rotation_axis: C::Direction,
     coord_system: C,
+    fixed_point_weights: bool,
 }
 
 impl RulesBuilder<Cartesian2D> {
@@ -71,6 +78,7 @@ impl RulesBuilder<Cartesian2D> {
             socket_collection,
             rotation_axis: CARTESIAN_2D_ROTATION_AXIS,
             coord_system: Cartesian2D,
+            fixed_point_weights: false,
         }
     }
 }
@@ -131,6 +139,7 @@ impl RulesBuilder<Cartesian3D> {
             socket_collection,
             rotation_axis: Direction::YForward,
             coord_system: Cartesian3D,
+            fixed_point_weights: false,
         }
     }
 }
@@ -144,16 +153,159 @@ impl RulesBuilder<Cartesian3D> {
 }
 
 impl<C: CoordinateSystem> RulesBuilder<C> {
+    /// Used to create Rules for any coordinate system `coord_system`, with the models rotating around `rotation_axis`.
+    ///
+    /// When the coordinate system is known, prefer [`RulesBuilder::new_cartesian_2d`] or [`RulesBuilder::new_cartesian_3d`]. Note that in 2d, the rotation axis should be [`CARTESIAN_2D_ROTATION_AXIS`].
+    pub fn new_raw(
+        models: ModelCollection<C>,
+        socket_collection: SocketCollection,
+        rotation_axis: C::Direction,
+        coord_system: C,
+    ) -> Self {
+        Self {
+            models,
+            socket_collection,
+            rotation_axis,
+            coord_system,
+            fixed_point_weights: false,
+        }
+    }
+
+    /// Quantizes the models weights to integer fixed-point values when building the [`Rules`], see [`Rules::fixed_point_weight`].
+    ///
+    /// The weighted model selection of the generators then samples the models with integer arithmetic only, so that the same seed yields the same generation on every platform and compiler (for example for multiplayer lockstep worlds). Disabled by default.
+    ///
+    /// Weights are quantized relatively to the greatest weight, so weights ratios greater than [`FIXED_POINT_WEIGHT_MAX`] are not preserved.
+    ///
+    /// **Note**: the [`super::node_heuristic::NodeSelectionHeuristic::MinimumEntropy`] node heuristic still computes floating point entropies, prefer another node heuristic for a fully integer generation.
+    pub fn with_fixed_point_weights(mut self) -> Self {
+        self.fixed_point_weights = true;
+        self
+    }
+
     /// Builds the [`Rules`] from the current configuration of the [`RulesBuilder`]
     ///
-    /// May return [`crate::RulesBuilderError::NoModelsOrSockets`] if `models` or `socket_collection` are empty.
+    /// May return [`crate::RulesBuilderError::NoModelsOrSockets`] if `models` or `socket_collection` are empty, or [`crate::RulesBuilderError::RotatedSocketsOnReorientedModel`] if a model that can be reoriented uses sockets with rotated connections (see [`super::model::ModelFacing`]).
+    ///
+    /// Warns about dead-end models (see [`Rules::dead_end_models`]) and about extreme weights ratios (see [`Rules::weight_stats`]).
     pub fn build(self) -> Result<Rules<C>, RulesBuilderError> {
-        Rules::new(
+        let rules = Rules::new(
             self.models,
             self.socket_collection,
             self.rotation_axis,
             self.coord_system,
-        )
+            self.fixed_point_weights,
+        )?;
+        for dead_end in rules.dead_end_models() {
+            warn!("Rules have a dead-end model: {}", dead_end);
+        }
+        Ok(rules)
+    }
+}
+
+/// Reason why a model variant cannot be placed on any node of a grid. See [`Rules::unplaceable_models`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnplaceableReason {
+    /// The model variant has no allowed neighbours in this direction (index of the direction in the coordinate system)
+    NoAllowedNeighbour(usize),
+    /// All the allowed neighbours of the model variant in this direction (index of the direction in the coordinate system) are themselves unplaceable
+    OnlyUnplaceableNeighbours(usize),
+    /// The grid has no nodes
+    NoNode,
+}
+
+impl fmt::Display for UnplaceableReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnplaceableReason::NoAllowedNeighbour(direction) => {
+                write!(f, "no allowed neighbour in direction {}", direction)
+            }
+            UnplaceableReason::OnlyUnplaceableNeighbours(direction) => {
+                write!(f, "only unplaceable neighbours in direction {}", direction)
+            }
+            UnplaceableReason::NoNode => write!(f, "the grid has no nodes"),
+        }
+    }
+}
+
+/// A model variant that can never be placed on any node of a grid. See [`Rules::unplaceable_models`]
+#[derive(Clone, Debug)]
+pub struct UnplaceableModel {
+    /// Index of the model variant
+    pub variant_index: ModelVariantIndex,
+    /// Original model and rotation of the variant
+    pub instance: ModelInstance,
+    /// Name of the original model
+    #[cfg(feature = "models-names")]
+    pub name: Cow<'static, str>,
+    /// Why the model variant cannot be placed
+    pub reason: UnplaceableReason,
+}
+
+impl fmt::Display for UnplaceableModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "model variant {} ({}", self.variant_index, self.instance)?;
+        #[cfg(feature = "models-names")]
+        write!(f, ", {}", self.name)?;
+        write!(f, "): {}", self.reason)
+    }
+}
+
+/// A model variant with no possible connection in some directions. See [`Rules::dead_end_models`]
+#[derive(Clone, Debug)]
+pub struct DeadEndModel {
+    /// Index of the model variant
+    pub variant_index: ModelVariantIndex,
+    /// Original model and rotation of the variant
+    pub instance: ModelInstance,
+    /// Name of the original model
+    #[cfg(feature = "models-names")]
+    pub name: Cow<'static, str>,
+    /// Indexes (in the coordinate system) of the directions in which no model variant can connect
+    pub directions: Vec<usize>,
+}
+
+impl fmt::Display for DeadEndModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "model variant {} ({}", self.variant_index, self.instance)?;
+        #[cfg(feature = "models-names")]
+        write!(f, ", {}", self.name)?;
+        write!(f, ") has no connection in directions {:?}", self.directions)
+    }
+}
+
+/// Fixed-point value of the greatest weight of [`Rules`] built with [`RulesBuilder::with_fixed_point_weights`]. The other weights are scaled accordingly and rounded, with a minimum of `1`
+pub const FIXED_POINT_WEIGHT_MAX: u32 = 1 << 20;
+
+/// Ratio between the greatest and the smallest weights of [`Rules`] above which weighted selections may be numerically unreliable
+pub const MAX_SAFE_WEIGHT_RATIO: f32 = 1e6;
+
+/// Statistics about the weights of the original models of [`Rules`]. See [`Rules::weight_stats`]
+#[derive(Clone, Debug)]
+pub struct WeightStats {
+    /// Smallest weight
+    pub min: f32,
+    /// Index of the original model with the smallest weight
+    pub min_model: ModelIndex,
+    /// Greatest weight
+    pub max: f32,
+    /// Index of the original model with the greatest weight
+    pub max_model: ModelIndex,
+    /// Sum of the weights of the original models
+    pub sum: f32,
+    /// Original models that were given an invalid weight (`<= 0.`), which was clamped to `f32::MIN_POSITIVE`
+    pub clamped_models: Vec<ModelIndex>,
+}
+
+impl WeightStats {
+    /// Returns the ratio between the greatest and the smallest weights
+    pub fn ratio(&self) -> f32 {
+        self.max / self.min
+    }
+
+    /// Returns `true` if the ratio between the greatest and the smallest weights is above [`MAX_SAFE_WEIGHT_RATIO`]
+    pub fn has_extreme_ratio(&self) -> bool {
+        self.ratio() > MAX_SAFE_WEIGHT_RATIO
     }
 }
 
@@ -178,14 +330,27 @@ impl fmt::Display for ModelInfo {
     }
 }
 
+/// Number of possible orientations (facing, rotation & flip) of a model
+const ORIENTATIONS_COUNT: usize = 48;
+
+#[inline]
+fn orientation_index(facing: ModelFacing, rotation: ModelRotation, flipped: bool) -> usize {
+    let index = facing.index() as usize * ALL_MODEL_ROTATIONS.len() + rotation.index() as usize;
+    match flipped {
+        true => index + ORIENTATIONS_COUNT / 2,
+        false => index,
+    }
+}
+
 /// Defines the rules of a generation: the coordinate system, the models, the way they can be rotated, the sockets and their connections.
 ///
 /// A same set of [`Rules`] can be shared by multiple generators.
+#[derive(Clone)]
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub struct Rules<C: CoordinateSystem> {
     /// Number of original input models used to build these rules.
     original_models_count: usize,
-    /// Maps a [`super::model::ModelIndex`] and a [`super::model::ModelRotation`] to an optionnal corresponding [`ModelVariantIndex`]
+    /// Maps a [`super::model::ModelIndex`] and an orientation ([`super::model::ModelFacing`], [`super::model::ModelRotation`] and whether or not it is flipped) to an optionnal corresponding [`ModelVariantIndex`]
     models_mapping: Array<Option<ModelVariantIndex>, Ix2>,
 
     /// All the model variations in this ruleset.
@@ -193,8 +358,19 @@ pub struct Rules<C: CoordinateSystem> {
     /// This is expanded from a given collection of base models, with added variations of rotations around an axis.
     models: Vec<ModelInstance>,
     weights: Vec<f32>,
+    /// Quantized `weights`, only if the rules were built with [`RulesBuilder::with_fixed_point_weights`]
+    fixed_point_weights: Option<Vec<u32>>,
+    /// Original models which were given an invalid weight, clamped to `f32::MIN_POSITIVE`
+    clamped_weights: Vec<ModelIndex>,
+    /// User data of the original models, indexed by [`ModelIndex`]
+    models_data: Vec<Option<ModelData>>,
     #[cfg(feature = "models-names")]
     names: Vec<Option<Cow<'static, str>>>,
+    #[cfg(feature = "models-sockets")]
+    sockets: Vec<Vec<Vec<SocketId>>>,
+    /// Names of the sockets, indexed by socket index
+    #[cfg(all(feature = "models-sockets", feature = "models-names"))]
+    socket_names: HashMap<u32, Cow<'static, str>>,
 
     /// The vector `allowed_neighbours[model_index][direction]` holds all the allowed adjacent models (indexes) to `model_index` in `direction`.
     ///
@@ -212,13 +388,23 @@ impl<C: CoordinateSystem> Rules<C> {
         socket_collection: SocketCollection,
         rotation_axis: C::Direction,
         coord_system: C,
+        fixed_point_weights: bool,
     ) -> Result<Rules<C>, RulesBuilderError> {
         let original_models_count = models.models_count();
-        let mut model_variations = models.create_variations(rotation_axis);
+        let models_data = models.models_data();
+        let clamped_weights = models.clamped_weights();
+        let mut model_variations = models.create_variations(rotation_axis, &socket_collection);
         // We test the expanded models because a model may have no rotations allowed.
         if model_variations.len() == 0 || socket_collection.is_empty() {
             return Err(RulesBuilderError::NoModelsOrSockets);
         }
+        if let Some(model_index) =
+            models.first_reoriented_model_with_rotated_sockets(&socket_collection)
+        {
+            return Err(RulesBuilderError::RotatedSocketsOnReorientedModel(
+                model_index,
+            ));
+        }
 
         // Temporary collection to reverse the relation: sockets_to_models.get(socket)[direction] will hold all the models that have 'socket' from 'direction'
         let mut sockets_to_models = HashMap::new();
@@ -271,18 +457,26 @@ impl<C: CoordinateSystem> Rules<C> {
         let mut model_instances = Vec::with_capacity(model_variations.len());
         #[cfg(feature = "models-names")]
         let mut names = Vec::with_capacity(model_variations.len());
+        #[cfg(feature = "models-sockets")]
+        let mut sockets = Vec::with_capacity(model_variations.len());
 
         let mut models_mapping =
-            Array::from_elem((original_models_count, ALL_MODEL_ROTATIONS.len()), None);
+            Array::from_elem((original_models_count, ORIENTATIONS_COUNT), None);
         for (index, model_variation) in model_variations.iter_mut().enumerate() {
             weights.push(model_variation.weight());
             model_instances.push(model_variation.to_instance());
             #[cfg(feature = "models-names")]
             names.push(model_variation.name.take());
+            #[cfg(feature = "models-sockets")]
+            sockets.push(model_variation.sockets().clone());
 
             models_mapping[(
                 model_variation.original_index(),
-                model_variation.rotation().index() as usize,
+                orientation_index(
+                    model_variation.facing(),
+                    model_variation.rotation(),
+                    model_variation.flipped(),
+                ),
             )] = Some(index);
         }
 
@@ -294,16 +488,39 @@ impl<C: CoordinateSystem> Rules<C> {
             );
         }
 
-        Ok(Rules {
+        let mut rules = Rules {
             original_models_count,
             models_mapping,
             models: model_instances,
             weights,
+            fixed_point_weights: match fixed_point_weights {
+                true => Some(Vec::new()),
+                false => None,
+            },
+            clamped_weights,
+            models_data,
             #[cfg(feature = "models-names")]
             names,
+            #[cfg(feature = "models-sockets")]
+            sockets,
+            #[cfg(all(feature = "models-sockets", feature = "models-names"))]
+            socket_names: socket_collection.names().clone(),
             allowed_neighbours,
             typestate: PhantomData,
-        })
+        };
+        rules.quantize_weights();
+
+        let stats = rules.weight_stats();
+        if stats.has_extreme_ratio() {
+            warn!(
+                "Rules have an extreme weights ratio {} (min: {}, max: {}), weighted selections may be numerically unreliable",
+                stats.ratio(),
+                stats.min,
+                stats.max
+            );
+        }
+
+        Ok(rules)
     }
 
     #[inline]
@@ -354,6 +571,255 @@ impl<C: CoordinateSystem> Rules<C> {
         }
     }
 
+    /// Returns the fixed-point weight of a model variant, if the rules were built with [`RulesBuilder::with_fixed_point_weights`]. Returns [`None`] otherwise, or if this model variant index is not valid.
+    pub fn fixed_point_weight(&self, model_index: ModelVariantIndex) -> Option<u32> {
+        self.fixed_point_weights.as_ref()?.get(model_index).copied()
+    }
+
+    /// Returns `true` if the rules were built with [`RulesBuilder::with_fixed_point_weights`]
+    pub fn has_fixed_point_weights(&self) -> bool {
+        self.fixed_point_weights.is_some()
+    }
+
+    #[inline]
+    pub(crate) fn fixed_point_weight_unchecked(&self, model_index: ModelVariantIndex) -> u32 {
+        self.fixed_point_weights
+            .as_ref()
+            .map_or(1, |weights| weights[model_index])
+    }
+
+    /// Recomputes the fixed-point weights from the weights, if enabled
+    fn quantize_weights(&mut self) {
+        let Some(fixed_point_weights) = &mut self.fixed_point_weights else {
+            return;
+        };
+        let max = self.weights.iter().cloned().fold(0., f32::max);
+        *fixed_point_weights = self
+            .weights
+            .iter()
+            .map(|weight| match max > 0. {
+                true => ((*weight as f64 / max as f64) * FIXED_POINT_WEIGHT_MAX as f64)
+                    .round()
+                    .max(1.) as u32,
+                false => 1,
+            })
+            .collect();
+    }
+
+    /// Returns the user data attached to the original model with index `model_index` (see [`Model::with_data`]).
+    ///
+    /// Returns [`None`] if this model index is not valid, if the model has no data, or if its data is not of type `T`.
+    pub fn model_data<T: Any>(&self, model_index: ModelIndex) -> Option<&T> {
+        self.models_data
+            .get(model_index)?
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<T>())
+    }
+
+    /// Returns the user data attached to the model of a [`ModelInstance`], such as the ones of a generated grid. See [`Rules::model_data`]
+    pub fn instance_data<T: Any>(&self, instance: &ModelInstance) -> Option<&T> {
+        self.model_data(instance.model_index)
+    }
+
+    /// Sets the user data of the original model with index `model_index`. Does nothing if `model_index` is not valid.
+    pub fn set_model_data<T: Any + Send + Sync>(&mut self, model_index: ModelIndex, data: T) {
+        if let Some(model_data) = self.models_data.get_mut(model_index) {
+            *model_data = Some(Arc::new(data));
+        }
+    }
+
+    /// Sets the weight of all the variations of the original model with index `model_index`. Does nothing if `model_index` is not valid.
+    ///
+    /// The `weight` value should be strictly superior to `0`. If it is not the case, the value will be overriden by `f32::MIN_POSITIVE`.
+    ///
+    /// **Note**: [`Rules`] are shared by the generators, a generator should be rebuilt with the modified [`Rules`] for them to be taken into account.
+    pub fn set_weight(&mut self, model_index: ModelIndex, weight: f32) {
+        if model_index >= self.original_models_count {
+            return;
+        }
+        self.clamped_weights.retain(|index| *index != model_index);
+        let weight = match weight > 0. {
+            true => weight,
+            false => {
+                self.clamped_weights.push(model_index);
+                f32::MIN_POSITIVE
+            }
+        };
+        for variant_index in self.models_mapping.row(model_index).iter().flatten() {
+            self.weights[*variant_index] = weight;
+        }
+        self.quantize_weights();
+    }
+
+    /// Returns the weight of the original model with index `model_index`, shared by all its variations. Returns [`None`] if this model index is not valid or if the model has no variations.
+    pub fn original_weight(&self, model_index: ModelIndex) -> Option<f32> {
+        if model_index >= self.original_models_count {
+            return None;
+        }
+        self.models_mapping
+            .row(model_index)
+            .iter()
+            .find_map(|variant_index| *variant_index)
+            .map(|variant_index| self.weights[variant_index])
+    }
+
+    /// Returns statistics about the weights of the original models of the rules.
+    ///
+    /// Can be used to detect weights with an extreme ratio, which make weighted selections numerically unreliable, or weights that were invalid and clamped to `f32::MIN_POSITIVE`.
+    pub fn weight_stats(&self) -> WeightStats {
+        let mut stats = WeightStats {
+            min: f32::MAX,
+            min_model: 0,
+            max: 0.,
+            max_model: 0,
+            sum: 0.,
+            clamped_models: self.clamped_weights.clone(),
+        };
+        for model_index in 0..self.original_models_count {
+            let Some(weight) = self.original_weight(model_index) else {
+                continue;
+            };
+            if weight < stats.min {
+                stats.min = weight;
+                stats.min_model = model_index;
+            }
+            if weight > stats.max {
+                stats.max = weight;
+                stats.max_model = model_index;
+            }
+            stats.sum += weight;
+        }
+        stats
+    }
+
+    /// Scales the weights of all the models so that the greatest weight is `1`. The ratios between the weights are kept.
+    ///
+    /// **Note**: [`Rules`] are shared by the generators, a generator should be rebuilt with the modified [`Rules`] for them to be taken into account.
+    pub fn normalize_weights(&mut self) {
+        let max = self.weights.iter().cloned().fold(0., f32::max);
+        if max <= 0. {
+            return;
+        }
+        for weight in self.weights.iter_mut() {
+            *weight = (*weight / max).max(f32::MIN_POSITIVE);
+        }
+        self.quantize_weights();
+    }
+
+    /// Returns the model variants that have at least one direction in which no model variant (including themselves) can connect.
+    ///
+    /// Such a model variant can only be placed on a non-looping border of a grid, with no neighbour in those directions, and causes contradictions anywhere else. This is usually an authoring error: a missing socket connection.
+    pub fn dead_end_models(&self) -> Vec<DeadEndModel> {
+        let directions_count = self.allowed_neighbours.ncols();
+        let mut dead_ends = Vec::new();
+        for model in 0..self.models_count() {
+            let directions: Vec<usize> = (0..directions_count)
+                .filter(|direction| self.allowed_models(model, *direction).is_empty())
+                .collect();
+            if !directions.is_empty() {
+                dead_ends.push(DeadEndModel {
+                    variant_index: model,
+                    instance: self.models[model],
+                    #[cfg(feature = "models-names")]
+                    name: self.name_unchecked(model),
+                    directions,
+                });
+            }
+        }
+        dead_ends
+    }
+
+    /// Analyzes the rules on `grid` and returns the model variants that can never be placed on any of its nodes, with the reason why.
+    ///
+    /// A model variant cannot be placed on a node if, in a direction where this node has a neighbour, the variant has no allowed neighbours (or only unplaceable ones). This depends on the grid: for example a variant with no allowed neighbours towards X+ can still be placed on the X+ border of a non-looping grid.
+    pub fn unplaceable_models<G: Grid<C>>(&self, grid: &G) -> Vec<UnplaceableModel> {
+        let directions_count = grid.directions_count();
+
+        // Nodes are grouped by the directions in which they have a neighbour, as a bitmask
+        let mut neighbours_masks = BTreeSet::new();
+        let mut neighbours = vec![None; directions_count];
+        for node_index in 0..grid.total_size() {
+            grid.get_neighbours_in_all_directions(node_index, &mut neighbours);
+            let mask = neighbours
+                .iter()
+                .enumerate()
+                .filter(|(_, neighbour)| neighbour.is_some())
+                .fold(0u64, |mask, (direction, _)| mask | (1 << direction));
+            neighbours_masks.insert(mask);
+        }
+
+        let mut placeable = vec![true; self.models_count()];
+        let mut reasons = vec![None; self.models_count()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for model in 0..self.models_count() {
+                if !placeable[model] {
+                    continue;
+                }
+                // Status of the model in each direction
+                let blocking: Vec<Option<UnplaceableReason>> = (0..directions_count)
+                    .map(|direction| {
+                        let allowed = self.allowed_models(model, direction);
+                        if allowed.is_empty() {
+                            Some(UnplaceableReason::NoAllowedNeighbour(direction))
+                        } else if allowed.iter().all(|neighbour| !placeable[*neighbour]) {
+                            Some(UnplaceableReason::OnlyUnplaceableNeighbours(direction))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                let can_be_placed = neighbours_masks.iter().any(|mask| {
+                    (0..directions_count).all(|direction| {
+                        mask & (1 << direction) == 0 || blocking[direction].is_none()
+                    })
+                });
+                if !can_be_placed {
+                    placeable[model] = false;
+                    changed = true;
+                    // Report the first blocking direction in which some nodes have a neighbour
+                    reasons[model] = (0..directions_count)
+                        .filter(|direction| {
+                            neighbours_masks
+                                .iter()
+                                .any(|mask| mask & (1 << direction) != 0)
+                        })
+                        .find_map(|direction| blocking[direction]);
+                }
+            }
+        }
+
+        let mut unplaceable_models = Vec::new();
+        for (model, reason) in reasons.into_iter().enumerate() {
+            if placeable[model] {
+                continue;
+            }
+            unplaceable_models.push(UnplaceableModel {
+                variant_index: model,
+                instance: self.models[model],
+                #[cfg(feature = "models-names")]
+                name: self.name_unchecked(model),
+                reason: reason.unwrap_or(UnplaceableReason::NoNode),
+            });
+        }
+        unplaceable_models
+    }
+
+    /// Returns whether or not the model variant `neighbour` is allowed next to the model variant `model` in `direction`.
+    ///
+    /// Returns `false` if one of the model variant indexes is not valid.
+    pub fn is_neighbour_allowed<Direction: Into<usize>>(
+        &self,
+        model: ModelVariantIndex,
+        direction: Direction,
+        neighbour: ModelVariantIndex,
+    ) -> bool {
+        self.is_valid_model_variant_index(model)
+            && self.is_valid_model_variant_index(neighbour)
+            && self.allowed_models(model, direction).contains(&neighbour)
+    }
+
     #[inline]
     fn is_valid_model_variant_index(&self, model_index: ModelVariantIndex) -> bool {
         model_index < self.models.len()
@@ -364,9 +830,36 @@ impl<C: CoordinateSystem> Rules<C> {
         &self,
         model_index: ModelIndex,
         rot: ModelRotation,
+    ) -> Option<ModelVariantIndex> {
+        self.oriented_variant_index(model_index, ModelFacing::Up, rot)
+    }
+
+    /// Returns `Some` [`ModelVariantIndex`] corresponding to the original model with index `model_index` rotated by `rot` and reoriented to `facing`, not flipped. Returns [`None`] if this variation does not exist.
+    pub fn oriented_variant_index(
+        &self,
+        model_index: ModelIndex,
+        facing: ModelFacing,
+        rot: ModelRotation,
+    ) -> Option<ModelVariantIndex> {
+        self.mapped_variant_index(model_index, orientation_index(facing, rot, false))
+    }
+
+    /// Returns `Some` [`ModelVariantIndex`] corresponding to a [`ModelInstance`]: its original model rotated, reoriented and flipped (see [`super::model::Model::with_flipped_variants`]) as the instance. Returns [`None`] if this variation does not exist.
+    pub fn instance_variant_index(&self, instance: &ModelInstance) -> Option<ModelVariantIndex> {
+        self.mapped_variant_index(
+            instance.model_index,
+            orientation_index(instance.facing(), instance.rotation, instance.flipped),
+        )
+    }
+
+    #[inline]
+    fn mapped_variant_index(
+        &self,
+        model_index: ModelIndex,
+        orientation_index: usize,
     ) -> Option<ModelVariantIndex> {
         if model_index < self.original_models_count {
-            self.models_mapping[(model_index, rot.index() as usize)]
+            self.models_mapping[(model_index, orientation_index)]
         } else {
             None
         }
@@ -400,6 +893,23 @@ impl<C: CoordinateSystem> Rules<C> {
             false => None,
         }
     }
+
+    /// Returns the sockets of a model variant, as `sockets[direction]`. Sockets on the rotation axis are already rotated with the model variant.
+    ///
+    /// Returns [`None`] if this model variant index is not valid.
+    #[cfg(feature = "models-sockets")]
+    pub fn sockets(&self, model_index: ModelVariantIndex) -> Option<&Vec<Vec<SocketId>>> {
+        match self.is_valid_model_variant_index(model_index) {
+            true => Some(&self.sockets[model_index]),
+            false => None,
+        }
+    }
+
+    /// Returns the name registered for a socket with [`SocketCollection::set_name`] if any, suffixed by the rotation of the socket for rotated sockets (for example `{name}@90`). See [`Rules::sockets`]
+    #[cfg(all(feature = "models-sockets", feature = "models-names"))]
+    pub fn socket_name(&self, socket: SocketId) -> Option<String> {
+        super::socket::socket_name(&self.socket_names, socket)
+    }
 }
 
 /// Represents a reference to a [`super::model::ModelVariation`] of some [`Rules`]
@@ -418,7 +928,7 @@ impl<C: CoordinateSystem> ModelVariantRef<C> for Model<C> {
     fn to_index(&self, rules: &Rules<C>) -> Result<ModelVariantIndex, NodeSetError> {
         let rot = self.first_rot();
         rules
-            .variant_index(self.index(), rot)
+            .oriented_variant_index(self.index(), self.first_facing(), rot)
             .ok_or(NodeSetError::InvalidModelRef(self.index(), rot))
     }
 }
@@ -426,7 +936,7 @@ impl<C: CoordinateSystem> ModelVariantRef<C> for &Model<C> {
     fn to_index(&self, rules: &Rules<C>) -> Result<ModelVariantIndex, NodeSetError> {
         let rot = self.first_rot();
         rules
-            .variant_index(self.index(), rot)
+            .oriented_variant_index(self.index(), self.first_facing(), rot)
             .ok_or(NodeSetError::InvalidModelRef(self.index(), rot))
     }
 }
@@ -454,7 +964,7 @@ impl<C: CoordinateSystem> ModelVariantRef<C> for (&Model<C>, ModelRotation) {
 impl<C: CoordinateSystem> ModelVariantRef<C> for ModelInstance {
     fn to_index(&self, rules: &Rules<C>) -> Result<ModelVariantIndex, NodeSetError> {
         rules
-            .variant_index(self.model_index, self.rotation)
+            .instance_variant_index(self)
             .ok_or(NodeSetError::InvalidModelRef(
                 self.model_index,
                 self.rotation,
@@ -464,7 +974,7 @@ impl<C: CoordinateSystem> ModelVariantRef<C> for ModelInstance {
 impl<C: CoordinateSystem> ModelVariantRef<C> for &ModelInstance {
     fn to_index(&self, rules: &Rules<C>) -> Result<ModelVariantIndex, NodeSetError> {
         rules
-            .variant_index(self.model_index, self.rotation)
+            .instance_variant_index(self)
             .ok_or(NodeSetError::InvalidModelRef(
                 self.model_index,
                 self.rotation,
@@ -0,0 +1,185 @@
+use ghx_grid::{
+    cartesian::{coordinates::CartesianCoordinates, grid::CartesianGrid},
+    direction::Direction,
+    grid::Grid,
+};
+
+use crate::NodeIndex;
+
+use super::{model::ModelVariantIndex, Generator};
+
+/// Computes the bans needed by a grid adjacent to the grid of `source`, so that both generations agree at their seam.
+///
+/// `face` is the face of the source grid touching `target_grid`: the border layer of `target_grid` on the opposite side lies next to the border layer of the source grid on the `face` side. Both grids must have the same size on the two other axis, and the target generator must use the same [`super::rules::Rules`] as `source`.
+///
+/// For each generated node on the border of `source`, the models not allowed next to it (in direction `face`) are banned from the facing node of `target_grid`. Returns `None` if the grids sizes do not match along the seam.
+///
+/// ### Example
+///
+/// ```
+/// use ghx_proc_gen::{
+///     generator::{builder::GeneratorBuilder, model::ModelCollection, rules::RulesBuilder, seam::seam_bans, socket::{SocketCollection, SocketsCartesian2D}},
+///     ghx_grid::{cartesian::grid::CartesianGrid, direction::Direction},
+/// };
+/// use std::sync::Arc;
+///
+/// let mut sockets = SocketCollection::new();
+/// let (land, water) = (sockets.create(), sockets.create());
+/// sockets.add_connection(land, vec![land]).add_connection(water, vec![water]);
+/// let mut models = ModelCollection::new();
+/// models.create(SocketsCartesian2D::Mono(land));
+/// models.create(SocketsCartesian2D::Mono(water));
+/// let rules = Arc::new(RulesBuilder::new_cartesian_2d(models, sockets).build().unwrap());
+///
+/// let mut west_generator = GeneratorBuilder::new()
+///     .with_shared_rules(rules.clone())
+///     .with_grid(CartesianGrid::new_cartesian_2d(8, 8, false, false))
+///     .build()
+///     .unwrap();
+/// west_generator.generate().unwrap();
+///
+/// let mut east_generator = GeneratorBuilder::new()
+///     .with_shared_rules(rules)
+///     .with_grid(CartesianGrid::new_cartesian_2d(8, 8, false, false))
+///     .build()
+///     .unwrap();
+/// if let Some(bans) = seam_bans(&west_generator, Direction::XForward, east_generator.grid()) {
+///     east_generator.set_initial_bans(bans).unwrap();
+///     east_generator.reinitialize();
+/// }
+/// ```
+pub fn seam_bans<C: CartesianCoordinates>(
+    source: &Generator<C, CartesianGrid<C>>,
+    face: Direction,
+    target_grid: &CartesianGrid<C>,
+) -> Option<Vec<(NodeIndex, ModelVariantIndex)>> {
+    let source_grid = source.grid();
+    let source_size = [
+        source_grid.size_x(),
+        source_grid.size_y(),
+        source_grid.size_z(),
+    ];
+    let target_size = [
+        target_grid.size_x(),
+        target_grid.size_y(),
+        target_grid.size_z(),
+    ];
+    let (axis, forward) = match face {
+        Direction::XForward => (0, true),
+        Direction::XBackward => (0, false),
+        Direction::YForward => (1, true),
+        Direction::YBackward => (1, false),
+        Direction::ZForward => (2, true),
+        Direction::ZBackward => (2, false),
+    };
+    if (0..3)
+        .any(|other_axis| other_axis != axis && source_size[other_axis] != target_size[other_axis])
+    {
+        return None;
+    }
+    let (source_border, target_border) = match forward {
+        true => (source_size[axis] - 1, 0),
+        false => (0, target_size[axis] - 1),
+    };
+
+    let rules = source.rules();
+    let mut bans = Vec::new();
+    for source_index in 0..source_grid.total_size() {
+        let source_pos = source_grid.pos_from_index(source_index);
+        let mut coords = [source_pos.x, source_pos.y, source_pos.z];
+        if coords[axis] != source_border {
+            continue;
+        }
+        let mut possible_models = source.internal.possible_model_indexes(source_index);
+        let (Some(source_model), None) = (possible_models.next(), possible_models.next()) else {
+            continue;
+        };
+        coords[axis] = target_border;
+        let target_index = target_grid.index_from_coords(coords[0], coords[1], coords[2]);
+        for target_model in 0..rules.models_count() {
+            if !rules.is_neighbour_allowed(source_model, face, target_model) {
+                bans.push((target_index, target_model));
+            }
+        }
+    }
+    Some(bans)
+}
+
+#[cfg(test)]
+mod tests {
+    use ghx_grid::{cartesian::grid::CartesianGrid, direction::Direction, grid::Grid};
+
+    use crate::generator::{
+        builder::GeneratorBuilder,
+        model::ModelCollection,
+        rules::RulesBuilder,
+        socket::{SocketCollection, SocketsCartesian2D},
+    };
+
+    use super::seam_bans;
+
+    #[test]
+    fn seam_bans_on_partial_seam() {
+        // Land and water never touch, sand goes anywhere
+        let mut sockets = SocketCollection::new();
+        let (land, water, sand) = (sockets.create(), sockets.create(), sockets.create());
+        sockets
+            .add_connection(land, vec![land])
+            .add_connection(water, vec![water])
+            .add_connection(sand, vec![land, water, sand]);
+        let mut models = ModelCollection::new();
+        let land_model = models.create(SocketsCartesian2D::Mono(land)).index();
+        let water_model = models.create(SocketsCartesian2D::Mono(water)).index();
+        models.create(SocketsCartesian2D::Mono(sand));
+        let rules = RulesBuilder::new_cartesian_2d(models, sockets)
+            .build()
+            .unwrap();
+
+        let source_grid = CartesianGrid::new_cartesian_2d(3, 2, false, false);
+        let mut source = GeneratorBuilder::new()
+            .with_rules(rules)
+            .with_grid(source_grid.clone())
+            .build()
+            .unwrap();
+        // Only one node of the seam is generated, the other one can still be land or sand
+        source
+            .set_and_propagate(source_grid.index_from_coords(2, 0, 0), land_model, false)
+            .unwrap();
+        assert!(!source.is_generated(source_grid.index_from_coords(2, 1, 0)));
+
+        let target_grid = CartesianGrid::new_cartesian_2d(2, 2, false, false);
+        let bans = seam_bans(&source, Direction::XForward, &target_grid).unwrap();
+        assert_eq!(
+            bans,
+            vec![(target_grid.index_from_coords(0, 0, 0), water_model)]
+        );
+
+        // Seen from the other side, the seam is on the target last x layer
+        let bans = seam_bans(&source, Direction::XBackward, &target_grid).unwrap();
+        assert!(bans.is_empty());
+    }
+
+    #[test]
+    fn seam_bans_on_mismatched_grids() {
+        let mut sockets = SocketCollection::new();
+        let a = sockets.create();
+        sockets.add_connection(a, vec![a]);
+        let mut models = ModelCollection::new();
+        models.create(SocketsCartesian2D::Mono(a));
+        let rules = RulesBuilder::new_cartesian_2d(models, sockets)
+            .build()
+            .unwrap();
+        let source = GeneratorBuilder::new()
+            .with_rules(rules)
+            .with_grid(CartesianGrid::new_cartesian_2d(3, 2, false, false))
+            .build()
+            .unwrap();
+
+        let target_grid = CartesianGrid::new_cartesian_2d(2, 3, false, false);
+        assert!(seam_bans(&source, Direction::XForward, &target_grid).is_none());
+        // Along y, the x sizes do not match either
+        assert!(seam_bans(&source, Direction::YForward, &target_grid).is_none());
+        let target_grid = CartesianGrid::new_cartesian_2d(3, 5, false, false);
+        assert!(seam_bans(&source, Direction::YForward, &target_grid).is_some());
+    }
+}
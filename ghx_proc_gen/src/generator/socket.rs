@@ -1,11 +1,21 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 
 use ghx_grid::cartesian::coordinates::{Cartesian2D, Cartesian3D};
 
+use crate::SocketsCsvError;
+
 use super::model::{ModelRotation, ModelTemplate, ALL_MODEL_ROTATIONS};
 
-/// Id of a possible connection type
-pub(crate) type SocketId = u64;
+const CSV_SEPARATOR: char = ',';
+const CSV_CONNECTED: &str = "x";
+/// Separates the name of a socket from its rotation in the CSV, for sockets with rotated connections
+const CSV_ROTATION_SEPARATOR: char = '@';
+
+/// Id of a possible connection type. Unique per [`Socket`], and per rotation for sockets on the rotation axis of the [`crate::generator::Rules`]
+pub type SocketId = u64;
 
 /// Used to create one or more [`Socket`]. Created sockets can then be used to define [`super::model::Model`] and
 /// define connections between them.
@@ -17,6 +27,12 @@ pub struct SocketCollection {
     uniques: HashMap<SocketId, HashSet<SocketId>>,
     /// For determinism and sequential access
     compatibles: HashMap<SocketId, Vec<SocketId>>,
+    /// Mirror image of the sockets that are not symmetric, see `add_mirror`
+    mirrors: HashMap<SocketId, Socket>,
+
+    /// Names given to the sockets for debug purposes, indexed by socket index
+    #[cfg(feature = "models-names")]
+    names: HashMap<u32, Cow<'static, str>>,
 }
 
 impl SocketCollection {
@@ -26,6 +42,9 @@ impl SocketCollection {
             incremental_socket_index: 0,
             uniques: HashMap::new(),
             compatibles: HashMap::new(),
+            mirrors: HashMap::new(),
+            #[cfg(feature = "models-names")]
+            names: HashMap::new(),
         }
     }
 
@@ -36,6 +55,28 @@ impl SocketCollection {
         socket
     }
 
+    /// Register the given name for `socket` (and all its rotations).
+    ///
+    /// Does nothing if the `models-names` feature is not enabled.
+    pub fn set_name(&mut self, _socket: Socket, _name: impl Into<Cow<'static, str>>) -> &mut Self {
+        #[cfg(feature = "models-names")]
+        {
+            self.names.insert(_socket.socket_index, _name.into());
+        }
+        self
+    }
+
+    /// Returns the name registered for a socket with [`SocketCollection::set_name`] if any, suffixed by the rotation of the socket for rotated sockets (for example `{name}@90`, as in [`SocketCollection::to_csv`])
+    #[cfg(feature = "models-names")]
+    pub fn name(&self, socket: SocketId) -> Option<String> {
+        socket_name(&self.names, socket)
+    }
+
+    #[cfg(feature = "models-names")]
+    pub(crate) fn names(&self) -> &HashMap<u32, Cow<'static, str>> {
+        &self.names
+    }
+
     /// Adds a connection between two sockets. [`super::model::Model`] with sockets `from` can connect to model with
     /// sockets `to` and vice versa.
     ///
@@ -90,6 +131,58 @@ impl SocketCollection {
         self
     }
 
+    /// Adds the connections given by a square compatibility `matrix` between `sockets`: `sockets[i]` is connected to `sockets[j]` if `matrix[i][j]` is `true`.
+    ///
+    /// Cells can be `bool` or any type convertible into a `bool`, such as a custom compatibility enum.
+    ///
+    /// - As with `add_connection`, there is **no** direction in the relation: a matrix only needs its upper or lower half filled, and `matrix[i][j]` and `matrix[j][i]` are merged.
+    /// - Panics if `matrix` is not a `sockets.len()` x `sockets.len()` matrix.
+    /// ### Example
+    /// ```
+    /// use ghx_proc_gen::generator::socket::SocketCollection;
+    ///
+    /// let mut sockets = SocketCollection::new();
+    /// let (grass, sand, water) = (sockets.create(), sockets.create(), sockets.create());
+    /// sockets.add_connections_from_matrix(
+    ///     &[grass, sand, water],
+    ///     &[
+    ///         [true, true, false],
+    ///         [true, true, true],
+    ///         [false, true, true],
+    ///     ],
+    /// );
+    /// // `sand` can be connected to all the sockets, `grass` and `water` can't be connected together
+    /// ```
+    pub fn add_connections_from_matrix<R, T>(
+        &mut self,
+        sockets: &[Socket],
+        matrix: &[R],
+    ) -> &mut Self
+    where
+        R: AsRef<[T]>,
+        T: Copy + Into<bool>,
+    {
+        assert_eq!(
+            matrix.len(),
+            sockets.len(),
+            "The connections matrix should have one row per socket"
+        );
+        for (from, row) in sockets.iter().zip(matrix.iter()) {
+            let row = row.as_ref();
+            assert_eq!(
+                row.len(),
+                sockets.len(),
+                "The connections matrix should have one column per socket"
+            );
+            for (to, connected) in sockets.iter().zip(row.iter()) {
+                if (*connected).into() {
+                    self.register_connection(from, to);
+                }
+            }
+        }
+        self
+    }
+
     /// Adds a connection between all possible rotations of two sockets that are on the rotation axis of the [`super::Rules`]. [`super::model::Model`] with sockets `from` can connect to model with sockets `to` and vice versa.
     ///
     /// - There is **no** direction in the relation, adding a connection from`a` to `b` also adds a connection from `b` to `a`
@@ -183,6 +276,221 @@ impl SocketCollection {
         self
     }
 
+    /// Removes the connection between two sockets, if any. Allows to subtract some connections from a baseline [`SocketCollection`].
+    ///
+    /// - As with `add_connection`, there is **no** direction in the relation, removing the connection from `a` to `b` also removes the connection from `b` to `a`
+    /// - Connections between rotated sockets (see `add_rotated_connection`) should be removed with `remove_rotated_connection`
+    /// ### Example
+    /// ```
+    /// use ghx_proc_gen::generator::socket::SocketCollection;
+    ///
+    /// let mut sockets = SocketCollection::new();
+    /// let (a, b) = (sockets.create(), sockets.create());
+    /// sockets.add_connection(a, vec![a, b]);
+    /// sockets.remove_connection(a, vec![b]);
+    /// assert!(sockets.is_connected(a, a));
+    /// assert!(!sockets.is_connected(b, a));
+    /// ```
+    pub fn remove_connection<I>(&mut self, from: Socket, to: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Socket>,
+    {
+        for to_socket in to.into_iter() {
+            self.unregister_connection(&from, &to_socket);
+        }
+        self
+    }
+
+    /// Removes the connections between all possible rotations of two sockets that are on the rotation axis of the [`super::Rules`], as added by `add_rotated_connection` or `add_constrained_rotated_connection`.
+    pub fn remove_rotated_connection<I>(&mut self, from: Socket, to: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Socket>,
+    {
+        for to_socket in to.into_iter() {
+            for to_rotation in ALL_MODEL_ROTATIONS {
+                for from_rotation in ALL_MODEL_ROTATIONS {
+                    self.unregister_connection(
+                        &from.rotated(*from_rotation),
+                        &to_socket.rotated(*to_rotation),
+                    );
+                }
+            }
+        }
+        self
+    }
+
+    /// Removes all the connections of a socket (and of all its rotations). Models using this socket won't be able to connect to anything on this side, until new connections are added.
+    ///
+    /// The socket itself stays valid, and its index is never reused by `create`.
+    pub fn remove_socket(&mut self, socket: Socket) -> &mut Self {
+        for rotation in ALL_MODEL_ROTATIONS {
+            let socket_id = socket.rotated(*rotation).id();
+            let Some(connectable_sockets) = self.uniques.remove(&socket_id) else {
+                continue;
+            };
+            self.compatibles.remove(&socket_id);
+            for to_id in connectable_sockets {
+                self.unregister_connection_half(to_id, socket_id);
+            }
+        }
+        self
+    }
+
+    /// Declares `mirrored` as the mirror image of `socket`, and `socket` as the mirror image of `mirrored`.
+    ///
+    /// Used to mirror the lateral sockets of the models flipped along the rotation axis (see [`super::model::Model::with_flipped_variants`]). Sockets without a declared mirror image are considered symmetric and are kept as is. The connections are not modified: the connections of `mirrored` should be declared like the ones of any other socket.
+    pub fn add_mirror(&mut self, socket: Socket, mirrored: Socket) -> &mut Self {
+        self.mirrors.insert(socket.id(), mirrored);
+        self.mirrors.insert(mirrored.id(), socket);
+        self
+    }
+
+    /// Returns the mirror image of `socket` declared with `add_mirror`, or `socket` itself if it has none
+    pub(crate) fn mirrored(&self, socket: Socket) -> Socket {
+        self.mirrors.get(&socket.id()).copied().unwrap_or(socket)
+    }
+
+    /// Returns `true` if `from` and `to` are connected
+    pub fn is_connected(&self, from: Socket, to: Socket) -> bool {
+        self.uniques
+            .get(&from.id())
+            .is_some_and(|connectable_sockets| connectable_sockets.contains(&to.id()))
+    }
+
+    /// Exports the compatibility table of the given named `sockets` as CSV, to be edited in a spreadsheet and read back with [`SocketCollection::set_connections_from_csv`].
+    ///
+    /// The first row and the first column hold the sockets names, and connected sockets have an `x` in their cell. A socket with rotated connections (see [`SocketCollection::add_rotated_connection`]) also has a row and a column for each of its other rotations, named `{name}@90`, `{name}@180` and `{name}@270`.
+    ///
+    /// Names should not contain commas, quotes, `@` or line breaks.
+    pub fn to_csv(&self, sockets: &[(&str, Socket)]) -> String {
+        let sockets = self.csv_sockets(sockets);
+        let mut csv = String::new();
+        for (name, _) in sockets.iter() {
+            csv.push(CSV_SEPARATOR);
+            csv.push_str(name);
+        }
+        csv.push('\n');
+        for (from_name, from) in sockets.iter() {
+            csv.push_str(from_name);
+            for (_, to) in sockets.iter() {
+                csv.push(CSV_SEPARATOR);
+                if self.is_connected(*from, *to) {
+                    csv.push_str(CSV_CONNECTED);
+                }
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Sets the connections of a compatibility table in CSV, as exported by [`SocketCollection::to_csv`]. Sockets are matched by name with the given named `sockets`, and rotations of sockets by their `@90`, `@180` or `@270` suffix.
+    ///
+    /// A cell containing `x`, `1` or `true` (case insensitive) connects the sockets of its row and column, an empty cell or a cell containing `0` or `false` disconnects them. Since connections have no direction, two sockets stay connected if any of their two cells connects them. Rows and columns can be in any order and do not need to list all the `sockets`.
+    ///
+    /// The table replaces the connections between the sockets of its rows and columns. Connections with sockets outside of the table are kept.
+    /// ### Example
+    /// ```
+    /// use ghx_proc_gen::generator::socket::SocketCollection;
+    ///
+    /// let mut sockets = SocketCollection::new();
+    /// let (grass, water) = (sockets.create(), sockets.create());
+    /// let named_sockets = [("grass", grass), ("water", water)];
+    /// sockets.add_connection(grass, vec![water]);
+    /// sockets
+    ///     .set_connections_from_csv(&named_sockets, ",grass,water\ngrass,x,\nwater,,x\n")
+    ///     .unwrap();
+    /// assert!(sockets.is_connected(grass, grass));
+    /// assert!(!sockets.is_connected(grass, water));
+    /// ```
+    pub fn set_connections_from_csv(
+        &mut self,
+        sockets: &[(&str, Socket)],
+        csv: &str,
+    ) -> Result<&mut Self, SocketsCsvError> {
+        let find_socket = |name: &str| -> Result<Socket, SocketsCsvError> {
+            let name = name.trim().trim_matches('"');
+            let (socket_name, rotation) = match name.rsplit_once(CSV_ROTATION_SEPARATOR) {
+                Some((socket_name, degrees)) => (
+                    socket_name,
+                    ALL_MODEL_ROTATIONS
+                        .iter()
+                        .find(|rotation| rotation.value().to_string() == degrees)
+                        .copied()
+                        .ok_or_else(|| SocketsCsvError::UnknownSocket(name.to_string()))?,
+                ),
+                None => (name, ModelRotation::Rot0),
+            };
+            sockets
+                .iter()
+                .find(|(named_socket, _)| *named_socket == socket_name)
+                .map(|(_, socket)| socket.rotated(rotation))
+                .ok_or_else(|| SocketsCsvError::UnknownSocket(name.to_string()))
+        };
+
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let Some((_, header)) = lines.next() else {
+            return Err(SocketsCsvError::MissingHeader);
+        };
+        let columns = header
+            .split(CSV_SEPARATOR)
+            .skip(1)
+            .map(find_socket)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut cells_sockets = Vec::new();
+        let mut connections = Vec::new();
+        for (line_index, line) in lines {
+            let mut cells = line.split(CSV_SEPARATOR);
+            // `split` always yields at least one item
+            let from = find_socket(cells.next().unwrap())?;
+            for (column_index, cell) in cells.enumerate() {
+                let Some(to) = columns.get(column_index) else {
+                    return Err(SocketsCsvError::InvalidRowLength(line_index + 1));
+                };
+                cells_sockets.push((from, *to));
+                match cell.trim().trim_matches('"').to_lowercase().as_str() {
+                    "x" | "1" | "true" => connections.push((from, *to)),
+                    "" | "0" | "false" => (),
+                    _ => {
+                        return Err(SocketsCsvError::InvalidCell(
+                            line_index + 1,
+                            column_index + 2,
+                            cell.to_string(),
+                        ))
+                    }
+                }
+            }
+        }
+        // Only modify the connections once the whole table is valid
+        for (from, to) in cells_sockets.iter() {
+            self.unregister_connection(from, to);
+        }
+        for (from, to) in connections.iter() {
+            self.register_connection(from, to);
+        }
+        Ok(self)
+    }
+
+    /// Returns the named `sockets` as rows and columns of a CSV table: each socket, followed by its other rotations if it has rotated connections
+    fn csv_sockets(&self, sockets: &[(&str, Socket)]) -> Vec<(String, Socket)> {
+        let mut csv_sockets = Vec::with_capacity(sockets.len());
+        for (name, socket) in sockets.iter() {
+            csv_sockets.push((name.to_string(), *socket));
+            if self.has_rotated_connections(*socket) {
+                for rotation in ALL_MODEL_ROTATIONS.iter().skip(1) {
+                    csv_sockets.push((
+                        format!("{}{}{}", name, CSV_ROTATION_SEPARATOR, rotation.value()),
+                        socket.rotated(*rotation),
+                    ));
+                }
+            }
+        }
+        csv_sockets
+    }
+
     fn register_connection_half(&mut self, from: &Socket, to: &Socket) {
         // TODO Decide if we check for existence
         let connectable_sockets = self.uniques.entry(from.id()).or_insert(HashSet::new());
@@ -200,6 +508,34 @@ impl SocketCollection {
         self.register_connection_half(to, from);
     }
 
+    fn unregister_connection_half(&mut self, from: SocketId, to: SocketId) {
+        let Some(connectable_sockets) = self.uniques.get_mut(&from) else {
+            return;
+        };
+        if connectable_sockets.remove(&to) {
+            if let Some(compatibles) = self.compatibles.get_mut(&from) {
+                compatibles.retain(|socket_id| *socket_id != to);
+            }
+        }
+    }
+
+    fn unregister_connection(&mut self, from: &Socket, to: &Socket) {
+        self.unregister_connection_half(from.id(), to.id());
+        self.unregister_connection_half(to.id(), from.id());
+    }
+
+    /// Returns `true` if some connections of `socket` depend on its rotation, as added by `add_rotated_connection` or `add_constrained_rotated_connection`
+    pub(crate) fn has_rotated_connections(&self, socket: Socket) -> bool {
+        ALL_MODEL_ROTATIONS
+            .iter()
+            .filter(|rotation| **rotation != ModelRotation::Rot0)
+            .any(|rotation| {
+                self.uniques
+                    .get(&socket.rotated(*rotation).id())
+                    .is_some_and(|connections| !connections.is_empty())
+            })
+    }
+
     pub(crate) fn get_compatibles(&self, socket: SocketId) -> Option<&Vec<SocketId>> {
         self.compatibles.get(&socket)
     }
@@ -209,6 +545,20 @@ impl SocketCollection {
     }
 }
 
+/// Returns the name of `socket` in `names`, suffixed by its rotation for rotated sockets
+#[cfg(feature = "models-names")]
+pub(crate) fn socket_name(
+    names: &HashMap<u32, Cow<'static, str>>,
+    socket: SocketId,
+) -> Option<String> {
+    let name = names.get(&(socket as u32))?;
+    let rotation = ALL_MODEL_ROTATIONS[(socket >> 32) as usize % ALL_MODEL_ROTATIONS.len()];
+    Some(match rotation {
+        ModelRotation::Rot0 => name.to_string(),
+        _ => format!("{}{}{}", name, CSV_ROTATION_SEPARATOR, rotation.value()),
+    })
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 /// Defines a contact point of a [`super::model::Model`]. Each model may have none or multiple socket(s) on
 /// each of his sides.
@@ -0,0 +1,480 @@
+use std::{
+    collections::HashMap,
+    fs,
+    hash::Hash,
+    path::{Path, PathBuf},
+};
+
+use ghx_grid::{
+    cartesian::coordinates::Cartesian3D,
+    direction::{Direction, DirectionTrait},
+};
+
+use crate::{TilesError, VoxError};
+
+use super::{
+    model::{ModelCollection, ALL_MODEL_ROTATIONS},
+    socket::{Socket, SocketCollection, SocketsCartesian2D, SocketsCartesian3D},
+};
+
+/// Pixels of a 2d tile, used to infer its sockets. See [`infer_sockets_from_tiles`]
+///
+/// `pixels` are stored row by row, the first row being the top of the tile (towards Y+) and the first pixel of a row being its left (towards X-). A pixel can be any hashable color type, such as `[u8; 4]`.
+pub struct TilePixels<'a, P> {
+    /// Width of the tile, in pixels
+    pub width: usize,
+    /// Height of the tile, in pixels
+    pub height: usize,
+    /// Pixels of the tile, of length `width * height`
+    pub pixels: &'a [P],
+}
+
+impl<'a, P: Clone> TilePixels<'a, P> {
+    /// Creates a [`TilePixels`] from row-major `pixels`. Panics if `pixels` is not of length `width * height`
+    pub fn new(width: usize, height: usize, pixels: &'a [P]) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "Tile pixels should be of length width * height"
+        );
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> P {
+        self.pixels[y * self.width + x].clone()
+    }
+
+    fn column(&self, x: usize) -> Vec<P> {
+        (0..self.height).map(|y| self.pixel(x, y)).collect()
+    }
+
+    fn row(&self, y: usize) -> Vec<P> {
+        (0..self.width).map(|x| self.pixel(x, y)).collect()
+    }
+}
+
+/// Infers the sockets of 2d tiles from their pixels: identical edges are given the same socket, and each socket is connected to itself, so that two tiles can be placed next to each other if and only if their facing edges are identical.
+///
+/// Creates the sockets and their connections in `sockets`, and returns the sockets of each tile, in the order of `tiles`, to be used to create models in a [`super::model::ModelCollection`].
+///
+/// Returns [`TilesError::InvalidTileSize`] without creating any socket if a tile has a width or a height of 0, or if its pixels are not of length `width * height`.
+///
+/// The pixels of an edge are compared as is: tiles are expected to be authored so that adjacent tiles share their edge pixels. Since rotating a model does not rotate its tile pixels, models created from inferred sockets should not be rotated (create rotated tiles instead).
+///
+/// ### Example
+/// ```
+/// use ghx_proc_gen::generator::{
+///     model::ModelCollection,
+///     socket::SocketCollection,
+///     tiles::{infer_sockets_from_tiles, TilePixels},
+/// };
+///
+/// let (grass, water) = ([0u8, 255, 0], [0u8, 0, 255]);
+/// let grass_tile = vec![grass; 4];
+/// let shore_tile = vec![grass, water, grass, water];
+///
+/// let mut sockets = SocketCollection::new();
+/// let tiles_sockets = infer_sockets_from_tiles(
+///     &[
+///         TilePixels::new(2, 2, &grass_tile),
+///         TilePixels::new(2, 2, &shore_tile),
+///     ],
+///     &mut sockets,
+/// )
+/// .unwrap();
+/// let mut models = ModelCollection::new();
+/// for tile_sockets in tiles_sockets {
+///     models.create(tile_sockets);
+/// }
+/// ```
+pub fn infer_sockets_from_tiles<P: Hash + Eq + Clone>(
+    tiles: &[TilePixels<P>],
+    sockets: &mut SocketCollection,
+) -> Result<Vec<SocketsCartesian2D>, TilesError> {
+    if let Some(invalid_tile) = tiles.iter().position(|tile| {
+        tile.width == 0 || tile.height == 0 || tile.pixels.len() != tile.width * tile.height
+    }) {
+        return Err(TilesError::InvalidTileSize(invalid_tile));
+    }
+
+    // Vertical edges (X axis) and horizontal edges (Y axis) never face each other, they use distinct sockets
+    let mut vertical_edges: HashMap<Vec<P>, Socket> = HashMap::new();
+    let mut horizontal_edges: HashMap<Vec<P>, Socket> = HashMap::new();
+    let mut edge_socket = |edges: &mut HashMap<Vec<P>, Socket>, edge: Vec<P>| -> Socket {
+        *edges.entry(edge).or_insert_with(|| {
+            let socket = sockets.create();
+            sockets.add_connection(socket, vec![socket]);
+            socket
+        })
+    };
+
+    let mut tiles_sockets = Vec::with_capacity(tiles.len());
+    for tile in tiles {
+        tiles_sockets.push(SocketsCartesian2D::Simple {
+            x_pos: edge_socket(&mut vertical_edges, tile.column(tile.width - 1)),
+            x_neg: edge_socket(&mut vertical_edges, tile.column(0)),
+            y_pos: edge_socket(&mut horizontal_edges, tile.row(0)),
+            y_neg: edge_socket(&mut horizontal_edges, tile.row(tile.height - 1)),
+        });
+    }
+    Ok(tiles_sockets)
+}
+
+/// Voxels of a cubic 3d tile, used to infer its sockets. See [`infer_sockets_from_voxel_tiles`]
+///
+/// The voxel at `(x, y, z)` is stored at `voxels[x + y * size + z * size * size]`, with Y being the up axis. A voxel can be any hashable type, such as an `Option<u8>` palette index (with `None` for empty voxels).
+///
+/// See [`load_vox_tiles`] to load voxel tiles from MagicaVoxel `.vox` files.
+pub struct VoxelTile<'a, V> {
+    /// Size of the tile, in voxels, in all 3 dimensions
+    pub size: usize,
+    /// Voxels of the tile, of length `size * size * size`
+    pub voxels: &'a [V],
+}
+
+impl<'a, V: Clone> VoxelTile<'a, V> {
+    /// Creates a [`VoxelTile`] from `voxels`. Panics if `voxels` is not of length `size * size * size`
+    pub fn new(size: usize, voxels: &'a [V]) -> Self {
+        assert_eq!(
+            voxels.len(),
+            size * size * size,
+            "Voxel tile voxels should be of length size * size * size"
+        );
+        Self { size, voxels }
+    }
+
+    fn voxel(&self, pos: [usize; 3]) -> V {
+        self.voxels[pos[0] + pos[1] * self.size + pos[2] * self.size * self.size].clone()
+    }
+
+    /// Content of a side face, as viewed from outside the tile: row by row from the bottom, each row from left to right
+    fn side_face(&self, normal: Direction) -> Vec<V> {
+        let normal_vec = direction_vec(normal);
+        // right = (-normal) x up
+        let right_vec = [normal_vec[2], 0, -normal_vec[0]];
+        let normal_axis = if normal_vec[0] != 0 { 0 } else { 2 };
+        let right_axis = 2 - normal_axis;
+        let last = self.size - 1;
+
+        let mut face = Vec::with_capacity(self.size * self.size);
+        for y in 0..self.size {
+            for h in 0..self.size {
+                let mut pos = [0, y, 0];
+                pos[normal_axis] = if normal_vec[normal_axis] > 0 { last } else { 0 };
+                pos[right_axis] = if right_vec[right_axis] > 0 {
+                    h
+                } else {
+                    last - h
+                };
+                face.push(self.voxel(pos));
+            }
+        }
+        face
+    }
+
+    /// Horizontal layer of voxels at height `y`, indexed by `x + z * size`
+    fn layer(&self, y: usize) -> Vec<V> {
+        let mut layer = Vec::with_capacity(self.size * self.size);
+        for z in 0..self.size {
+            for x in 0..self.size {
+                layer.push(self.voxel([x, y, z]));
+            }
+        }
+        layer
+    }
+}
+
+fn direction_vec(direction: Direction) -> [i32; 3] {
+    match direction {
+        Direction::XForward => [1, 0, 0],
+        Direction::XBackward => [-1, 0, 0],
+        Direction::YForward => [0, 1, 0],
+        Direction::YBackward => [0, -1, 0],
+        Direction::ZForward => [0, 0, 1],
+        Direction::ZBackward => [0, 0, -1],
+    }
+}
+
+/// Mirrors a side face horizontally
+fn mirrored_face<V: Clone>(face: &[V], size: usize) -> Vec<V> {
+    face.chunks(size)
+        .flat_map(|row| row.iter().rev().cloned())
+        .collect()
+}
+
+/// Rotates a horizontal layer by 90° around the Y axis, in the same way as the models are rotated by the rules
+fn rotated_layer<V: Clone>(layer: &[V], size: usize) -> Vec<V> {
+    let basis = Direction::YForward.rotation_basis();
+    let (e0, e1) = (direction_vec(basis[0]), direction_vec(basis[1]));
+    let last = size as i32 - 1;
+    let mut rotated = layer.to_vec();
+    for z in 0..size {
+        for x in 0..size {
+            // Doubled coordinates, centered on the middle of the layer
+            let (cx, cz) = (2 * x as i32 - last, 2 * z as i32 - last);
+            let (a, b) = (cx * e0[0] + cz * e0[2], cx * e1[0] + cz * e1[2]);
+            // A rotation moves basis[0] onto basis[1], and basis[1] onto basis[2] = -basis[0]
+            let (rx, rz) = (a * e1[0] - b * e0[0], a * e1[2] - b * e0[2]);
+            let (rx, rz) = (((rx + last) / 2) as usize, ((rz + last) / 2) as usize);
+            rotated[rx + rz * size] = layer[x + z * size].clone();
+        }
+    }
+    rotated
+}
+
+/// Infers the sockets of cubic 3d voxel tiles from their boundary voxels: two tiles can be placed next to each other if and only if their touching faces are identical, for all the rotations of the tiles around the Y axis.
+///
+/// - Side faces get one socket per distinct face, connected to the socket of its mirrored face (which is what a face looks like from the other side).
+/// - Top and bottom layers get one socket per distinct layer up to a rotation, with a rotation (see [`SocketCollection::add_rotated_connection`]): they only connect when their layers match once the models are rotated.
+///
+/// Creates the sockets and their connections in `sockets`, and returns the sockets of each tile, in the order of `tiles`, to be used to create models in a [`ModelCollection`]. The rotation axis of the rules should be Y+ (the default of [`super::rules::RulesBuilder::new_cartesian_3d`]).
+///
+/// Returns [`TilesError::InvalidTileSize`] if a tile has a size of 0 or if its voxels are not of length `size * size * size`, and [`TilesError::MismatchedTileSize`] if the tiles do not all have the same size. No socket is created in these cases.
+pub fn infer_sockets_from_voxel_tiles<V: Hash + Eq + Clone>(
+    tiles: &[VoxelTile<V>],
+    sockets: &mut SocketCollection,
+) -> Result<Vec<SocketsCartesian3D>, TilesError> {
+    for (index, tile) in tiles.iter().enumerate() {
+        if tile.size == 0 || tile.voxels.len() != tile.size * tile.size * tile.size {
+            return Err(TilesError::InvalidTileSize(index));
+        }
+        if tile.size != tiles[0].size {
+            return Err(TilesError::MismatchedTileSize(index));
+        }
+    }
+
+    let mut side_sockets: HashMap<Vec<V>, Socket> = HashMap::new();
+    // Every rotation of a known layer is registered, with the rotation to apply to the socket of its un-rotated layer
+    let mut layer_sockets: HashMap<Vec<V>, Socket> = HashMap::new();
+
+    let mut tiles_sockets = Vec::with_capacity(tiles.len());
+    for tile in tiles {
+        let size = tile.size;
+        let mut side_socket = |normal: Direction| -> Socket {
+            let face = tile.side_face(normal);
+            if let Some(socket) = side_sockets.get(&face) {
+                return *socket;
+            }
+            let socket = sockets.create();
+            let mirrored = mirrored_face(&face, size);
+            if mirrored == face {
+                sockets.add_connection(socket, vec![socket]);
+            } else if let Some(mirrored_socket) = side_sockets.get(&mirrored) {
+                sockets.add_connection(socket, vec![*mirrored_socket]);
+            }
+            side_sockets.insert(face, socket);
+            socket
+        };
+        let (x_pos, x_neg) = (
+            side_socket(Direction::XForward),
+            side_socket(Direction::XBackward),
+        );
+        let (z_pos, z_neg) = (
+            side_socket(Direction::ZForward),
+            side_socket(Direction::ZBackward),
+        );
+
+        let mut layer_socket = |layer: Vec<V>| -> Socket {
+            if let Some(socket) = layer_sockets.get(&layer) {
+                return *socket;
+            }
+            let socket = sockets.create();
+            let mut rotations = vec![layer];
+            for _ in 1..ALL_MODEL_ROTATIONS.len() {
+                rotations.push(rotated_layer(rotations.last().unwrap(), size));
+            }
+            for (a, layer_a) in rotations.iter().enumerate() {
+                for (b, layer_b) in rotations.iter().enumerate() {
+                    if layer_a == layer_b {
+                        sockets.add_connection(
+                            socket.rotated(ALL_MODEL_ROTATIONS[a]),
+                            vec![socket.rotated(ALL_MODEL_ROTATIONS[b])],
+                        );
+                    }
+                }
+            }
+            // Iterate in reverse so that the smallest rotation is kept for symmetric layers
+            for (rotation, layer) in rotations.into_iter().enumerate().rev() {
+                layer_sockets.insert(layer, socket.rotated(ALL_MODEL_ROTATIONS[rotation]));
+            }
+            socket
+        };
+        let y_pos = layer_socket(tile.layer(size - 1));
+        let y_neg = layer_socket(tile.layer(0));
+
+        tiles_sockets.push(SocketsCartesian3D::Simple {
+            x_pos,
+            x_neg,
+            z_pos,
+            z_neg,
+            y_pos,
+            y_neg,
+        });
+    }
+    Ok(tiles_sockets)
+}
+
+/// Creates a [`ModelCollection`] and a [`SocketCollection`] from voxel tiles, with sockets inferred by [`infer_sockets_from_voxel_tiles`]. Model `i` is created from `tiles[i]`, with all its rotations allowed.
+pub fn infer_models_from_voxel_tiles<V: Hash + Eq + Clone>(
+    tiles: &[VoxelTile<V>],
+) -> Result<(ModelCollection<Cartesian3D>, SocketCollection), TilesError> {
+    let mut sockets = SocketCollection::new();
+    let mut models = ModelCollection::new();
+    for tile_sockets in infer_sockets_from_voxel_tiles(tiles, &mut sockets)? {
+        models.create(tile_sockets).with_all_rotations();
+    }
+    Ok((models, sockets))
+}
+
+/// Voxels of a cubic tile loaded from a MagicaVoxel `.vox` file. See [`parse_vox_tile`] and [`load_vox_tiles`]
+///
+/// Each voxel is the palette index of the `.vox` model, or `None` if empty. Palettes are not loaded: tiles are expected to share the same palette. The Z-up axes of MagicaVoxel are converted to the Y-up axes of [`VoxelTile`].
+pub struct VoxTileData {
+    /// Size of the tile, in voxels, in all 3 dimensions
+    pub size: usize,
+    /// Voxels of the tile, of length `size * size * size`, indexed as in [`VoxelTile`]
+    pub voxels: Vec<Option<u8>>,
+}
+
+impl VoxTileData {
+    /// Returns a [`VoxelTile`] borrowing these voxels, to infer sockets from
+    pub fn as_tile(&self) -> VoxelTile<Option<u8>> {
+        VoxelTile::new(self.size, &self.voxels)
+    }
+}
+
+/// Size in bytes of a chunk header of a `.vox` file: its id, the size of its content and the size of its children
+const VOX_CHUNK_HEADER_SIZE: usize = 12;
+
+fn read_vox_i32(bytes: &[u8], offset: usize) -> Result<i32, VoxError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|int| i32::from_le_bytes([int[0], int[1], int[2], int[3]]))
+        .ok_or(VoxError::InvalidFormat("unexpected end of data"))
+}
+
+fn read_vox_size(bytes: &[u8], offset: usize) -> Result<usize, VoxError> {
+    usize::try_from(read_vox_i32(bytes, offset)?)
+        .map_err(|_| VoxError::InvalidFormat("negative chunk size"))
+}
+
+/// Parses the content of a MagicaVoxel `.vox` file containing a single cubic model.
+///
+/// Only the `SIZE` and `XYZI` chunks of the model are read: materials, palettes and scene graph chunks are ignored. Files with multiple models are not supported.
+pub fn parse_vox_tile(bytes: &[u8]) -> Result<VoxTileData, VoxError> {
+    if bytes.get(0..4) != Some(b"VOX ".as_slice()) {
+        return Err(VoxError::InvalidFormat("missing VOX header"));
+    }
+    // The file header (id and version) is followed by the MAIN chunk, whose children are the chunks of the models
+    if bytes.get(8..12) != Some(b"MAIN".as_slice()) {
+        return Err(VoxError::InvalidFormat("missing MAIN chunk"));
+    }
+    let mut offset = 8 + VOX_CHUNK_HEADER_SIZE + read_vox_size(bytes, 12)?;
+
+    let mut model_size = None;
+    let mut model_voxels: Option<&[u8]> = None;
+    while offset + VOX_CHUNK_HEADER_SIZE <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let content_size = read_vox_size(bytes, offset + 4)?;
+        let children_size = read_vox_size(bytes, offset + 8)?;
+        let content_start = offset + VOX_CHUNK_HEADER_SIZE;
+        let content = bytes
+            .get(content_start..content_start + content_size)
+            .ok_or(VoxError::InvalidFormat("unexpected end of data"))?;
+        match chunk_id {
+            b"SIZE" | b"XYZI" if model_voxels.is_some() => {
+                return Err(VoxError::InvalidFormat(
+                    "files with multiple models are not supported",
+                ))
+            }
+            b"SIZE" => {
+                model_size = Some((
+                    read_vox_i32(content, 0)?,
+                    read_vox_i32(content, 4)?,
+                    read_vox_i32(content, 8)?,
+                ));
+            }
+            b"XYZI" => {
+                let voxels_count = read_vox_size(content, 0)?;
+                model_voxels = Some(
+                    content
+                        .get(4..4 + voxels_count * 4)
+                        .ok_or(VoxError::InvalidFormat("unexpected end of data"))?,
+                );
+            }
+            _ => (),
+        }
+        offset = content_start + content_size + children_size;
+    }
+
+    let (size_x, size_y, size_z) =
+        model_size.ok_or(VoxError::InvalidFormat("missing SIZE chunk"))?;
+    if size_x <= 0 || size_x != size_y || size_x != size_z {
+        return Err(VoxError::NotCubic(size_x, size_y, size_z));
+    }
+    let size = size_x as usize;
+    let mut voxels = vec![None; size * size * size];
+    for voxel in model_voxels.unwrap_or_default().chunks_exact(4) {
+        let (x, y, z) = (voxel[0] as usize, voxel[1] as usize, voxel[2] as usize);
+        if x >= size || y >= size || z >= size {
+            return Err(VoxError::InvalidFormat("voxel out of the model bounds"));
+        }
+        // MagicaVoxel is Z-up: its Z axis is the Y axis of the tile, and its Y axis the Z- axis of the tile
+        voxels[x + z * size + (size - 1 - y) * size * size] = Some(voxel[3]);
+    }
+    Ok(VoxTileData { size, voxels })
+}
+
+/// Loads all the `.vox` files of the folder at `path` (not recursively) with [`parse_vox_tile`]. Returns the file stem and the voxels of each tile, sorted by file name so that the tiles order does not depend on the file system.
+pub fn load_vox_tiles<P: AsRef<Path>>(path: P) -> Result<Vec<(String, VoxTileData)>, VoxError> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let file = entry?.path();
+        if file
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("vox"))
+        {
+            files.push(file);
+        }
+    }
+    files.sort();
+
+    let load_file =
+        |file: &PathBuf| -> Result<VoxTileData, VoxError> { parse_vox_tile(&fs::read(file)?) };
+    let mut tiles = Vec::with_capacity(files.len());
+    for file in files {
+        let tile = load_file(&file).map_err(|err| VoxError::File(file.clone(), Box::new(err)))?;
+        let name = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        tiles.push((name, tile));
+    }
+    Ok(tiles)
+}
+
+/// Creates a [`ModelCollection`] and a [`SocketCollection`] from the `.vox` files of the folder at `path`, loaded with [`load_vox_tiles`]. Sockets are inferred as in [`infer_models_from_voxel_tiles`].
+///
+/// Models are created in the order of the file names, with all their rotations allowed, and are named after their file stem if the `models-names` feature is enabled.
+pub fn infer_models_from_vox_folder<P: AsRef<Path>>(
+    path: P,
+) -> Result<(ModelCollection<Cartesian3D>, SocketCollection), VoxError> {
+    let tiles = load_vox_tiles(path)?;
+    let voxel_tiles: Vec<VoxelTile<Option<u8>>> =
+        tiles.iter().map(|(_, tile)| tile.as_tile()).collect();
+
+    let mut sockets = SocketCollection::new();
+    let mut models = ModelCollection::new();
+    let tiles_sockets = infer_sockets_from_voxel_tiles(&voxel_tiles, &mut sockets)?;
+    for ((name, _), tile_sockets) in tiles.iter().zip(tiles_sockets) {
+        models
+            .create(tile_sockets)
+            .with_all_rotations()
+            .with_name(name.clone());
+    }
+    Ok((models, sockets))
+}
@@ -0,0 +1,287 @@
+use ghx_grid::{
+    cartesian::{
+        coordinates::{CartesianCoordinates, CartesianPosition},
+        grid::CartesianGrid,
+    },
+    grid::Grid,
+};
+
+use crate::NodeIndex;
+
+/// How the axis of a grid are mapped to the axis of the world
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AxisMapping {
+    /// Grid axis `x`, `y` and `z` are world axis `x`, `y` and `z`. This is how the Bevy plugins spawn the nodes
+    #[default]
+    Identity,
+    /// Grid axis `y` and `z` are swapped: a grid generated with `y` up is placed in a `z` up world (and vice-versa)
+    SwapYZ,
+}
+
+impl AxisMapping {
+    /// Maps a grid vector to a world vector. This mapping is its own inverse
+    pub fn map(&self, v: [f32; 3]) -> [f32; 3] {
+        match self {
+            AxisMapping::Identity => v,
+            AxisMapping::SwapYZ => [v[0], v[2], v[1]],
+        }
+    }
+}
+
+/// Strategy used to offset the z coordinate of the nodes, so that 2d sprites are drawn in the right order. Offsets are expressed in node units, in `[0, 1[`.
+///
+/// To bias the ordering of specific models, use a world offset on their assets.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub enum ZOrdering {
+    /// No offset
+    #[default]
+    None,
+    /// Nodes with a lower y are drawn on top. Fits top-down views
+    FromY,
+    /// Nodes with a lower x + y are drawn on top. Fits isometric views
+    FromXY,
+    /// Custom offset, computed from the position of a node and the size of its grid (along x, y and z)
+    Custom(fn(&CartesianPosition, [u32; 3]) -> f32),
+}
+
+impl ZOrdering {
+    /// Returns the z offset, in node units, of a node at `pos` in a grid of size `grid_size` (along x, y and z)
+    pub fn z_offset(&self, pos: &CartesianPosition, grid_size: [u32; 3]) -> f32 {
+        match self {
+            ZOrdering::None => 0.,
+            ZOrdering::FromY => 1. - pos.y as f32 / grid_size[1] as f32,
+            ZOrdering::FromXY => 1. - (pos.x + pos.y) as f32 / (grid_size[0] + grid_size[1]) as f32,
+            ZOrdering::Custom(z_offset_fn) => z_offset_fn(pos, grid_size),
+        }
+    }
+}
+
+/// Transform between the nodes of a [`CartesianGrid`] and world positions.
+///
+/// Computes the same positions as the `bevy_ghx_proc_gen` node spawner, so that consumers not using Bevy (servers, custom renderers, ...) can place the nodes identically.
+///
+/// ### Example
+///
+/// ```
+/// use ghx_proc_gen::{ghx_grid::{cartesian::grid::CartesianGrid, grid::Grid}, grid_transform::GridTransform};
+///
+/// let grid = CartesianGrid::new_cartesian_3d(10, 5, 10, false, false, false);
+/// let node_index = grid.index_from_coords(3, 1, 7);
+///
+/// let transform = GridTransform::new([2., 2., 2.]).with_origin([-10., 0., -10.]);
+/// let center = transform.node_center(&grid, node_index);
+/// assert_eq!(center, [-3., 3., 5.]);
+/// assert_eq!(transform.node_at(&grid, center), Some(node_index));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridTransform {
+    /// Size of a node in world units, along each grid axis
+    pub node_size: [f32; 3],
+    /// World position of the minimum corner of the grid (the corner of the node at position `(0, 0, 0)`)
+    pub origin: [f32; 3],
+    /// Mapping from the grid axis to the world axis, applied after scaling by [`GridTransform::node_size`]
+    pub axis_mapping: AxisMapping,
+    /// How to offset the z coordinate of the nodes, so that 2d sprites are ordered from back to front
+    pub z_ordering: ZOrdering,
+}
+
+impl Default for GridTransform {
+    fn default() -> Self {
+        Self::new([1., 1., 1.])
+    }
+}
+
+impl GridTransform {
+    /// Creates a [`GridTransform`] with nodes of size `node_size`, an origin at `(0, 0, 0)`, an [`AxisMapping::Identity`] and [`ZOrdering::None`]
+    pub fn new(node_size: [f32; 3]) -> Self {
+        Self {
+            node_size,
+            origin: [0., 0., 0.],
+            axis_mapping: AxisMapping::Identity,
+            z_ordering: ZOrdering::None,
+        }
+    }
+
+    /// Sets the world position of the minimum corner of the grid
+    pub fn with_origin(mut self, origin: [f32; 3]) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Sets the [`AxisMapping`]
+    pub fn with_axis_mapping(mut self, axis_mapping: AxisMapping) -> Self {
+        self.axis_mapping = axis_mapping;
+        self
+    }
+
+    /// Sets the [`ZOrdering`]
+    pub fn with_z_ordering(mut self, z_ordering: ZOrdering) -> Self {
+        self.z_ordering = z_ordering;
+        self
+    }
+
+    /// Returns the position of the node at `pos` in node units, relatively to the minimum corner of `grid`, with the z offset of the [`ZOrdering`] applied
+    pub fn node_units<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+        pos: &CartesianPosition,
+    ) -> [f32; 3] {
+        let grid_size = [grid.size_x(), grid.size_y(), grid.size_z()];
+        [
+            pos.x as f32,
+            pos.y as f32,
+            pos.z as f32 + self.z_ordering.z_offset(pos, grid_size),
+        ]
+    }
+
+    /// Converts a position in node units (which can be fractional, or outside of the grid) to a world position
+    pub fn units_to_world(&self, units: [f32; 3]) -> [f32; 3] {
+        let scaled = [
+            self.node_size[0] * units[0],
+            self.node_size[1] * units[1],
+            self.node_size[2] * units[2],
+        ];
+        let mapped = self.axis_mapping.map(scaled);
+        [
+            self.origin[0] + mapped[0],
+            self.origin[1] + mapped[1],
+            self.origin[2] + mapped[2],
+        ]
+    }
+
+    /// Converts a world position to a position in node units, ignoring the z offset
+    pub fn world_to_units(&self, world: [f32; 3]) -> [f32; 3] {
+        let mapped = self.axis_mapping.map([
+            world[0] - self.origin[0],
+            world[1] - self.origin[1],
+            world[2] - self.origin[2],
+        ]);
+        [
+            mapped[0] / self.node_size[0],
+            mapped[1] / self.node_size[1],
+            mapped[2] / self.node_size[2],
+        ]
+    }
+
+    /// Returns the world position of the center of the node at `pos`
+    pub fn position_center<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+        pos: &CartesianPosition,
+    ) -> [f32; 3] {
+        let units = self.node_units(grid, pos);
+        self.units_to_world([units[0] + 0.5, units[1] + 0.5, units[2] + 0.5])
+    }
+
+    /// Returns the world position of the center of the node with index `node_index`
+    pub fn node_center<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+        node_index: NodeIndex,
+    ) -> [f32; 3] {
+        self.position_center(grid, &grid.pos_from_index(node_index))
+    }
+
+    /// Returns the position of the node containing the world position `world`, or `None` if it is outside of the grid. The z offset is ignored
+    pub fn position_at<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+        world: [f32; 3],
+    ) -> Option<CartesianPosition> {
+        let units = self.world_to_units(world);
+        let sizes = [grid.size_x(), grid.size_y(), grid.size_z()];
+        let mut coords = [0; 3];
+        for axis in 0..3 {
+            let coord = units[axis].floor();
+            if !(coord >= 0. && coord < sizes[axis] as f32) {
+                return None;
+            }
+            coords[axis] = coord as u32;
+        }
+        Some(CartesianPosition::new(coords[0], coords[1], coords[2]))
+    }
+
+    /// Returns the index of the node containing the world position `world`, or `None` if it is outside of the grid. The z offset is ignored
+    pub fn node_at<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+        world: [f32; 3],
+    ) -> Option<NodeIndex> {
+        self.position_at(grid, world)
+            .map(|pos| grid.index_from_coords(pos.x, pos.y, pos.z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ghx_grid::{
+        cartesian::{coordinates::CartesianPosition, grid::CartesianGrid},
+        grid::Grid,
+    };
+
+    use super::{AxisMapping, GridTransform, ZOrdering};
+
+    #[test]
+    fn node_center_and_node_at_round_trip() {
+        let grid = CartesianGrid::new_cartesian_3d(4, 3, 5, false, false, false);
+        let transform = GridTransform::new([2., 0.5, 3.]).with_origin([-4., 1., 10.]);
+        for node_index in 0..grid.total_size() {
+            let center = transform.node_center(&grid, node_index);
+            assert_eq!(transform.node_at(&grid, center), Some(node_index));
+        }
+        assert_eq!(
+            transform.node_center(&grid, grid.index_from_coords(1, 2, 3)),
+            [-1., 2.25, 20.5]
+        );
+        // Nodes contain their minimum corner but not their maximum corner
+        assert_eq!(
+            transform.position_at(&grid, [-4., 1., 10.]),
+            Some(CartesianPosition::new(0, 0, 0))
+        );
+        assert_eq!(
+            transform.position_at(&grid, [-2., 1.5, 13.]),
+            Some(CartesianPosition::new(1, 1, 1))
+        );
+    }
+
+    #[test]
+    fn positions_outside_of_the_grid() {
+        let grid = CartesianGrid::new_cartesian_2d(4, 4, false, false);
+        let transform = GridTransform::new([1., 1., 1.]).with_origin([-2., -2., 0.]);
+        assert_eq!(transform.node_at(&grid, [-2.5, 0., 0.5]), None);
+        assert_eq!(transform.node_at(&grid, [2., 0., 0.5]), None);
+        assert_eq!(transform.node_at(&grid, [0., 0., 1.]), None);
+        assert_eq!(transform.node_at(&grid, [f32::NAN, 0., 0.5]), None);
+        assert_eq!(
+            transform.node_at(&grid, [1.99, -2., 0.]),
+            Some(grid.index_from_coords(3, 0, 0))
+        );
+    }
+
+    #[test]
+    fn swap_yz_mapping() {
+        let grid = CartesianGrid::new_cartesian_3d(2, 3, 4, false, false, false);
+        let transform = GridTransform::new([1., 2., 3.])
+            .with_origin([0., 0., 0.])
+            .with_axis_mapping(AxisMapping::SwapYZ);
+        let node_index = grid.index_from_coords(1, 2, 3);
+        // Grid y goes along world z, and grid z along world y
+        assert_eq!(transform.node_center(&grid, node_index), [1.5, 10.5, 5.]);
+        assert_eq!(transform.node_at(&grid, [1.5, 10.5, 5.]), Some(node_index));
+        assert_eq!(transform.world_to_units([1., 3., 4.]), [1., 2., 1.]);
+    }
+
+    #[test]
+    fn z_ordering_offsets_the_node_centers() {
+        let grid = CartesianGrid::new_cartesian_2d(4, 4, false, false);
+        let transform = GridTransform::new([1., 1., 1.]).with_z_ordering(ZOrdering::FromY);
+        let node_index = grid.index_from_coords(2, 1, 0);
+        let center = transform.node_center(&grid, node_index);
+        assert_eq!(center, [2.5, 1.5, 1.25]);
+        // Lookups ignore the z offset
+        assert_eq!(
+            transform.node_at(&grid, [center[0], center[1], 0.5]),
+            Some(node_index)
+        );
+    }
+}
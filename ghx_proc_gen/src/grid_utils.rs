@@ -0,0 +1,698 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    ops::Range,
+};
+
+use bitvec::bitvec;
+use ghx_grid::{
+    cartesian::{
+        coordinates::{
+            Cartesian2D, Cartesian3D, CartesianCoordinates, CartesianPosition, GridDelta,
+        },
+        grid::CartesianGrid,
+    },
+    coordinate_system::CoordinateSystem,
+    direction::Direction,
+    grid::{Grid, GridData},
+};
+
+use crate::{GridDefinitionError, NodeIndex};
+
+/// Checks that a cartesian grid of size `size_x` x `size_y` x `size_z` is not degenerate: no axis may have a size of 0, and its count of nodes must fit in a `usize`. Returns the count of nodes of the grid.
+///
+/// Degenerate grids can be created by the [`CartesianGrid`] constructors, but make the generation panic later on. See [`try_cartesian_2d`] and [`try_cartesian_3d`].
+pub fn check_cartesian_grid_size(
+    size_x: u32,
+    size_y: u32,
+    size_z: u32,
+) -> Result<usize, GridDefinitionError> {
+    for (axis, size) in [('x', size_x), ('y', size_y), ('z', size_z)] {
+        if size == 0 {
+            return Err(GridDefinitionError::EmptyAxis(axis));
+        }
+    }
+    (size_x as usize)
+        .checked_mul(size_y as usize)
+        .and_then(|size| size.checked_mul(size_z as usize))
+        // Node positions are computed with `u32` coordinates
+        .filter(|size| u32::try_from(*size).is_ok())
+        .ok_or(GridDefinitionError::SizeOverflow(size_x, size_y, size_z))
+}
+
+/// Same as [`CartesianGrid::new_cartesian_2d`], but returns a [`GridDefinitionError`] instead of a degenerate grid, see [`check_cartesian_grid_size`]
+pub fn try_cartesian_2d(
+    size_x: u32,
+    size_y: u32,
+    looping_x: bool,
+    looping_y: bool,
+) -> Result<CartesianGrid<Cartesian2D>, GridDefinitionError> {
+    check_cartesian_grid_size(size_x, size_y, 1)?;
+    Ok(CartesianGrid::new_cartesian_2d(
+        size_x, size_y, looping_x, looping_y,
+    ))
+}
+
+/// Same as [`CartesianGrid::new_cartesian_3d`], but returns a [`GridDefinitionError`] instead of a degenerate grid, see [`check_cartesian_grid_size`]
+pub fn try_cartesian_3d(
+    size_x: u32,
+    size_y: u32,
+    size_z: u32,
+    looping_x: bool,
+    looping_y: bool,
+    looping_z: bool,
+) -> Result<CartesianGrid<Cartesian3D>, GridDefinitionError> {
+    check_cartesian_grid_size(size_x, size_y, size_z)?;
+    Ok(CartesianGrid::new_cartesian_3d(
+        size_x, size_y, size_z, looping_x, looping_y, looping_z,
+    ))
+}
+
+/// Returns the unit [`GridDelta`] pointing in `direction`
+pub fn direction_delta(direction: Direction) -> GridDelta {
+    match direction {
+        Direction::XForward => GridDelta::new(1, 0, 0),
+        Direction::XBackward => GridDelta::new(-1, 0, 0),
+        Direction::YForward => GridDelta::new(0, 1, 0),
+        Direction::YBackward => GridDelta::new(0, -1, 0),
+        Direction::ZForward => GridDelta::new(0, 0, 1),
+        Direction::ZBackward => GridDelta::new(0, 0, -1),
+    }
+}
+
+/// Returns the [`Direction`] of a unit `delta`, or `None` if `delta` is not a unit step along a single axis. Inverse of [`direction_delta`]
+pub fn direction_from_delta(delta: &GridDelta) -> Option<Direction> {
+    match (delta.dx, delta.dy, delta.dz) {
+        (1, 0, 0) => Some(Direction::XForward),
+        (-1, 0, 0) => Some(Direction::XBackward),
+        (0, 1, 0) => Some(Direction::YForward),
+        (0, -1, 0) => Some(Direction::YBackward),
+        (0, 0, 1) => Some(Direction::ZForward),
+        (0, 0, -1) => Some(Direction::ZBackward),
+        _ => None,
+    }
+}
+
+/// Extension trait adding arithmetic and neighbour queries to a [`CartesianPosition`], so that callers don't have to cast coordinates back and forth.
+///
+/// [`CartesianPosition`] and [`GridDelta`] are defined in `ghx_grid`, which is why those are methods rather than `Add`/`Sub` implementations.
+pub trait CartesianPositionExt: Sized {
+    /// Returns this position translated by `delta`, or `None` if a coordinate would be negative or overflow. Does not check any grid bounds, see [`CartesianPositionExt::translated_in`]
+    fn translated(&self, delta: &GridDelta) -> Option<Self>;
+
+    /// Returns this position translated by `delta` in `grid`, wrapping around the looping axis of the grid (axis by axis). Returns `None` if the translated position is outside of `grid`
+    fn translated_in<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+        delta: &GridDelta,
+    ) -> Option<Self>;
+
+    /// Returns the [`GridDelta`] going from `other` to this position
+    fn delta_from(&self, other: &Self) -> GridDelta;
+
+    /// Returns an iterator on the neighbours of this position in `grid`, with the [`Direction`] of each neighbour. Neighbours across looping axis are included, and this position must be inside of `grid`
+    fn neighbours<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+    ) -> std::vec::IntoIter<(Direction, Self)>;
+}
+
+impl CartesianPositionExt for CartesianPosition {
+    fn translated(&self, delta: &GridDelta) -> Option<Self> {
+        Some(CartesianPosition::new(
+            self.x.checked_add_signed(delta.dx)?,
+            self.y.checked_add_signed(delta.dy)?,
+            self.z.checked_add_signed(delta.dz)?,
+        ))
+    }
+
+    fn translated_in<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+        delta: &GridDelta,
+    ) -> Option<Self> {
+        let mut position = CartesianPosition::new(self.x, self.y, self.z);
+        for (axis, units) in [
+            (Direction::XForward, delta.dx),
+            (Direction::YForward, delta.dy),
+            (Direction::ZForward, delta.dz),
+        ] {
+            if units != 0 {
+                position =
+                    grid.pos_from_index(grid.get_index_in_direction(&position, axis, units)?);
+            }
+        }
+        Some(position)
+    }
+
+    fn delta_from(&self, other: &Self) -> GridDelta {
+        GridDelta::new(
+            (self.x as i64 - other.x as i64) as i32,
+            (self.y as i64 - other.y as i64) as i32,
+            (self.z as i64 - other.z as i64) as i32,
+        )
+    }
+
+    fn neighbours<C: CartesianCoordinates>(
+        &self,
+        grid: &CartesianGrid<C>,
+    ) -> std::vec::IntoIter<(Direction, Self)> {
+        let mut neighbours_indexes = vec![None; grid.directions_count()];
+        grid.get_neighbours_in_all_directions(
+            grid.index_from_coords(self.x, self.y, self.z),
+            &mut neighbours_indexes,
+        );
+        grid.coord_system()
+            .directions()
+            .iter()
+            .filter_map(|direction| {
+                neighbours_indexes[(*direction).into()]
+                    .map(|neighbour_index| (*direction, grid.pos_from_index(neighbour_index)))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Distance given by [`GridDataExt::distance_field`] to the nodes which cannot be reached from any source
+pub const UNREACHABLE_DISTANCE: u32 = u32::MAX;
+
+/// Extension trait adding some post-processing utilities to a [`GridData`], such as connectivity queries or combinators
+pub trait GridDataExt<D> {
+    /// Coordinate system of the grid
+    type Coordinates: CoordinateSystem;
+    /// Grid type of the grid data
+    type Grid: Grid<Self::Coordinates>;
+
+    /// Returns the indexes of all the nodes connected to `start` (including `start`) through nodes satisfying `predicate`, in breadth-first order.
+    ///
+    /// Connectivity follows the directions of the grid coordinate system: 4-connectivity for 2d grids, 6-connectivity for 3d grids (looping axis included).
+    ///
+    /// Returns an empty `Vec` if `start` is not in the grid or does not satisfy `predicate`.
+    ///
+    /// ### Example
+    ///
+    /// Find all the water cells of the lake containing node 42:
+    /// ```ignore
+    /// let lake = grid_data.flood_fill(42, |_, node| node == &Some(WATER));
+    /// ```
+    fn flood_fill<F: Fn(NodeIndex, &D) -> bool>(
+        &self,
+        start: NodeIndex,
+        predicate: F,
+    ) -> Vec<NodeIndex>;
+
+    /// Returns a grid of the distances (in nodes count) from the nearest of the `sources` nodes, moving only through nodes satisfying `passable`.
+    ///
+    /// Connectivity is the same as in [`GridDataExt::flood_fill`]. Sources are at a distance of 0 even if they are not passable, sources not in the grid are ignored. Nodes that cannot be reached have a distance of [`UNREACHABLE_DISTANCE`].
+    ///
+    /// ### Example
+    ///
+    /// Distances from the player spawn to every walkable node, e.g. to place the exit as far as possible:
+    /// ```ignore
+    /// let distances = grid_data.distance_field(&[spawn_index], |_, node| is_walkable(node));
+    /// ```
+    fn distance_field<F: Fn(NodeIndex, &D) -> bool>(
+        &self,
+        sources: &[NodeIndex],
+        passable: F,
+    ) -> GridData<Self::Coordinates, u32, Self::Grid>;
+
+    /// Returns a new grid, on the same grid definition, where each node is the result of `f` applied to the corresponding node of this grid.
+    ///
+    /// ### Example
+    ///
+    /// Derive a walkability mask from a generated grid:
+    /// ```ignore
+    /// let walkable = grid_data.map(|node| is_walkable(node));
+    /// ```
+    fn map<E, F: FnMut(&D) -> E>(&self, f: F) -> GridData<Self::Coordinates, E, Self::Grid>;
+
+    /// Returns a new grid, on the same grid definition, where each node is a pair of references to the corresponding nodes of this grid and of `other`.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `other` does not have the same number of nodes as this grid.
+    ///
+    /// ### Example
+    ///
+    /// Combine a generated grid with a weight field:
+    /// ```ignore
+    /// let costs = grid_data.zip(&weights).map(|(node, weight)| cost(node, **weight));
+    /// ```
+    fn zip<'a, E>(
+        &'a self,
+        other: &'a GridData<Self::Coordinates, E, Self::Grid>,
+    ) -> GridData<Self::Coordinates, (&'a D, &'a E), Self::Grid>;
+
+    /// Returns the nodes which differ between this grid and `other`, as `(node_index, self_node, other_node)`, in index order.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `other` does not have the same number of nodes as this grid.
+    ///
+    /// ### Example
+    ///
+    /// Only respawn the nodes that changed between two generations:
+    /// ```ignore
+    /// for (node_index, _previous, current) in previous_grid.diff(&current_grid) {
+    ///     respawn_node(node_index, current);
+    /// }
+    /// ```
+    fn diff<'a>(
+        &'a self,
+        other: &'a GridData<Self::Coordinates, D, Self::Grid>,
+    ) -> Vec<(NodeIndex, &'a D, &'a D)>
+    where
+        D: PartialEq;
+}
+
+impl<C: CoordinateSystem, D, G: Grid<C>> GridDataExt<D> for GridData<C, D, G> {
+    type Coordinates = C;
+    type Grid = G;
+
+    fn flood_fill<F: Fn(NodeIndex, &D) -> bool>(
+        &self,
+        start: NodeIndex,
+        predicate: F,
+    ) -> Vec<NodeIndex> {
+        let grid = self.grid();
+        if start >= grid.total_size() || !predicate(start, self.get(start)) {
+            return Vec::new();
+        }
+
+        let mut visited = bitvec![0; grid.total_size()];
+        let mut neighbours = vec![None; grid.directions_count()];
+        let mut queue = VecDeque::from([start]);
+        let mut filled = Vec::new();
+        visited.set(start, true);
+        while let Some(node_index) = queue.pop_front() {
+            filled.push(node_index);
+            grid.get_neighbours_in_all_directions(node_index, &mut neighbours);
+            for neighbour in neighbours.iter().flatten() {
+                if !visited[*neighbour] && predicate(*neighbour, self.get(*neighbour)) {
+                    visited.set(*neighbour, true);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        filled
+    }
+
+    fn distance_field<F: Fn(NodeIndex, &D) -> bool>(
+        &self,
+        sources: &[NodeIndex],
+        passable: F,
+    ) -> GridData<C, u32, G> {
+        let grid = self.grid();
+        let mut distances = vec![UNREACHABLE_DISTANCE; grid.total_size()];
+        let mut neighbours = vec![None; grid.directions_count()];
+        let mut queue = VecDeque::new();
+        for source in sources.iter().filter(|source| **source < grid.total_size()) {
+            if distances[*source] != 0 {
+                distances[*source] = 0;
+                queue.push_back(*source);
+            }
+        }
+        while let Some(node_index) = queue.pop_front() {
+            let neighbour_distance = distances[node_index] + 1;
+            grid.get_neighbours_in_all_directions(node_index, &mut neighbours);
+            for neighbour in neighbours.iter().flatten() {
+                if distances[*neighbour] == UNREACHABLE_DISTANCE
+                    && passable(*neighbour, self.get(*neighbour))
+                {
+                    distances[*neighbour] = neighbour_distance;
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        GridData::new(grid.clone(), distances)
+    }
+
+    fn map<E, F: FnMut(&D) -> E>(&self, f: F) -> GridData<C, E, G> {
+        GridData::new(self.grid().clone(), self.nodes().iter().map(f).collect())
+    }
+
+    fn zip<'a, E>(&'a self, other: &'a GridData<C, E, G>) -> GridData<C, (&'a D, &'a E), G> {
+        assert_eq!(
+            self.grid().total_size(),
+            other.grid().total_size(),
+            "Cannot zip grids data of different sizes"
+        );
+        GridData::new(
+            self.grid().clone(),
+            self.nodes().iter().zip(other.nodes().iter()).collect(),
+        )
+    }
+
+    fn diff<'a>(&'a self, other: &'a GridData<C, D, G>) -> Vec<(NodeIndex, &'a D, &'a D)>
+    where
+        D: PartialEq,
+    {
+        assert_eq!(
+            self.grid().total_size(),
+            other.grid().total_size(),
+            "Cannot diff grids data of different sizes"
+        );
+        self.nodes()
+            .iter()
+            .zip(other.nodes().iter())
+            .enumerate()
+            .filter(|(_, (node, other_node))| node != other_node)
+            .map(|(node_index, (node, other_node))| (node_index, node, other_node))
+            .collect()
+    }
+}
+
+/// Extension trait adding iterators and pathfinding utilities to a [`GridData`] on a [`CartesianGrid`]
+///
+/// Iterators yield `(NodeIndex, CartesianPosition, &D)` items, and out of bounds rows, columns or layers yield nothing, so that traversals don't have to do any index arithmetic.
+pub trait CartesianGridDataExt<D> {
+    /// Iterates over all the nodes of the grid, in index order
+    fn iter_nodes(&self) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)>;
+    /// Mutable version of [`CartesianGridDataExt::iter_nodes`]
+    fn iter_nodes_mut(&mut self) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)>;
+    /// Iterates over the nodes of the row (along the X axis) at `y` and `z`
+    fn iter_row(&self, y: u32, z: u32) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)>;
+    /// Mutable version of [`CartesianGridDataExt::iter_row`]
+    fn iter_row_mut(
+        &mut self,
+        y: u32,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)>;
+    /// Iterates over the nodes of the column (along the Y axis) at `x` and `z`
+    fn iter_column(
+        &self,
+        x: u32,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)>;
+    /// Mutable version of [`CartesianGridDataExt::iter_column`]
+    fn iter_column_mut(
+        &mut self,
+        x: u32,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)>;
+    /// Iterates over the nodes of the layer (the XY plane) at `z`. For a 2d grid, the only layer is at `z = 0`
+    fn iter_layer(&self, z: u32) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)>;
+    /// Mutable version of [`CartesianGridDataExt::iter_layer`]
+    fn iter_layer_mut(
+        &mut self,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)>;
+
+    /// Finds a path of minimal cost from `start` to `goal` with A*, and returns the indexes of its nodes (`start` and `goal` included).
+    ///
+    /// `cost` returns the cost of moving into a node, or [None] if the node can't be traversed (for example from the [`crate::generator::model::ModelInstance`] generated on it). Costs of 0 are counted as 1. Connectivity is the same as in [`GridDataExt::flood_fill`].
+    ///
+    /// Returns [None] if `start` or `goal` are not in the grid, or if `goal` can't be reached.
+    ///
+    /// ### Example
+    ///
+    /// Check that the exit of a generated dungeon can be reached from its entrance, walls being impassable and water slower to cross:
+    /// ```ignore
+    /// let path = grid_data.find_path(entrance, exit, |_, node| match node {
+    ///     Some(instance) if instance.model_index == WALL => None,
+    ///     Some(instance) if instance.model_index == WATER => Some(3),
+    ///     _ => Some(1),
+    /// });
+    /// ```
+    fn find_path<F: Fn(NodeIndex, &D) -> Option<u32>>(
+        &self,
+        start: NodeIndex,
+        goal: NodeIndex,
+        cost: F,
+    ) -> Option<Vec<NodeIndex>>;
+
+    /// Resamples this grid data onto `target`, a grid of a different size, and returns the resulting grid data.
+    ///
+    /// Each node of `target` covers a block of nodes of this grid, given to `reduce` in index order. When upscaling along an axis, blocks only contain the nearest node along that axis. Blocks are never empty.
+    ///
+    /// ### Example
+    ///
+    /// Build a minimap by keeping the most frequent model of each 4x4 block:
+    /// ```ignore
+    /// let minimap_grid = CartesianGrid::new_cartesian_2d(grid.size_x() / 4, grid.size_y() / 4, false, false);
+    /// let minimap = grid_data.resample(minimap_grid, |block| most_frequent_model(block));
+    /// ```
+    fn resample<E, F: FnMut(&[&D]) -> E>(
+        &self,
+        target: CartesianGrid<Self::Coordinates>,
+        reduce: F,
+    ) -> GridData<Self::Coordinates, E, CartesianGrid<Self::Coordinates>>;
+
+    /// Nearest-neighbor resampling of this grid data onto `target`. Reduced blocks keep their first node.
+    ///
+    /// See [`CartesianGridDataExt::resample`]
+    fn resample_nearest(
+        &self,
+        target: CartesianGrid<Self::Coordinates>,
+    ) -> GridData<Self::Coordinates, D, CartesianGrid<Self::Coordinates>>
+    where
+        D: Clone;
+}
+
+impl<C: CartesianCoordinates, D> CartesianGridDataExt<D> for GridData<C, D, CartesianGrid<C>> {
+    fn iter_nodes(&self) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)> {
+        let grid = self.grid();
+        self.nodes()
+            .iter()
+            .enumerate()
+            .map(move |(node_index, node)| (node_index, grid.pos_from_index(node_index), node))
+    }
+
+    fn iter_nodes_mut(&mut self) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)> {
+        let grid = self.grid().clone();
+        self.nodes_mut()
+            .iter_mut()
+            .enumerate()
+            .map(move |(node_index, node)| (node_index, grid.pos_from_index(node_index), node))
+    }
+
+    fn iter_row(&self, y: u32, z: u32) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)> {
+        let (first, len, step) = row_indexes(self.grid(), y, z);
+        strided(self, first, len, step)
+    }
+
+    fn iter_row_mut(
+        &mut self,
+        y: u32,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)> {
+        let (first, len, step) = row_indexes(self.grid(), y, z);
+        strided_mut(self, first, len, step)
+    }
+
+    fn iter_column(
+        &self,
+        x: u32,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)> {
+        let (first, len, step) = column_indexes(self.grid(), x, z);
+        strided(self, first, len, step)
+    }
+
+    fn iter_column_mut(
+        &mut self,
+        x: u32,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)> {
+        let (first, len, step) = column_indexes(self.grid(), x, z);
+        strided_mut(self, first, len, step)
+    }
+
+    fn iter_layer(&self, z: u32) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &D)> {
+        let (first, len, step) = layer_indexes(self.grid(), z);
+        strided(self, first, len, step)
+    }
+
+    fn iter_layer_mut(
+        &mut self,
+        z: u32,
+    ) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &mut D)> {
+        let (first, len, step) = layer_indexes(self.grid(), z);
+        strided_mut(self, first, len, step)
+    }
+
+    fn find_path<F: Fn(NodeIndex, &D) -> Option<u32>>(
+        &self,
+        start: NodeIndex,
+        goal: NodeIndex,
+        cost: F,
+    ) -> Option<Vec<NodeIndex>> {
+        let grid = self.grid();
+        if start >= grid.total_size() || goal >= grid.total_size() {
+            return None;
+        }
+
+        let goal_pos = grid.pos_from_index(goal);
+        // Manhattan distance, with the shortest way around each axis so that it stays admissible for looping grids
+        let heuristic = |node_index: NodeIndex| -> u64 {
+            let pos = grid.pos_from_index(node_index);
+            let axis_distance = |a: u32, b: u32, size: u32| {
+                let distance = a.abs_diff(b);
+                distance.min(size - distance) as u64
+            };
+            axis_distance(pos.x, goal_pos.x, grid.size_x())
+                + axis_distance(pos.y, goal_pos.y, grid.size_y())
+                + axis_distance(pos.z, goal_pos.z, grid.size_z())
+        };
+
+        let mut costs = vec![u64::MAX; grid.total_size()];
+        let mut came_from: Vec<Option<NodeIndex>> = vec![None; grid.total_size()];
+        let mut neighbours = vec![None; grid.directions_count()];
+        let mut open_set = BinaryHeap::new();
+        costs[start] = 0;
+        open_set.push(Reverse((heuristic(start), start)));
+        while let Some(Reverse((estimated_cost, node_index))) = open_set.pop() {
+            if node_index == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while let Some(previous) = came_from[current] {
+                    path.push(previous);
+                    current = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            // Stale entry, the node was reached with a lower cost since
+            if estimated_cost > costs[node_index] + heuristic(node_index) {
+                continue;
+            }
+            grid.get_neighbours_in_all_directions(node_index, &mut neighbours);
+            for neighbour in neighbours.iter().flatten() {
+                let Some(move_cost) = cost(*neighbour, self.get(*neighbour)) else {
+                    continue;
+                };
+                let neighbour_cost = costs[node_index] + move_cost.max(1) as u64;
+                if neighbour_cost < costs[*neighbour] {
+                    costs[*neighbour] = neighbour_cost;
+                    came_from[*neighbour] = Some(node_index);
+                    open_set.push(Reverse((
+                        neighbour_cost + heuristic(*neighbour),
+                        *neighbour,
+                    )));
+                }
+            }
+        }
+        None
+    }
+
+    fn resample<E, F: FnMut(&[&D]) -> E>(
+        &self,
+        target: CartesianGrid<C>,
+        mut reduce: F,
+    ) -> GridData<C, E, CartesianGrid<C>> {
+        let grid = self.grid();
+        let mut block = Vec::new();
+        let mut nodes = Vec::with_capacity(target.total_size());
+        for target_index in 0..target.total_size() {
+            let target_pos = target.pos_from_index(target_index);
+            block.clear();
+            for z in source_range(target_pos.z, grid.size_z(), target.size_z()) {
+                for y in source_range(target_pos.y, grid.size_y(), target.size_y()) {
+                    for x in source_range(target_pos.x, grid.size_x(), target.size_x()) {
+                        block.push(self.get(grid.index_from_coords(x, y, z)));
+                    }
+                }
+            }
+            nodes.push(reduce(&block));
+        }
+        GridData::new(target, nodes)
+    }
+
+    fn resample_nearest(&self, target: CartesianGrid<C>) -> GridData<C, D, CartesianGrid<C>>
+    where
+        D: Clone,
+    {
+        self.resample(target, |block| block[0].clone())
+    }
+}
+
+/// Returns `len` nodes of `grid_data`, starting at index `first` and spaced by `step`. Only the positions of the returned nodes are computed
+fn strided<'a, C: CartesianCoordinates, D>(
+    grid_data: &'a GridData<C, D, CartesianGrid<C>>,
+    first: usize,
+    len: usize,
+    step: usize,
+) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &'a D)> + 'a {
+    let grid = grid_data.grid();
+    (0..len).map(move |i| {
+        let node_index = first + i * step;
+        (
+            node_index,
+            grid.pos_from_index(node_index),
+            grid_data.get(node_index),
+        )
+    })
+}
+
+/// Mutable version of [`strided`]
+fn strided_mut<'a, C: CartesianCoordinates, D>(
+    grid_data: &'a mut GridData<C, D, CartesianGrid<C>>,
+    first: usize,
+    len: usize,
+    step: usize,
+) -> impl Iterator<Item = (NodeIndex, CartesianPosition, &'a mut D)> + 'a {
+    let grid = grid_data.grid().clone();
+    // The nodes slice iterator skips the nodes in between without visiting them
+    grid_data
+        .iter_mut()
+        .skip(first)
+        .step_by(step.max(1))
+        .take(len)
+        .enumerate()
+        .map(move |(i, node)| {
+            let node_index = first + i * step;
+            (node_index, grid.pos_from_index(node_index), node)
+        })
+}
+
+/// Returns the first index, count and stride of the nodes of a row. The count is 0 if the row is out of bounds
+fn row_indexes<C: CartesianCoordinates>(
+    grid: &CartesianGrid<C>,
+    y: u32,
+    z: u32,
+) -> (usize, usize, usize) {
+    match y < grid.size_y() && z < grid.size_z() {
+        true => (grid.index_from_coords(0, y, z), grid.size_x() as usize, 1),
+        false => (0, 0, 1),
+    }
+}
+
+/// Returns the first index, count and stride of the nodes of a column. The count is 0 if the column is out of bounds
+fn column_indexes<C: CartesianCoordinates>(
+    grid: &CartesianGrid<C>,
+    x: u32,
+    z: u32,
+) -> (usize, usize, usize) {
+    match x < grid.size_x() && z < grid.size_z() {
+        true => (
+            grid.index_from_coords(x, 0, z),
+            grid.size_y() as usize,
+            grid.size_x() as usize,
+        ),
+        false => (0, 0, 1),
+    }
+}
+
+/// Returns the first index, count and stride of the nodes of a layer. The count is 0 if the layer is out of bounds
+fn layer_indexes<C: CartesianCoordinates>(
+    grid: &CartesianGrid<C>,
+    z: u32,
+) -> (usize, usize, usize) {
+    match z < grid.size_z() {
+        true => (
+            grid.index_from_coords(0, 0, z),
+            (grid.size_x() * grid.size_y()) as usize,
+            1,
+        ),
+        false => (0, 0, 1),
+    }
+}
+
+/// Returns the range of source coordinates covered by the `target` coordinate along an axis. The range is never empty.
+fn source_range(target: u32, source_size: u32, target_size: u32) -> Range<u32> {
+    let start = (target as u64 * source_size as u64 / target_size as u64) as u32;
+    let end = ((target as u64 + 1) * source_size as u64 / target_size as u64) as u32;
+    start..end.max(start + 1)
+}
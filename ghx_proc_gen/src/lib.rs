@@ -10,6 +10,12 @@ pub use ghx_grid;
 
 /// Model synthesis/Wave function Collapse generator
 pub mod generator;
+/// Transforms between grid nodes and world positions, shared by every renderer
+pub mod grid_transform;
+/// Validated grid constructors, position arithmetic, and post-processing utilities for the grids data, such as flood fill, distance fields or pathfinding
+pub mod grid_utils;
+
+mod macros;
 
 /// Our grid elements are called Nodes
 pub type NodeIndex = GridIndex;
@@ -22,12 +28,30 @@ pub struct GeneratorError {
     pub node_index: NodeIndex,
 }
 
+/// Error returned by [`generator::Generator::generate_reported`] when all the tries of a generation failed
+#[derive(thiserror::Error, Debug, Clone)]
+#[error("Failed to generate after {} tries", failed_tries.len())]
+pub struct GenerationFailedError {
+    /// Seed and contradiction of each failed try, in order. The last one is the error [`generator::Generator::generate`] would have returned
+    pub failed_tries: Vec<generator::FailedTry>,
+}
+
+/// Error returned by [`generator::Generator::restore`] when a snapshot does not match the generator
+#[derive(thiserror::Error, Debug, Clone, Copy)]
+#[error("Snapshot was taken from a generator with a different grid size or models count")]
+pub struct SnapshotMismatchError;
+
 /// Error returned by a [`generator::rules::RulesBuilder`] when correct [`generator::rules::Rules`] cannot be built
 #[derive(thiserror::Error, Debug, Clone, Copy)]
 pub enum RulesBuilderError {
     /// Rules cannot be built without models or sockets
     #[error("Empty models or sockets collection")]
     NoModelsOrSockets,
+    /// A model that can be reoriented to other facings than [`generator::model::ModelFacing::Up`] uses sockets with rotated connections, which are not supported on reoriented models
+    #[error(
+        "Model {0} can be reoriented to other facings but uses sockets with rotated connections"
+    )]
+    RotatedSocketsOnReorientedModel(ModelIndex),
 }
 
 /// Error returned by a [`generator::Generator`] when a node set operation fails
@@ -45,6 +69,9 @@ pub enum NodeSetError {
     /// An operation requested to set a model on a node that does not allow it
     #[error("Model variant `{0}` not allowed by the Rules on node {1}")]
     IllegalModel(ModelVariantIndex, NodeIndex),
+    /// A commit was requested without a pending selection, see [`generator::Generator::select_next`]
+    #[error("No pending selection to commit")]
+    NoPendingSelection,
     /// Wraps a [`GeneratorError`]
     #[error("Generation error: {0}")]
     GenerationError(#[from] GeneratorError),
@@ -60,3 +87,76 @@ pub enum GeneratorBuilderError {
     #[error("Given grid size {0:?} does not match the expected size {1:?}")]
     InvalidGridSize(usize, usize),
 }
+
+/// Errors returned by the validated grid constructors of [`grid_utils`], such as [`grid_utils::try_cartesian_2d`]
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridDefinitionError {
+    /// An axis of the grid has a size of 0
+    #[error("The {0} axis of the grid has a size of 0")]
+    EmptyAxis(char),
+    /// The count of nodes of the grid does not fit in a `usize`
+    #[error("The grid size {0}x{1}x{2} overflows")]
+    SizeOverflow(u32, u32, u32),
+}
+
+/// Errors returned by [`generator::socket::SocketCollection::set_connections_from_csv`]
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum SocketsCsvError {
+    /// The CSV does not have a header row
+    #[error("Missing header row with the sockets names")]
+    MissingHeader,
+    /// A socket name of the CSV does not match any of the given sockets
+    #[error("Unknown socket name `{0}`")]
+    UnknownSocket(String),
+    /// A row of the CSV has more cells than the header row
+    #[error("Row {0} has more cells than the header row")]
+    InvalidRowLength(usize),
+    /// A cell of the CSV could not be read as connected or not connected
+    #[error("Invalid cell `{2}` at row {0}, column {1}")]
+    InvalidCell(usize, usize, String),
+}
+
+/// Errors returned by the sockets inference helpers of [`generator::tiles`]
+#[derive(thiserror::Error, Debug, Clone, Copy)]
+pub enum TilesError {
+    /// A tile has a zero size, or a count of pixels that does not match its size
+    #[error("Tile {0} is empty or its pixels do not match its size")]
+    InvalidTileSize(usize),
+    /// A voxel tile does not have the same size as the first tile
+    #[error("Tile {0} does not have the same size as the first tile")]
+    MismatchedTileSize(usize),
+}
+
+/// Errors returned when loading voxel tiles from `.vox` files, see [`generator::tiles::load_vox_tiles`]
+#[derive(thiserror::Error, Debug)]
+pub enum VoxError {
+    /// A file or folder could not be read
+    #[error("Failed to read .vox tiles: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data is not a valid `.vox` model
+    #[error("Invalid .vox data: {0}")]
+    InvalidFormat(&'static str),
+    /// The `.vox` model is not a cube
+    #[error("The .vox model of size {0}x{1}x{2} is not a cube")]
+    NotCubic(i32, i32, i32),
+    /// A `.vox` file of a folder could not be loaded
+    #[error("Failed to load {0:?}: {1}")]
+    File(std::path::PathBuf, Box<VoxError>),
+    /// Wraps a [`TilesError`]
+    #[error("Tiles error: {0}")]
+    Tiles(#[from] TilesError),
+}
+
+/// Errors returned by a [`generator::pipeline::GenerationPipeline`]
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum GenerationPipelineError {
+    /// The generator of a pass could not be built, usually because of invalid constraints returned by a pass mapping
+    #[error("Failed to build the generator of pass {0}: {1}")]
+    Build(usize, GeneratorBuilderError),
+    /// The generation of a pass failed
+    #[error("Failed to generate pass {0}: {1}")]
+    Generation(usize, GeneratorError),
+    /// The first pass has a mapping, while there is no previous pass to map from
+    #[error("The first pass of a pipeline cannot be a mapped pass")]
+    MappedFirstPass,
+}
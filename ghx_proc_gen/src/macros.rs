@@ -0,0 +1,58 @@
+/// Declares sockets, their connections and models in a compact table form, and returns the resulting [`crate::generator::model::ModelCollection`] and [`crate::generator::socket::SocketCollection`], ready to be given to a [`crate::generator::rules::RulesBuilder`].
+///
+/// - `sockets` lists the sockets to create. Each socket is bound to a variable of the same name, usable in the rest of the table, and is registered with this name (see [`crate::generator::socket::SocketCollection::set_name`]).
+/// - `connections` lists the connections between sockets, see [`crate::generator::socket::SocketCollection::add_connection`].
+/// - `rotated_connections` (optional) lists the connections between sockets on the rotation axis, see [`crate::generator::socket::SocketCollection::add_rotated_connection`].
+/// - `models` lists the models, by name, in creation order. Each model can be followed by `=>` and a list of [`crate::generator::model::Model`] methods calls such as `with_weight(2.)` or `with_all_rotations()`.
+///
+/// ### Example
+///
+/// ```
+/// use ghx_proc_gen::{
+///     generator::{rules::RulesBuilder, socket::SocketsCartesian2D},
+///     rules_table,
+/// };
+///
+/// let (models, sockets) = rules_table! {
+///     sockets: [land, beach, sea],
+///     connections: [
+///         land => [land, beach],
+///         beach => [beach, sea],
+///         sea => [sea],
+///     ],
+///     models: [
+///         "land": SocketsCartesian2D::Mono(land),
+///         "beach": SocketsCartesian2D::Mono(beach) => with_weight(0.5),
+///         "sea": SocketsCartesian2D::Mono(sea) => with_weight(2.),
+///     ],
+/// };
+/// let rules = RulesBuilder::new_cartesian_2d(models, sockets).build().unwrap();
+/// ```
+#[macro_export]
+macro_rules! rules_table {
+    (
+        sockets: [$($socket:ident),* $(,)?],
+        connections: [$($from:ident => [$($to:ident),* $(,)?]),* $(,)?],
+        $(rotated_connections: [$($rotated_from:ident => [$($rotated_to:ident),* $(,)?]),* $(,)?],)?
+        models: [
+            $($name:literal : $template:expr $(=> $($modifier:ident($($arg:expr),*)),+)?),* $(,)?
+        ] $(,)?
+    ) => {{
+        let mut sockets = $crate::generator::socket::SocketCollection::new();
+        $(
+            let $socket = sockets.create();
+            sockets.set_name($socket, stringify!($socket));
+        )*
+        $(sockets.add_connection($from, vec![$($to),*]);)*
+        $($(sockets.add_rotated_connection($rotated_from, vec![$($rotated_to),*]);)*)?
+
+        let mut models = $crate::generator::model::ModelCollection::new();
+        $(
+            models
+                .create($template)
+                .with_name($name)
+                $($(.$modifier($($arg),*))+)?;
+        )*
+        (models, sockets)
+    }};
+}